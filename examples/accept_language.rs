@@ -0,0 +1,17 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+    redmine.set_language(Some("en"));
+
+    let result = redmine.issues().show(1).execute();
+    println!("Result: {:?}", result);
+
+    let result = redmine.in_language("de", |api| api.issues().show(1).execute());
+    println!("Result: {:?}", result);
+}
@@ -0,0 +1,16 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine
+        .attachments()
+        .copy(1, 2, |a| a.filename.ends_with(".png"))
+        .execute();
+    println!("Result: {:?}", result);
+}
@@ -0,0 +1,17 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use std::fs::File;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let attachment = redmine.attachments().show(1).execute().unwrap();
+    let mut file = File::create(&attachment.filename).unwrap();
+
+    let result = redmine.attachments().download(attachment.id).write_to(&mut file);
+    println!("Result: {:?}", result);
+}
@@ -0,0 +1,21 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    // running this again after an interruption skips the attachments already downloaded
+    let result = redmine
+        .attachments()
+        .search(1)
+        .download_all("/tmp/redmine-backup")
+        .concurrency(8)
+        .per_host(4)
+        .execute();
+
+    println!("Result: {:?}", result);
+}
@@ -0,0 +1,24 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.attachments().search(1)
+        .filename("*.pdf")
+        .min_size(1024 * 1024)
+        .execute()
+        .unwrap();
+    for item in result {
+        println!(
+            "Issue: {:?}, Filename: {}, Size: {}",
+            item.issue_id,
+            item.attachment.filename,
+            item.attachment.filesize
+        );
+    }
+}
@@ -0,0 +1,18 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.attachments().update(1)
+        .description("Screenshot of the login page")
+        .execute();
+    println!("Result: {:?}", result);
+
+    let result = redmine.attachments().show(1).execute();
+    println!("Result: {:?}", result);
+}
@@ -0,0 +1,31 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::cassette::{Recorder, Replayer};
+use redmine_api::http::BlockingTransport;
+
+fn main() {
+    {
+        let redmine = RedmineApi::with_transport(
+            "http://localhost:8080".to_string(),
+            "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+            Box::new(Recorder::new(Box::new(BlockingTransport), "issues.json.gz")),
+        );
+
+        let result = redmine.issues().list().project_id(1).execute();
+        println!("Recorded: {:?}", result);
+    }
+
+    let replayer = match Replayer::open("issues.json.gz") {
+        Ok(replayer) => replayer,
+        Err(e) => return println!("Error: {}", e),
+    };
+    let redmine = RedmineApi::with_transport(
+        "http://localhost:8080".to_string(),
+        "any key".to_string(),
+        Box::new(replayer),
+    );
+
+    let result = redmine.issues().list().project_id(1).execute();
+    println!("Replayed: {:?}", result);
+}
@@ -0,0 +1,18 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::cleanup;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let report = cleanup::time_entries_before(&redmine, 1, "2016-01-01")
+        .execute()
+        .unwrap();
+    for item in report.candidates {
+        println!("Would delete: {}", item.description);
+    }
+}
@@ -0,0 +1,31 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let definitions = redmine.custom_fields().list().execute().unwrap();
+    let issue = redmine.issues().show(1).execute().unwrap();
+    for cf in &issue.custom_fields {
+        println!(
+            "Name: {}, Value: {:?}",
+            cf.name,
+            issue.custom_field_value(&cf.name, &definitions)
+        );
+    }
+
+    for cf in definitions {
+        let trackers: Vec<&str> = cf.trackers.iter().map(|t| t.name()).collect();
+        println!(
+            "Name: {}, Format: {}, Required: {}, Trackers: {}",
+            cf.name,
+            cf.field_format,
+            cf.is_required,
+            trackers.join(", ")
+        );
+    }
+}
@@ -0,0 +1,31 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::errors::Result;
+use redmine_api::http::{Request, Response, Transport};
+use std::io::Cursor;
+
+/// Transport which only prints requests and answers them with an empty issue list.
+struct DryRun;
+impl Transport for DryRun {
+    fn send(&self, request: Request) -> Result<Response> {
+        println!("{:?} {}", request.method, request.url);
+
+        Ok(Response {
+            status: 200,
+            location: None,
+            body: Box::new(Cursor::new(b"{\"issues\":[],\"total_count\":0}".to_vec())),
+        })
+    }
+}
+
+fn main() {
+    let redmine = RedmineApi::with_transport(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+        Box::new(DryRun),
+    );
+
+    let result = redmine.issues().list().project_id(1).execute();
+    println!("Result: {:?}", result);
+}
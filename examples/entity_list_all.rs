@@ -0,0 +1,23 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::entity::Listable;
+
+fn print_count<T: Listable>(api: &T) {
+    match api.list_all() {
+        Ok(items) => println!("{}: {}", api.name(), items.len()),
+        Err(e) => println!("{}: {}", api.name(), e),
+    }
+}
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    print_count(redmine.issues());
+    print_count(redmine.projects());
+    print_count(redmine.time_entries());
+    print_count(redmine.users());
+}
@@ -0,0 +1,19 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let activities = redmine.enumerations().time_entry_activities().execute().unwrap();
+    let activity = activities
+        .by_name("Development")
+        .or(activities.default_activity())
+        .expect("No activity to log time with");
+
+    let result = redmine.time_entries().create(1, 1.5, activity.id).execute();
+    println!("Logged 1.5 hours as {}: {:?}", activity.name, result);
+}
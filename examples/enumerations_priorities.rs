@@ -0,0 +1,15 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.enumerations().issue_priorities().execute().unwrap();
+    for priority in result {
+        println!("ID: {}, Name: {}, Default: {}", priority.id, priority.name, priority.is_default);
+    }
+}
@@ -0,0 +1,52 @@
+extern crate redmine_api;
+#[macro_use]
+extern crate serde_derive;
+
+use redmine_api::RedmineApi;
+use redmine_api::errors::*;
+use redmine_api::extension::{Extension, ExtensionClient};
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+struct Sprint {
+    id: u32,
+    name: String,
+    status: String,
+}
+
+struct AgileApi {
+    client: ExtensionClient,
+}
+impl Extension for AgileApi {
+    fn new(client: ExtensionClient) -> Self {
+        AgileApi { client: client }
+    }
+}
+impl AgileApi {
+    fn sprints(&self, project_id: u32) -> Result<Vec<Sprint>> {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            agile_sprints: Vec<Sprint>,
+        }
+
+        let path = format!("/projects/{}/agile_sprints.json", project_id);
+        let wrapper: Wrapper = self.client.get_json(&path, &HashMap::new())?;
+        Ok(wrapper.agile_sprints)
+    }
+}
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    match redmine.extension::<AgileApi>().sprints(1) {
+        Ok(sprints) => {
+            for sprint in sprints {
+                println!("#{} {} ({})", sprint.id, sprint.name, sprint.status);
+            }
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+}
@@ -0,0 +1,28 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::federation::Federation;
+
+fn main() {
+    let federation = Federation::new()
+        .add(
+            "it",
+            RedmineApi::new(
+                "http://localhost:8080".to_string(),
+                "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+            ),
+        )
+        .add(
+            "hr",
+            RedmineApi::new(
+                "http://localhost:8081".to_string(),
+                "96b3ddaa1d27af3f7cb8adf0910e4c954f437917".to_string(),
+            ),
+        )
+        .route("hr-", "hr");
+
+    let result = federation.issues(|filter| { filter.status_id(1); }).unwrap();
+    for tagged in result {
+        println!("Origin: {}, ID: {:?}", tagged.origin, tagged.item.id);
+    }
+}
@@ -0,0 +1,24 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let upload = redmine.uploads().file("Cargo.toml")
+        .and_then(|u| u.execute())
+        .unwrap();
+    redmine.files().create(1, &upload)
+        .version_id(1)
+        .description("Manifest of the release")
+        .execute()
+        .unwrap();
+
+    let files = redmine.files().list(1).execute().unwrap();
+    for file in files {
+        println!("ID: {}, Filename: {}, Downloads: {}", file.id, file.filename, file.downloads);
+    }
+}
@@ -0,0 +1,37 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let group = redmine
+        .groups()
+        .create("Reviewers")
+        .user_ids(vec![1])
+        .execute()
+        .unwrap();
+    println!("Created group {} with id {}", group.name, group.id);
+
+    redmine.groups().add_user(group.id, 3).execute().unwrap();
+    redmine.groups().remove_user(group.id, 1).execute().unwrap();
+
+    let group = redmine
+        .groups()
+        .show(group.id)
+        .include("users")
+        .include("memberships")
+        .execute()
+        .unwrap();
+    for user in group.users {
+        println!("Member: {}", user.name());
+    }
+    for membership in group.memberships {
+        println!("Project: {}", membership.project.name());
+    }
+
+    redmine.groups().delete(group.id).execute().unwrap();
+}
@@ -0,0 +1,266 @@
+//! Interactive terminal issue browser. Lists issues page by page, narrows them down by filters,
+//! shows single issues with their journals and comments on or closes them.
+//!
+//! Run with `cargo run --example issue_browser --features tui`. The redmine application is taken
+//! from the `REDMINE_HOST` and `REDMINE_KEY` environment variables.
+
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::errors::*;
+use redmine_api::issues::{Issue, IssueFilter};
+use redmine_api::metadata::MetadataCache;
+use std::env;
+use std::io::{self, BufRead, Write};
+
+/// Number of issues shown per page.
+const PAGE_SIZE: u32 = 20;
+
+/// Filters of the issue list as entered by the user.
+#[derive(Default)]
+struct Filters {
+    project_id: Option<u32>,
+    tracker_id: Option<u32>,
+    assigned_to_me: bool,
+    closed: bool,
+}
+impl Filters {
+    /// Parses filters like `project=1 tracker=2 mine closed`.
+    fn parse(input: &str) -> Result<Filters> {
+        let mut filters = Filters::default();
+        for token in input.split_whitespace() {
+            let mut parts = token.splitn(2, '=');
+            match (parts.next(), parts.next()) {
+                (Some("project"), Some(id)) => filters.project_id = Some(parse_id(id)?),
+                (Some("tracker"), Some(id)) => filters.tracker_id = Some(parse_id(id)?),
+                (Some("mine"), None) => filters.assigned_to_me = true,
+                (Some("closed"), None) => filters.closed = true,
+                _ => return Err(format!("Unknown filter: {}", token).into()),
+            }
+        }
+
+        Ok(filters)
+    }
+
+    /// Applies the filters to an issue filter of the api.
+    fn apply(&self, filter: &mut IssueFilter) {
+        if let Some(id) = self.project_id {
+            filter.project_id(id);
+        }
+        if let Some(id) = self.tracker_id {
+            filter.tracker_id(id);
+        }
+        if self.assigned_to_me {
+            filter.assigned_to_me();
+        }
+        if self.closed {
+            filter.closed();
+        }
+    }
+}
+
+/// The screens of the browser.
+enum Screen {
+    List,
+    Detail(u32),
+    Quit,
+}
+
+struct Browser {
+    redmine: RedmineApi,
+    metadata: MetadataCache,
+    filters: Filters,
+    offset: u32,
+    message: Option<String>,
+}
+impl Browser {
+    /// Shows a page of issues and handles the command entered afterwards.
+    fn list(&mut self) -> Result<Screen> {
+        let mut filter = self.redmine.issues().list();
+        self.filters.apply(&mut filter);
+        let result = filter.offset(self.offset).limit(PAGE_SIZE).execute()?;
+        let total = result.total_count().unwrap_or(0);
+
+        clear();
+        println!("Issues {}-{} of {}\n", self.offset + 1, self.offset + PAGE_SIZE, total);
+        for issue in result.issues() {
+            println!(
+                "{:>6}  {:<12} {:<12} {}",
+                issue.id,
+                shorten(issue.status.name(), 12),
+                shorten(issue.assigned_to.as_ref().map_or("-", |a| a.name()), 12),
+                shorten(&issue.subject, 50)
+            );
+        }
+        self.footer(
+            "<id> open, n/p next/previous page, f <filters> filter \
+             (project=<id> tracker=<id> mine closed), f reset, q quit",
+        );
+
+        let input = prompt()?;
+        let mut words = input.splitn(2, ' ');
+        match (words.next().unwrap_or(""), words.next()) {
+            ("q", _) => return Ok(Screen::Quit),
+            ("n", _) if self.offset + PAGE_SIZE < total => self.offset += PAGE_SIZE,
+            ("n", _) => {}
+            ("p", _) => self.offset = self.offset.saturating_sub(PAGE_SIZE),
+            ("f", filters) => {
+                self.filters = Filters::parse(filters.unwrap_or(""))?;
+                self.offset = 0;
+            }
+            (id, None) if !id.is_empty() => return Ok(Screen::Detail(parse_id(id)?)),
+            _ => {}
+        }
+
+        Ok(Screen::List)
+    }
+
+    /// Shows a single issue with its journals and handles the command entered afterwards.
+    fn detail(&mut self, id: u32) -> Result<Screen> {
+        let issue = self.redmine.issues().show(id).include("journals").execute()?;
+
+        clear();
+        print_issue(&issue);
+        self.footer("c <text> comment, x close, b back, q quit");
+
+        let input = prompt()?;
+        let mut words = input.splitn(2, ' ');
+        match (words.next().unwrap_or(""), words.next()) {
+            ("q", _) => return Ok(Screen::Quit),
+            ("b", _) => return Ok(Screen::List),
+            ("c", Some(text)) => {
+                self.redmine.issues().update(id).notes(text).execute()?;
+                self.message = Some("Comment added".to_string());
+            }
+            ("x", _) => {
+                let statuses = self.metadata.issue_statuses()?;
+                match statuses.iter().find(|s| s.is_closed) {
+                    Some(status) => {
+                        self.redmine.issues().update(id).status_id(status.id).execute()?;
+                        self.message = Some(format!("Issue set to {}", status.name));
+                    }
+                    None => return Err("There is no closed issue status".into()),
+                }
+            }
+            _ => {}
+        }
+
+        Ok(Screen::Detail(id))
+    }
+
+    /// Prints the message of the last action, if any, and the available commands.
+    fn footer(&mut self, commands: &str) {
+        println!();
+        if let Some(message) = self.message.take() {
+            println!("{}", message);
+        }
+        println!("{}", commands);
+    }
+}
+
+/// Prints the fields, description and journals of an issue.
+fn print_issue(issue: &Issue) {
+    println!("#{} {}\n", issue.id, issue.subject);
+    println!("Project:  {}", issue.project.name());
+    println!("Tracker:  {}", issue.tracker.name());
+    println!("Status:   {}", issue.status.name());
+    println!("Priority: {}", issue.priority.name());
+    println!("Author:   {}", issue.author.name());
+    println!(
+        "Assignee: {}",
+        issue.assigned_to.as_ref().map_or("-", |a| a.name())
+    );
+    println!("Done:     {}%", issue.done_ratio);
+    println!("\n{}", issue.description.as_ref().map_or("", |d| d.as_str()));
+
+    for journal in &issue.journals {
+        println!("\n--- {} on {}", journal.user.name(), journal.created_on);
+        for detail in &journal.details {
+            println!(
+                "    {} changed from {} to {}",
+                detail.name,
+                detail.old_value.as_ref().map_or("-", |v| v.as_str()),
+                detail.new_value.as_ref().map_or("-", |v| v.as_str())
+            );
+        }
+        if let Some(ref notes) = journal.notes {
+            if !notes.is_empty() {
+                println!("{}", notes);
+            }
+        }
+    }
+}
+
+/// Clears the terminal and moves the cursor to the top left corner.
+fn clear() {
+    print!("\x1b[2J\x1b[H");
+}
+
+/// Reads a line entered by the user.
+fn prompt() -> Result<String> {
+    print!("> ");
+    io::stdout().flush()?;
+
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        return Ok("q".to_string());
+    }
+
+    Ok(line.trim().to_string())
+}
+
+/// Shortens `s` to `width` characters.
+fn shorten(s: &str, width: usize) -> String {
+    if s.chars().count() <= width {
+        s.to_string()
+    } else {
+        s.chars().take(width - 1).chain(Some('…')).collect()
+    }
+}
+
+fn parse_id(s: &str) -> Result<u32> {
+    s.parse().chain_err(|| format!("Invalid id: {}", s))
+}
+
+fn main() {
+    let host = env::var("REDMINE_HOST").unwrap_or_else(|_| "http://localhost:8080".to_string());
+    let apikey = env::var("REDMINE_KEY").unwrap_or_else(
+        |_| "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let redmine = RedmineApi::new(host, apikey);
+    let mut browser = Browser {
+        metadata: MetadataCache::new(&redmine),
+        redmine: redmine,
+        filters: Filters::default(),
+        offset: 0,
+        message: None,
+    };
+
+    let mut screen = Screen::List;
+    loop {
+        let result = match screen {
+            Screen::List => browser.list(),
+            Screen::Detail(id) => browser.detail(id),
+            Screen::Quit => break,
+        };
+
+        screen = match result {
+            Ok(next) => next,
+            Err(e) => {
+                println!("\nError: {}\nPress enter to continue or q to quit", e);
+                match prompt() {
+                    Ok(ref input) if input != "q" => {}
+                    _ => break,
+                }
+
+                // leave the failing screen, resetting filters which may have caused the error
+                if let Screen::List = screen {
+                    browser.filters = Filters::default();
+                    browser.offset = 0;
+                }
+                Screen::List
+            }
+        };
+    }
+}
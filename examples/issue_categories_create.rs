@@ -0,0 +1,26 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let category = redmine
+        .issue_categories()
+        .create(1, "Authentication")
+        .assigned_to_id(1)
+        .execute()
+        .unwrap();
+    println!("Created category {} with id {}", category.name, category.id);
+
+    // move the issues of the new category to category 1 and delete it
+    redmine
+        .issue_categories()
+        .delete(category.id)
+        .reassign_to_id(1)
+        .execute()
+        .unwrap();
+}
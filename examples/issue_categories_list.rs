@@ -0,0 +1,20 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.issue_categories().list(1).execute().unwrap();
+    for category in result {
+        println!(
+            "ID: {}, Name: {}, Assignee: {}",
+            category.id,
+            category.name,
+            category.assigned_to.as_ref().map_or("", |a| a.name())
+        );
+    }
+}
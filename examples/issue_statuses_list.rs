@@ -0,0 +1,15 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.issue_statuses().list().execute().unwrap();
+    for status in result {
+        println!("ID: {}, Name: {}, Closed: {}", status.id, status.name, status.is_closed);
+    }
+}
@@ -0,0 +1,16 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.issues().add_watchers(1, vec![1, 5]).execute();
+    println!("Users: {:?}", result);
+
+    let result = redmine.issues().add_group_watchers(1, 10).execute();
+    println!("Group: {:?}", result);
+}
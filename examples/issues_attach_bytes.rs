@@ -0,0 +1,21 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let upload = redmine.uploads().bytes("report.csv", b"id;hours\n1;0.5\n")
+        .content_type("text/csv")
+        .execute()
+        .unwrap();
+
+    let result = redmine.issues().update(1)
+        .notes("Weekly report attached.")
+        .add_upload(upload)
+        .execute();
+    println!("Result: {:?}", result);
+}
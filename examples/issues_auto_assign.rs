@@ -0,0 +1,20 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::assignment::AssignmentStrategy;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let mut filter = redmine.issues().list();
+    filter.project_id(1);
+
+    let result = redmine
+        .issues()
+        .auto_assign(filter, vec![1.into(), 5.into()], AssignmentStrategy::RoundRobin)
+        .execute();
+    println!("Result: {:?}", result);
+}
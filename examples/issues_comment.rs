@@ -0,0 +1,16 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.issues().comment(23, "This is a new note.").execute();
+    println!("Result: {:?}", result);
+
+    let result = redmine.issues().comment_private(23, "This is a private note.").execute();
+    println!("Result: {:?}", result);
+}
@@ -0,0 +1,17 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.issues().conversation(1)
+        .note("Imported from the mailing list:")
+        .notes_many(vec!["> Does the export work?", "> Yes, since 1.2."], false)
+        .private_note("Thread id: 4711")
+        .execute();
+    println!("Result: {:?}", result.map(|issue| issue.id));
+}
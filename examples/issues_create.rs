@@ -14,5 +14,5 @@ fn main() {
         .estimated_hours(3.4)
         .execute();
 
-    println!("Result: {:?}", result);
+    println!("Result: {:?}", result.map(|issue| issue.id));
 }
@@ -0,0 +1,22 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let subject = format!(
+        "[ALERT] Disk usage above 90% on {}",
+        (1..40).map(|i| format!("node-{:02}", i)).collect::<Vec<_>>().join(", ")
+    );
+
+    let result = redmine.issues().create(1, 1, 1, 1, &subject)
+        .description("Raised by monitoring")
+        .truncate_subject(true)
+        .execute();
+
+    println!("Result: {:?}", result.map(|issue| issue.subject));
+}
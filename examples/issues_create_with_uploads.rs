@@ -0,0 +1,23 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let mut uploads = Vec::new();
+    for path in &["Cargo.toml", "README.md"] {
+        let upload = redmine.uploads().file(path)
+            .and_then(|u| u.description("Attached on creation").execute())
+            .unwrap();
+        uploads.push(upload);
+    }
+
+    let result = redmine.issues().create(1, 1, 1, 1, "Issue with attachments")
+        .uploads(uploads)
+        .execute();
+    println!("Result: {:?}", result.map(|issue| issue.id));
+}
@@ -0,0 +1,28 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::errors::ErrorKind;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.issues().update(1)
+        .custom_field_by_name("Severity", "High")
+        .execute();
+
+    match result {
+        Ok(issue) => println!("Updated issue {}", issue.id),
+        Err(e) => {
+            match *e.kind() {
+                ErrorKind::UnknownCustomField(ref name) => println!("No custom field {}", name),
+                ErrorKind::InvalidCustomFieldValue(ref name, ref value) => {
+                    println!("{} isn't a possible value of {}", value, name)
+                }
+                _ => println!("Error: {}", e),
+            }
+        }
+    }
+}
@@ -0,0 +1,23 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::issues::{IssueBuilder, IssueFilter};
+
+fn main() {
+    // requests can be built without a redmine application ...
+    let builder = IssueBuilder::detached(1, 1, 1, 1, "Nightly build failed")
+        .description("See the build log.");
+    println!("Body: {}", builder.to_json().unwrap());
+
+    let mut filter = IssueFilter::detached();
+    filter.project_id(1).unassigned();
+    println!("Query: {:?}", filter.query_pairs());
+
+    // ... and bound to one for execution
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+    println!("Created: {:?}", builder.bind(&redmine).execute().map(|i| i.id));
+    println!("Found: {:?}", filter.bind(&redmine).execute().map(|l| l.total_count()));
+}
@@ -0,0 +1,20 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let before = redmine.issues().show(1).execute().expect("Can't get issue");
+    thread::sleep(Duration::from_secs(60));
+    let after = redmine.issues().show(1).execute().expect("Can't get issue");
+
+    for change in before.diff(&after) {
+        println!("{}", change);
+    }
+}
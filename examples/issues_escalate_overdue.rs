@@ -0,0 +1,23 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::escalation::EscalationPolicy;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let mut filter = redmine.issues().list();
+    filter.project_id(1);
+
+    let policy = EscalationPolicy::new()
+        .overdue()
+        .stale_after_days(14)
+        .priority_id(4)
+        .note("Issue #{id} is overdue since {due_date}, please have a look.");
+
+    let result = redmine.issues().escalate_overdue(filter, policy).execute();
+    println!("Result: {:?}", result);
+}
@@ -0,0 +1,28 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let groups = match redmine.groups().cache() {
+        Ok(groups) => groups,
+        Err(e) => return println!("Error: {}", e),
+    };
+
+    // issues assigned to group 7 itself or to one of its members
+    let mut filter = redmine.issues().list();
+    filter.member_of_group(7);
+
+    match filter.execute_all() {
+        Ok(list) => {
+            for issue in list {
+                println!("#{}: {:?}", issue.id, groups.assignee(&issue));
+            }
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+}
@@ -0,0 +1,14 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let journals = redmine.issues().journals(1);
+    println!("Count: {:?}", journals.len());
+    println!("First: {:?}", journals.get(0));
+}
@@ -0,0 +1,36 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::budget::Budget;
+use std::time::Duration;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let mut continuation = None;
+    loop {
+        // every call returns after about a second, so the progress can be shown in between
+        let budget = Budget::new(Duration::from_secs(1));
+        let partial = redmine
+            .issues()
+            .list()
+            .any_status()
+            .execute_within(&budget, continuation.as_ref())
+            .unwrap();
+
+        for issue in partial.result {
+            println!("ID: {}, Subject: {}", issue.id, issue.subject);
+        }
+
+        match partial.continuation {
+            Some(c) => {
+                println!("-- continuing at {}", c);
+                continuation = Some(c);
+            }
+            None => break,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let list = redmine.issues().list()
+        .project_id(1)
+        .prefetch()
+        .execute()
+        .unwrap();
+
+    let resolver = list.resolver().unwrap();
+    for issue in list.issues() {
+        match resolver.assigned_to(issue) {
+            Some(user) => println!("#{} {} {}", issue.id, user.firstname, user.lastname),
+            None => println!("#{} unassigned", issue.id),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let q = query!(project == 1 && status.is_open() && assigned_to.is_none() &&
+                   updated_on > 7.days_ago()).unwrap();
+    let result = redmine.issues().list().query(&q).execute();
+    println!("Result: {:?}", result);
+}
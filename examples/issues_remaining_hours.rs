@@ -0,0 +1,17 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let issue = redmine.issues().show(1).execute().unwrap();
+    println!(
+        "Remaining: {:?} h ({:?} h without subtasks)",
+        issue.remaining_hours(),
+        issue.own_remaining_hours()
+    );
+}
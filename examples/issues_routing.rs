@@ -0,0 +1,49 @@
+extern crate redmine_api;
+extern crate serde_json;
+
+use redmine_api::RedmineApi;
+use redmine_api::issues::Issue;
+use redmine_api::routing::{Action, RoutingRules};
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let rules: RoutingRules = serde_json::from_str(
+        r##"{
+          "rules": [
+            {"name": "noise", "when": {"changed": ["% Done"]}, "then": "ignore"},
+            {"name": "urgent", "when": {"priority": ["Urgent"]}, "then": "escalate",
+             "continue": true},
+            {"name": "ops", "when": {"project": [1]}, "then": {"notify": "#ops"}}
+          ]
+        }"##,
+    ).unwrap();
+
+    let mut known: HashMap<u32, Issue> = HashMap::new();
+    loop {
+        let issues = redmine.issues().list().project_id(1).execute().unwrap();
+        for issue in issues {
+            let changes = known.get(&issue.id).map_or(Vec::new(), |old| old.diff(&issue));
+            if known.contains_key(&issue.id) && changes.is_empty() {
+                continue;
+            }
+
+            for decision in rules.decide(&issue, &changes) {
+                match decision.action {
+                    Action::Notify(channel) => println!("{}: #{}", channel, issue.id),
+                    Action::Escalate => println!("Escalating #{}", issue.id),
+                    Action::Ignore => {}
+                }
+            }
+            known.insert(issue.id, issue);
+        }
+
+        thread::sleep(Duration::from_secs(60));
+    }
+}
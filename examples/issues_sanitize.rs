@@ -0,0 +1,22 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::sanitize::escape;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let issue = redmine.issues().show(1).execute().unwrap();
+    let description = issue.description.unwrap_or_default();
+
+    // the raw markup is shown as is, so it only has to be escaped
+    println!("<h1>{}</h1>", escape(&issue.subject));
+    println!("<pre>{}</pre>", escape(&description));
+
+    // html rendered from it, e.g. by a textile library, has to be cleaned
+    let rendered = format!("<p>{}</p>", description.replace("\n\n", "</p><p>"));
+    println!("{}", redmine.sanitizer().clean_html(&rendered));
+}
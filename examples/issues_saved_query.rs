@@ -0,0 +1,19 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let queries = redmine.saved_queries().list().execute().unwrap();
+    for q in queries {
+        let result = redmine.issues().list().saved_query(&q).execute().unwrap();
+        println!("Query {} ({}) matches:", q.name, q.id);
+        for issue in result.issues() {
+            println!("  ID: {}, Subject: {}", issue.id, issue.subject);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let checked = redmine.issues().show(1)
+        .include("journals")
+        .include("watchers")
+        .execute_checked()
+        .unwrap();
+
+    println!("Issue: {}, Journals: {}", checked.value.subject, checked.value.journals.len());
+    for warning in &checked.warnings {
+        println!("Warning: {}", warning);
+    }
+}
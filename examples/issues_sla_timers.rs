@@ -0,0 +1,16 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let issue = redmine.issues().show(1).include("journals").execute().unwrap();
+
+    println!("First response: {:?}", issue.first_response_time());
+    println!("First assignment: {:?}", issue.time_to_first_assignment());
+    println!("Time per status: {:?}", issue.time_per_status());
+}
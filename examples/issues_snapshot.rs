@@ -0,0 +1,14 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let before = redmine.issues().show(1).snapshot().unwrap();
+    let after = redmine.issues().show(1).snapshot().unwrap();
+    println!("Changed fields: {:?}", before.changed_fields(&after));
+}
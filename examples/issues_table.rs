@@ -0,0 +1,20 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    match redmine.issues().list().project_id(1).execute() {
+        Ok(list) => {
+            println!("{}", list.table());
+            for issue in list {
+                println!("{}", issue);
+            }
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+}
@@ -0,0 +1,19 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::tags::TagField;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+    let tags = TagField::new(1);
+
+    let result = redmine.issues().update(1).add_tag(&tags, "infra").execute();
+    println!("Result: {:?}", result);
+
+    for issue in redmine.issues().list().tag(&tags, "infra").execute().unwrap() {
+        println!("#{} {:?}", issue.id, issue.tags(&tags));
+    }
+}
@@ -0,0 +1,31 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    match redmine.issues().triage_inbox(1).execute() {
+        Ok(items) => {
+            for item in items {
+                println!(
+                    "{} (author reported {} before)",
+                    item.issue,
+                    item.prior_reports
+                );
+                for candidate in item.duplicate_candidates {
+                    println!(
+                        "    duplicate of #{} {}? ({:.0}%)",
+                        candidate.id,
+                        candidate.subject,
+                        candidate.similarity * 100.0
+                    );
+                }
+            }
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+}
@@ -0,0 +1,21 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::errors::ErrorKind;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    match redmine.issues().update(1).execute() {
+        Err(e) => {
+            match *e.kind() {
+                ErrorKind::NothingToUpdate(id) => println!("Nothing set for issue {}", id),
+                _ => println!("Error: {}", e),
+            }
+        }
+        Ok(result) => println!("Result: {:?}", result),
+    }
+}
@@ -0,0 +1,13 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.memberships().create(1, 5, vec![3]).execute();
+    println!("Result: {:?}", result.map(|membership| membership.id));
+}
@@ -0,0 +1,22 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.memberships().list(1).limit(100).execute().unwrap();
+    for membership in result {
+        let member = membership.user.or(membership.group);
+        let roles: Vec<String> = membership.roles.into_iter().map(|r| r.name).collect();
+        println!(
+            "ID: {}, Member: {}, Roles: {}",
+            membership.id,
+            member.as_ref().map_or("", |m| m.name()),
+            roles.join(", ")
+        );
+    }
+}
@@ -0,0 +1,35 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::metadata::MetadataCache;
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+    let cache = MetadataCache::new(&redmine).ttl(Duration::from_secs(60));
+
+    let workers: Vec<_> = (0..2)
+        .map(|n| {
+            let cache = cache.clone();
+            thread::spawn(move || {
+                for tracker in cache.trackers().unwrap().iter() {
+                    println!("Worker {}: Tracker {} ({})", n, tracker.name, tracker.id);
+                }
+                for status in cache.issue_statuses().unwrap().iter() {
+                    println!("Worker {}: Status {}, closed: {}", n, status.name, status.is_closed);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    // pull the metadata again, e.g. after an administrator has added a tracker
+    cache.refresh().unwrap();
+    println!("Trackers: {}", cache.trackers().unwrap().len());
+}
@@ -0,0 +1,21 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::mirror::{self, Mapping};
+
+fn main() {
+    let src = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+    let dst = RedmineApi::new(
+        "http://localhost:8081".to_string(),
+        "96b3ddaa1d27af3f7cb8adf0910e4c954f437917".to_string(),
+    );
+
+    let mut mapping = Mapping::new(1, 2);
+    mapping.trackers.insert(1, 3);
+
+    let result = mirror::sync_issue(&src, &dst, 1, &mapping);
+    println!("Result: {:?}", result);
+}
@@ -0,0 +1,23 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    redmine
+        .news()
+        .create(1, "Version 1.0 released")
+        .summary("Fixes the LDAP login")
+        .description("See the changelog for details.")
+        .execute()
+        .unwrap();
+
+    let result = redmine.news().list_for_project(1).limit(5).execute().unwrap();
+    for news in result {
+        println!("ID: {}, Title: {}, Author: {}", news.id, news.title, news.author.name());
+    }
+}
@@ -0,0 +1,20 @@
+extern crate futures;
+extern crate redmine_api;
+
+use futures::Future;
+use redmine_api::RedmineApi;
+use redmine_api::nonblocking::AsyncRedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+    let redmine = AsyncRedmineApi::new(&redmine).unwrap();
+
+    let issues = redmine.run(|api| api.issues().list().execute());
+    let projects = redmine.run(|api| api.projects().list().execute());
+
+    let result = issues.join(projects).wait();
+    println!("Result: {:?}", result);
+}
@@ -0,0 +1,29 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::issues::IssueBuilder;
+use redmine_api::outbox::Outbox;
+
+fn main() {
+    // comments written offline are persisted right away ...
+    let outbox = Outbox::open("redmine-outbox").unwrap();
+    outbox.comment(1, "Replaced the toner, printing works again").unwrap();
+    outbox
+        .push(IssueBuilder::detached_update(1).status_id(3).to_pending_write().unwrap())
+        .unwrap();
+    println!("Pending writes: {}", outbox.pending().unwrap().len());
+
+    // ... and sent once redmine can be reached
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+    let report = outbox.flush(&redmine).unwrap();
+    println!("Delivered: {:?}", report.delivered);
+    for (entry, message) in &report.rejected {
+        println!("Rejected {} {}: {}", entry.id, entry.write.path, message);
+    }
+    if let Some(error) = report.error {
+        println!("{} writes still pending: {}", report.pending, error);
+    }
+}
@@ -0,0 +1,19 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    match redmine.projects().activity(1).execute() {
+        Ok(events) => {
+            for event in events {
+                println!("{} {:?}: {}", event.updated, event.kind, event.title);
+            }
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+}
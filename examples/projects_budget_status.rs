@@ -0,0 +1,16 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::projects::BudgetSource;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.projects().budget_status(1, BudgetSource::VersionEstimates)
+        .warn_at(0.8)
+        .execute();
+    println!("Result: {:?}", result);
+}
@@ -0,0 +1,23 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine
+        .projects()
+        .update(1)
+        .default_version_id(2)
+        .default_assigned_to_id(5)
+        .execute();
+    println!("Result: {:?}", result);
+
+    if let Ok(project) = redmine.projects().show(1).execute() {
+        println!("Default version: {:?}", project.default_version);
+        println!("Default assignee: {:?}", project.default_assignee);
+    }
+}
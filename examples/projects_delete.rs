@@ -7,6 +7,7 @@ fn main() {
         "http://localhost:8080".to_string(),
         "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
     );
+    redmine.allow_destructive();
 
     let result = redmine.projects().delete(3).execute();
     println!("Result: {:?}", result);
@@ -0,0 +1,16 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::digest::DigestFormat;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    match redmine.projects().digest(1).execute() {
+        Ok(digest) => println!("{}", digest.render(DigestFormat::Html)),
+        Err(e) => println!("Error: {}", e),
+    }
+}
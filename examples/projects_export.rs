@@ -0,0 +1,15 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use std::fs::File;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let mut file = File::create("project_1.json").unwrap();
+    let result = redmine.export_all(1).write_to(&mut file);
+    println!("Result: {:?}", result);
+}
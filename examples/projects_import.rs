@@ -0,0 +1,26 @@
+extern crate redmine_api;
+extern crate serde_json;
+
+use redmine_api::RedmineApi;
+use redmine_api::export::ProjectExport;
+use redmine_api::import::ImportOptions;
+use std::fs::File;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let file = File::open("project_1.json").unwrap();
+    let export: ProjectExport = serde_json::from_reader(file).unwrap();
+
+    let mut options = ImportOptions {
+        identifier: Some("restored".to_string()),
+        ..Default::default()
+    };
+    options.users.insert(1, 1);
+
+    let result = redmine.import(&export, options).execute();
+    println!("Result: {:?}", result);
+}
@@ -0,0 +1,19 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::projects::ProjectStatus;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.projects().list()
+        .status(ProjectStatus::Archived)
+        .execute()
+        .unwrap();
+    for project in result.archived() {
+        println!("Archived: {}", project);
+    }
+}
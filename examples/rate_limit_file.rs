@@ -0,0 +1,22 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::ratelimit::FileRateLimiter;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    // all processes using this file share 5 requests per second
+    match FileRateLimiter::new("/tmp/redmine.bucket", 5.0, 5) {
+        Ok(limiter) => redmine.rate_limit(limiter),
+        Err(e) => return println!("Error: {}", e),
+    }
+
+    for id in 1..20 {
+        let result = redmine.issues().show(id).execute();
+        println!("Issue {}: {:?}", id, result.map(|issue| issue.subject));
+    }
+}
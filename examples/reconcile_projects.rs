@@ -0,0 +1,33 @@
+extern crate redmine_api;
+extern crate serde_json;
+
+use redmine_api::RedmineApi;
+use redmine_api::reconcile::{Reconciler, Spec};
+use std::env;
+use std::fs::File;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    // pass the spec file and `--apply` to apply the plan
+    let args: Vec<String> = env::args().collect();
+    let path = args.get(1).map_or("projects.json", |s| s.as_str());
+    let apply = args.iter().any(|a| a == "--apply");
+
+    let spec: Spec = serde_json::from_reader(File::open(path).unwrap()).unwrap();
+    let plan = Reconciler::new(&redmine, spec).prune(true).plan().unwrap();
+    println!("{}", plan);
+
+    if !apply || plan.is_empty() {
+        return;
+    }
+
+    let report = plan.apply(&redmine).unwrap();
+    println!("Applied {} changes", report.applied.len());
+    if let Some((change, error)) = report.failed {
+        println!("Failed: {}: {}", change, error);
+    }
+}
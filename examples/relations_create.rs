@@ -0,0 +1,17 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::relations::RelationType;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine
+        .relations()
+        .create(1, 2, RelationType::Precedes { delay: Some(2) })
+        .execute();
+    println!("Result: {:?}", result);
+}
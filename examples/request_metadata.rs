@@ -0,0 +1,38 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    redmine.request_metadata(|_method, _path| {
+        vec![("X-Origin".to_string(), "deploy-bot".to_string())]
+    });
+
+    // append each write to an audit log
+    redmine.observe(|event| if event.is_write() {
+        let mut log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open("redmine_audit.log")
+            .unwrap();
+        writeln!(
+            log,
+            "{:?} {} status={:?} correlation_id={}",
+            event.method,
+            event.url,
+            event.status,
+            event.header("X-Correlation-Id").unwrap_or("-")
+        ).unwrap();
+    });
+
+    let result = redmine.with_headers(&[("X-Correlation-Id", "deploy-1234")], |api| {
+        api.issues().update(1).notes("Deployed to production").execute()
+    });
+    println!("Result: {:?}", result);
+}
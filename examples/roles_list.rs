@@ -0,0 +1,21 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let roles = redmine.roles().list().execute().unwrap();
+    for role in roles {
+        let role = redmine.roles().show(role.id).execute().unwrap();
+        println!(
+            "ID: {}, Name: {}, Permissions: {}",
+            role.id,
+            role.name,
+            role.permissions.join(", ")
+        );
+    }
+}
@@ -0,0 +1,30 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::search::{SearchResult, SearchScope};
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let results = redmine
+        .search()
+        .query("login")
+        .scope(SearchScope::MyProjects)
+        .titles_only(true)
+        .issues()
+        .news()
+        .limit(10)
+        .execute()
+        .unwrap();
+    println!("Found {:?} results", results.total_count());
+    for result in results {
+        match result {
+            SearchResult::Issue(hit) => println!("Issue: {} ({})", hit.title, hit.url),
+            SearchResult::News(hit) => println!("News: {} ({})", hit.title, hit.url),
+            other => println!("Other: {}", other.hit().title),
+        }
+    }
+}
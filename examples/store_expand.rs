@@ -0,0 +1,28 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::projects::Project;
+use redmine_api::users::User;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+    let store = redmine.store();
+
+    let result = redmine.issues().list().execute().unwrap();
+    for issue in result {
+        // every author and project is requested only once
+        let author = store.expand::<User>(&issue.author).unwrap();
+        let project = store.expand::<Project>(&issue.project).unwrap();
+        println!(
+            "ID: {}, Project: {}, Author: {} <{}>",
+            issue.id,
+            project.identifier,
+            author.login,
+            author.mail
+        );
+    }
+    println!("Entities in store: {}", store.len());
+}
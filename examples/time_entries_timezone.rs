@@ -0,0 +1,26 @@
+extern crate chrono;
+extern crate redmine_api;
+
+use chrono::{FixedOffset, Utc};
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+    // the team logs time in UTC+2
+    redmine.set_timezone(FixedOffset::east_opt(2 * 3600).unwrap());
+
+    let now = Utc::now();
+    let result = redmine
+        .time_entries()
+        .create(1, 0.5, 9)
+        .spent_at(&now)
+        .comments("late fix")
+        .execute();
+    println!("Result: {:?}", result);
+
+    let result = redmine.time_entries().list().from_datetime(&now).execute();
+    println!("Today: {:?}", result);
+}
@@ -0,0 +1,25 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let trackers = redmine.trackers().list().execute().unwrap();
+    let bug = trackers.by_name("Bug").expect("No tracker named Bug");
+
+    let status_id = bug.default_status.as_ref().map_or(1, |s| s.id());
+    let id = redmine
+        .issues()
+        .create(1, bug.id, status_id, 2, "Login fails")
+        .execute()
+        .unwrap();
+    println!("Created bug #{}", id);
+
+    for tracker in trackers {
+        println!("ID: {}, Name: {}", tracker.id, tracker.name);
+    }
+}
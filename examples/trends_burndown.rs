@@ -0,0 +1,35 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::trends::TrendStore;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    // run daily, e.g. by cron, to build up the history
+    let store = TrendStore::open("trends.sqlite").unwrap();
+    let counts = store.record(&redmine, 1).unwrap();
+    for count in counts {
+        println!(
+            "Version {}, {}: {} issues",
+            count.version_id,
+            count.status,
+            count.issues
+        );
+    }
+
+    println!("Date        Open  Closed  Total  Open hours");
+    for point in store.series(1, None).unwrap() {
+        println!(
+            "{}  {:>4}  {:>6}  {:>5}  {:>10.1}",
+            point.date,
+            point.open,
+            point.closed,
+            point.total,
+            point.open_estimated_hours
+        );
+    }
+}
@@ -0,0 +1,23 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let upload = redmine.uploads().resumable("backup.tar.gz").unwrap()
+        .content_type("application/gzip")
+        .retries(5)
+        .token_file("backup.tar.gz.token")
+        .progress(|sent, total| println!("{} of {} bytes sent", sent, total))
+        .execute()
+        .unwrap();
+
+    let result = redmine.issues().update(1)
+        .add_upload(upload)
+        .execute();
+    println!("Result: {:?}", result);
+}
@@ -7,6 +7,7 @@ fn main() {
         "http://localhost:8080".to_string(),
         "96b3ddaa1d27af3f7cb8adf0910e4c954f437917".to_string(),
     );
+    redmine.allow_destructive();
 
     let result = redmine.users().delete(6).execute();
     println!("Result: {:?}", result);
@@ -0,0 +1,15 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let user = redmine.users().show(1).include("memberships").execute().unwrap();
+    for m in user.memberships_with_role("Manager") {
+        println!("Manager of {} ({})", m.project.name(), m.project.id());
+    }
+}
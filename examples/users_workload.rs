@@ -0,0 +1,16 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine
+        .users()
+        .workload(vec![1, 5], "2017-09-01", "2017-09-30")
+        .execute();
+    println!("Result: {:?}", result);
+}
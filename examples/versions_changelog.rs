@@ -0,0 +1,20 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::versions::ChangelogFormat;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine
+        .versions()
+        .changelog(1, ChangelogFormat::Markdown)
+        .execute();
+    match result {
+        Ok(text) => println!("{}", text),
+        Err(e) => println!("Error: {}", e),
+    }
+}
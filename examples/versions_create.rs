@@ -0,0 +1,20 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::versions::{VersionSharing, VersionStatus};
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.versions().create(1, "2.0")
+        .status(VersionStatus::Open)
+        .sharing(VersionSharing::Descendants)
+        .due_date("2018-03-31")
+        .description("Second major release")
+        .execute();
+
+    println!("Result: {:?}", result.map(|version| version.id));
+}
@@ -0,0 +1,22 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+use redmine_api::versions::VersionStatus;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let result = redmine.versions().list(1).execute().unwrap();
+    for version in result.into_iter().filter(|v| v.status != VersionStatus::Closed) {
+        println!(
+            "ID: {}, Name: {}, Due: {}, Sharing: {:?}",
+            version.id,
+            version.name,
+            version.due_date.unwrap_or_default(),
+            version.sharing
+        );
+    }
+}
@@ -0,0 +1,18 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    // redmine installed in a sub path
+    let redmine = RedmineApi::new(
+        "http://localhost:8080/redmine/".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    if let Ok(issue) = redmine.issues().show(1).execute() {
+        println!("Issue: {}", issue.web_url(&redmine));
+    }
+    if let Ok(project) = redmine.projects().show(1).execute() {
+        println!("Project: {}", project.web_url(&redmine));
+    }
+}
@@ -0,0 +1,16 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let pages = redmine.wiki().index(1).execute().unwrap();
+    for page in pages {
+        let parent = page.parent.map(|p| p.title).unwrap_or_default();
+        println!("Title: {}, Parent: {}, Version: {}", page.title, parent, page.version);
+    }
+}
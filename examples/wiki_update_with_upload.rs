@@ -0,0 +1,21 @@
+extern crate redmine_api;
+
+use redmine_api::RedmineApi;
+
+fn main() {
+    let redmine = RedmineApi::new(
+        "http://localhost:8080".to_string(),
+        "bbde69d1999dde8f497199f49bb7b577389b6c0e".to_string(),
+    );
+
+    let upload = redmine.uploads().file("diagram.png").unwrap()
+        .content_type("image/png")
+        .execute()
+        .unwrap();
+
+    let result = redmine.wiki().update(1, "Architecture")
+        .text("h1. Architecture\n\n!diagram.png!")
+        .add_upload(upload)
+        .execute();
+    println!("Result: {:?}", result);
+}
@@ -0,0 +1,209 @@
+//! This module reads the activity of a project from the Atom feed redmine provides, which is a
+//! lightweight alternative to polling the issues, wiki and repository apis for changes. Requires
+//! the `activity` feature.
+
+use std::collections::HashMap;
+use xml::reader::{EventReader, XmlEvent};
+use super::errors::*;
+use super::ClientHandle;
+
+/// Struct to provide builder pattern for activity feeds. Is used as return type for
+/// [projects.activity](../projects/struct.Api.html#method.activity).
+pub struct ActivityFeed {
+    client: ClientHandle,
+    project_id: u32,
+    from: Option<String>,
+    with_subprojects: Option<bool>,
+}
+impl ActivityFeed {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle, project_id: u32) -> Self {
+        ActivityFeed {
+            client: client,
+            project_id: project_id,
+            from: None,
+            with_subprojects: None,
+        }
+    }
+
+    /// Sets the last day of the feed. Redmine returns the activity of the days before, newest
+    /// first.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - a string slice holding the date in `YYYY-MM-DD` format
+    pub fn from(mut self, date: &str) -> Self {
+        self.from = Some(date.to_string());
+        self
+    }
+
+    /// Sets whether the activity of subprojects is included.
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - a boolean: true means subprojects are included
+    pub fn with_subprojects(mut self, b: bool) -> Self {
+        self.with_subprojects = Some(b);
+        self
+    }
+
+    /// Performs request to redmine application and returns the events of the feed.
+    pub fn execute(&self) -> Result<Vec<ActivityEvent>> {
+        let mut params: HashMap<&str, String> = HashMap::new();
+        if let Some(ref d) = self.from {
+            params.insert("from", d.clone());
+        }
+        if let Some(b) = self.with_subprojects {
+            params.insert("with_subprojects", if b { "1" } else { "0" }.to_string());
+        }
+
+        let result = self.client.get(
+            &format!("/projects/{}/activity.atom", self.project_id),
+            &params,
+        )?;
+
+        parse_feed(&result)
+    }
+}
+
+/// Enumeration of the kinds of events, derived from the link of an event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActivityKind {
+    /// An issue has been created or edited.
+    Issue { id: u32 },
+    /// A wiki page has been created or edited.
+    WikiEdit { page: String },
+    /// A changeset has been committed to a repository.
+    Changeset { revision: String },
+    News,
+    Message,
+    Document,
+    File,
+    /// An event of a kind unknown to this library, e.g. added by a plugin.
+    Other,
+}
+impl ActivityKind {
+    /// Derives the kind of an event from its link.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - a string slice holding the link of the event
+    fn from_url(url: &str) -> Self {
+        // skip anchors and query strings like `#change-12` or `?version=3`
+        let path = url.split(&['#', '?'][..]).next().unwrap_or("");
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let after = |name: &str| {
+            segments.iter().position(|s| *s == name).and_then(
+                |i| segments.get(i + 1),
+            )
+        };
+
+        if let Some(id) = after("issues").and_then(|id| id.parse::<u32>().ok()) {
+            return ActivityKind::Issue { id: id };
+        }
+        if let Some(page) = after("wiki") {
+            return ActivityKind::WikiEdit { page: page.to_string() };
+        }
+        if let Some(revision) = after("revisions") {
+            return ActivityKind::Changeset { revision: revision.to_string() };
+        }
+        if after("news").is_some() {
+            return ActivityKind::News;
+        }
+        if after("boards").is_some() {
+            return ActivityKind::Message;
+        }
+        if after("documents").is_some() {
+            return ActivityKind::Document;
+        }
+        if segments.contains(&"files") || segments.contains(&"attachments") {
+            return ActivityKind::File;
+        }
+
+        ActivityKind::Other
+    }
+}
+
+impl Default for ActivityKind {
+    fn default() -> Self {
+        ActivityKind::Other
+    }
+}
+
+/// Represents a single event of an activity feed.
+#[derive(Debug, Default, Clone)]
+pub struct ActivityEvent {
+    pub kind: ActivityKind,
+    pub title: String,
+    pub url: String,
+    pub author: Option<String>,
+    /// The time of the event, e.g. `2017-09-16T10:11:12Z`.
+    pub updated: String,
+    /// The content of the event as html, e.g. the notes of an issue edit.
+    pub content: Option<String>,
+}
+
+/// Parses an Atom feed into events.
+///
+/// # Arguments
+///
+/// * `xml` - a string slice holding the feed
+fn parse_feed(xml: &str) -> Result<Vec<ActivityEvent>> {
+    let mut events = Vec::new();
+    // the event which is being parsed and the names of the open elements
+    let mut event: Option<ActivityEvent> = None;
+    let mut path: Vec<String> = Vec::new();
+
+    for e in EventReader::from_str(xml) {
+        match e.chain_err(|| "Can't parse activity feed")? {
+            XmlEvent::StartElement { name, attributes, .. } => {
+                if name.local_name == "entry" {
+                    event = Some(ActivityEvent::default());
+                }
+                if let Some(ref mut ev) = event {
+                    if name.local_name == "link" {
+                        let href = attributes.iter().find(|a| a.name.local_name == "href");
+                        if let Some(href) = href {
+                            ev.url = href.value.clone();
+                        }
+                    }
+                }
+                path.push(name.local_name);
+            }
+            XmlEvent::EndElement { name } => {
+                path.pop();
+                if name.local_name == "entry" {
+                    if let Some(mut ev) = event.take() {
+                        ev.kind = ActivityKind::from_url(&ev.url);
+                        events.push(ev);
+                    }
+                }
+            }
+            XmlEvent::Characters(text) |
+            XmlEvent::CData(text) => {
+                if let Some(ref mut ev) = event {
+                    let element = path.last().map(|s| s.as_str()).unwrap_or("");
+                    let parent = if path.len() > 1 {
+                        path[path.len() - 2].as_str()
+                    } else {
+                        ""
+                    };
+                    match (parent, element) {
+                        ("entry", "title") => ev.title.push_str(&text),
+                        ("entry", "updated") => ev.updated.push_str(&text),
+                        ("entry", "content") => {
+                            ev.content.get_or_insert_with(String::new).push_str(&text)
+                        }
+                        ("author", "name") => {
+                            ev.author.get_or_insert_with(String::new).push_str(&text)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(events)
+}
@@ -0,0 +1,117 @@
+//! This module holds everything needed to read the information redmine shows on its
+//! administration info page: the redmine version and the installed plugins. Requires an api key
+//! of an administrator. The plugins listing isn't exposed by every redmine application, so it's
+//! optional.
+
+extern crate serde_json;
+
+use std::collections::HashMap;
+use super::errors::*;
+use super::http;
+use super::ClientHandle;
+
+/// Struct to provide a unified interface for the admin info api. Is used as return type for
+/// [RedmineApi::server_info](../struct.RedmineApi.html#method.server_info).
+pub struct ServerInfoShow {
+    client: ClientHandle,
+}
+impl ServerInfoShow {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Self {
+        ServerInfoShow { client: client }
+    }
+
+    /// Performs requests to redmine application and returns the server information together
+    /// with the installed plugins, if they are exposed.
+    pub fn execute(&self) -> Result<ServerInfo> {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            info: ServerInfo,
+        }
+
+        let result = self.client.get("/admin/info.json", &HashMap::new())?;
+        let wrapper: Wrapper = serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+        let mut info = wrapper.info;
+
+        if info.plugins.is_none() {
+            info.plugins = self.plugins()?;
+        }
+
+        Ok(info)
+    }
+
+    /// Returns the installed plugins or None if the listing isn't exposed.
+    fn plugins(&self) -> Result<Option<Vec<Plugin>>> {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            plugins: Vec<Plugin>,
+        }
+
+        let url = self.client.get_base_url("/admin/plugins.json")?;
+        let mut response = self.client.send(http::Method::Get, url, None)?;
+        if response.status == 403 || response.status == 404 {
+            return Ok(None);
+        }
+        if !response.is_success() {
+            bail!("Error: {}", response.status);
+        }
+
+        let wrapper: Wrapper = serde_json::from_str(&response.text()?).chain_err(
+            || "Can't parse json",
+        )?;
+
+        Ok(Some(wrapper.plugins))
+    }
+}
+
+/// Represents the information about a redmine application.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ServerInfo {
+    pub redmine_version: String,
+    pub ruby_version: Option<String>,
+    pub rails_version: Option<String>,
+    pub database_adapter: Option<String>,
+    /// The installed plugins. None if the redmine application doesn't expose them.
+    pub plugins: Option<Vec<Plugin>>,
+}
+impl ServerInfo {
+    /// Returns major, minor and patch version of redmine, e.g. `(3, 4, 2)` for `3.4.2.stable`.
+    /// Returns None if the version can't be parsed.
+    pub fn version(&self) -> Option<(u32, u32, u32)> {
+        let mut parts = self.redmine_version.split('.').map(|p| p.parse::<u32>());
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch))) => Some((major, minor, patch)),
+            (Some(Ok(major)), Some(Ok(minor)), _) => Some((major, minor, 0)),
+            _ => None,
+        }
+    }
+
+    /// Returns the plugin with the given id, e.g. `redmine_agile`.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - a string slice holding the id of the plugin
+    pub fn plugin(&self, id: &str) -> Option<&Plugin> {
+        self.plugins.as_ref().and_then(
+            |plugins| plugins.iter().find(|p| p.id == id),
+        )
+    }
+
+    /// Returns true if the plugin with the given id is installed. Returns false if the plugins
+    /// aren't exposed.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - a string slice holding the id of the plugin
+    pub fn has_plugin(&self, id: &str) -> bool {
+        self.plugin(id).is_some()
+    }
+}
+
+/// Represents a plugin installed in a redmine application.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Plugin {
+    pub id: String,
+    pub name: String,
+    pub version: Option<String>,
+}
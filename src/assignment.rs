@@ -0,0 +1,167 @@
+//! This module provides load balancing of unassigned issues over a pool of users, e.g. for triage
+//! automation. Assignments run as dry run unless told otherwise and refuse to update more issues
+//! than their batch limit allows.
+
+use super::errors::*;
+use super::issues::{self, Issue, IssueFilter};
+use super::users::UserRef;
+use super::ClientHandle;
+
+/// Default maximum number of issues an assignment updates without raising the limit.
+const DEFAULT_MAX_UPDATES: usize = 50;
+
+/// Enumeration of the strategies issues are distributed over the pool with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AssignmentStrategy {
+    /// Assigns the issues to the users of the pool in turn.
+    RoundRobin,
+    /// Assigns every issue to the user with the fewest open issues, counting the issues assigned
+    /// during this run.
+    LeastLoaded,
+}
+
+/// Struct to provide builder pattern for automatic assignments. Is used as return type for
+/// [issues.auto_assign](../issues/struct.Api.html#method.auto_assign).
+pub struct AutoAssign {
+    client: ClientHandle,
+    filter: IssueFilter,
+    pool: Vec<UserRef>,
+    strategy: AssignmentStrategy,
+    dry_run: bool,
+    max_updates: usize,
+}
+impl AutoAssign {
+    /// Creates a new instance running as dry run. Should not be called externally.
+    pub fn new(
+        client: ClientHandle,
+        filter: IssueFilter,
+        pool: Vec<UserRef>,
+        strategy: AssignmentStrategy,
+    ) -> Self {
+        AutoAssign {
+            client: client,
+            filter: filter,
+            pool: pool,
+            strategy: strategy,
+            dry_run: true,
+            max_updates: DEFAULT_MAX_UPDATES,
+        }
+    }
+
+    /// Sets whether assignments are only reported (true, default) or actually made (false).
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - a boolean: true means nothing is updated
+    pub fn dry_run(mut self, b: bool) -> Self {
+        self.dry_run = b;
+        self
+    }
+
+    /// Sets the maximum number of issues which may be assigned. If more issues are unassigned,
+    /// nothing is updated and an error is returned. Defaults to 50.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the batch limit
+    pub fn max_updates(mut self, n: usize) -> Self {
+        self.max_updates = n;
+        self
+    }
+
+    /// Performs requests to redmine application, distributes the unassigned issues matching the
+    /// filter over the pool and assigns them unless running as dry run.
+    pub fn execute(&self) -> Result<AssignmentReport> {
+        if self.pool.is_empty() {
+            bail!("Can't assign issues to an empty pool");
+        }
+
+        let mut issues: Vec<Issue> = self.filter
+            .execute_all()?
+            .into_iter()
+            .filter(|i| i.assigned_to.is_none())
+            .collect();
+        // oldest issues first
+        issues.sort_by_key(|i| i.id);
+
+        let api = issues::Api::new(self.client.clone());
+        let mut report = AssignmentReport {
+            dry_run: self.dry_run,
+            assignments: self.distribute(&api, &issues)?,
+            ..Default::default()
+        };
+        if self.dry_run {
+            return Ok(report);
+        }
+
+        if report.assignments.len() > self.max_updates {
+            bail!(
+                "Refusing to assign {} issues, limit is {}",
+                report.assignments.len(),
+                self.max_updates
+            );
+        }
+
+        for &(issue_id, user_id) in &report.assignments {
+            match api.update(issue_id).assigned_to_id(user_id).execute() {
+                Ok(_) => report.assigned.push(issue_id),
+                Err(e) => report.failed.push((issue_id, e.to_string())),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Returns pairs of issue id and user id according to the strategy.
+    fn distribute(&self, api: &issues::Api, issues: &[Issue]) -> Result<Vec<(u32, u32)>> {
+        match self.strategy {
+            AssignmentStrategy::RoundRobin => {
+                Ok(
+                    issues
+                        .iter()
+                        .zip(self.pool.iter().cycle())
+                        .map(|(i, u)| (i.id, u.id))
+                        .collect(),
+                )
+            }
+            AssignmentStrategy::LeastLoaded => {
+                // number of open issues per user of the pool
+                let mut load = Vec::new();
+                for user in &self.pool {
+                    let open = api.list()
+                        .assigned_to_id(user.id)
+                        .limit(1)
+                        .execute()?
+                        .total_count()
+                        .unwrap_or(0);
+                    load.push((user.id, open));
+                }
+
+                let mut result = Vec::new();
+                for issue in issues {
+                    // the pool isn't empty, so there always is a least loaded user
+                    let least = match load.iter_mut().min_by_key(|entry| entry.1) {
+                        Some(entry) => entry,
+                        None => break,
+                    };
+                    least.1 += 1;
+                    result.push((issue.id, least.0));
+                }
+
+                Ok(result)
+            }
+        }
+    }
+}
+
+/// Holds the result of an automatic assignment.
+#[derive(Debug, Default)]
+pub struct AssignmentReport {
+    pub dry_run: bool,
+    /// Pairs of issue id and the id of the user the issue is assigned to.
+    pub assignments: Vec<(u32, u32)>,
+    /// Ids of the issues which have been assigned.
+    pub assigned: Vec<u32>,
+    /// Ids of the issues which couldn't be assigned together with the error message.
+    pub failed: Vec<(u32, String)>,
+}
@@ -0,0 +1,515 @@
+//! This module holds everything needed to represent the redmine attachments api as described by
+//! following link: http://www.redmine.org/projects/redmine/wiki/Rest_Attachments.
+
+#[cfg(feature = "issues")]
+use std::io::Cursor;
+use std::io::Write;
+#[cfg(all(feature = "blocking", feature = "issues"))]
+use std::path::Path;
+#[cfg(all(feature = "blocking", feature = "issues"))]
+use super::crawler::BulkDownload;
+use super::errors::*;
+#[cfg(feature = "issues")]
+use super::issues;
+#[cfg(feature = "issues")]
+use super::uploads;
+use super::{ClientHandle, NamedObject, RedmineApi, Tristate};
+
+/// This struct exposes all methods provided by the redmine attachments api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns an AttachmentSearch (builder pattern) which ultimately walks through all issues of
+    /// a project (open and closed) and returns the attachments matching the search parameters.
+    /// Requires the `issues` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.attachments().search(1)
+    ///     .filename("*.xlsx")
+    ///     .min_size(10 * 1024 * 1024)
+    ///     .execute();
+    /// ```
+    #[cfg(feature = "issues")]
+    pub fn search(&self, project_id: u32) -> AttachmentSearch {
+        AttachmentSearch {
+            client: self.client.clone(),
+            project_id: project_id,
+            ..Default::default()
+        }
+    }
+
+    /// Returns AttachmentShow struct which offers an `execute` function which returns the
+    /// metadata of the attachment specified by `id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the attachment id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.attachments().show(1).execute();
+    /// ```
+    pub fn show(&self, id: u32) -> AttachmentShow {
+        AttachmentShow::new(self.client.clone(), id)
+    }
+
+    /// Returns an AttachmentBuilder (builder pattern) and ultimately updates filename or
+    /// description of an existing attachment.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the attachment id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.attachments().update(1)
+    ///     .description("Screenshot of the login page")
+    ///     .execute();
+    /// ```
+    pub fn update<'a>(&self, id: u32) -> AttachmentBuilder<'a> {
+        AttachmentBuilder::for_update(self.client.clone(), format!("/attachments/{}.json", id))
+    }
+
+    /// Returns AttachmentDelete struct which offers an `execute` function which deletes the
+    /// attachment specified by `id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the attachment id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.attachments().delete(1).execute();
+    /// ```
+    pub fn delete(&self, id: u32) -> AttachmentDelete {
+        AttachmentDelete::new(self.client.clone(), id)
+    }
+
+    /// Returns AttachmentDownload struct which offers an `execute` function which returns the
+    /// content of the attachment specified by `id` parameter and a `write_to` function which
+    /// streams it to a sink, e.g. a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the attachment id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use std::io;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let content = redmine.attachments().download(1).execute();
+    /// let written = redmine.attachments().download(1).write_to(&mut io::sink());
+    /// ```
+    pub fn download(&self, id: u32) -> AttachmentDownload {
+        AttachmentDownload {
+            client: self.client.clone(),
+            id: id,
+        }
+    }
+
+    /// Returns AttachmentCopy struct which offers an `execute` function which copies the
+    /// attachments of an issue accepted by `filter` to another issue. Redmine can't copy
+    /// attachments itself, so they are downloaded and uploaded again. Requires the `issues`
+    /// feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_issue` - an integer holding the id of the issue the attachments are copied from
+    /// * `to_issue` - an integer holding the id of the issue the attachments are copied to
+    /// * `filter` - a closure returning true for the attachments which should be copied
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.attachments()
+    ///     .copy(1, 2, |a| a.filename.ends_with(".pdf"))
+    ///     .execute();
+    /// ```
+    #[cfg(feature = "issues")]
+    pub fn copy<F>(&self, from_issue: u32, to_issue: u32, filter: F) -> AttachmentCopy<F>
+    where
+        F: Fn(&Attachment) -> bool,
+    {
+        AttachmentCopy {
+            client: self.client.clone(),
+            from_issue: from_issue,
+            to_issue: to_issue,
+            filter: filter,
+        }
+    }
+}
+
+show_endpoint! {
+    /// Helper struct to provide a unified interface for all attachment api methods.
+    AttachmentShow => Attachment, attachment, "/attachments/{}.json"
+}
+
+builder_endpoint! {
+    /// Struct to provide builder pattern for update of attachments. Can be serialized to be used
+    /// as json parameter for request to redmine application.
+    AttachmentBuilder [update] => Attachment, attachment {
+        /// Sets filename of the attachment.
+        filename: &'a str,
+        /// Sets description of the attachment.
+        description: &'a str,
+    }
+}
+
+delete_endpoint! {
+    /// Helper struct to provide a unified interface for all attachment api methods.
+    AttachmentDelete, "/attachments/{}.json"
+}
+
+/// Helper struct to provide a unified interface for all attachment api methods.
+pub struct AttachmentDownload {
+    client: ClientHandle,
+    id: u32,
+}
+impl AttachmentDownload {
+    /// Performs request to redmine application and returns the content of the attachment.
+    pub fn execute(&self) -> Result<Vec<u8>> {
+        let mut content = Vec::new();
+        self.write_to(&mut content)?;
+
+        Ok(content)
+    }
+
+    /// Performs request to redmine application and streams the content of the attachment to
+    /// `sink` without buffering it completely. Returns the number of bytes written.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - the writer the content is written to
+    pub fn write_to<W: Write>(&self, sink: &mut W) -> Result<u64> {
+        // redmine serves the content without the filename being part of the url
+        let url = self.client.web_url(&format!("/attachments/download/{}", self.id));
+
+        self.client.download(&url, sink)
+    }
+}
+
+/// Struct to provide a unified interface for copying attachments. Is used as return type for
+/// attachments.copy function.
+#[cfg(feature = "issues")]
+pub struct AttachmentCopy<F> {
+    client: ClientHandle,
+    from_issue: u32,
+    to_issue: u32,
+    filter: F,
+}
+#[cfg(feature = "issues")]
+impl<F> AttachmentCopy<F>
+where
+    F: Fn(&Attachment) -> bool,
+{
+    /// Performs requests to redmine application, copies the attachments and returns the copied
+    /// attachments of the source issue. All copies are attached to the target issue by a single
+    /// update, so nothing is attached if a download or upload fails.
+    pub fn execute(&self) -> Result<Vec<Attachment>> {
+        let api = issues::Api::new(self.client.clone());
+        let attachments: Vec<Attachment> = api.show(self.from_issue)
+            .include("attachments")
+            .execute()?
+            .attachments
+            .into_iter()
+            .filter(|a| (self.filter)(a))
+            .collect();
+        if attachments.is_empty() {
+            return Ok(attachments);
+        }
+
+        let uploads = uploads::Api::new(self.client.clone());
+        let mut builder = api.update(self.to_issue);
+        for attachment in &attachments {
+            let mut content = Vec::new();
+            self.client.download(&attachment.content_url, &mut content)?;
+
+            let mut upload = uploads.reader(&attachment.filename, Cursor::new(content));
+            if let Some(ref ct) = attachment.content_type {
+                upload = upload.content_type(ct);
+            }
+            if let Some(ref d) = attachment.description {
+                upload = upload.description(d);
+            }
+            builder = builder.add_upload(upload.execute()?);
+        }
+        builder.execute()?;
+
+        Ok(attachments)
+    }
+}
+
+/// Holds parameters the attachments of a project should be filtered by and implements builder
+/// pattern. Is used as return type for attachments.search function.
+#[cfg(feature = "issues")]
+#[derive(Default)]
+pub struct AttachmentSearch {
+    client: ClientHandle,
+    project_id: u32,
+    filename: Option<String>,
+    content_type: Option<String>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    author_id: Option<u32>,
+}
+#[cfg(feature = "issues")]
+impl AttachmentSearch {
+    /// Sets filter to get only attachments whose filename matches a glob pattern. `*` matches any
+    /// number of characters, `?` matches a single character. Matching is case insensitive.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - a string slice holding the glob pattern, e.g. `*.pdf`
+    pub fn filename(mut self, pattern: &str) -> Self {
+        self.filename = Some(pattern.to_string());
+        self
+    }
+
+    /// Sets filter to get only attachments of a specific content type.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the content type, e.g. `application/pdf`
+    pub fn content_type(mut self, s: &str) -> Self {
+        self.content_type = Some(s.to_string());
+        self
+    }
+
+    /// Sets filter to get only attachments of at least the given size.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the size in bytes
+    pub fn min_size(mut self, n: u64) -> Self {
+        self.min_size = Some(n);
+        self
+    }
+
+    /// Sets filter to get only attachments of at most the given size.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the size in bytes
+    pub fn max_size(mut self, n: u64) -> Self {
+        self.max_size = Some(n);
+        self
+    }
+
+    /// Sets filter to get only attachments uploaded by a specific user.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the user id
+    pub fn author_id(mut self, id: u32) -> Self {
+        self.author_id = Some(id);
+        self
+    }
+
+    /// Returns a BulkDownload (builder pattern) which ultimately downloads all attachments
+    /// matching the search parameters into a directory, concurrently and resumable. Requires the
+    /// `blocking` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - the directory the attachments are stored in
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.attachments().search(1)
+    ///     .download_all("/tmp/backup")
+    ///     .concurrency(8)
+    ///     .execute();
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn download_all<P: AsRef<Path>>(self, dir: P) -> BulkDownload {
+        let apikey = self.client.apikey.clone();
+        BulkDownload::new(self, apikey, dir.as_ref().to_path_buf())
+    }
+
+    /// Performs requests to redmine application and returns all attachments of the project
+    /// matching the search parameters.
+    pub fn execute(&self) -> Result<Vec<AttachmentMatch>> {
+        let issues = issues::Api::new(self.client.clone())
+            .list()
+            .project_id(self.project_id)
+            .any_status()
+            .include("attachments")
+            .execute_all()?;
+
+        let mut result = Vec::new();
+        for issue in issues {
+            for attachment in issue.attachments {
+                if self.matches(&attachment) {
+                    result.push(AttachmentMatch {
+                        issue_id: issue.id,
+                        attachment: attachment,
+                    });
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Checks if an attachment matches the search parameters.
+    fn matches(&self, attachment: &Attachment) -> bool {
+        if let Some(ref pattern) = self.filename {
+            if !glob_matches(&pattern.to_lowercase(), &attachment.filename.to_lowercase()) {
+                return false;
+            }
+        }
+
+        if let Some(ref ct) = self.content_type {
+            if attachment.content_type.as_ref() != Some(ct) {
+                return false;
+            }
+        }
+
+        if let Some(n) = self.min_size {
+            if attachment.filesize < n {
+                return false;
+            }
+        }
+
+        if let Some(n) = self.max_size {
+            if attachment.filesize > n {
+                return false;
+            }
+        }
+
+        if let Some(id) = self.author_id {
+            if attachment.author.id != id {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Holds an attachment found by [AttachmentSearch](struct.AttachmentSearch.html) together with
+/// the id of the issue it belongs to.
+#[cfg(feature = "issues")]
+#[derive(Debug, Clone)]
+pub struct AttachmentMatch {
+    pub issue_id: u32,
+    pub attachment: Attachment,
+}
+
+/// Represents an attachment as pulled from redmine application.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Attachment {
+    pub id: u32,
+    pub filename: String,
+    pub filesize: u64,
+    pub content_type: Option<String>,
+    pub description: Option<String>,
+    pub content_url: String,
+    pub author: NamedObject,
+    pub created_on: String,
+}
+impl Attachment {
+    /// Returns the url of the page showing this attachment in the redmine application, e.g.
+    /// `http://www.redmine.org/attachments/1`, for linking users to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - the api pointing to the redmine application
+    pub fn web_url(&self, api: &RedmineApi) -> String {
+        api.client.web_url(&format!("/attachments/{}", self.id))
+    }
+}
+
+/// Checks if `text` matches the glob `pattern` supporting `*` and `?` wildcards.
+#[cfg(feature = "issues")]
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+
+    let (mut pi, mut ti) = (0, 0);
+    // position of the last `*` in pattern and the text position it was tried at
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+
+    p[pi..].iter().all(|c| *c == '*')
+}
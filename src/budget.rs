@@ -0,0 +1,124 @@
+//! This module bounds the time composite operations may take, e.g. walking through all pages of
+//! issues or escalating a batch of issues. Operations given a [Budget](struct.Budget.html) don't
+//! start another request once it's exhausted, but stop cleanly and return the results gathered so
+//! far together with a [Continuation](struct.Continuation.html), which resumes the operation in a
+//! later call. This keeps interactive tools responsive regardless of the size of the data.
+//!
+//! # Example
+//!
+//! ```
+//! use redmine_api::RedmineApi;
+//! use redmine_api::budget::Budget;
+//! use std::time::Duration;
+//!
+//! let redmine = RedmineApi::new(
+//!     "http://www.redmine.org/".to_string(),
+//!     "1234".to_string()
+//! );
+//!
+//! let budget = Budget::new(Duration::from_secs(2));
+//! if let Ok(partial) = redmine.issues().list().execute_within(&budget, None) {
+//!     println!("Got {} issues", partial.result.issues().len());
+//!
+//!     if let Some(ref token) = partial.continuation {
+//!         let rest = redmine.issues().list().execute_within(&Budget::unlimited(), Some(token));
+//!     }
+//! }
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use super::errors::*;
+
+/// Time budget of a composite operation. A budget is started when it's created and shared by
+/// all requests of the operation.
+#[derive(Debug, Clone, Copy)]
+pub struct Budget {
+    deadline: Option<Instant>,
+}
+impl Budget {
+    /// Creates a budget which is exhausted after `duration`.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - the total time the operation may take
+    pub fn new(duration: Duration) -> Self {
+        Budget::until(Instant::now() + duration)
+    }
+
+    /// Creates a budget which is exhausted at `deadline`.
+    ///
+    /// # Arguments
+    ///
+    /// * `deadline` - the point in time the operation has to stop at
+    pub fn until(deadline: Instant) -> Self {
+        Budget { deadline: Some(deadline) }
+    }
+
+    /// Creates a budget which is never exhausted.
+    pub fn unlimited() -> Self {
+        Budget { deadline: None }
+    }
+
+    /// Returns the time left or `None` for unlimited budgets.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline.map(|d| {
+            let now = Instant::now();
+            if d > now { d - now } else { Duration::from_secs(0) }
+        })
+    }
+
+    /// Checks if no time is left.
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining() == Some(Duration::from_secs(0))
+    }
+}
+
+/// Token to resume an operation which has been stopped by its budget. It's only meaningful to
+/// the kind of operation which returned it. Tokens can be stored as strings, see
+/// [Display](#impl-Display) and [FromStr](#impl-FromStr).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Continuation {
+    position: u32,
+}
+impl Continuation {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(position: u32) -> Self {
+        Continuation { position: position }
+    }
+
+    /// Returns the position the operation continues at, e.g. the offset of the next page.
+    pub fn position(&self) -> u32 {
+        self.position
+    }
+}
+impl fmt::Display for Continuation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.position)
+    }
+}
+impl FromStr for Continuation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.trim().parse().map(Continuation::new).chain_err(
+            || format!("Invalid continuation token: {}", s),
+        )
+    }
+}
+
+/// Holds the result of an operation with a budget.
+#[derive(Debug)]
+pub struct Partial<T> {
+    /// The results gathered before the budget was exhausted or all results if it wasn't.
+    pub result: T,
+    /// Token to resume the operation, `None` if it has been completed.
+    pub continuation: Option<Continuation>,
+}
+impl<T> Partial<T> {
+    /// Checks if the operation has been completed.
+    pub fn is_complete(&self) -> bool {
+        self.continuation.is_none()
+    }
+}
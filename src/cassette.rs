@@ -0,0 +1,437 @@
+//! This module provides transports which record the interactions with a redmine application into
+//! a cassette file and replay them later, e.g. for tests of tools built on this library without
+//! a running redmine application. Requires the `cassette` feature.
+//!
+//! Cassettes are scrubbed before they are stored, so they can be committed to repositories
+//! safely: api keys are removed from urls and responses, email addresses are replaced by
+//! `scrubbed@example.com` and binary content like uploaded or downloaded attachments is replaced
+//! by its size. Cassettes are stored as gzip compressed json.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use redmine_api::RedmineApi;
+//! use redmine_api::cassette::{Recorder, Replayer};
+//! use redmine_api::http::BlockingTransport;
+//!
+//! // record the interactions with a real redmine application ...
+//! {
+//!     let redmine = RedmineApi::with_transport(
+//!         "http://www.redmine.org/".to_string(),
+//!         "1234".to_string(),
+//!         Box::new(Recorder::new(Box::new(BlockingTransport), "issues.json.gz")),
+//!     );
+//!     let result = redmine.issues().show(1).execute();
+//! }
+//!
+//! // ... and replay them
+//! let redmine = RedmineApi::with_transport(
+//!     "http://www.redmine.org/".to_string(),
+//!     "5678".to_string(),
+//!     Box::new(Replayer::open("issues.json.gz").unwrap()),
+//! );
+//! let result = redmine.issues().show(1).execute();
+//! ```
+
+extern crate serde_json;
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use std::fs::File;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use url::Url;
+use super::errors::*;
+use super::http::{Body, Method, Request, Response, Transport};
+
+/// Placeholder for scrubbed api keys.
+const SCRUBBED_KEY: &str = "SCRUBBED";
+
+/// Placeholder for scrubbed email addresses.
+const SCRUBBED_MAIL: &str = "scrubbed@example.com";
+
+/// Represents a recorded request and its response.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Interaction {
+    pub method: String,
+    /// Url of the request with the api key scrubbed.
+    pub url: String,
+    pub request_body: Option<String>,
+    pub status: u16,
+    pub location: Option<String>,
+    pub response_body: String,
+}
+
+/// Transport recording all interactions of another transport. The cassette is written when the
+/// recorder is dropped, i.e. together with the [RedmineApi](../struct.RedmineApi.html) using
+/// it, or by calling [save](#method.save).
+pub struct Recorder {
+    inner: Box<dyn Transport>,
+    path: PathBuf,
+    interactions: Mutex<Vec<Interaction>>,
+}
+impl Recorder {
+    /// Creates a new instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - the transport actually sending the requests
+    /// * `path` - the path of the cassette file, which is overwritten
+    pub fn new<P: AsRef<Path>>(inner: Box<dyn Transport>, path: P) -> Self {
+        Recorder {
+            inner: inner,
+            path: path.as_ref().to_path_buf(),
+            interactions: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Writes the interactions recorded so far to the cassette file.
+    pub fn save(&self) -> Result<()> {
+        let file = File::create(&self.path).chain_err(|| {
+            format!("Can't create {}", self.path.display())
+        })?;
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        serde_json::to_writer(&mut encoder, &*self.interactions.lock().unwrap())
+            .chain_err(|| "Can't serialize json")?;
+        encoder.finish()?;
+
+        Ok(())
+    }
+}
+impl Transport for Recorder {
+    fn send(&self, request: Request) -> Result<Response> {
+        let method = request.method;
+        let url = scrub_url(&request.url);
+
+        // binary bodies can only be read once, so they are buffered before being sent
+        let (body, request_body) = match request.body {
+            Some(Body::Json(bytes)) => {
+                let recorded = scrub_text(&bytes);
+                (Some(Body::Json(bytes)), Some(recorded))
+            }
+            Some(Body::Binary(mut reader)) => {
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes)?;
+                let recorded = binary_placeholder(bytes.len());
+                (Some(Body::Binary(Box::new(Cursor::new(bytes)))), Some(recorded))
+            }
+            None => (None, None),
+        };
+
+        let mut response = self.inner.send(Request {
+            method: method,
+            url: request.url,
+            headers: request.headers,
+            body: body,
+        })?;
+        let mut bytes = Vec::new();
+        response.body.read_to_end(&mut bytes)?;
+        let response_body = if url.contains("/attachments/download/") {
+            binary_placeholder(bytes.len())
+        } else {
+            scrub_text(&bytes)
+        };
+
+        self.interactions.lock().unwrap().push(Interaction {
+            method: method_name(method).to_string(),
+            url: url,
+            request_body: request_body,
+            status: response.status,
+            location: response.location.clone(),
+            response_body: response_body,
+        });
+
+        Ok(Response {
+            status: response.status,
+            location: response.location,
+            body: Box::new(Cursor::new(bytes)),
+        })
+    }
+}
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let _ = self.save();
+    }
+}
+
+/// Transport answering requests with the interactions of a cassette. Every interaction is
+/// replayed once, in recorded order; requests are matched by method and url regardless of the
+/// api key and the order of query parameters.
+pub struct Replayer {
+    interactions: Mutex<Vec<(Interaction, bool)>>,
+}
+impl Replayer {
+    /// Reads the cassette file and creates a new instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the cassette file
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).chain_err(
+            || format!("Can't open {}", path.display()),
+        )?;
+        let interactions: Vec<Interaction> = serde_json::from_reader(GzDecoder::new(file))
+            .chain_err(|| "Can't parse json")?;
+
+        Ok(Replayer::from_interactions(interactions))
+    }
+
+    /// Creates a new instance replaying the given interactions.
+    ///
+    /// # Arguments
+    ///
+    /// * `interactions` - a vector holding the interactions
+    pub fn from_interactions(interactions: Vec<Interaction>) -> Self {
+        // urls of older cassettes may have unordered query parameters
+        let interactions = interactions
+            .into_iter()
+            .map(|mut i| {
+                i.url = scrub_url(&i.url);
+                (i, false)
+            })
+            .collect();
+
+        Replayer { interactions: Mutex::new(interactions) }
+    }
+}
+impl Transport for Replayer {
+    fn send(&self, request: Request) -> Result<Response> {
+        let method = method_name(request.method);
+        let url = scrub_url(&request.url);
+
+        let mut interactions = self.interactions.lock().unwrap();
+        let found = interactions.iter_mut().find(|entry| {
+            !entry.1 && entry.0.method == method && entry.0.url == url
+        });
+        match found {
+            Some(entry) => {
+                entry.1 = true;
+
+                Ok(Response {
+                    status: entry.0.status,
+                    location: entry.0.location.clone(),
+                    body: Box::new(Cursor::new(entry.0.response_body.clone().into_bytes())),
+                })
+            }
+            None => bail!("No recorded interaction for {} {}", method, url),
+        }
+    }
+}
+
+/// Returns the name of a http method as stored in cassettes.
+fn method_name(method: Method) -> &'static str {
+    match method {
+        Method::Get => "GET",
+        Method::Post => "POST",
+        Method::Put => "PUT",
+        Method::Delete => "DELETE",
+    }
+}
+
+/// Returns the placeholder for binary content.
+///
+/// # Arguments
+///
+/// * `len` - the number of bytes of the content
+fn binary_placeholder(len: usize) -> String {
+    format!("<binary: {} bytes>", len)
+}
+
+/// Replaces the api key and email addresses in the query string of a url and sorts the query
+/// parameters, which are sent in random order.
+///
+/// # Arguments
+///
+/// * `url` - a string slice holding the url
+fn scrub_url(url: &str) -> String {
+    let mut parsed = match Url::parse(url) {
+        Ok(parsed) => parsed,
+        Err(_) => return scrub_mails(url),
+    };
+
+    // values are scrubbed decoded, since `@` is percent encoded in query strings
+    let mut pairs: Vec<(String, String)> = parsed
+        .query_pairs()
+        .map(|(k, v)| if k == "key" {
+            (k.into_owned(), SCRUBBED_KEY.to_string())
+        } else {
+            (k.into_owned(), scrub_mails(&v))
+        })
+        .collect();
+    pairs.sort();
+    if !pairs.is_empty() {
+        parsed.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+
+    scrub_mails(parsed.as_str())
+}
+
+/// Scrubs a request or response body. Json has its api keys and email addresses replaced, other
+/// text its email addresses and everything else is treated as binary content.
+///
+/// # Arguments
+///
+/// * `bytes` - the body
+fn scrub_text(bytes: &[u8]) -> String {
+    if bytes.is_empty() {
+        return String::new();
+    }
+
+    match serde_json::from_slice::<serde_json::Value>(bytes) {
+        Ok(mut value) => {
+            scrub_json(&mut value);
+            scrub_mails(&value.to_string())
+        }
+        Err(_) => {
+            match ::std::str::from_utf8(bytes) {
+                Ok(text) => scrub_mails(text),
+                Err(_) => binary_placeholder(bytes.len()),
+            }
+        }
+    }
+}
+
+/// Replaces the values of all `api_key` fields, e.g. of the current user.
+///
+/// # Arguments
+///
+/// * `value` - the json value, which is changed in place
+fn scrub_json(value: &mut serde_json::Value) {
+    match *value {
+        serde_json::Value::Object(ref mut map) => {
+            for (key, v) in map.iter_mut() {
+                if key == "api_key" {
+                    *v = serde_json::Value::String(SCRUBBED_KEY.to_string());
+                } else {
+                    scrub_json(v);
+                }
+            }
+        }
+        serde_json::Value::Array(ref mut values) => {
+            for v in values {
+                scrub_json(v);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replaces all email addresses in a text.
+///
+/// # Arguments
+///
+/// * `text` - a string slice holding the text
+fn scrub_mails(text: &str) -> String {
+    let is_local = |c: char| c.is_alphanumeric() || "._%+-".contains(c);
+    let is_domain = |c: char| c.is_alphanumeric() || ".-".contains(c);
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '@' {
+            let mut start = i;
+            while start > 0 && is_local(chars[start - 1]) {
+                start -= 1;
+            }
+            let mut end = i + 1;
+            while end < chars.len() && is_domain(chars[end]) {
+                end += 1;
+            }
+            let domain: String = chars[i + 1..end].iter().collect();
+            let domain = domain.trim_end_matches('.');
+
+            if start < i && domain.contains('.') {
+                // the local part has already been copied, so it's removed again
+                let local_len: usize = chars[start..i].iter().map(|c| c.len_utf8()).sum();
+                let len = result.len() - local_len;
+                result.truncate(len);
+                result.push_str(SCRUBBED_MAIL);
+                i += 1 + domain.chars().count();
+                continue;
+            }
+        }
+
+        result.push(chars[i]);
+        i += 1;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use super::super::http::{Method, Request, Transport};
+    use super::{scrub_mails, scrub_text, scrub_url, Interaction, Replayer};
+
+    #[test]
+    fn query_parameters_are_sorted_and_key_is_scrubbed() {
+        let a = scrub_url("http://localhost/issues.json?status_id=open&key=abc&project_id=1");
+        let b = scrub_url("http://localhost/issues.json?project_id=1&status_id=open&key=def");
+        assert_eq!(a, b);
+        assert_eq!(a, "http://localhost/issues.json?key=SCRUBBED&project_id=1&status_id=open");
+    }
+
+    #[test]
+    fn mails_in_urls_are_scrubbed() {
+        let url = scrub_url("http://localhost/users.json?name=john.doe%40example.com&key=abc");
+        assert!(!url.contains("john"), "{}", url);
+        assert!(url.contains("scrubbed%40example.com"), "{}", url);
+    }
+
+    #[test]
+    fn nested_api_keys_are_scrubbed() {
+        let body = br#"{"users": [{"login": "jdoe", "api_key": "abc", "mail": "j@example.com"}],
+            "user": {"api_key": "def"}}"#;
+        let scrubbed = scrub_text(body);
+        assert!(!scrubbed.contains("abc"), "{}", scrubbed);
+        assert!(!scrubbed.contains("def"), "{}", scrubbed);
+        assert!(!scrubbed.contains("j@example.com"), "{}", scrubbed);
+        assert!(scrubbed.contains("jdoe"), "{}", scrubbed);
+    }
+
+    #[test]
+    fn binary_bodies_are_replaced_by_size() {
+        assert_eq!(scrub_text(&[0xff, 0xfe, 0x00, 0x81]), "<binary: 4 bytes>");
+        assert_eq!(scrub_text(b""), "");
+    }
+
+    #[test]
+    fn mails_in_text_are_scrubbed() {
+        assert_eq!(
+            scrub_mails("Ask john.doe+redmine@mail.example.com."),
+            "Ask scrubbed@example.com."
+        );
+        assert_eq!(scrub_mails("Meet @home or at 10@night"), "Meet @home or at 10@night");
+    }
+
+    #[test]
+    fn replay_ignores_order_of_query_parameters() {
+        let replayer = Replayer::from_interactions(vec![Interaction {
+            method: "GET".to_string(),
+            url: "http://localhost/issues.json?status_id=open&project_id=1&key=SCRUBBED"
+                .to_string(),
+            request_body: None,
+            status: 200,
+            location: None,
+            response_body: "{}".to_string(),
+        }]);
+
+        let mut response = replayer
+            .send(Request {
+                method: Method::Get,
+                url: "http://localhost/issues.json?key=1234&project_id=1&status_id=open"
+                    .to_string(),
+                headers: Vec::new(),
+                body: None,
+            })
+            .unwrap();
+        let mut body = String::new();
+        response.body.read_to_string(&mut body).unwrap();
+        assert_eq!(body, "{}");
+    }
+}
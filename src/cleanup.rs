@@ -0,0 +1,262 @@
+//! This module provides data retention operations, e.g. deleting old time entries or purging
+//! large attachments. Every operation runs as dry run unless told otherwise and refuses to delete
+//! more entities than its confirmation threshold allows.
+
+use super::errors::*;
+use super::RedmineApi;
+
+/// Default maximum number of deletions an operation performs without raising the threshold.
+const DEFAULT_MAX_DELETIONS: usize = 100;
+
+/// Returns a Cleanup (builder pattern) which ultimately deletes all time entries of a project
+/// spent before a date.
+///
+/// # Arguments
+///
+/// * `api` - the redmine application to clean up
+/// * `project_id` - an integer holding the project id
+/// * `before` - a string slice holding the date in `YYYY-MM-DD` format; entries spent on this day
+///   are kept
+///
+/// # Example
+///
+/// ```
+/// use redmine_api::RedmineApi;
+/// use redmine_api::cleanup;
+///
+/// let redmine = RedmineApi::new(
+///     "http://www.redmine.org/".to_string(),
+///     "1234".to_string()
+/// );
+///
+/// // report only
+/// let report = cleanup::time_entries_before(&redmine, 1, "2015-01-01").execute();
+///
+/// // delete up to 500 entries
+/// let report = cleanup::time_entries_before(&redmine, 1, "2015-01-01")
+///     .dry_run(false)
+///     .max_deletions(500)
+///     .execute();
+/// ```
+pub fn time_entries_before<'a>(
+    api: &'a RedmineApi,
+    project_id: u32,
+    before: &str,
+) -> Cleanup<'a> {
+    Cleanup::new(
+        api,
+        Target::TimeEntriesBefore {
+            project_id: project_id,
+            before: before.to_string(),
+        },
+    )
+}
+
+/// Returns a Cleanup (builder pattern) which ultimately deletes all attachments larger than
+/// `min_size` bytes from closed issues of a project.
+///
+/// # Arguments
+///
+/// * `api` - the redmine application to clean up
+/// * `project_id` - an integer holding the project id
+/// * `min_size` - an integer holding the size in bytes; smaller attachments are kept
+///
+/// # Example
+///
+/// ```
+/// use redmine_api::RedmineApi;
+/// use redmine_api::cleanup;
+///
+/// let redmine = RedmineApi::new(
+///     "http://www.redmine.org/".to_string(),
+///     "1234".to_string()
+/// );
+///
+/// let report = cleanup::large_attachments_on_closed_issues(&redmine, 1, 50 * 1024 * 1024)
+///     .execute();
+/// ```
+pub fn large_attachments_on_closed_issues<'a>(
+    api: &'a RedmineApi,
+    project_id: u32,
+    min_size: u64,
+) -> Cleanup<'a> {
+    Cleanup::new(
+        api,
+        Target::LargeAttachmentsOnClosedIssues {
+            project_id: project_id,
+            min_size: min_size,
+        },
+    )
+}
+
+/// Enumeration of the supported cleanup operations.
+#[derive(Debug)]
+enum Target {
+    TimeEntriesBefore { project_id: u32, before: String },
+    LargeAttachmentsOnClosedIssues { project_id: u32, min_size: u64 },
+}
+
+/// Struct to provide builder pattern for cleanup operations.
+pub struct Cleanup<'a> {
+    api: &'a RedmineApi,
+    target: Target,
+    dry_run: bool,
+    max_deletions: usize,
+}
+impl<'a> Cleanup<'a> {
+    /// Creates a new instance running as dry run.
+    fn new(api: &'a RedmineApi, target: Target) -> Self {
+        Cleanup {
+            api: api,
+            target: target,
+            dry_run: true,
+            max_deletions: DEFAULT_MAX_DELETIONS,
+        }
+    }
+
+    /// Sets whether entities are only reported (true, default) or actually deleted (false).
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - a boolean: true means nothing is deleted
+    pub fn dry_run(mut self, b: bool) -> Self {
+        self.dry_run = b;
+        self
+    }
+
+    /// Sets the maximum number of entities which may be deleted. If more entities match, nothing
+    /// is deleted and an error is returned. Defaults to 100.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the confirmation threshold
+    pub fn max_deletions(mut self, n: usize) -> Self {
+        self.max_deletions = n;
+        self
+    }
+
+    /// Performs requests to redmine application, collects the matching entities and deletes them
+    /// unless running as dry run.
+    pub fn execute(&self) -> Result<CleanupReport> {
+        let candidates = self.candidates()?;
+
+        let mut report = CleanupReport {
+            dry_run: self.dry_run,
+            candidates: candidates,
+            ..Default::default()
+        };
+        if self.dry_run {
+            return Ok(report);
+        }
+
+        if report.candidates.len() > self.max_deletions {
+            bail!(
+                "Refusing to delete {} entities, threshold is {}",
+                report.candidates.len(),
+                self.max_deletions
+            );
+        }
+
+        for item in &report.candidates {
+            let result = match self.target {
+                Target::TimeEntriesBefore { .. } => {
+                    self.api.time_entries().delete(item.id).execute()
+                }
+                Target::LargeAttachmentsOnClosedIssues { .. } => {
+                    self.api.attachments().delete(item.id).execute()
+                }
+            };
+            match result {
+                Ok(_) => report.deleted.push(item.id),
+                Err(e) => report.failed.push((item.id, e.to_string())),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Collects the entities matching the cleanup operation.
+    fn candidates(&self) -> Result<Vec<CleanupItem>> {
+        match self.target {
+            Target::TimeEntriesBefore {
+                project_id,
+                ref before,
+            } => {
+                let time_entries = self.api
+                    .time_entries()
+                    .list()
+                    .project_id(project_id)
+                    .to(before)
+                    .execute_all()?;
+
+                // redmine's `to` filter is inclusive
+                Ok(
+                    time_entries
+                        .into_iter()
+                        .filter(|t| t.spent_on < *before)
+                        .map(|t| {
+                            CleanupItem {
+                                id: t.id,
+                                description: format!(
+                                    "Time entry #{} ({} hours on {})",
+                                    t.id,
+                                    t.hours,
+                                    t.spent_on
+                                ),
+                            }
+                        })
+                        .collect(),
+                )
+            }
+            Target::LargeAttachmentsOnClosedIssues {
+                project_id,
+                min_size,
+            } => {
+                let issues = self.api
+                    .issues()
+                    .list()
+                    .project_id(project_id)
+                    .closed()
+                    .include("attachments")
+                    .execute_all()?;
+
+                let mut items = Vec::new();
+                for issue in issues {
+                    for a in issue.attachments.iter().filter(|a| a.filesize >= min_size) {
+                        items.push(CleanupItem {
+                            id: a.id,
+                            description: format!(
+                                "Attachment #{} {} ({} bytes) of issue #{}",
+                                a.id,
+                                a.filename,
+                                a.filesize,
+                                issue.id
+                            ),
+                        });
+                    }
+                }
+
+                Ok(items)
+            }
+        }
+    }
+}
+
+/// Holds the result of a cleanup operation.
+#[derive(Debug, Default)]
+pub struct CleanupReport {
+    pub dry_run: bool,
+    /// Entities matching the cleanup operation.
+    pub candidates: Vec<CleanupItem>,
+    /// Ids of the entities which have been deleted.
+    pub deleted: Vec<u32>,
+    /// Ids of the entities which couldn't be deleted together with the error message.
+    pub failed: Vec<(u32, String)>,
+}
+
+/// Describes a single entity matching a cleanup operation.
+#[derive(Debug, Clone)]
+pub struct CleanupItem {
+    pub id: u32,
+    pub description: String,
+}
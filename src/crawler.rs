@@ -0,0 +1,316 @@
+//! This module downloads attachments in bulk, e.g. for backups of projects with gigabytes of
+//! attachments. Downloads run concurrently on a bounded number of worker threads, which are all
+//! joined before [execute](struct.BulkDownload.html#method.execute) returns, and at most a few
+//! downloads run against the same host at once. Completed downloads are recorded in a state
+//! file, so an interrupted backup continues where it left off when it's run again. Requires the
+//! `blocking` feature, since the workers send their requests with the blocking transport.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use url::Url;
+use super::attachments::{AttachmentMatch, AttachmentSearch};
+use super::errors::*;
+use super::http::{self, Transport};
+
+/// Default number of worker threads.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Default number of downloads running against the same host at once.
+const DEFAULT_PER_HOST: usize = 2;
+
+/// Name of the state file in the target directory unless set otherwise.
+const DEFAULT_STATE_FILE: &str = ".redmine-download-state";
+
+/// Struct to provide builder pattern for bulk downloads. Is used as return type for
+/// [download_all](../attachments/struct.AttachmentSearch.html#method.download_all) of
+/// attachment searches.
+pub struct BulkDownload {
+    search: AttachmentSearch,
+    apikey: String,
+    dir: PathBuf,
+    state_file: Option<PathBuf>,
+    concurrency: usize,
+    per_host: usize,
+}
+impl BulkDownload {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(search: AttachmentSearch, apikey: String, dir: PathBuf) -> Self {
+        BulkDownload {
+            search: search,
+            apikey: apikey,
+            dir: dir,
+            state_file: None,
+            concurrency: DEFAULT_CONCURRENCY,
+            per_host: DEFAULT_PER_HOST,
+        }
+    }
+
+    /// Sets the number of worker threads downloading attachments. Defaults to 4.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the number of workers
+    pub fn concurrency(mut self, n: usize) -> Self {
+        self.concurrency = n;
+        self
+    }
+
+    /// Sets the number of downloads running against the same host at once. Defaults to 2.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the number of downloads per host
+    pub fn per_host(mut self, n: usize) -> Self {
+        self.per_host = n;
+        self
+    }
+
+    /// Sets the file completed downloads are recorded in. Defaults to
+    /// `.redmine-download-state` in the target directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the state file
+    pub fn state_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.state_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Performs requests to redmine application and downloads all attachments matching the
+    /// search into `<dir>/<issue id>/<attachment id>-<filename>`. Attachments recorded in the
+    /// state file are skipped. A failing download doesn't stop the others; failures are
+    /// collected in the report.
+    pub fn execute(&self) -> Result<DownloadReport> {
+        if self.concurrency == 0 || self.per_host == 0 {
+            bail!("Concurrency and downloads per host must be at least 1");
+        }
+
+        fs::create_dir_all(&self.dir).chain_err(|| {
+            format!("Can't create {}", self.dir.display())
+        })?;
+        let state_path = self.state_file.clone().unwrap_or_else(
+            || self.dir.join(DEFAULT_STATE_FILE),
+        );
+        let completed = read_state(&state_path)?;
+        let state = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&state_path)
+            .chain_err(|| format!("Can't open {}", state_path.display()))?;
+
+        let mut report = DownloadReport::default();
+        let mut jobs = VecDeque::new();
+        for m in self.search.execute()? {
+            if completed.contains(&m.attachment.id) {
+                report.skipped.push(m.attachment.id);
+            } else {
+                jobs.push_back(m);
+            }
+        }
+
+        let shared = Arc::new(Shared {
+            apikey: self.apikey.clone(),
+            dir: self.dir.clone(),
+            jobs: Mutex::new(jobs),
+            hosts: HostSlots::new(self.per_host),
+            state: Mutex::new(state),
+            report: Mutex::new(report),
+        });
+
+        let workers: Vec<_> = (0..self.concurrency)
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                thread::spawn(move || shared.work())
+            })
+            .collect();
+        let mut panicked = false;
+        for worker in workers {
+            panicked |= worker.join().is_err();
+        }
+        if panicked {
+            bail!("A download worker panicked");
+        }
+
+        let report = lock(&shared.report);
+        Ok(report.clone())
+    }
+}
+
+/// Holds the result of a bulk download.
+#[derive(Debug, Default, Clone)]
+pub struct DownloadReport {
+    /// Ids of the attachments which have been downloaded.
+    pub downloaded: Vec<u32>,
+    /// Ids of the attachments which had been downloaded by a previous run.
+    pub skipped: Vec<u32>,
+    /// Ids of the attachments which couldn't be downloaded together with the error message.
+    pub failed: Vec<(u32, String)>,
+    /// Number of bytes downloaded.
+    pub bytes: u64,
+}
+
+/// State shared by the workers of a bulk download.
+struct Shared {
+    apikey: String,
+    dir: PathBuf,
+    jobs: Mutex<VecDeque<AttachmentMatch>>,
+    hosts: HostSlots,
+    state: Mutex<File>,
+    report: Mutex<DownloadReport>,
+}
+impl Shared {
+    /// Downloads attachments until there are none left.
+    fn work(&self) {
+        loop {
+            let job = match lock(&self.jobs).pop_front() {
+                Some(job) => job,
+                None => return,
+            };
+            let id = job.attachment.id;
+
+            match self.download(&job) {
+                Ok(bytes) => {
+                    let mut report = lock(&self.report);
+                    report.downloaded.push(id);
+                    report.bytes += bytes;
+                }
+                Err(e) => lock(&self.report).failed.push((id, e.to_string())),
+            }
+        }
+    }
+
+    /// Downloads a single attachment and records it in the state file.
+    fn download(&self, job: &AttachmentMatch) -> Result<u64> {
+        let mut url = Url::parse(&job.attachment.content_url).chain_err(|| {
+            format!("Can't parse url: {}", job.attachment.content_url)
+        })?;
+        let host = url.host_str().unwrap_or("").to_string();
+        url.query_pairs_mut().append_pair("key", &self.apikey);
+
+        let dir = self.dir.join(job.issue_id.to_string());
+        fs::create_dir_all(&dir)?;
+        let target = dir.join(format!(
+            "{}-{}",
+            job.attachment.id,
+            job.attachment.filename.replace(['/', '\\'], "_")
+        ));
+
+        self.hosts.acquire(&host);
+        let result = fetch(url, &target);
+        self.hosts.release(&host);
+        let bytes = result?;
+
+        let mut state = lock(&self.state);
+        writeln!(state, "{}", job.attachment.id)?;
+        state.sync_data()?;
+
+        Ok(bytes)
+    }
+}
+
+/// Limits the number of downloads running against the same host at once.
+struct HostSlots {
+    limit: usize,
+    busy: Mutex<HashMap<String, usize>>,
+    freed: Condvar,
+}
+impl HostSlots {
+    /// Creates a new instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - the number of downloads per host
+    fn new(limit: usize) -> Self {
+        HostSlots {
+            limit: limit,
+            busy: Mutex::new(HashMap::new()),
+            freed: Condvar::new(),
+        }
+    }
+
+    /// Blocks until a download against `host` may start.
+    fn acquire(&self, host: &str) {
+        let mut busy = lock(&self.busy);
+        loop {
+            let n = *busy.get(host).unwrap_or(&0);
+            if n < self.limit {
+                busy.insert(host.to_string(), n + 1);
+                return;
+            }
+            busy = self.freed.wait(busy).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Marks a download against `host` as finished.
+    fn release(&self, host: &str) {
+        if let Some(n) = lock(&self.busy).get_mut(host) {
+            *n -= 1;
+        }
+        self.freed.notify_all();
+    }
+}
+
+/// Locks a mutex. A worker panicking while holding the lock doesn't affect the others, so
+/// poisoning is ignored.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Downloads `url` to `target`. The content is written to a `.part` file first, which is renamed
+/// once complete, so interrupted downloads never leave truncated files behind.
+///
+/// # Arguments
+///
+/// * `url` - the url of the attachment including the api key
+/// * `target` - the path the attachment is stored at
+fn fetch(url: Url, target: &Path) -> Result<u64> {
+    let mut response = http::BlockingTransport.send(http::Request {
+        method: http::Method::Get,
+        url: url.into_string(),
+        headers: Vec::new(),
+        body: None,
+    })?;
+    if !response.is_success() {
+        bail!("Error: {}", response.status);
+    }
+
+    let mut part = OsString::from(target.as_os_str());
+    part.push(".part");
+    let part = PathBuf::from(part);
+
+    let mut file = File::create(&part).chain_err(
+        || format!("Can't create {}", part.display()),
+    )?;
+    let bytes = io::copy(&mut response.body, &mut file)?;
+    file.sync_all()?;
+    fs::rename(&part, target)?;
+
+    Ok(bytes)
+}
+
+/// Reads the ids of the attachments downloaded by previous runs.
+///
+/// # Arguments
+///
+/// * `path` - the path of the state file, which may not exist yet
+fn read_state(path: &Path) -> Result<HashSet<u32>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(HashSet::new()),
+        Err(e) => return Err(e).chain_err(|| format!("Can't open {}", path.display())),
+    };
+
+    let mut ids = HashSet::new();
+    for line in BufReader::new(file).lines() {
+        if let Ok(id) = line?.trim().parse() {
+            ids.insert(id);
+        }
+    }
+
+    Ok(ids)
+}
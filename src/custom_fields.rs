@@ -0,0 +1,313 @@
+//! This module holds everything needed to represent the redmine custom fields api as described by
+//! following link: http://www.redmine.org/projects/redmine/wiki/Rest_CustomFields. It also
+//! provides typed access to custom field values based on the field definitions.
+
+extern crate serde_json;
+
+use std::collections::HashMap;
+use super::errors::*;
+use super::metadata::MetadataCache;
+use super::{ClientHandle, CustomField, NamedObject};
+
+/// This struct exposes all methods provided by the redmine custom fields api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns CustomFieldListExecutor struct which provides an `execute` function for retrieving
+    /// the list of custom field definitions. Requires administrator privileges.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.custom_fields().list().execute();
+    /// ```
+    pub fn list(&self) -> CustomFieldListExecutor {
+        CustomFieldListExecutor { client: self.client.clone() }
+    }
+
+    /// Returns the definition of the custom field with the given name. The definitions are
+    /// pulled from redmine application on first use and cached in the
+    /// [MetadataCache](../metadata/struct.MetadataCache.html) of the api afterwards. Fails with
+    /// [ErrorKind::UnknownCustomField](../errors/enum.ErrorKind.html) if there is no such custom
+    /// field. Requires administrator privileges.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the custom field
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(severity) = redmine.custom_fields().by_name("Severity") {
+    ///     println!("Severity has id {}", severity.id);
+    /// }
+    /// ```
+    pub fn by_name(&self, name: &str) -> Result<CustomFieldDefinition> {
+        let definitions = MetadataCache::with_client(self.client.clone()).custom_fields()?;
+
+        match definitions.iter().find(|cf| cf.name == name) {
+            Some(definition) => Ok(definition.clone()),
+            None => bail!(ErrorKind::UnknownCustomField(name.to_string())),
+        }
+    }
+
+    /// Drops the cached metadata including the custom field definitions, so they are pulled
+    /// again on next use, e.g. after custom fields have been changed. Same as
+    /// [MetadataCache::invalidate](../metadata/struct.MetadataCache.html#method.invalidate).
+    pub fn clear_cache(&self) {
+        MetadataCache::with_client(self.client.clone()).invalidate();
+    }
+}
+
+/// Helper struct to provide a unified interface for all custom field api methods.
+pub struct CustomFieldListExecutor {
+    client: ClientHandle,
+}
+impl CustomFieldListExecutor {
+    /// Performs request to redmine application and returns a list of custom field definitions.
+    pub fn execute(&self) -> Result<CustomFieldDefinitionList> {
+        let result = self.client.get("/custom_fields.json", &HashMap::new())?;
+
+        serde_json::from_str(&result).chain_err(|| "Can't parse json")
+    }
+}
+
+/// Holds a vector of [CustomFieldDefinition](struct.CustomFieldDefinition.html)s. Implements
+/// IntoIterator trait for easy iteration.
+#[derive(Deserialize, Debug, Default)]
+pub struct CustomFieldDefinitionList {
+    custom_fields: Vec<CustomFieldDefinition>,
+}
+impl CustomFieldDefinitionList {
+    /// Returns the definition of the custom field with the given id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the custom field id
+    pub fn by_id(&self, id: u32) -> Option<&CustomFieldDefinition> {
+        self.custom_fields.iter().find(|cf| cf.id == id)
+    }
+
+    /// Returns the definition of the custom field with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the custom field
+    pub fn by_name(&self, name: &str) -> Option<&CustomFieldDefinition> {
+        self.custom_fields.iter().find(|cf| cf.name == name)
+    }
+}
+impl IntoIterator for CustomFieldDefinitionList {
+    type Item = CustomFieldDefinition;
+    type IntoIter = ::std::vec::IntoIter<CustomFieldDefinition>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.custom_fields.into_iter()
+    }
+}
+
+/// Represents a custom field definition as pulled from redmine application.
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct CustomFieldDefinition {
+    pub id: u32,
+    pub name: String,
+    pub customized_type: String,
+    pub field_format: String,
+    #[serde(default)]
+    pub is_required: bool,
+    #[serde(default)]
+    pub multiple: bool,
+    /// Whether the custom field is visible to everyone. Otherwise only the roles listed in
+    /// `roles` see it.
+    #[serde(default = "visible")]
+    pub visible: bool,
+    pub default_value: Option<String>,
+    /// The values a list or enumeration custom field accepts. Is empty for other formats.
+    #[serde(default)]
+    pub possible_values: Vec<PossibleValue>,
+    /// The trackers an issue custom field is enabled for. Is empty for other customized types.
+    #[serde(default)]
+    pub trackers: Vec<NamedObject>,
+    /// The roles which see the custom field if it isn't visible to everyone.
+    #[serde(default)]
+    pub roles: Vec<NamedObject>,
+}
+impl CustomFieldDefinition {
+    /// Checks if an issue custom field is enabled for the tracker specified by `tracker_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tracker_id` - an integer holding the tracker id
+    pub fn is_for_tracker(&self, tracker_id: u32) -> bool {
+        self.trackers.iter().any(|t| t.id() == tracker_id)
+    }
+
+    /// Checks if the custom field is visible to a member with the role specified by `role_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `role_id` - an integer holding the role id
+    pub fn is_visible_to(&self, role_id: u32) -> bool {
+        self.visible || self.roles.iter().any(|r| r.id() == role_id)
+    }
+
+    /// Returns the value to send to redmine application for `value`, which may be a possible
+    /// value or, e.g. for enumerations, its label. Returns `None` if the custom field has
+    /// possible values and `value` isn't one of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - a string slice holding the value
+    pub fn accept(&self, value: &str) -> Option<String> {
+        if self.possible_values.is_empty() {
+            return Some(value.to_string());
+        }
+
+        self.possible_values
+            .iter()
+            .find(|pv| pv.value == value)
+            .or_else(|| {
+                self.possible_values.iter().find(|pv| {
+                    pv.label.as_ref().is_some_and(|l| l == value)
+                })
+            })
+            .map(|pv| pv.value.clone())
+    }
+}
+
+/// Custom fields are visible to everyone unless redmine application says otherwise.
+fn visible() -> bool {
+    true
+}
+
+/// Represents a possible value of a list or enumeration custom field.
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct PossibleValue {
+    pub value: String,
+    /// The label shown in the redmine application, if it differs from the value, e.g. the name
+    /// of an enumeration value whose value is its id.
+    pub label: Option<String>,
+}
+
+/// Represents a typed custom field value. The variant is chosen by the `field_format` of the
+/// custom field definition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustomFieldValue {
+    /// Formats `string`, `text` and `link`.
+    Text(String),
+    /// Format `int`.
+    Int(i64),
+    /// Format `float`.
+    Float(f64),
+    /// Format `date`, given as `YYYY-MM-DD`.
+    Date(String),
+    /// Format `bool`.
+    Bool(bool),
+    /// Formats `list` and `enumeration`.
+    List(String),
+    /// Format `user`, holding the user id.
+    User(u32),
+    /// Format `version`, holding the version id.
+    Version(u32),
+    /// Any format with multiple values enabled.
+    Multi(Vec<CustomFieldValue>),
+}
+impl CustomFieldValue {
+    /// Converts a raw custom field value to a typed value. Returns `None` if the value is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_format` - a string slice holding the field format of the custom field definition
+    /// * `value` - a json value holding the raw value as pulled from redmine application
+    pub fn parse(field_format: &str, value: &serde_json::Value) -> Result<Option<Self>> {
+        match *value {
+            serde_json::Value::Null => Ok(None),
+            serde_json::Value::Array(ref items) => {
+                let mut values = Vec::new();
+                for item in items {
+                    if let Some(v) = CustomFieldValue::parse(field_format, item)? {
+                        values.push(v);
+                    }
+                }
+                Ok(Some(CustomFieldValue::Multi(values)))
+            }
+            serde_json::Value::String(ref s) => CustomFieldValue::parse_str(field_format, s),
+            _ => CustomFieldValue::parse_str(field_format, &value.to_string()),
+        }
+    }
+
+    /// Converts a single raw value given as string to a typed value.
+    fn parse_str(field_format: &str, s: &str) -> Result<Option<Self>> {
+        if s.is_empty() {
+            return Ok(None);
+        }
+
+        let value = match field_format {
+            "int" => CustomFieldValue::Int(
+                s.parse().chain_err(|| format!("Can't parse integer: {}", s))?,
+            ),
+            "float" => CustomFieldValue::Float(
+                s.parse().chain_err(|| format!("Can't parse float: {}", s))?,
+            ),
+            "date" => {
+                if !is_date(s) {
+                    bail!("Can't parse date: {}", s);
+                }
+                CustomFieldValue::Date(s.to_string())
+            }
+            "bool" => CustomFieldValue::Bool(s == "1" || s == "true"),
+            "list" | "enumeration" => CustomFieldValue::List(s.to_string()),
+            "user" => CustomFieldValue::User(
+                s.parse().chain_err(|| format!("Can't parse user id: {}", s))?,
+            ),
+            "version" => CustomFieldValue::Version(
+                s.parse().chain_err(|| format!("Can't parse version id: {}", s))?,
+            ),
+            _ => CustomFieldValue::Text(s.to_string()),
+        };
+
+        Ok(Some(value))
+    }
+}
+
+impl CustomField {
+    /// Converts the raw value to a typed value using the given custom field definition.
+    ///
+    /// # Arguments
+    ///
+    /// * `definition` - the [CustomFieldDefinition](struct.CustomFieldDefinition.html) of this
+    ///   custom field
+    pub fn typed(&self, definition: &CustomFieldDefinition) -> Result<Option<CustomFieldValue>> {
+        CustomFieldValue::parse(&definition.field_format, &self.value)
+    }
+}
+
+/// Checks if a string slice holds a date in `YYYY-MM-DD` format.
+fn is_date(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    bytes.len() == 10 &&
+        bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+}
@@ -0,0 +1,108 @@
+//! Internal helpers for the dates redmine uses, i.e. `YYYY-MM-DD` for dates and
+//! `YYYY-MM-DDThh:mm:ssZ` for timestamps, without pulling in a date library.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Returns the current date (UTC) in `YYYY-MM-DD` format.
+#[cfg(feature = "issues")]
+pub fn today() -> String {
+    days_ago(0)
+}
+
+/// Returns the seconds since the unix epoch.
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Returns the date `n` days before today (UTC) in `YYYY-MM-DD` format.
+///
+/// # Arguments
+///
+/// * `n` - an integer holding the number of days
+#[cfg(feature = "issues")]
+pub fn days_ago(n: u32) -> String {
+    format_days(now() / 86_400 - i64::from(n))
+}
+
+/// Formats the number of days since 1970-01-01 as `YYYY-MM-DD`.
+///
+/// # Arguments
+///
+/// * `days` - an integer holding the days since the unix epoch
+pub fn format_days(days: i64) -> String {
+    // see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+/// Returns the number of days since 1970-01-01 of a date.
+///
+/// # Arguments
+///
+/// * `y` - an integer holding the year
+/// * `m` - an integer holding the month (1-12)
+/// * `d` - an integer holding the day of the month
+pub fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    // see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+}
+
+/// Parses a timestamp like `2017-09-16T10:11:12Z` or `2017-09-16T12:11:12+02:00` and returns the
+/// seconds since the unix epoch. Returns None if the timestamp can't be parsed.
+///
+/// # Arguments
+///
+/// * `s` - a string slice holding the timestamp
+#[cfg(feature = "issues")]
+pub fn parse_timestamp(s: &str) -> Option<i64> {
+    if s.len() < 19 || !s.bytes().all(|b| b < 128) {
+        return None;
+    }
+
+    let field = |from: usize, to: usize| s[from..to].parse::<i64>().ok();
+    let (y, mo, d, h, mi, se) = match (
+        field(0, 4),
+        field(5, 7),
+        field(8, 10),
+        field(11, 13),
+        field(14, 16),
+        field(17, 19),
+    ) {
+        (Some(y), Some(mo), Some(d), Some(h), Some(mi), Some(se)) => (y, mo, d, h, mi, se),
+        _ => return None,
+    };
+
+    // skip fractional seconds
+    let zone = s[19..].trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+    let offset = if zone.is_empty() || zone == "Z" {
+        0
+    } else if zone.len() == 6 {
+        let sign = if zone.starts_with('-') { -1 } else { 1 };
+        match (zone[1..3].parse::<i64>(), zone[4..6].parse::<i64>()) {
+            (Ok(oh), Ok(om)) => sign * (oh * 3600 + om * 60),
+            _ => return None,
+        }
+    } else {
+        return None;
+    };
+
+    Some(days_from_civil(y, mo, d) * 86_400 + h * 3600 + mi * 60 + se - offset)
+}
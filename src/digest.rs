@@ -0,0 +1,290 @@
+//! This module provides weekly digests of a project as posted to mailing lists or chat rooms:
+//! created and closed issues, priority changes and the users who logged the most time. A digest
+//! is collected once and can be rendered as Markdown or HTML.
+
+extern crate serde_json;
+
+use std::collections::HashMap;
+use super::dates;
+use super::errors::*;
+use super::issues;
+use super::{fetch_all, ClientHandle, NamedObject};
+
+/// Enumeration of the formats a digest can be rendered in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DigestFormat {
+    Markdown,
+    Html,
+}
+
+/// Struct to provide builder pattern for digests. Is used as return type for
+/// [projects.digest](../projects/struct.Api.html#method.digest).
+pub struct Digest {
+    client: ClientHandle,
+    project_id: u32,
+    days: u32,
+    top_loggers: usize,
+}
+impl Digest {
+    /// Creates a new instance covering the last 7 days. Should not be called externally.
+    pub fn new(client: ClientHandle, project_id: u32) -> Self {
+        Digest {
+            client: client,
+            project_id: project_id,
+            days: 7,
+            top_loggers: 5,
+        }
+    }
+
+    /// Sets the number of days the digest covers. Defaults to 7.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the number of days
+    pub fn days(mut self, n: u32) -> Self {
+        self.days = n;
+        self
+    }
+
+    /// Sets the number of users listed as top time loggers. Defaults to 5.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the number of users
+    pub fn top_loggers(mut self, n: usize) -> Self {
+        self.top_loggers = n;
+        self
+    }
+
+    /// Performs requests to redmine application and collects the digest.
+    pub fn execute(&self) -> Result<DigestReport> {
+        let since = dates::days_ago(self.days);
+        let api = issues::Api::new(self.client.clone());
+
+        let updated = api.list()
+            .project_id(self.project_id)
+            .any_status()
+            .updated_since(&since)
+            .execute_all()?;
+
+        let mut report = DigestReport {
+            project_id: self.project_id,
+            since: since.clone(),
+            ..Default::default()
+        };
+
+        let priorities = self.priorities()?;
+        for issue in updated {
+            let entry = (issue.id, issue.subject.clone());
+            if issue.created_on[..] >= since[..] {
+                report.created.push(entry.clone());
+            }
+            if issue.closed_on.as_ref().is_some_and(|d| d[..] >= since[..]) {
+                report.closed.push(entry);
+            }
+
+            // journals aren't included in issue lists
+            let issue = api.show(issue.id).include("journals").execute()?;
+            for journal in issue.journals.iter().filter(|j| j.created_on[..] >= since[..]) {
+                for detail in &journal.details {
+                    if detail.property != "attr" || detail.name != "priority_id" {
+                        continue;
+                    }
+                    let name = |id: &Option<String>| {
+                        id.as_ref()
+                            .and_then(|id| id.parse::<u32>().ok())
+                            .and_then(|id| priorities.get(&id).cloned())
+                            .unwrap_or_default()
+                    };
+                    report.priority_changes.push(PriorityChange {
+                        issue_id: issue.id,
+                        subject: issue.subject.clone(),
+                        old_priority: name(&detail.old_value),
+                        new_priority: name(&detail.new_value),
+                        user: journal.user.name.clone(),
+                    });
+                }
+            }
+        }
+        report.created.sort();
+        report.closed.sort();
+
+        report.top_loggers = self.logged_time(&since)?;
+        report.top_loggers.truncate(self.top_loggers);
+
+        Ok(report)
+    }
+
+    /// Returns the names of the issue priorities by id.
+    fn priorities(&self) -> Result<HashMap<u32, String>> {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            issue_priorities: Vec<NamedObject>,
+        }
+
+        let result = self.client.get(
+            "/enumerations/issue_priorities.json",
+            &HashMap::new(),
+        )?;
+        let wrapper: Wrapper = serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+
+        Ok(
+            wrapper
+                .issue_priorities
+                .into_iter()
+                .map(|p| (p.id, p.name))
+                .collect(),
+        )
+    }
+
+    /// Returns the names of the users who logged time since `since` together with their hours,
+    /// most hours first.
+    fn logged_time(&self, since: &str) -> Result<Vec<(String, f32)>> {
+        #[derive(Deserialize)]
+        struct LoggedTime {
+            hours: f32,
+            user: NamedObject,
+        }
+
+        let entries = fetch_all(
+            &self.client,
+            &format!(
+                "/projects/{}/time_entries.json?from={}",
+                self.project_id,
+                since
+            ),
+            "time_entries",
+        )?;
+
+        let mut hours: HashMap<String, f32> = HashMap::new();
+        for entry in entries {
+            let entry: LoggedTime =
+                serde_json::from_value(entry).chain_err(|| "Can't parse json")?;
+            *hours.entry(entry.user.name).or_insert(0.0) += entry.hours;
+        }
+
+        let mut result: Vec<(String, f32)> = hours.into_iter().collect();
+        result.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1).unwrap_or(::std::cmp::Ordering::Equal).then_with(
+                || a.0.cmp(&b.0),
+            )
+        });
+
+        Ok(result)
+    }
+}
+
+/// Holds the data of a digest. Use [render](#method.render) to turn it into text.
+#[derive(Debug, Default)]
+pub struct DigestReport {
+    pub project_id: u32,
+    /// The first day covered by the digest in `YYYY-MM-DD` format.
+    pub since: String,
+    /// Ids and subjects of the issues created since the first day.
+    pub created: Vec<(u32, String)>,
+    /// Ids and subjects of the issues closed since the first day.
+    pub closed: Vec<(u32, String)>,
+    pub priority_changes: Vec<PriorityChange>,
+    /// Names of the users who logged the most time together with their hours.
+    pub top_loggers: Vec<(String, f32)>,
+}
+impl DigestReport {
+    /// Renders the digest.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - the [DigestFormat](enum.DigestFormat.html) of the text
+    pub fn render(&self, format: DigestFormat) -> String {
+        let mut sections: Vec<(String, Vec<String>)> = Vec::new();
+
+        sections.push((
+            "Created issues".to_string(),
+            self.created
+                .iter()
+                .map(|&(id, ref subject)| format!("#{} {}", id, subject))
+                .collect(),
+        ));
+        sections.push((
+            "Closed issues".to_string(),
+            self.closed
+                .iter()
+                .map(|&(id, ref subject)| format!("#{} {}", id, subject))
+                .collect(),
+        ));
+        sections.push((
+            "Priority changes".to_string(),
+            self.priority_changes
+                .iter()
+                .map(|c| {
+                    format!(
+                        "#{} {}: {} -> {} ({})",
+                        c.issue_id,
+                        c.subject,
+                        c.old_priority,
+                        c.new_priority,
+                        c.user
+                    )
+                })
+                .collect(),
+        ));
+        sections.push((
+            "Top time loggers".to_string(),
+            self.top_loggers
+                .iter()
+                .map(|&(ref name, hours)| format!("{}: {:.2} h", name, hours))
+                .collect(),
+        ));
+
+        let title = format!("Digest since {}", self.since);
+        match format {
+            DigestFormat::Markdown => {
+                let mut text = format!("# {}\n", title);
+                for (heading, lines) in sections {
+                    text.push_str(&format!("\n## {}\n\n", heading));
+                    if lines.is_empty() {
+                        text.push_str("None\n");
+                    }
+                    for line in lines {
+                        text.push_str(&format!("* {}\n", line));
+                    }
+                }
+                text
+            }
+            DigestFormat::Html => {
+                let mut text = format!("<h1>{}</h1>\n", escape_html(&title));
+                for (heading, lines) in sections {
+                    text.push_str(&format!("<h2>{}</h2>\n", escape_html(&heading)));
+                    if lines.is_empty() {
+                        text.push_str("<p>None</p>\n");
+                        continue;
+                    }
+                    text.push_str("<ul>\n");
+                    for line in lines {
+                        text.push_str(&format!("<li>{}</li>\n", escape_html(&line)));
+                    }
+                    text.push_str("</ul>\n");
+                }
+                text
+            }
+        }
+    }
+}
+
+/// Describes a change of the priority of an issue.
+#[derive(Debug, Default, Clone)]
+pub struct PriorityChange {
+    pub issue_id: u32,
+    pub subject: String,
+    pub old_priority: String,
+    pub new_priority: String,
+    /// The name of the user who changed the priority.
+    pub user: String,
+}
+
+/// Escapes the characters having a special meaning in HTML.
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,151 @@
+//! This module holds traits abstracting over the apis of the different redmine resources, so
+//! generic tooling (pagination, caching, batch operations, command line interfaces, etc.) can be
+//! written once for issues, projects, users and time entries. The traits are implemented by the
+//! `Api` struct of each module.
+//!
+//! # Example
+//!
+//! ```
+//! use redmine_api::RedmineApi;
+//! use redmine_api::entity::Listable;
+//!
+//! fn count<T: Listable>(api: &T) -> usize {
+//!     api.list_all().map(|items| items.len()).unwrap_or(0)
+//! }
+//!
+//! let redmine = RedmineApi::new(
+//!     "http://www.redmine.org/".to_string(),
+//!     "1234".to_string()
+//! );
+//!
+//! println!("{} issues, {} users", count(redmine.issues()), count(redmine.users()));
+//! ```
+
+extern crate serde_json;
+
+use super::budget::{Budget, Continuation, Partial};
+use super::errors::*;
+
+/// Number of entities requested per page when walking through all pages.
+const PAGE_SIZE: u32 = 100;
+
+/// A resource of the redmine application, e.g. issues.
+pub trait Resource {
+    /// The type representing a single entity of the resource.
+    type Entity;
+
+    /// Returns the name of the resource as used in urls, e.g. `issues` or `time_entries`.
+    fn name(&self) -> &'static str;
+}
+
+/// A resource whose entities can be listed page by page.
+pub trait Listable: Resource {
+    /// Performs request to redmine application and returns a single page of entities.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - an integer holding the number of entities to skip
+    /// * `limit` - an integer holding the maximum number of entities to return
+    fn list_page(&self, offset: u32, limit: u32) -> Result<Page<Self::Entity>>;
+
+    /// Performs as many requests to redmine application as needed to walk through all pages and
+    /// returns all entities.
+    fn list_all(&self) -> Result<Vec<Self::Entity>> {
+        let mut items = Vec::new();
+        loop {
+            let page = self.list_page(items.len() as u32, PAGE_SIZE)?;
+
+            let count = page.items.len();
+            let total_count = page.total_count.unwrap_or(0) as usize;
+            items.extend(page.items);
+
+            if count == 0 || items.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Walks through the pages like [list_all](#method.list_all), but doesn't request another
+    /// page once `budget` is exhausted. At least one page is requested, so every call makes
+    /// progress. If entities are left, the result holds a continuation which resumes at the next
+    /// page when passed to a later call.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - the time the pages may take to be requested
+    /// * `from` - a continuation returned by a previous call or `None` to start at the first page
+    fn list_within(
+        &self,
+        budget: &Budget,
+        from: Option<&Continuation>,
+    ) -> Result<Partial<Vec<Self::Entity>>> {
+        let offset = from.map(|c| c.position()).unwrap_or(0);
+
+        let mut items = Vec::new();
+        loop {
+            let next = offset + items.len() as u32;
+            let page = self.list_page(next, PAGE_SIZE)?;
+
+            let count = page.items.len();
+            let total_count = page.total_count.unwrap_or(0);
+            items.extend(page.items);
+
+            let next = offset + items.len() as u32;
+            if count == 0 || next >= total_count {
+                return Ok(Partial {
+                    result: items,
+                    continuation: None,
+                });
+            }
+            if budget.is_exhausted() {
+                return Ok(Partial {
+                    result: items,
+                    continuation: Some(Continuation::new(next)),
+                });
+            }
+        }
+    }
+}
+
+/// A resource whose entities can be shown one at a time.
+pub trait Showable: Resource {
+    /// Performs request to redmine application and returns a single entity.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the id of the entity
+    fn fetch(&self, id: u32) -> Result<Self::Entity>;
+}
+
+/// A resource whose entities can be created from json.
+pub trait Creatable: Resource {
+    /// Performs request to redmine application to create an entity and returns its id.
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - a json object holding the fields of the entity as expected by redmine, e.g.
+    ///   `{"project_id": 1, "subject": "Hello"}` for an issue
+    fn create_from_json(&self, fields: &serde_json::Value) -> Result<u32>;
+}
+
+/// A resource whose entities can be deleted.
+pub trait Deletable: Resource {
+    /// Performs request to redmine application and deletes an entity.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the id of the entity
+    fn remove(&self, id: u32) -> Result<()>;
+}
+
+/// Holds a single page of entities as returned by
+/// [Listable::list_page](trait.Listable.html#tymethod.list_page).
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Total number of entities, which may be more than the page holds. Redmine doesn't report
+    /// it for all resources.
+    pub total_count: Option<u32>,
+}
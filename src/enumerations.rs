@@ -0,0 +1,306 @@
+//! This module holds everything needed to represent the redmine enumerations api as described by
+//! following link: http://www.redmine.org/projects/redmine/wiki/Rest_Enumerations.
+
+extern crate serde_json;
+
+use std::collections::HashMap;
+use super::errors::*;
+use super::ClientHandle;
+
+/// This struct exposes all methods provided by the redmine enumerations api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns PriorityListExecutor struct which provides an `execute` function for retrieving
+    /// all issue priorities.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(priorities) = redmine.enumerations().issue_priorities().execute() {
+    ///     if let Some(urgent) = priorities.by_name("Urgent") {
+    ///         let result = redmine.issues().create(1, 1, 1, urgent.id, "Server is down")
+    ///             .execute();
+    ///     }
+    /// }
+    /// ```
+    pub fn issue_priorities(&self) -> PriorityListExecutor {
+        PriorityListExecutor { client: self.client.clone() }
+    }
+
+    /// Returns TimeEntryActivityListExecutor struct which provides an `execute` function for
+    /// retrieving all time entry activities.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(activities) = redmine.enumerations().time_entry_activities().execute() {
+    ///     if let Some(development) = activities.by_name("Development") {
+    ///         let result = redmine.time_entries().create(1, 1.5, development.id).execute();
+    ///     }
+    /// }
+    /// ```
+    pub fn time_entry_activities(&self) -> TimeEntryActivityListExecutor {
+        TimeEntryActivityListExecutor { client: self.client.clone() }
+    }
+
+    /// Returns DocumentCategoryListExecutor struct which provides an `execute` function for
+    /// retrieving all document categories.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(categories) = redmine.enumerations().document_categories().execute() {
+    ///     for category in categories {
+    ///         println!("{}: {}", category.id, category.name);
+    ///     }
+    /// }
+    /// ```
+    pub fn document_categories(&self) -> DocumentCategoryListExecutor {
+        DocumentCategoryListExecutor { client: self.client.clone() }
+    }
+}
+
+/// Helper struct to provide a unified interface for all enumeration api methods.
+pub struct PriorityListExecutor {
+    client: ClientHandle,
+}
+impl PriorityListExecutor {
+    /// Performs request to redmine application and returns a list of issue priorities.
+    pub fn execute(&self) -> Result<PriorityList> {
+        let result = self.client.get(
+            "/enumerations/issue_priorities.json",
+            &HashMap::new(),
+        )?;
+
+        serde_json::from_str(&result).chain_err(|| "Can't parse json")
+    }
+}
+
+/// Helper struct to provide a unified interface for all enumeration api methods.
+pub struct TimeEntryActivityListExecutor {
+    client: ClientHandle,
+}
+impl TimeEntryActivityListExecutor {
+    /// Performs request to redmine application and returns a list of time entry activities.
+    pub fn execute(&self) -> Result<TimeEntryActivityList> {
+        let result = self.client.get(
+            "/enumerations/time_entry_activities.json",
+            &HashMap::new(),
+        )?;
+
+        serde_json::from_str(&result).chain_err(|| "Can't parse json")
+    }
+}
+
+/// Helper struct to provide a unified interface for all enumeration api methods.
+pub struct DocumentCategoryListExecutor {
+    client: ClientHandle,
+}
+impl DocumentCategoryListExecutor {
+    /// Performs request to redmine application and returns a list of document categories.
+    pub fn execute(&self) -> Result<DocumentCategoryList> {
+        let result = self.client.get(
+            "/enumerations/document_categories.json",
+            &HashMap::new(),
+        )?;
+
+        serde_json::from_str(&result).chain_err(|| "Can't parse json")
+    }
+}
+
+/// Holds a vector of [Priority](struct.Priority.html)s, lowest first. Implements IntoIterator
+/// trait for easy iteration.
+#[derive(Deserialize, Debug, Default)]
+pub struct PriorityList {
+    issue_priorities: Vec<Priority>,
+}
+impl PriorityList {
+    /// Returns the priority with the given id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the priority id
+    pub fn by_id(&self, id: u32) -> Option<&Priority> {
+        self.issue_priorities.iter().find(|p| p.id == id)
+    }
+
+    /// Returns the priority with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the priority
+    pub fn by_name(&self, name: &str) -> Option<&Priority> {
+        self.issue_priorities.iter().find(|p| p.name == name)
+    }
+
+    /// Returns the priority new issues get unless stated otherwise, if any.
+    pub fn default_priority(&self) -> Option<&Priority> {
+        self.issue_priorities.iter().find(|p| p.is_default)
+    }
+
+    /// Returns the position of the priority with the given id. Redmine lists priorities from
+    /// the lowest to the highest, so a higher position means a higher priority.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the priority id
+    pub fn position(&self, id: u32) -> Option<usize> {
+        self.issue_priorities.iter().position(|p| p.id == id)
+    }
+}
+impl IntoIterator for PriorityList {
+    type Item = Priority;
+    type IntoIter = ::std::vec::IntoIter<Priority>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.issue_priorities.into_iter()
+    }
+}
+
+/// Holds a vector of [TimeEntryActivity](struct.TimeEntryActivity.html)s. Implements
+/// IntoIterator trait for easy iteration.
+#[derive(Deserialize, Debug, Default)]
+pub struct TimeEntryActivityList {
+    time_entry_activities: Vec<TimeEntryActivity>,
+}
+impl TimeEntryActivityList {
+    /// Returns the activity with the given id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the activity id
+    pub fn by_id(&self, id: u32) -> Option<&TimeEntryActivity> {
+        self.time_entry_activities.iter().find(|a| a.id == id)
+    }
+
+    /// Returns the activity with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the activity
+    pub fn by_name(&self, name: &str) -> Option<&TimeEntryActivity> {
+        self.time_entry_activities.iter().find(|a| a.name == name)
+    }
+
+    /// Returns the activity flagged as default, if any.
+    pub fn default_activity(&self) -> Option<&TimeEntryActivity> {
+        self.time_entry_activities.iter().find(|a| a.is_default)
+    }
+}
+impl IntoIterator for TimeEntryActivityList {
+    type Item = TimeEntryActivity;
+    type IntoIter = ::std::vec::IntoIter<TimeEntryActivity>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.time_entry_activities.into_iter()
+    }
+}
+
+/// Holds a vector of [DocumentCategory](struct.DocumentCategory.html)s. Implements IntoIterator
+/// trait for easy iteration.
+#[derive(Deserialize, Debug, Default)]
+pub struct DocumentCategoryList {
+    document_categories: Vec<DocumentCategory>,
+}
+impl DocumentCategoryList {
+    /// Returns the document category with the given id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the document category id
+    pub fn by_id(&self, id: u32) -> Option<&DocumentCategory> {
+        self.document_categories.iter().find(|c| c.id == id)
+    }
+
+    /// Returns the document category with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the document category
+    pub fn by_name(&self, name: &str) -> Option<&DocumentCategory> {
+        self.document_categories.iter().find(|c| c.name == name)
+    }
+
+    /// Returns the document category flagged as default, if any.
+    pub fn default_category(&self) -> Option<&DocumentCategory> {
+        self.document_categories.iter().find(|c| c.is_default)
+    }
+}
+impl IntoIterator for DocumentCategoryList {
+    type Item = DocumentCategory;
+    type IntoIter = ::std::vec::IntoIter<DocumentCategory>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.document_categories.into_iter()
+    }
+}
+
+/// Represents an issue priority of a redmine application.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Priority {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub is_default: bool,
+    /// Inactive priorities can't be given to issues anymore (redmine 3.4 or newer).
+    #[serde(default = "active")]
+    pub active: bool,
+}
+
+/// Represents a time entry activity of a redmine application. Projects may disable some of the
+/// activities, which are listed nonetheless.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct TimeEntryActivity {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub is_default: bool,
+    /// Inactive activities can't be given to time entries anymore (redmine 3.4 or newer).
+    #[serde(default = "active")]
+    pub active: bool,
+}
+
+/// Represents a document category of a redmine application.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct DocumentCategory {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub is_default: bool,
+    /// Inactive categories can't be given to documents anymore (redmine 3.4 or newer).
+    #[serde(default = "active")]
+    pub active: bool,
+}
+
+/// Enumerations are active unless stated otherwise.
+fn active() -> bool {
+    true
+}
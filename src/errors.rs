@@ -2,6 +2,218 @@
 error_chain! {
     foreign_links {
         Io(::std::io::Error);
-        Reqwest(::reqwest::Error);
+        Reqwest(::reqwest::Error) #[cfg(feature = "blocking")];
+    }
+
+    errors {
+        Validation(e: ValidationError) {
+            description("validation failed")
+            display("Validation failed: {}", e.messages().join(", "))
+        }
+        NothingToUpdate(id: u32) {
+            description("nothing to update")
+            display("Nothing to update for issue {}", id)
+        }
+        UnknownCustomField(name: String) {
+            description("unknown custom field")
+            display("Unknown custom field: {}", name)
+        }
+        InvalidCustomFieldValue(name: String, value: String) {
+            description("invalid custom field value")
+            display("Invalid value for custom field {}: {}", name, value)
+        }
+    }
+}
+
+/// Holds the validation errors redmine responds with if an entity can't be created or updated
+/// (status 422), e.g. "Subject cannot be blank".
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct ValidationError {
+    errors: Vec<String>,
+}
+impl ValidationError {
+    /// Creates a new instance, e.g. for checks done before sending a request. Messages should
+    /// be worded like the ones of redmine, so [fields](#method.fields) can map them.
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - a vector holding the error messages
+    pub fn new(messages: Vec<String>) -> Self {
+        ValidationError { errors: messages }
+    }
+
+    /// Returns the error messages as sent by redmine.
+    pub fn messages(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Returns the fields the error messages refer to. Messages which can't be mapped to a known
+    /// field are returned as `Field::Other`. Mapping relies on the english field labels, so the
+    /// api user's language should be set to english.
+    pub fn fields(&self) -> Vec<Field> {
+        let mut fields = Vec::new();
+        for message in &self.errors {
+            let field = Field::from_message(message);
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+        fields
+    }
+}
+
+/// Enumeration of the builder fields validation errors can refer to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+    Activity,
+    AssignedTo,
+    Category,
+    Comments,
+    Description,
+    DoneRatio,
+    DueDate,
+    EstimatedHours,
+    Firstname,
+    FixedVersion,
+    Homepage,
+    Hours,
+    Identifier,
+    Issue,
+    Lastname,
+    Login,
+    Mail,
+    Name,
+    ParentIssue,
+    Password,
+    Priority,
+    Project,
+    SpentOn,
+    StartDate,
+    Status,
+    Subject,
+    Tracker,
+    /// Holds the message which couldn't be mapped to a known field.
+    Other(String),
+}
+impl Field {
+    /// Maps a validation error message to the field it refers to by its leading label. The label
+    /// has to be followed by the message itself (e.g. "is invalid"), so custom fields whose
+    /// labels start like a known one, e.g. "Hours billed", aren't mistaken for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - a string slice holding the error message, e.g. "Subject cannot be blank"
+    pub fn from_message(message: &str) -> Field {
+        // ordered by length, so a longer label always wins over a shorter one it starts with
+        let labels = [
+            ("Estimated time", Field::EstimatedHours),
+            ("Target version", Field::FixedVersion),
+            ("Parent task", Field::ParentIssue),
+            ("Description", Field::Description),
+            ("First name", Field::Firstname),
+            ("Identifier", Field::Identifier),
+            ("Start date", Field::StartDate),
+            ("Last name", Field::Lastname),
+            ("Due date", Field::DueDate),
+            ("Activity", Field::Activity),
+            ("Assignee", Field::AssignedTo),
+            ("Category", Field::Category),
+            ("Homepage", Field::Homepage),
+            ("Password", Field::Password),
+            ("Priority", Field::Priority),
+            ("Comment", Field::Comments),
+            ("Project", Field::Project),
+            ("Subject", Field::Subject),
+            ("Tracker", Field::Tracker),
+            ("% Done", Field::DoneRatio),
+            ("Status", Field::Status),
+            ("Email", Field::Mail),
+            ("Hours", Field::Hours),
+            ("Issue", Field::Issue),
+            ("Login", Field::Login),
+            ("Date", Field::SpentOn),
+            ("Name", Field::Name),
+        ];
+
+        // redmine's messages start with one of these words
+        let verbs = ["is ", "isn't ", "are ", "has ", "must ", "cannot ", "can't ", "doesn't "];
+
+        for (label, field) in labels.iter() {
+            if !message.starts_with(label) || !message[label.len()..].starts_with(' ') {
+                continue;
+            }
+            let rest = &message[label.len() + 1..];
+            if verbs.iter().any(|v| rest.starts_with(v)) {
+                return field.clone();
+            }
+        }
+
+        Field::Other(message.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Field, ValidationError};
+
+    #[test]
+    fn labels_map_to_fields() {
+        assert_eq!(Field::from_message("Subject cannot be blank"), Field::Subject);
+        assert_eq!(Field::from_message("Assignee is invalid"), Field::AssignedTo);
+        assert_eq!(Field::from_message("% Done is not included in the list"), Field::DoneRatio);
+        assert_eq!(Field::from_message("Email has already been taken"), Field::Mail);
+        assert_eq!(Field::from_message("Date cannot be blank"), Field::SpentOn);
+    }
+
+    #[test]
+    fn longer_labels_win() {
+        assert_eq!(
+            Field::from_message("Due date must be greater than start date"),
+            Field::DueDate
+        );
+        assert_eq!(Field::from_message("Start date is not a valid date"), Field::StartDate);
+        assert_eq!(Field::from_message("Estimated time is invalid"), Field::EstimatedHours);
+        assert_eq!(
+            Field::from_message("Target version is not included in the list"),
+            Field::FixedVersion
+        );
+        assert_eq!(Field::from_message("Parent task is invalid"), Field::ParentIssue);
+        assert_eq!(Field::from_message("Last name cannot be blank"), Field::Lastname);
+    }
+
+    #[test]
+    fn unknown_labels() {
+        assert_eq!(
+            Field::from_message("Severity cannot be blank"),
+            Field::Other("Severity cannot be blank".to_string())
+        );
+        // labels are matched case sensitively at the start of the message
+        assert_eq!(
+            Field::from_message("subject cannot be blank"),
+            Field::Other("subject cannot be blank".to_string())
+        );
+    }
+
+    #[test]
+    fn custom_field_labels_starting_like_known_ones() {
+        for message in &[
+            "Hours billed cannot be blank",
+            "Status reason is not included in the list",
+            "Date of incident is not a valid date",
+            "Projected revenue is not a number",
+            "Hourslot is invalid",
+        ] {
+            assert_eq!(Field::from_message(message), Field::Other(message.to_string()));
+        }
+    }
+
+    #[test]
+    fn fields_are_deduplicated() {
+        let e = ValidationError::new(vec![
+            "Subject cannot be blank".to_string(),
+            "Subject is too long (maximum is 255 characters)".to_string(),
+            "Tracker cannot be blank".to_string(),
+        ]);
+        assert_eq!(e.fields(), vec![Field::Subject, Field::Tracker]);
     }
 }
@@ -0,0 +1,456 @@
+//! This module provides escalation of overdue or stale issues as done by SLA bots: matching issues
+//! get their priority bumped, a templated note and/or an additional watcher. Escalations run as
+//! dry run unless told otherwise and refuse to update more issues than their batch limit allows.
+
+use super::budget::{Budget, Continuation};
+use super::dates;
+use super::enumerations::{self, PriorityList};
+use super::errors::*;
+use super::issues::{self, Issue, IssueFilter};
+use super::ClientHandle;
+
+/// Default maximum number of issues an escalation updates without raising the limit.
+const DEFAULT_MAX_UPDATES: usize = 50;
+
+/// Describes which issues are escalated and how. Implements builder pattern.
+///
+/// # Example
+///
+/// ```
+/// use redmine_api::escalation::EscalationPolicy;
+///
+/// let policy = EscalationPolicy::new()
+///     .overdue()
+///     .stale_after_days(14)
+///     .priority_id(4)
+///     .note("Issue #{id} is overdue since {due_date}, please have a look.")
+///     .add_watcher(5);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct EscalationPolicy {
+    overdue: bool,
+    stale_after_days: Option<u32>,
+    priority_id: Option<u32>,
+    note: Option<String>,
+    watcher_id: Option<u32>,
+}
+impl EscalationPolicy {
+    /// Creates a new policy which doesn't match any issue yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Escalates issues whose due date has passed.
+    pub fn overdue(mut self) -> Self {
+        self.overdue = true;
+        self
+    }
+
+    /// Escalates issues which haven't been updated for the given number of days.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the number of days
+    pub fn stale_after_days(mut self, n: u32) -> Self {
+        self.stale_after_days = Some(n);
+        self
+    }
+
+    /// Sets the priority escalated issues get. Issues which already have this or a higher
+    /// priority are skipped, so running the escalation repeatedly doesn't add the same note again
+    /// and urgent issues aren't downgraded. Priorities are compared by their position as listed
+    /// by redmine application; issues with a priority which isn't listed (e.g. an inactive one)
+    /// are skipped as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the priority id
+    pub fn priority_id(mut self, id: u32) -> Self {
+        self.priority_id = Some(id);
+        self
+    }
+
+    /// Sets the note added to escalated issues. The placeholders `{id}`, `{subject}`,
+    /// `{due_date}` and `{updated_on}` are replaced by the values of the issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - a string slice holding the note template
+    pub fn note(mut self, template: &str) -> Self {
+        self.note = Some(template.to_string());
+        self
+    }
+
+    /// Sets a user who is added as watcher to escalated issues.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the user id
+    pub fn add_watcher(mut self, id: u32) -> Self {
+        self.watcher_id = Some(id);
+        self
+    }
+
+    /// Checks if an issue has to be escalated. `target` holds the position of the priority set by
+    /// the policy within `priorities`.
+    fn matches(
+        &self,
+        issue: &Issue,
+        today: &str,
+        stale_before: &Option<String>,
+        priorities: &Option<(PriorityList, usize)>,
+    ) -> bool {
+        if let Some((ref priorities, target)) = *priorities {
+            match priorities.position(issue.priority.id) {
+                Some(position) if position < target => {}
+                _ => return false,
+            }
+        }
+
+        let overdue = self.overdue &&
+            issue.due_date.as_ref().map(|d| d.as_str() < today).unwrap_or(false);
+        let stale = match *stale_before {
+            Some(ref date) => issue.updated_on[..] < date[..],
+            None => false,
+        };
+
+        overdue || stale
+    }
+
+    /// Returns the note for an issue with all placeholders replaced.
+    fn render_note(&self, issue: &Issue) -> Option<String> {
+        self.note.as_ref().map(|template| {
+            template
+                .replace("{id}", &issue.id.to_string())
+                .replace("{subject}", &issue.subject)
+                .replace("{due_date}", issue.due_date.as_ref().map_or("", |d| d.as_str()))
+                .replace("{updated_on}", &issue.updated_on)
+        })
+    }
+}
+
+/// Struct to provide builder pattern for escalations. Is used as return type for
+/// [issues.escalate_overdue](../issues/struct.Api.html#method.escalate_overdue).
+pub struct Escalation {
+    client: ClientHandle,
+    filter: IssueFilter,
+    policy: EscalationPolicy,
+    dry_run: bool,
+    max_updates: usize,
+    budget: Budget,
+    from: Option<Continuation>,
+}
+impl Escalation {
+    /// Creates a new instance running as dry run. Should not be called externally.
+    pub fn new(client: ClientHandle, filter: IssueFilter, policy: EscalationPolicy) -> Self {
+        Escalation {
+            client: client,
+            filter: filter,
+            policy: policy,
+            dry_run: true,
+            max_updates: DEFAULT_MAX_UPDATES,
+            budget: Budget::unlimited(),
+            from: None,
+        }
+    }
+
+    /// Sets whether issues are only reported (true, default) or actually escalated (false).
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - a boolean: true means nothing is updated
+    pub fn dry_run(mut self, b: bool) -> Self {
+        self.dry_run = b;
+        self
+    }
+
+    /// Sets the maximum number of issues which may be escalated. If more issues match, nothing
+    /// is updated and an error is returned. Defaults to 50.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the batch limit
+    pub fn max_updates(mut self, n: usize) -> Self {
+        self.max_updates = n;
+        self
+    }
+
+    /// Sets the time listing and escalating the issues may take. The issues are listed page by
+    /// page in order of their ids; once the budget is exhausted no further page is requested and
+    /// no further issue is escalated, and the report holds a continuation to resume with. The
+    /// batch limit only applies to the issues listed within the budget. Defaults to unlimited.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - the time budget
+    pub fn budget(mut self, budget: Budget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Resumes an escalation which has been stopped by its budget: issues handled by the previous
+    /// run are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `continuation` - the continuation of the report of the previous run
+    pub fn resume(mut self, continuation: Continuation) -> Self {
+        self.from = Some(continuation);
+        self
+    }
+
+    /// Performs requests to redmine application, collects the issues matching the filter and the
+    /// policy and escalates them in order of their ids unless running as dry run.
+    pub fn execute(&self) -> Result<EscalationReport> {
+        let today = dates::today();
+        let stale_before = self.policy.stale_after_days.map(dates::days_ago);
+        let priorities = match self.policy.priority_id {
+            Some(id) => {
+                let priorities = enumerations::Api::new(self.client.clone())
+                    .issue_priorities()
+                    .execute()?;
+                match priorities.position(id) {
+                    Some(target) => Some((priorities, target)),
+                    None => bail!("Unknown priority {}", id),
+                }
+            }
+            None => None,
+        };
+
+        // the continuation holds the id of the first issue not handled yet, listing in order of
+        // ids makes sure all issues below the last listed one have been seen
+        let first_id = self.from.map(|c| c.position()).unwrap_or(0);
+        let mut filter = self.filter.clone();
+        filter.sort("id");
+        if first_id > 0 {
+            filter.issue_id_from(first_id);
+        }
+        let listing = filter.execute_within(&self.budget, None)?;
+        let rest = match listing.continuation {
+            Some(_) => listing.result.issues().last().map(|i| Continuation::new(i.id + 1)),
+            None => None,
+        };
+
+        let mut candidates: Vec<Issue> = listing
+            .result
+            .into_iter()
+            .filter(|i| i.id >= first_id)
+            .filter(|i| self.policy.matches(i, &today, &stale_before, &priorities))
+            .collect();
+        candidates.sort_by_key(|i| i.id);
+
+        let mut report = EscalationReport {
+            dry_run: self.dry_run,
+            candidates: candidates.iter().map(|i| i.id).collect(),
+            continuation: rest,
+            ..Default::default()
+        };
+        if self.dry_run {
+            return Ok(report);
+        }
+
+        if candidates.len() > self.max_updates {
+            bail!(
+                "Refusing to escalate {} issues, limit is {}",
+                candidates.len(),
+                self.max_updates
+            );
+        }
+
+        let api = issues::Api::new(self.client.clone());
+        for issue in &candidates {
+            if self.budget.is_exhausted() {
+                report.continuation = Some(Continuation::new(issue.id));
+                break;
+            }
+
+            match self.escalate(&api, issue) {
+                Ok(_) => report.escalated.push(issue.id),
+                Err(e) => report.failed.push((issue.id, e.to_string())),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Applies the policy to a single issue.
+    fn escalate(&self, api: &issues::Api, issue: &Issue) -> Result<()> {
+        let note = self.policy.render_note(issue);
+
+        if self.policy.priority_id.is_some() || note.is_some() {
+            let mut builder = api.update(issue.id);
+            if let Some(id) = self.policy.priority_id {
+                builder = builder.priority_id(id);
+            }
+            if let Some(ref n) = note {
+                builder = builder.notes(n);
+            }
+            builder.execute()?;
+        }
+
+        if let Some(id) = self.policy.watcher_id {
+            api.add_watcher(issue.id, id).execute()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Holds the result of an escalation.
+#[derive(Debug, Default)]
+pub struct EscalationReport {
+    pub dry_run: bool,
+    /// Ids of the issues matching the policy.
+    pub candidates: Vec<u32>,
+    /// Ids of the issues which have been escalated.
+    pub escalated: Vec<u32>,
+    /// Ids of the issues which couldn't be escalated together with the error message.
+    pub failed: Vec<(u32, String)>,
+    /// Token to resume the escalation if it has been stopped by its budget, see
+    /// [Escalation::resume](struct.Escalation.html#method.resume).
+    pub continuation: Option<Continuation>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use url::Url;
+    use super::super::budget::{Budget, Continuation};
+    use super::super::errors::*;
+    use super::super::{http, RedmineApi};
+    use super::EscalationPolicy;
+
+    /// Responds with four priorities and a first page of overdue issues holding the given
+    /// priority ids, and records the query strings of the issue lists.
+    struct Canned {
+        priority_ids: Vec<u32>,
+        total_count: usize,
+        lists: Arc<Mutex<Vec<String>>>,
+    }
+    impl http::Transport for Canned {
+        fn send(&self, request: http::Request) -> Result<http::Response> {
+            let url = Url::parse(&request.url).unwrap();
+            let body = match url.path() {
+                "/enumerations/issue_priorities.json" => PRIORITIES.to_string(),
+                "/issues.json" => {
+                    self.lists.lock().unwrap().push(url.query().unwrap_or("").to_string());
+                    let issues: Vec<String> = self.priority_ids
+                        .iter()
+                        .enumerate()
+                        .map(|(i, p)| issue(i as u32 + 1, *p))
+                        .collect();
+                    format!(
+                        r#"{{"issues": [{}], "total_count": {}}}"#,
+                        issues.join(","),
+                        self.total_count
+                    )
+                }
+                _ => String::new(),
+            };
+
+            Ok(http::Response {
+                status: 200,
+                location: None,
+                body: Box::new(Cursor::new(body.into_bytes())),
+            })
+        }
+    }
+
+    const PRIORITIES: &str = r#"{"issue_priorities": [
+        {"id": 1, "name": "Low", "is_default": false},
+        {"id": 2, "name": "Normal", "is_default": true},
+        {"id": 5, "name": "High", "is_default": false},
+        {"id": 4, "name": "Urgent", "is_default": false}
+    ]}"#;
+
+    fn issue(id: u32, priority_id: u32) -> String {
+        format!(
+            r#"{{
+                "id": {},
+                "project": {{"id": 1, "name": "Redmine"}},
+                "tracker": {{"id": 1, "name": "Bug"}},
+                "status": {{"id": 1, "name": "New"}},
+                "priority": {{"id": {}, "name": "P"}},
+                "author": {{"id": 3, "name": "A"}},
+                "subject": "Printer is broken",
+                "description": "",
+                "start_date": "2017-09-14",
+                "due_date": "2017-09-30",
+                "done_ratio": 0,
+                "created_on": "2017-09-14T08:00:00Z",
+                "updated_on": "2017-09-14T08:00:00Z"
+            }}"#,
+            id,
+            priority_id
+        )
+    }
+
+    fn api(priority_ids: Vec<u32>, total_count: usize) -> (RedmineApi, Arc<Mutex<Vec<String>>>) {
+        let lists = Arc::new(Mutex::new(Vec::new()));
+        let api = RedmineApi::with_transport(
+            "http://localhost".to_string(),
+            "1234".to_string(),
+            Box::new(Canned {
+                priority_ids: priority_ids,
+                total_count: total_count,
+                lists: lists.clone(),
+            }),
+        );
+
+        (api, lists)
+    }
+
+    #[test]
+    fn issues_at_or_above_target_priority_are_skipped() {
+        let (api, _) = api(vec![1, 4, 5, 2], 4);
+        let policy = EscalationPolicy::new().overdue().priority_id(5);
+        let report = api.issues()
+            .escalate_overdue(api.issues().list(), policy)
+            .execute()
+            .unwrap();
+
+        // ids don't tell the order, Urgent (4) ranks above High (5)
+        assert_eq!(report.candidates, vec![1, 4]);
+    }
+
+    #[test]
+    fn unknown_target_priority_is_an_error() {
+        let (api, _) = api(vec![1], 1);
+        let policy = EscalationPolicy::new().overdue().priority_id(3);
+        let result = api.issues().escalate_overdue(api.issues().list(), policy).execute();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn exhausted_budget_stops_listing() {
+        let (api, lists) = api(vec![1, 1], 300);
+        let policy = EscalationPolicy::new().overdue();
+        let report = api.issues()
+            .escalate_overdue(api.issues().list(), policy)
+            .budget(Budget::new(Duration::from_millis(0)))
+            .execute()
+            .unwrap();
+
+        assert_eq!(report.candidates, vec![1, 2]);
+        assert_eq!(report.continuation, Some(Continuation::new(3)));
+        let lists = lists.lock().unwrap();
+        assert_eq!(lists.len(), 1);
+        assert!(lists[0].contains("sort=id"));
+    }
+
+    #[test]
+    fn resumed_escalation_lists_remaining_issues() {
+        let (api, lists) = api(vec![1], 1);
+        let policy = EscalationPolicy::new().overdue();
+        api.issues()
+            .escalate_overdue(api.issues().list(), policy)
+            .resume(Continuation::new(3))
+            .execute()
+            .unwrap();
+
+        assert!(lists.lock().unwrap()[0].contains("issue_id=%3E%3D3"));
+    }
+}
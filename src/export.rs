@@ -0,0 +1,197 @@
+//! This module provides a full export of a project, e.g. for backups or offline analysis. The
+//! export can be collected into a [ProjectExport](struct.ProjectExport.html) or streamed to a
+//! writer as json.
+
+extern crate serde_json;
+
+use std::collections::HashMap;
+use std::io::Write;
+use super::errors::*;
+use super::issues::Issue;
+use super::projects::Project;
+use super::time_entries::TimeEntry;
+use super::{fetch_all, RedmineApi};
+
+/// Holds everything exported from a single project. Versions, wiki pages and memberships are kept
+/// as raw json.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ProjectExport {
+    pub project: Project,
+    /// Issues including journals and attachment metadata.
+    pub issues: Vec<Issue>,
+    pub versions: Vec<serde_json::Value>,
+    /// Wiki pages including their text.
+    pub wiki_pages: Vec<serde_json::Value>,
+    pub memberships: Vec<serde_json::Value>,
+    pub time_entries: Vec<TimeEntry>,
+}
+
+/// Helper struct to provide a unified interface for exporting a project. Is used as return type
+/// for [RedmineApi::export_all](../struct.RedmineApi.html#method.export_all).
+pub struct ProjectExporter<'a> {
+    api: &'a RedmineApi,
+    project_id: u32,
+}
+impl<'a> ProjectExporter<'a> {
+    /// Creates a new instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - the redmine application holding the project
+    /// * `project_id` - an integer holding the project id
+    pub fn new(api: &'a RedmineApi, project_id: u32) -> Self {
+        ProjectExporter {
+            api: api,
+            project_id: project_id,
+        }
+    }
+
+    /// Performs requests to redmine application and returns the complete export of the project.
+    pub fn execute(&self) -> Result<ProjectExport> {
+        let mut issues = Vec::new();
+        for id in self.issue_ids()? {
+            issues.push(self.issue(id)?);
+        }
+
+        Ok(ProjectExport {
+            project: self.api.projects().show(self.project_id).execute()?,
+            issues: issues,
+            versions: self.versions()?,
+            wiki_pages: self.wiki_pages()?,
+            memberships: self.memberships()?,
+            time_entries: self.time_entries()?,
+        })
+    }
+
+    /// Performs requests to redmine application and writes the export of the project as json to
+    /// `writer`. Issues are written one at a time, so the export doesn't have to fit into memory.
+    /// The output can be read back as [ProjectExport](struct.ProjectExport.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - a writer receiving the json document
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let project = self.api.projects().show(self.project_id).execute()?;
+
+        writer.write_all(b"{\"project\":")?;
+        serde_json::to_writer(&mut *writer, &project).chain_err(|| "Can't write json")?;
+
+        writer.write_all(b",\"issues\":[")?;
+        for (i, id) in self.issue_ids()?.into_iter().enumerate() {
+            if i > 0 {
+                writer.write_all(b",")?;
+            }
+            serde_json::to_writer(&mut *writer, &self.issue(id)?)
+                .chain_err(|| "Can't write json")?;
+        }
+
+        writer.write_all(b"],\"versions\":")?;
+        serde_json::to_writer(&mut *writer, &self.versions()?)
+            .chain_err(|| "Can't write json")?;
+        writer.write_all(b",\"wiki_pages\":")?;
+        serde_json::to_writer(&mut *writer, &self.wiki_pages()?)
+            .chain_err(|| "Can't write json")?;
+        writer.write_all(b",\"memberships\":")?;
+        serde_json::to_writer(&mut *writer, &self.memberships()?)
+            .chain_err(|| "Can't write json")?;
+        writer.write_all(b",\"time_entries\":")?;
+        serde_json::to_writer(&mut *writer, &self.time_entries()?)
+            .chain_err(|| "Can't write json")?;
+        writer.write_all(b"}")?;
+
+        Ok(())
+    }
+
+    /// Returns the ids of all open and closed issues of the project.
+    fn issue_ids(&self) -> Result<Vec<u32>> {
+        Ok(
+            self.api
+                .issues()
+                .list()
+                .project_id(self.project_id)
+                .any_status()
+                .execute_all()?
+                .into_iter()
+                .map(|i| i.id)
+                .collect(),
+        )
+    }
+
+    /// Returns a single issue including journals and attachment metadata.
+    fn issue(&self, id: u32) -> Result<Issue> {
+        self.api
+            .issues()
+            .show(id)
+            .include("journals")
+            .include("attachments")
+            .execute()
+    }
+
+    /// Returns all time entries of the project.
+    fn time_entries(&self) -> Result<Vec<TimeEntry>> {
+        Ok(
+            self.api
+                .time_entries()
+                .list()
+                .project_id(self.project_id)
+                .execute_all()?
+                .into_iter()
+                .collect(),
+        )
+    }
+
+    /// Returns all versions of the project as raw json.
+    fn versions(&self) -> Result<Vec<serde_json::Value>> {
+        fetch_all(
+            &self.api.client,
+            &(format!("/projects/{}/versions.json", self.project_id)),
+            "versions",
+        )
+    }
+
+    /// Returns all memberships of the project as raw json.
+    fn memberships(&self) -> Result<Vec<serde_json::Value>> {
+        fetch_all(
+            &self.api.client,
+            &(format!("/projects/{}/memberships.json", self.project_id)),
+            "memberships",
+        )
+    }
+
+    /// Returns all wiki pages of the project including their text as raw json. Projects without
+    /// enabled wiki module result in an empty list.
+    fn wiki_pages(&self) -> Result<Vec<serde_json::Value>> {
+        let index = match fetch_all(
+            &self.api.client,
+            &(format!("/projects/{}/wiki/index.json", self.project_id)),
+            "wiki_pages",
+        ) {
+            Ok(index) => index,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let mut pages = Vec::new();
+        for entry in index {
+            let title = match entry.get("title").and_then(|t| t.as_str()) {
+                Some(t) => t.to_string(),
+                None => continue,
+            };
+
+            let result = self.api.client.get(
+                &(format!(
+                    "/projects/{}/wiki/{}.json",
+                    self.project_id,
+                    title
+                )),
+                &HashMap::new(),
+            )?;
+            let mut value: serde_json::Value =
+                serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+            if let Some(page) = value.get_mut("wiki_page") {
+                pages.push(page.take());
+            }
+        }
+
+        Ok(pages)
+    }
+}
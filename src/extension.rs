@@ -0,0 +1,169 @@
+//! This module lets other crates add api modules to [RedmineApi](../struct.RedmineApi.html), e.g.
+//! for the endpoints of redmine plugins. Extensions send their requests with the client of the
+//! `RedmineApi` they are created from, so they share its host, api key, transport, language and
+//! rate limiter instead of bringing their own http stack.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate redmine_api;
+//! #[macro_use]
+//! extern crate serde_derive;
+//!
+//! use redmine_api::RedmineApi;
+//! use redmine_api::errors::*;
+//! use redmine_api::extension::{Extension, ExtensionClient};
+//! use std::collections::HashMap;
+//!
+//! #[derive(Deserialize, Debug)]
+//! struct Sprint {
+//!     id: u32,
+//!     name: String,
+//! }
+//!
+//! struct AgileApi {
+//!     client: ExtensionClient,
+//! }
+//! impl Extension for AgileApi {
+//!     fn new(client: ExtensionClient) -> Self {
+//!         AgileApi { client: client }
+//!     }
+//! }
+//! impl AgileApi {
+//!     fn sprints(&self, project_id: u32) -> Result<Vec<Sprint>> {
+//!         #[derive(Deserialize)]
+//!         struct Wrapper {
+//!             agile_sprints: Vec<Sprint>,
+//!         }
+//!
+//!         let path = format!("/projects/{}/agile_sprints.json", project_id);
+//!         let wrapper: Wrapper = self.client.get_json(&path, &HashMap::new())?;
+//!         Ok(wrapper.agile_sprints)
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let redmine = RedmineApi::new(
+//!         "http://www.redmine.org/".to_string(),
+//!         "1234".to_string()
+//!     );
+//!
+//!     let result = redmine.extension::<AgileApi>().sprints(1);
+//! }
+//! ```
+
+extern crate serde_json;
+
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+use std::collections::HashMap;
+use super::errors::*;
+use super::{failure, http, ClientHandle};
+
+/// An api module provided by another crate. Is created by
+/// [RedmineApi::extension](../struct.RedmineApi.html#method.extension).
+pub trait Extension {
+    /// Creates the api module.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - the client the module sends its requests with
+    fn new(client: ExtensionClient) -> Self;
+}
+
+/// Sends requests to the redmine application on behalf of an [Extension](trait.Extension.html).
+/// Paths are relative to the host, e.g. `/agile_sprints.json`, and the api key is added to
+/// every request.
+#[derive(Clone)]
+pub struct ExtensionClient {
+    client: ClientHandle,
+}
+impl ExtensionClient {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Self {
+        ExtensionClient { client: client }
+    }
+
+    /// Performs GET request and returns the response body. Fails if the request has failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - a string slice holding the api endpoint, e.g. '/agile_sprints.json'
+    /// * `params` - a hashmap holding query parameters
+    pub fn get(&self, path: &str, params: &HashMap<&str, String>) -> Result<String> {
+        let mut url = self.client.get_base_url(path)?;
+        for (key, value) in params {
+            url.query_pairs_mut().append_pair(key, value);
+        }
+
+        let mut response = self.client.send(http::Method::Get, url, None)?;
+        let body = response.text()?;
+
+        // put response body in error message if request has failed
+        if !response.is_success() {
+            return Err(failure(response.status, &body));
+        }
+
+        Ok(body)
+    }
+
+    /// Performs GET request and deserializes the json response body.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - a string slice holding the api endpoint, e.g. '/agile_sprints.json'
+    /// * `params` - a hashmap holding query parameters
+    pub fn get_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &HashMap<&str, String>,
+    ) -> Result<T> {
+        serde_json::from_str(&self.get(path, params)?).chain_err(|| "Can't parse json")
+    }
+
+    /// Performs POST request sending `object` as json and returns the response body.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - a string slice holding the api endpoint, e.g. '/agile_sprints.json'
+    /// * `object` - a struct implementing the serde Serialize trait
+    pub fn post<T: Serialize>(&self, path: &str, object: &T) -> Result<String> {
+        let mut response = self.client.post(path, object)?;
+        let body = response.text()?;
+
+        // put response body in error message if request has failed
+        if !response.is_success() {
+            return Err(failure(response.status, &body));
+        }
+
+        Ok(body)
+    }
+
+    /// Performs PUT request sending `object` as json.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - a string slice holding the api endpoint, e.g. '/agile_sprints/1.json'
+    /// * `object` - a struct implementing the serde Serialize trait
+    pub fn put<T: Serialize>(&self, path: &str, object: &T) -> Result<()> {
+        self.client.update(path, object)
+    }
+
+    /// Performs DELETE request.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - a string slice holding the api endpoint, e.g. '/agile_sprints/1.json'
+    pub fn delete(&self, path: &str) -> Result<()> {
+        self.client.delete(path)
+    }
+
+    /// Returns the url of a page of the redmine application as shown to users.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - a string slice holding the path of the page, e.g. '/agile/board'
+    pub fn web_url(&self, path: &str) -> String {
+        self.client.web_url(path)
+    }
+}
@@ -0,0 +1,145 @@
+//! This module allows talking to more than one redmine application at once, e.g. if every
+//! department of an organization runs its own redmine. Requests can be routed to a single
+//! instance by project identifier prefix or sent to all instances with merged results.
+
+use super::errors::*;
+use super::issues::{Issue, IssueFilter};
+use super::projects::Project;
+use super::RedmineApi;
+
+/// Manages multiple [RedmineApi](../struct.RedmineApi.html) instances, each of them known by a
+/// unique name.
+///
+/// # Example
+///
+/// ```
+/// use redmine_api::RedmineApi;
+/// use redmine_api::federation::Federation;
+///
+/// let federation = Federation::new()
+///     .add("it", RedmineApi::new("http://it.example.com/".to_string(), "1234".to_string()))
+///     .add("hr", RedmineApi::new("http://hr.example.com/".to_string(), "5678".to_string()))
+///     .route("infra-", "it")
+///     .route("hr-", "hr");
+///
+/// let issues = federation.issues(|filter| { filter.status_id(1); });
+/// ```
+#[derive(Default)]
+pub struct Federation {
+    members: Vec<(String, RedmineApi)>,
+    routes: Vec<(String, String)>,
+}
+impl Federation {
+    /// Creates a new instance without any redmine applications.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds a redmine application.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the unique name of the redmine application
+    /// * `api` - a [RedmineApi](../struct.RedmineApi.html) connected to the redmine application
+    pub fn add(mut self, name: &str, api: RedmineApi) -> Self {
+        self.members.push((name.to_string(), api));
+        self
+    }
+
+    /// Routes all projects whose identifier starts with `prefix` to the redmine application
+    /// named `name`. If more than one prefix matches, the longest one wins.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - a string slice holding the project identifier prefix
+    /// * `name` - a string slice holding the name of a redmine application
+    pub fn route(mut self, prefix: &str, name: &str) -> Self {
+        self.routes.push((prefix.to_string(), name.to_string()));
+        self
+    }
+
+    /// Returns the redmine application with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of a redmine application
+    pub fn get(&self, name: &str) -> Option<&RedmineApi> {
+        self.members.iter().find(|m| m.0 == name).map(|m| &m.1)
+    }
+
+    /// Returns the name of the redmine application responsible for a project.
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - a string slice holding the project identifier
+    pub fn origin_of(&self, identifier: &str) -> Option<&str> {
+        self.routes
+            .iter()
+            .filter(|r| identifier.starts_with(&r.0 as &str))
+            .max_by_key(|r| r.0.len())
+            .map(|r| &r.1 as &str)
+    }
+
+    /// Returns the redmine application responsible for a project.
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - a string slice holding the project identifier
+    pub fn for_project(&self, identifier: &str) -> Option<&RedmineApi> {
+        self.origin_of(identifier).and_then(|name| self.get(name))
+    }
+
+    /// Performs requests to all redmine applications and returns the merged list of issues, each
+    /// tagged with the name of the application it belongs to.
+    ///
+    /// # Arguments
+    ///
+    /// * `configure` - a closure setting the filter parameters, applied to every application
+    pub fn issues<F>(&self, configure: F) -> Result<Vec<Tagged<Issue>>>
+    where
+        F: Fn(&mut IssueFilter),
+    {
+        let mut result = Vec::new();
+        for (name, api) in &self.members {
+            let mut filter = api.issues().list();
+            configure(&mut filter);
+
+            let issues = filter.execute().chain_err(
+                || format!("Can't list issues of {}", name),
+            )?;
+            result.extend(issues.into_iter().map(|i| Tagged::new(name, i)));
+        }
+
+        Ok(result)
+    }
+
+    /// Performs requests to all redmine applications and returns the merged list of projects,
+    /// each tagged with the name of the application it belongs to.
+    pub fn projects(&self) -> Result<Vec<Tagged<Project>>> {
+        let mut result = Vec::new();
+        for (name, api) in &self.members {
+            let projects = api.projects().list().execute().chain_err(
+                || format!("Can't list projects of {}", name),
+            )?;
+            result.extend(projects.into_iter().map(|p| Tagged::new(name, p)));
+        }
+
+        Ok(result)
+    }
+}
+
+/// Wraps an entity together with the name of the redmine application it was pulled from.
+#[derive(Debug)]
+pub struct Tagged<T> {
+    pub origin: String,
+    pub item: T,
+}
+impl<T> Tagged<T> {
+    /// Creates a new instance.
+    fn new(origin: &str, item: T) -> Self {
+        Tagged {
+            origin: origin.to_string(),
+            item: item,
+        }
+    }
+}
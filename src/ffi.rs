@@ -0,0 +1,355 @@
+//! This module exposes a small C ABI, so tooling written in other languages (e.g. Python via
+//! ctypes or C# via P/Invoke) can use this library instead of maintaining its own redmine
+//! client. Entities are passed in and out as json strings in the format of the redmine api.
+//! Requires the `ffi` feature; build a shared library with
+//! `cargo rustc --release --lib --features ffi --crate-type cdylib`.
+//!
+//! Functions returning a string return a newly allocated, nul terminated string which has to be
+//! released with `redmine_string_free`. They return a null pointer on failure;
+//! `redmine_last_error` then describes the failure. Clients must only be used by the thread
+//! which created them.
+//!
+//! ```c
+//! typedef struct RedmineApi RedmineApi;
+//!
+//! RedmineApi *redmine_client_new(const char *host, const char *apikey);
+//! void redmine_client_free(RedmineApi *client);
+//! char *redmine_issues_list(const RedmineApi *client, const char *filter_json);
+//! char *redmine_issues_create(const RedmineApi *client, const char *issue_json);
+//! const char *redmine_last_error(void);
+//! void redmine_string_free(char *s);
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use redmine_api::ffi::*;
+//! use std::ffi::{CStr, CString};
+//!
+//! let host = CString::new("http://www.redmine.org/").unwrap();
+//! let apikey = CString::new("1234").unwrap();
+//! let filter = CString::new(r#"{"project_id": 1, "status_id": "closed"}"#).unwrap();
+//!
+//! unsafe {
+//!     let client = redmine_client_new(host.as_ptr(), apikey.as_ptr());
+//!
+//!     let issues = redmine_issues_list(client, filter.as_ptr());
+//!     if issues.is_null() {
+//!         println!("{}", CStr::from_ptr(redmine_last_error()).to_string_lossy());
+//!     } else {
+//!         println!("{}", CStr::from_ptr(issues).to_string_lossy());
+//!         redmine_string_free(issues);
+//!     }
+//!
+//!     redmine_client_free(client);
+//! }
+//! ```
+
+extern crate serde_json;
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use super::entity::Creatable;
+use super::errors::*;
+use super::issues::{Issue, IssueList};
+use super::RedmineApi;
+
+thread_local! {
+    /// Message of the last failure on this thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Creates a client for the redmine application at `host`. Returns a null pointer if an
+/// argument is no valid utf-8. The client has to be released with `redmine_client_free`.
+///
+/// # Arguments
+///
+/// * `host` - a nul terminated string holding the url of a redmine application
+/// * `apikey` - a nul terminated string holding the apikey provided by redmine
+///
+/// # Safety
+///
+/// `host` and `apikey` have to be null or point to nul terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn redmine_client_new(
+    host: *const c_char,
+    apikey: *const c_char,
+) -> *mut RedmineApi {
+    guard(|| {
+        let host = read_str(host, "host")?;
+        let apikey = read_str(apikey, "apikey")?;
+
+        Ok(Box::into_raw(Box::new(RedmineApi::new(host, apikey))))
+    }).unwrap_or(ptr::null_mut())
+}
+
+/// Releases a client created by `redmine_client_new`. Does nothing if `client` is null.
+///
+/// # Arguments
+///
+/// * `client` - the client to release
+///
+/// # Safety
+///
+/// `client` has to be null or returned by `redmine_client_new` and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn redmine_client_free(client: *mut RedmineApi) {
+    if !client.is_null() {
+        drop(Box::from_raw(client));
+    }
+}
+
+/// Lists issues and returns them as json object `{"issues": [...], "total_count": n}`.
+///
+/// The filter is a json object holding any of `project_id`, `tracker_id`, `assigned_to_id`
+/// (an id or `"me"`), `status_id` (an id, `"open"`, `"closed"` or `"*"`), `offset` and `limit`.
+/// If `all` is `true`, all pages are pulled. A null filter lists the first page of open issues.
+///
+/// # Arguments
+///
+/// * `client` - a client created by `redmine_client_new`
+/// * `filter_json` - a nul terminated string holding the filter or null
+///
+/// # Safety
+///
+/// `client` has to be returned by `redmine_client_new` and `filter_json` has to be null or
+/// point to a nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn redmine_issues_list(
+    client: *const RedmineApi,
+    filter_json: *const c_char,
+) -> *mut c_char {
+    guard(|| {
+        let redmine = read_client(client)?;
+        let filter: serde_json::Value = if filter_json.is_null() {
+            serde_json::Value::Object(serde_json::Map::new())
+        } else {
+            serde_json::from_str(&read_str(filter_json, "filter")?).chain_err(
+                || "Can't parse json",
+            )?
+        };
+
+        let result = list_issues(redmine, &filter)?;
+        write_str(&IssuePage {
+            issues: result.issues(),
+            total_count: result.total_count(),
+        })
+    }).unwrap_or(ptr::null_mut())
+}
+
+/// Creates an issue and returns its id as json object `{"id": n}`. The issue is given as json
+/// object holding the fields expected by redmine, e.g. `{"project_id": 1, "subject": "Hello"}`.
+/// Nothing is requested after the issue has been created, so a null result means that no issue
+/// has been created and the call can be retried.
+///
+/// # Arguments
+///
+/// * `client` - a client created by `redmine_client_new`
+/// * `issue_json` - a nul terminated string holding the fields of the issue
+///
+/// # Safety
+///
+/// `client` has to be returned by `redmine_client_new` and `issue_json` has to point to a nul
+/// terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn redmine_issues_create(
+    client: *const RedmineApi,
+    issue_json: *const c_char,
+) -> *mut c_char {
+    guard(|| {
+        let redmine = read_client(client)?;
+        let fields: serde_json::Value = serde_json::from_str(&read_str(issue_json, "issue")?)
+            .chain_err(|| "Can't parse json")?;
+
+        let id = redmine.issues().create_from_json(&fields)?;
+        write_str(&Created { id: id })
+    }).unwrap_or(ptr::null_mut())
+}
+
+/// Returns the message of the failure of the last call on the calling thread or null if it has
+/// succeeded. The message is owned by the library and valid until the next call on the same
+/// thread.
+///
+/// # Example
+///
+/// ```
+/// use redmine_api::ffi::*;
+/// use std::ffi::CString;
+/// use std::ptr;
+///
+/// let host = CString::new("http://www.redmine.org/").unwrap();
+/// let apikey = CString::new("1234").unwrap();
+///
+/// unsafe {
+///     assert!(redmine_client_new(ptr::null(), apikey.as_ptr()).is_null());
+///     assert!(!redmine_last_error().is_null());
+///
+///     let client = redmine_client_new(host.as_ptr(), apikey.as_ptr());
+///     assert!(redmine_last_error().is_null());
+///     redmine_client_free(client);
+/// }
+/// ```
+#[no_mangle]
+pub extern "C" fn redmine_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |e| e.as_ptr()))
+}
+
+/// Releases a string returned by this library. Does nothing if `s` is null.
+///
+/// # Arguments
+///
+/// * `s` - the string to release
+///
+/// # Safety
+///
+/// `s` has to be null or returned by this library and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn redmine_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Helper struct for serialization of issue lists.
+#[derive(Serialize)]
+struct IssuePage<'a> {
+    issues: &'a [Issue],
+    total_count: Option<u32>,
+}
+
+/// Helper struct for serialization of the id of a created entity.
+#[derive(Serialize)]
+struct Created {
+    id: u32,
+}
+
+/// Runs `f`, recording its error or panic as last error, so neither crosses the C ABI. The error
+/// of a previous call is cleared first.
+///
+/// # Arguments
+///
+/// * `f` - the body of an exported function
+fn guard<F, T>(f: F) -> Option<T>
+where
+    F: FnOnce() -> Result<T>,
+{
+    LAST_ERROR.with(|e| *e.borrow_mut() = None);
+    let message = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => return Some(value),
+        Ok(Err(e)) => e.to_string(),
+        Err(_) => "Panic in redmine_api".to_string(),
+    };
+
+    LAST_ERROR.with(|e| {
+        *e.borrow_mut() = CString::new(message.replace('\0', "")).ok();
+    });
+    None
+}
+
+/// Borrows the client behind `client`.
+///
+/// # Arguments
+///
+/// * `client` - a client created by `redmine_client_new`
+unsafe fn read_client<'a>(client: *const RedmineApi) -> Result<&'a RedmineApi> {
+    match client.as_ref() {
+        Some(redmine) => Ok(redmine),
+        None => bail!("Client is null"),
+    }
+}
+
+/// Copies a nul terminated utf-8 string.
+///
+/// # Arguments
+///
+/// * `s` - the string to copy
+/// * `name` - a string slice holding the name of the argument for error messages
+unsafe fn read_str(s: *const c_char, name: &str) -> Result<String> {
+    if s.is_null() {
+        bail!("{} is null", name);
+    }
+
+    CStr::from_ptr(s).to_str().map(|s| s.to_string()).chain_err(
+        || format!("{} is no valid utf-8", name),
+    )
+}
+
+/// Serializes `object` to a newly allocated json string.
+///
+/// # Arguments
+///
+/// * `object` - a struct implementing the serde Serialize trait
+fn write_str<T: ::serde::Serialize>(object: &T) -> Result<*mut c_char> {
+    let json = serde_json::to_string(object).chain_err(|| "Can't serialize json")?;
+
+    // serde_json escapes nul characters, so the string never contains one
+    Ok(CString::new(json).chain_err(|| "Can't serialize json")?.into_raw())
+}
+
+/// Lists issues as described by a json filter.
+///
+/// # Arguments
+///
+/// * `redmine` - the api the requests are sent with
+/// * `filter` - a json object holding the filter
+fn list_issues(redmine: &RedmineApi, filter: &serde_json::Value) -> Result<IssueList> {
+    let mut issues = redmine.issues().list();
+
+    if let Some(id) = id_field(filter, "project_id")? {
+        issues.project_id(id);
+    }
+    if let Some(id) = id_field(filter, "tracker_id")? {
+        issues.tracker_id(id);
+    }
+    match filter.get("assigned_to_id") {
+        Some(serde_json::Value::String(s)) if s == "me" => {
+            issues.assigned_to_me();
+        }
+        _ => if let Some(id) = id_field(filter, "assigned_to_id")? {
+            issues.assigned_to_id(id);
+        },
+    }
+    match filter.get("status_id") {
+        Some(serde_json::Value::String(s)) if s == "closed" => {
+            issues.closed();
+        }
+        Some(serde_json::Value::String(s)) if s == "*" => {
+            issues.any_status();
+        }
+        Some(serde_json::Value::String(s)) if s == "open" => {}
+        _ => if let Some(id) = id_field(filter, "status_id")? {
+            issues.status_id(id);
+        },
+    }
+    if let Some(n) = id_field(filter, "offset")? {
+        issues.offset(n);
+    }
+    if let Some(n) = id_field(filter, "limit")? {
+        issues.limit(n);
+    }
+
+    if filter.get("all").and_then(|v| v.as_bool()).unwrap_or(false) {
+        issues.execute_all()
+    } else {
+        issues.execute()
+    }
+}
+
+/// Returns the non-negative integer held by `key` of the filter, if any.
+///
+/// # Arguments
+///
+/// * `filter` - a json object holding the filter
+/// * `key` - a string slice holding the name of the field
+fn id_field(filter: &serde_json::Value, key: &str) -> Result<Option<u32>> {
+    match filter.get(key) {
+        None | Some(&serde_json::Value::Null) => Ok(None),
+        Some(value) => match value.as_u64() {
+            Some(n) if n <= u64::from(u32::MAX) => Ok(Some(n as u32)),
+            _ => bail!("Invalid {}: {}", key, value),
+        },
+    }
+}
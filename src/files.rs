@@ -0,0 +1,235 @@
+//! This module holds everything needed to represent the redmine files api as described by
+//! following link: http://www.redmine.org/projects/redmine/wiki/Rest_Files. Files are published
+//! in the Files tab of a project, optionally for a version. Requires redmine 3.4 or newer.
+
+extern crate serde_json;
+
+use std::collections::HashMap;
+use super::errors::*;
+use super::uploads::Upload;
+use super::{failure, ClientHandle, NamedObject, RedmineApi, Tristate};
+
+/// This struct exposes all methods provided by the redmine files api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns FileListExecutor struct which offers an `execute` function which returns the
+    /// files of the project specified by `project_id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.files().list(1).execute();
+    /// ```
+    pub fn list(&self, project_id: u32) -> FileListExecutor {
+        FileListExecutor {
+            client: self.client.clone(),
+            project_id: project_id,
+        }
+    }
+
+    /// Returns a FileBuilder (builder pattern) and ultimately publishes an uploaded file in the
+    /// project specified by `project_id` parameter. Filename and description are taken from the
+    /// upload unless they are set.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    /// * `upload` - an [Upload](../uploads/struct.Upload.html) holding the token of the content
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(upload) = redmine.uploads().bytes("setup.exe", b"MZ").execute() {
+    ///     let result = redmine.files().create(1, &upload)
+    ///         .version_id(2)
+    ///         .description("Installer for Windows")
+    ///         .execute();
+    /// }
+    /// ```
+    pub fn create<'a>(&self, project_id: u32, upload: &'a Upload) -> FileBuilder<'a> {
+        let builder = FileBuilder {
+            client: self.client.clone(),
+            path: format!("/projects/{}/files.json", project_id),
+            token: &upload.token,
+            ..Default::default()
+        }.filename(&upload.filename);
+
+        match upload.description {
+            Some(ref d) => builder.description(d),
+            None => builder,
+        }
+    }
+}
+
+/// Helper struct to provide a unified interface for all file api methods.
+pub struct FileListExecutor {
+    client: ClientHandle,
+    project_id: u32,
+}
+impl FileListExecutor {
+    /// Performs request to redmine application and returns a list of files.
+    pub fn execute(&self) -> Result<FileList> {
+        let result = self.client.get(
+            &format!("/projects/{}/files.json", self.project_id),
+            &HashMap::new(),
+        )?;
+
+        serde_json::from_str(&result).chain_err(|| "Can't parse json")
+    }
+}
+
+/// Holds a vector of [ProjectFile](struct.ProjectFile.html)s. Implements IntoIterator trait for
+/// easy iteration.
+#[derive(Deserialize, Debug, Default)]
+pub struct FileList {
+    files: Vec<ProjectFile>,
+}
+impl FileList {
+    /// Returns the files published for the version with the given id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the version id
+    pub fn of_version(&self, id: u32) -> Vec<&ProjectFile> {
+        self.files
+            .iter()
+            .filter(|f| f.version.as_ref().is_some_and(|v| v.id == id))
+            .collect()
+    }
+}
+impl IntoIterator for FileList {
+    type Item = ProjectFile;
+    type IntoIter = ::std::vec::IntoIter<ProjectFile>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.files.into_iter()
+    }
+}
+
+/// Helper struct for serialization.
+#[derive(Serialize)]
+struct FileBuilderWrapper<'a> {
+    file: &'a FileBuilder<'a>,
+}
+
+/// Struct to provide builder pattern for publishing files. Can be serialized to be used as json
+/// parameter for request to redmine application.
+#[derive(Debug, Default, Serialize)]
+pub struct FileBuilder<'a> {
+    // internal
+    #[serde(skip_serializing)]
+    client: ClientHandle,
+    #[serde(skip_serializing)]
+    path: String,
+
+    // fields used for serialization
+    token: &'a str,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    version_id: Tristate<u32>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    filename: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    description: Tristate<&'a str>,
+}
+impl<'a> FileBuilder<'a> {
+    /// Sets the version the file is published for.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the version id
+    pub fn version_id(mut self, id: u32) -> Self {
+        self.version_id = Tristate::Set(id);
+        self
+    }
+
+    /// Sets filename of the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the filename
+    pub fn filename(mut self, s: &'a str) -> Self {
+        self.filename = Tristate::Set(s);
+        self
+    }
+
+    /// Sets description of the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the description
+    pub fn description(mut self, s: &'a str) -> Self {
+        self.description = Tristate::Set(s);
+        self
+    }
+
+    /// Performs request to redmine application to publish the file. Redmine responds without
+    /// the file, so nothing is returned.
+    pub fn execute(&self) -> Result<()> {
+        let mut response = self.client.post(&self.path, &FileBuilderWrapper { file: self })?;
+
+        // put response body in error message if request has failed
+        if !response.is_success() {
+            let body = response.text()?;
+            return Err(failure(response.status, &body));
+        }
+
+        Ok(())
+    }
+}
+
+/// Represents a file published in the Files tab of a project.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ProjectFile {
+    pub id: u32,
+    pub filename: String,
+    pub filesize: u64,
+    pub content_type: Option<String>,
+    pub description: Option<String>,
+    pub content_url: String,
+    pub author: NamedObject,
+    pub created_on: String,
+    /// The version the file is published for, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<NamedObject>,
+    /// MD5 or SHA256 checksum of the content, depending on the redmine version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    #[serde(default)]
+    pub downloads: u32,
+}
+impl ProjectFile {
+    /// Returns the url of the page showing this file in the redmine application, e.g.
+    /// `http://www.redmine.org/attachments/1`, for linking users to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - the api pointing to the redmine application
+    pub fn web_url(&self, api: &RedmineApi) -> String {
+        api.client.web_url(&format!("/attachments/{}", self.id))
+    }
+}
@@ -0,0 +1,432 @@
+//! This module holds canonical json samples of the entities of a redmine application as returned
+//! by the show endpoints, e.g. `GET /issues/1.json`, together with functions returning them
+//! deserialized. Tools built on this library can use them as realistic sample data in their own
+//! tests, e.g. as responses of a [Transport](../http/trait.Transport.html) or as input of
+//! functions processing entities. Requires the `fixtures` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use redmine_api::fixtures;
+//!
+//! let issue = fixtures::issue();
+//! assert_eq!(issue.subject, "Login fails with LDAP accounts");
+//! ```
+
+extern crate serde_json;
+
+use serde::de::DeserializeOwned;
+use super::attachments::Attachment;
+use super::groups::Group;
+use super::issues::Issue;
+use super::projects::Project;
+use super::relations::Relation;
+use super::search::SearchResult;
+use super::time_entries::TimeEntry;
+use super::users::User;
+use super::versions::Version;
+use super::wiki::WikiPage;
+
+/// Response of `GET /issues/1.json?include=journals,attachments,relations`.
+pub const ISSUE: &str = r#"{
+  "issue": {
+    "id": 1,
+    "project": {"id": 1, "name": "Redmine"},
+    "tracker": {"id": 1, "name": "Bug"},
+    "status": {"id": 2, "name": "In Progress"},
+    "priority": {"id": 3, "name": "High"},
+    "author": {"id": 3, "name": "John Doe"},
+    "assigned_to": {"id": 5, "name": "Jane Roe"},
+    "category": {"id": 2, "name": "Authentication"},
+    "fixed_version": {"id": 1, "name": "1.0"},
+    "parent": {"id": 4},
+    "subject": "Login fails with LDAP accounts",
+    "description": "Users authenticated via LDAP get a 500 error after submitting the form.",
+    "start_date": "2017-09-14",
+    "due_date": "2017-09-30",
+    "done_ratio": 40,
+    "is_private": false,
+    "estimated_hours": 4.5,
+    "total_estimated_hours": 6.5,
+    "spent_hours": 1.5,
+    "total_spent_hours": 2.75,
+    "custom_fields": [
+      {"id": 1, "name": "Affected version", "value": "0.9"},
+      {"id": 2, "name": "Browsers", "value": ["Firefox", "Chrome"]}
+    ],
+    "created_on": "2017-09-14T08:12:45Z",
+    "updated_on": "2017-09-16T10:11:12Z",
+    "closed_on": null,
+    "attachments": [
+      {
+        "id": 1,
+        "filename": "production.log",
+        "filesize": 5120,
+        "content_type": "text/plain",
+        "description": "Log of the failed login",
+        "content_url": "http://localhost:8080/attachments/download/1/production.log",
+        "author": {"id": 3, "name": "John Doe"},
+        "created_on": "2017-09-14T08:12:45Z"
+      }
+    ],
+    "relations": [
+      {"id": 1, "issue_id": 1, "issue_to_id": 2, "relation_type": "relates", "delay": null}
+    ],
+    "journals": [
+      {
+        "id": 1,
+        "user": {"id": 5, "name": "Jane Roe"},
+        "notes": "I can reproduce this, looking into it.",
+        "created_on": "2017-09-16T10:11:12Z",
+        "private_notes": false,
+        "details": [
+          {"property": "attr", "name": "status_id", "old_value": "1", "new_value": "2"},
+          {"property": "attr", "name": "assigned_to_id", "old_value": null, "new_value": "5"}
+        ]
+      }
+    ]
+  }
+}"#;
+
+/// Response of `GET /projects/1.json`.
+pub const PROJECT: &str = r#"{
+  "project": {
+    "id": 1,
+    "name": "Redmine",
+    "identifier": "redmine",
+    "description": "Redmine is a flexible project management web application.",
+    "homepage": "http://www.redmine.org",
+    "status": 1,
+    "is_public": true,
+    "default_version": {"id": 1, "name": "1.0"},
+    "default_assignee": {"id": 5, "name": "Jane Roe"},
+    "custom_fields": [
+      {"id": 3, "name": "Customer", "value": "ACME"}
+    ],
+    "created_on": "2017-01-02T09:00:00Z",
+    "updated_on": "2017-09-16T10:11:12Z"
+  }
+}"#;
+
+/// Response of `GET /users/3.json?include=memberships`.
+pub const USER: &str = r#"{
+  "user": {
+    "id": 3,
+    "login": "jdoe",
+    "firstname": "John",
+    "lastname": "Doe",
+    "mail": "jdoe@example.com",
+    "created_on": "2017-01-02T09:00:00Z",
+    "last_login_on": "2017-09-16T07:58:03Z",
+    "memberships": [
+      {
+        "id": 1,
+        "project": {"id": 1, "name": "Redmine"},
+        "roles": [
+          {"id": 3, "name": "Manager"},
+          {"id": 4, "name": "Developer", "inherited": true}
+        ]
+      },
+      {
+        "id": 4,
+        "project": {"id": 2, "name": "Website"},
+        "roles": [
+          {"id": 5, "name": "Reporter"}
+        ]
+      }
+    ]
+  }
+}"#;
+
+/// Response of `GET /time_entries/1.json`.
+pub const TIME_ENTRY: &str = r#"{
+  "time_entry": {
+    "id": 1,
+    "project": {"id": 1, "name": "Redmine"},
+    "issue": {"id": 1},
+    "user": {"id": 5, "name": "Jane Roe"},
+    "activity": {"id": 9, "name": "Development"},
+    "hours": 1.5,
+    "comments": "Analyzed the LDAP login",
+    "spent_on": "2017-09-16",
+    "created_on": "2017-09-16T16:30:00Z",
+    "updated_on": "2017-09-16T16:30:00Z"
+  }
+}"#;
+
+/// Response of `GET /versions/1.json`.
+pub const VERSION: &str = r#"{
+  "version": {
+    "id": 1,
+    "project": {"id": 1, "name": "Redmine"},
+    "name": "1.0",
+    "description": "First stable release",
+    "status": "open",
+    "due_date": "2017-10-31",
+    "sharing": "none",
+    "wiki_page_title": "Release_1_0",
+    "estimated_hours": 120.0,
+    "spent_hours": 42.5,
+    "custom_fields": [],
+    "created_on": "2017-01-02T09:00:00Z",
+    "updated_on": "2017-09-01T12:00:00Z"
+  }
+}"#;
+
+/// Response of `GET /attachments/1.json`.
+pub const ATTACHMENT: &str = r#"{
+  "attachment": {
+    "id": 1,
+    "filename": "production.log",
+    "filesize": 5120,
+    "content_type": "text/plain",
+    "description": "Log of the failed login",
+    "content_url": "http://localhost:8080/attachments/download/1/production.log",
+    "author": {"id": 3, "name": "John Doe"},
+    "created_on": "2017-09-14T08:12:45Z"
+  }
+}"#;
+
+/// Response of `GET /groups/7.json?include=users,memberships`.
+pub const GROUP: &str = r#"{
+  "group": {
+    "id": 7,
+    "name": "Developers",
+    "users": [
+      {"id": 3, "name": "John Doe"},
+      {"id": 5, "name": "Jane Roe"}
+    ],
+    "memberships": [
+      {
+        "id": 12,
+        "project": {"id": 1, "name": "Redmine"},
+        "roles": [{"id": 4, "name": "Developer"}]
+      }
+    ]
+  }
+}"#;
+
+/// Response of `GET /relations/2.json`.
+pub const RELATION: &str = r#"{
+  "relation": {
+    "id": 2,
+    "issue_id": 1,
+    "issue_to_id": 3,
+    "relation_type": "precedes",
+    "delay": 2
+  }
+}"#;
+
+/// Response of `GET /search.json?q=ldap&limit=2`.
+pub const SEARCH_RESULTS: &str = r#"{
+  "results": [
+    {
+      "id": 1,
+      "title": "Bug #1 (In Progress): Login fails with LDAP",
+      "type": "issue",
+      "url": "http://localhost/issues/1",
+      "description": "Users can't log in since the LDAP server has been moved.",
+      "datetime": "2017-09-14T08:12:45Z"
+    },
+    {
+      "id": 4,
+      "title": "Revision 1234 (redmine): Fix LDAP login",
+      "type": "changeset",
+      "url": "http://localhost/projects/redmine/repository/revisions/1234",
+      "description": "Fix LDAP login",
+      "datetime": "2017-09-15T11:00:00Z"
+    }
+  ],
+  "total_count": 2,
+  "offset": 0,
+  "limit": 2
+}"#;
+
+/// Response of `GET /projects/1/wiki/Installation.json`.
+pub const WIKI_PAGE: &str = r#"{
+  "wiki_page": {
+    "title": "Installation",
+    "parent": {"title": "Wiki"},
+    "text": "h1. Installation\n\nSee the guide for your platform.",
+    "version": 3,
+    "author": {"id": 3, "name": "John Doe"},
+    "comments": "Link the platform guides",
+    "created_on": "2017-01-02T09:00:00Z",
+    "updated_on": "2017-09-16T10:11:12Z"
+  }
+}"#;
+
+/// Deserializes the entity of a show response, which is wrapped in an object with a single key.
+///
+/// # Arguments
+///
+/// * `json` - a string slice holding the response
+/// * `key` - a string slice holding the key the entity is wrapped in, e.g. `issue`
+fn unwrap<T: DeserializeOwned>(json: &str, key: &str) -> T {
+    let mut value: serde_json::Value = serde_json::from_str(json).expect("Invalid fixture");
+    let entity = value
+        .get_mut(key)
+        .map(|v| v.take())
+        .expect("Fixture misses entity");
+
+    serde_json::from_value(entity).expect("Can't deserialize fixture")
+}
+
+/// Returns the issue of [ISSUE](constant.ISSUE.html) with journals, attachments and relations.
+pub fn issue() -> Issue {
+    unwrap(ISSUE, "issue")
+}
+
+/// Returns the project of [PROJECT](constant.PROJECT.html).
+pub fn project() -> Project {
+    unwrap(PROJECT, "project")
+}
+
+/// Returns the user of [USER](constant.USER.html) with memberships.
+pub fn user() -> User {
+    unwrap(USER, "user")
+}
+
+/// Returns the time entry of [TIME_ENTRY](constant.TIME_ENTRY.html).
+pub fn time_entry() -> TimeEntry {
+    unwrap(TIME_ENTRY, "time_entry")
+}
+
+/// Returns the version of [VERSION](constant.VERSION.html).
+pub fn version() -> Version {
+    unwrap(VERSION, "version")
+}
+
+/// Returns the attachment of [ATTACHMENT](constant.ATTACHMENT.html).
+pub fn attachment() -> Attachment {
+    unwrap(ATTACHMENT, "attachment")
+}
+
+/// Returns the group of [GROUP](constant.GROUP.html) with its users and memberships.
+pub fn group() -> Group {
+    unwrap(GROUP, "group")
+}
+
+/// Returns the relation of [RELATION](constant.RELATION.html).
+pub fn relation() -> Relation {
+    unwrap(RELATION, "relation")
+}
+
+/// Returns the search results of [SEARCH_RESULTS](constant.SEARCH_RESULTS.html).
+pub fn search_results() -> Vec<SearchResult> {
+    unwrap(SEARCH_RESULTS, "results")
+}
+
+/// Returns the wiki page of [WIKI_PAGE](constant.WIKI_PAGE.html).
+pub fn wiki_page() -> WikiPage {
+    unwrap(WIKI_PAGE, "wiki_page")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use super::*;
+
+    /// Asserts that `entity` serializes to the entity of the fixture, i.e. no field is lost or
+    /// changed by a round trip.
+    fn assert_round_trip<T: Serialize>(entity: &T, json: &str, key: &str) {
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_value(entity).unwrap(), value[key]);
+    }
+
+    #[test]
+    fn issue_round_trip() {
+        let issue = issue();
+        assert_eq!(issue.journals.len(), 1);
+        assert_eq!(issue.attachments.len(), 1);
+        assert_eq!(issue.custom_fields[0].as_str(), Some("0.9"));
+        assert_eq!(issue.remaining_hours(), Some(3.75));
+        assert_eq!(issue.own_remaining_hours(), Some(3.0));
+        assert_round_trip(&issue, ISSUE, "issue");
+    }
+
+    #[test]
+    fn project_round_trip() {
+        assert_round_trip(&project(), PROJECT, "project");
+    }
+
+    #[test]
+    fn user_round_trip() {
+        let user = user();
+        assert_eq!(user.memberships_with_role("Manager").len(), 1);
+        assert_eq!(user.memberships_with_role("Developer")[0].project.name(), "Redmine");
+        assert_round_trip(&user, USER, "user");
+    }
+
+    #[test]
+    fn search_results_round_trip() {
+        let results = search_results();
+        assert!(match results[0] {
+            SearchResult::Issue(ref hit) => hit.id == 1,
+            _ => false,
+        });
+        assert!(match results[1] {
+            SearchResult::Other(ref kind, _) => kind == "changeset",
+            _ => false,
+        });
+        assert_round_trip(&results, SEARCH_RESULTS, "results");
+    }
+
+    #[test]
+    fn time_entry_round_trip() {
+        assert_round_trip(&time_entry(), TIME_ENTRY, "time_entry");
+    }
+
+    #[test]
+    fn time_entry_of_deleted_user() {
+        for user in &[r#""user": null,"#, ""] {
+            let json = TIME_ENTRY.replace(r#""user": {"id": 5, "name": "Jane Roe"},"#, user);
+            let entry: TimeEntry = unwrap(&json, "time_entry");
+            assert!(entry.user.is_none());
+        }
+    }
+
+    #[test]
+    fn lenient_list_skips_malformed_issue() {
+        let issue: serde_json::Value = serde_json::from_str(ISSUE).unwrap();
+        let json = format!(
+            r#"{{"issues": [{}, {{"id": 2, "subject": null}}], "total_count": 2}}"#,
+            issue["issue"]
+        );
+
+        let (list, total_count) = ::lenient_list::<Issue>(&json, "issues", 25).unwrap();
+        assert_eq!(list.value.len(), 1);
+        assert_eq!(list.errors.len(), 1);
+        assert_eq!((list.errors[0].index, list.errors[0].id), (26, Some(2)));
+        assert_eq!(total_count, Some(2));
+    }
+
+    #[test]
+    fn version_round_trip() {
+        assert_round_trip(&version(), VERSION, "version");
+    }
+
+    #[test]
+    fn attachment_round_trip() {
+        assert_round_trip(&attachment(), ATTACHMENT, "attachment");
+    }
+
+    #[test]
+    fn group_round_trip() {
+        assert_eq!(group().users.len(), 2);
+        assert_eq!(group().memberships[0].roles[0].name, "Developer");
+        assert_round_trip(&group(), GROUP, "group");
+    }
+
+    #[test]
+    fn relation_round_trip() {
+        let relation = relation();
+        assert_eq!(relation.relation_type.delay(), Some(2));
+        assert_round_trip(&relation, RELATION, "relation");
+    }
+
+    #[test]
+    fn wiki_page_round_trip() {
+        assert_round_trip(&wiki_page(), WIKI_PAGE, "wiki_page");
+    }
+}
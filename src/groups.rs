@@ -0,0 +1,436 @@
+//! This module holds everything needed to represent the redmine groups api as described by
+//! following link: http://www.redmine.org/projects/redmine/wiki/Rest_Groups. Most endpoints
+//! require an api key of an administrator. Issues can be assigned to groups as well as users and
+//! redmine returns both as plain name and id; a [GroupCache](struct.GroupCache.html) tells them
+//! apart.
+
+extern crate serde_json;
+
+use std::collections::{HashMap, HashSet};
+use super::errors::*;
+#[cfg(feature = "issues")]
+use super::issues::Issue;
+use super::memberships::Membership;
+use super::{fetch_all, ClientHandle, NamedObject, RedmineApi, Tristate};
+
+/// This struct exposes all methods provided by the redmine groups api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns GroupListExecutor struct which offers an `execute` function which returns the
+    /// groups of the redmine application. Requires an api key of an administrator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.groups().list().execute();
+    /// ```
+    pub fn list(&self) -> GroupListExecutor {
+        GroupListExecutor::new(self.client.clone(), "/groups.json".to_string())
+    }
+
+    /// Returns GroupShow struct which offers an `execute` function which returns the group
+    /// specified by `id` parameter. Its users and memberships are pulled if they are included.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the group id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.groups().show(1).include("users").execute();
+    /// ```
+    pub fn show(&self, id: u32) -> GroupShow {
+        GroupShow {
+            client: self.client.clone(),
+            id: id,
+            include: Vec::new(),
+        }
+    }
+
+    /// Returns a GroupBuilder (builder pattern) and ultimately creates a new group.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the group
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.groups().create("Developers")
+    ///     .user_ids(vec![3, 5])
+    ///     .execute();
+    /// ```
+    pub fn create<'a>(&self, name: &'a str) -> GroupBuilder<'a> {
+        GroupBuilder::for_create(self.client.clone(), "/groups.json".to_string()).name(name)
+    }
+
+    /// Returns a GroupBuilder (builder pattern) and ultimately updates an existing group. Setting
+    /// `user_ids` replaces all users of the group.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the group id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.groups().update(1).name("Maintainers").execute();
+    /// ```
+    pub fn update<'a>(&self, id: u32) -> GroupBuilder<'a> {
+        GroupBuilder::for_update(self.client.clone(), format!("/groups/{}.json", id))
+    }
+
+    /// Returns GroupDelete struct which offers an `execute` function which deletes the group
+    /// specified by `id` parameter. Its users keep their accounts, but lose the memberships they
+    /// got via the group.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the group id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.groups().delete(1).execute();
+    /// ```
+    pub fn delete(&self, id: u32) -> GroupDelete {
+        GroupDelete::new(self.client.clone(), id)
+    }
+
+    /// Returns GroupAddUser struct which offers an `execute` function which adds a user to a
+    /// group.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - an integer holding the group id
+    /// * `user_id` - an integer holding the user id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.groups().add_user(1, 5).execute();
+    /// ```
+    pub fn add_user(&self, group_id: u32, user_id: u32) -> GroupAddUser {
+        GroupAddUser {
+            client: self.client.clone(),
+            group_id: group_id,
+            user_id: user_id,
+        }
+    }
+
+    /// Returns GroupRemoveUser struct which offers an `execute` function which removes a user
+    /// from a group.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - an integer holding the group id
+    /// * `user_id` - an integer holding the user id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.groups().remove_user(1, 5).execute();
+    /// ```
+    pub fn remove_user(&self, group_id: u32, user_id: u32) -> GroupRemoveUser {
+        GroupRemoveUser {
+            client: self.client.clone(),
+            group_id: group_id,
+            user_id: user_id,
+        }
+    }
+
+    /// Returns the users who are members of the group specified by `id` parameter. Requires an
+    /// api key of an administrator.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the group id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.groups().members(1);
+    /// ```
+    pub fn members(&self, id: u32) -> Result<Vec<NamedObject>> {
+        Ok(self.show(id).include("users").execute()?.users)
+    }
+
+    /// Pulls the ids of all groups and returns them as [GroupCache](struct.GroupCache.html).
+    /// Requires an api key of an administrator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(groups) = redmine.groups().cache() {
+    ///     if let Ok(issue) = redmine.issues().show(1).execute() {
+    ///         println!("Assigned to: {:?}", groups.assignee(&issue));
+    ///     }
+    /// }
+    /// ```
+    pub fn cache(&self) -> Result<GroupCache> {
+        let groups = fetch_all(&self.client, "/groups.json", "groups")?;
+
+        Ok(GroupCache {
+            ids: groups
+                .iter()
+                .filter_map(|g| g["id"].as_u64())
+                .map(|id| id as u32)
+                .collect(),
+        })
+    }
+}
+
+/// Helper struct to provide a unified interface for all group api methods.
+pub struct GroupShow {
+    client: ClientHandle,
+    id: u32,
+    include: Vec<String>,
+}
+impl GroupShow {
+    /// Requests additional associated data to be included in the group, i.e. `users` or
+    /// `memberships`.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the name of the associated data
+    pub fn include(mut self, s: &str) -> Self {
+        self.include.push(s.to_string());
+        self
+    }
+
+    /// Performs request to redmine application and returns a single group.
+    pub fn execute(&self) -> Result<Group> {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            group: Group,
+        }
+
+        let mut params: HashMap<&str, String> = HashMap::new();
+        if !self.include.is_empty() {
+            params.insert("include", self.include.join(","));
+        }
+
+        let result = self.client.get(&format!("/groups/{}.json", self.id), &params)?;
+        let wrapper: Wrapper = serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+
+        Ok(wrapper.group)
+    }
+}
+
+list_endpoint! {
+    /// Helper struct to provide a unified interface for all group api methods.
+    GroupListExecutor => GroupList<Group>, groups
+}
+
+builder_endpoint! {
+    /// Struct to provide builder pattern for creation and update of groups. Can be serialized to
+    /// be used as json parameter for request to redmine application.
+    GroupBuilder [create, update] => Group, group {
+        /// Sets name of the group.
+        name: &'a str,
+        /// Sets the users of the group, replacing the previous ones on update.
+        user_ids: Vec<u32>,
+    }
+}
+
+delete_endpoint! {
+    /// Helper struct to provide a unified interface for all group api methods.
+    GroupDelete, "/groups/{}.json"
+}
+
+/// Helper struct to provide a unified interface for all group api methods.
+pub struct GroupAddUser {
+    client: ClientHandle,
+    group_id: u32,
+    user_id: u32,
+}
+impl GroupAddUser {
+    /// Performs request to redmine application and adds a user to a group.
+    pub fn execute(&self) -> Result<()> {
+        #[derive(Serialize)]
+        struct Wrapper {
+            user_id: u32,
+        }
+
+        let response = self.client.post(
+            &format!("/groups/{}/users.json", self.group_id),
+            &Wrapper { user_id: self.user_id },
+        )?;
+
+        if !response.is_success() {
+            bail!("Error: {}", response.status);
+        }
+
+        Ok(())
+    }
+}
+
+/// Helper struct to provide a unified interface for all group api methods.
+pub struct GroupRemoveUser {
+    client: ClientHandle,
+    group_id: u32,
+    user_id: u32,
+}
+impl GroupRemoveUser {
+    /// Performs request to redmine application and removes a user from a group.
+    pub fn execute(&self) -> Result<()> {
+        self.client.delete(&format!("/groups/{}/users/{}.json", self.group_id, self.user_id))
+    }
+}
+
+/// Represents a group as pulled from redmine application.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Group {
+    pub id: u32,
+    pub name: String,
+    /// The users of the group. Is only pulled from redmine application if `users` are included.
+    #[serde(default)]
+    pub users: Vec<NamedObject>,
+    /// The projects the group is a member of together with the roles. Is only pulled from
+    /// redmine application if `memberships` are included.
+    #[serde(default)]
+    pub memberships: Vec<Membership>,
+}
+impl Group {
+    /// Returns the url of the page showing this group in the redmine application, e.g.
+    /// `http://www.redmine.org/groups/1`, for linking users to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - the api pointing to the redmine application
+    pub fn web_url(&self, api: &RedmineApi) -> String {
+        api.client.web_url(&format!("/groups/{}", self.id))
+    }
+}
+
+/// Holds the ids of all groups of a redmine application to tell group assignees from user
+/// assignees.
+#[derive(Debug, Default, Clone)]
+pub struct GroupCache {
+    ids: HashSet<u32>,
+}
+impl GroupCache {
+    /// Returns true if `id` belongs to a group.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the id of a user or group
+    pub fn is_group(&self, id: u32) -> bool {
+        self.ids.contains(&id)
+    }
+
+    /// Returns the user or group an issue is assigned to. Requires the `issues` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue` - the issue
+    #[cfg(feature = "issues")]
+    pub fn assignee(&self, issue: &Issue) -> Option<Assignee> {
+        issue.assigned_to.as_ref().map(|a| if self.is_group(a.id) {
+            Assignee::Group(a.clone())
+        } else {
+            Assignee::User(a.clone())
+        })
+    }
+}
+
+/// The user or group an issue is assigned to.
+#[derive(Debug, Clone)]
+pub enum Assignee {
+    User(NamedObject),
+    Group(NamedObject),
+}
+impl Assignee {
+    /// Returns the id of the user or group.
+    pub fn id(&self) -> u32 {
+        match *self {
+            Assignee::User(ref o) | Assignee::Group(ref o) => o.id,
+        }
+    }
+
+    /// Returns the name of the user or group.
+    pub fn name(&self) -> &str {
+        match *self {
+            Assignee::User(ref o) | Assignee::Group(ref o) => &o.name,
+        }
+    }
+
+    /// Returns true if the issue is assigned to a group.
+    pub fn is_group(&self) -> bool {
+        match *self {
+            Assignee::Group(_) => true,
+            Assignee::User(_) => false,
+        }
+    }
+}
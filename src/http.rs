@@ -0,0 +1,282 @@
+//! This module holds the transport independent representation of requests sent to and responses
+//! received from a redmine application. The apis only build [Request](struct.Request.html)s and
+//! read [Response](struct.Response.html)s; sending them is up to a
+//! [Transport](trait.Transport.html). Targets without the blocking reqwest client, e.g.
+//! `wasm32-unknown-unknown`, can disable the default `blocking` feature and supply their own
+//! transport via [RedmineApi::with_transport](../struct.RedmineApi.html#method.with_transport).
+//!
+//! The TLS backend of the blocking client is chosen by feature: `native-tls`, which is enabled
+//! by default, uses the TLS library of the operating system, `rustls` uses rustls, e.g. for
+//! static musl builds. Disable the default features to use rustls:
+//!
+//! ```toml
+//! [dependencies]
+//! redmine_api = { version = "0.0.1", default-features = false, features = ["rustls"] }
+//! ```
+//!
+//! With `blocking` alone, only plain http urls can be used. If both backends are enabled,
+//! native-tls is used.
+//!
+//! With the `async` feature, transports can also send requests without blocking, see
+//! [Transport::send_async](trait.Transport.html#method.send_async). The asynchronous executor in
+//! [nonblocking](../nonblocking/index.html) uses it, so the same builders work in both worlds.
+
+#[cfg(feature = "async")]
+use futures::future::{self, Future};
+#[cfg(feature = "async")]
+use futures::Stream;
+use std::fmt;
+#[cfg(feature = "async")]
+use std::io::Cursor;
+use std::io::Read;
+use std::time::Duration;
+#[cfg(feature = "blocking")]
+use reqwest;
+#[cfg(feature = "blocking")]
+use reqwest::header::{CONTENT_TYPE, LOCATION};
+use super::errors::*;
+
+/// Enumeration of the http methods used by the redmine api.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+/// Body of a request.
+pub enum Body {
+    /// Serialized json, sent as `application/json`.
+    Json(Vec<u8>),
+    /// Binary content, e.g. a file which should be uploaded, sent as `application/octet-stream`.
+    Binary(Box<dyn Read + Send>),
+}
+impl fmt::Debug for Body {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Body::Json(ref bytes) => write!(f, "Json({})", String::from_utf8_lossy(bytes)),
+            Body::Binary(_) => write!(f, "Binary"),
+        }
+    }
+}
+
+/// Represents a request to a redmine application.
+#[derive(Debug)]
+pub struct Request {
+    pub method: Method,
+    /// Absolute url including query string and api key.
+    pub url: String,
+    /// Additional headers as pairs of name and value, e.g. `Accept-Language`.
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Body>,
+}
+
+/// Represents a response of a redmine application.
+pub struct Response {
+    pub status: u16,
+    /// Content of the location header, which holds the url of a created entity.
+    pub location: Option<String>,
+    pub body: Box<dyn Read + Send>,
+}
+impl Response {
+    /// Returns true if the status code signals success.
+    pub fn is_success(&self) -> bool {
+        self.status >= 200 && self.status < 300
+    }
+
+    /// Reads the body of the response as string.
+    pub fn text(&mut self) -> Result<String> {
+        let mut result = String::new();
+        self.body.read_to_string(&mut result)?;
+
+        Ok(result)
+    }
+}
+impl fmt::Debug for Response {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Response")
+            .field("status", &self.status)
+            .field("location", &self.location)
+            .finish()
+    }
+}
+
+/// Describes a request which has been sent, as passed to the observer set by
+/// [RedmineApi::observe](../struct.RedmineApi.html#method.observe).
+#[derive(Debug, Clone)]
+pub struct RequestEvent {
+    pub method: Method,
+    /// Absolute url including query string, without api key.
+    pub url: String,
+    /// Additional headers which have been sent, including metadata headers.
+    pub headers: Vec<(String, String)>,
+    /// Status code of the response or `None` if no response has been received.
+    pub status: Option<u16>,
+    /// Message of the error if no response has been received.
+    pub error: Option<String>,
+    /// Time from sending the request until the response has been received.
+    pub duration: Duration,
+}
+impl RequestEvent {
+    /// Returns true for requests changing data, i.e. all but GET requests, e.g. for audit logs.
+    pub fn is_write(&self) -> bool {
+        self.method != Method::Get
+    }
+
+    /// Returns the value of the header with the given name, ignoring case.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the header
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Sends requests to a redmine application. Is shared by all threads executing requests of the
+/// same [RedmineApi](../struct.RedmineApi.html), so it has to be `Send` and `Sync`.
+pub trait Transport: Send + Sync {
+    /// Sends `request` and returns the response. Responses with error status codes are no
+    /// errors at this level.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - the request which should be sent
+    fn send(&self, request: Request) -> Result<Response>;
+
+    /// Sends `request` without blocking and returns a future resolving to the response, whose
+    /// body has been read completely. The default implementation sends the request with
+    /// [send](#tymethod.send) right away, which is fine for transports not doing any I/O, e.g.
+    /// replaying recorded responses. Requires the `async` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - the request which should be sent
+    #[cfg(feature = "async")]
+    fn send_async(
+        &self,
+        request: Request,
+    ) -> Box<dyn Future<Item = Response, Error = Error> + Send> {
+        let result = self.send(request).and_then(|mut response| {
+            let mut body = Vec::new();
+            response.body.read_to_end(&mut body)?;
+
+            Ok(Response {
+                status: response.status,
+                location: response.location,
+                body: Box::new(Cursor::new(body)),
+            })
+        });
+
+        Box::new(future::result(result))
+    }
+}
+
+/// Transport sending requests with the blocking reqwest client, or with the asynchronous reqwest
+/// client if they are sent by [send_async](trait.Transport.html#method.send_async). Is used by
+/// default and requires the `blocking` feature, which is enabled by the `native-tls` and
+/// `rustls` features.
+#[cfg(feature = "blocking")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BlockingTransport;
+#[cfg(feature = "blocking")]
+impl Transport for BlockingTransport {
+    fn send(&self, request: Request) -> Result<Response> {
+        let url = request.url;
+        let client = reqwest::Client::builder().build()?;
+        let mut builder = match request.method {
+            Method::Get => client.get(&url),
+            Method::Post => client.post(&url),
+            Method::Put => client.put(&url),
+            Method::Delete => client.delete(&url),
+        };
+
+        for (name, value) in request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+
+        builder = match request.body {
+            Some(Body::Json(bytes)) => builder.header(CONTENT_TYPE, "application/json").body(bytes),
+            Some(Body::Binary(reader)) => {
+                builder
+                    .header(CONTENT_TYPE, "application/octet-stream")
+                    .body(reqwest::Body::new(reader))
+            }
+            None => builder,
+        };
+
+        let response = builder.send().chain_err(
+            || format!("Can't send request to {}", url),
+        )?;
+
+        Ok(Response {
+            status: response.status().as_u16(),
+            location: response
+                .headers()
+                .get(LOCATION)
+                .and_then(|l| l.to_str().ok())
+                .map(|l| l.to_string()),
+            body: Box::new(response),
+        })
+    }
+    #[cfg(feature = "async")]
+    fn send_async(
+        &self,
+        request: Request,
+    ) -> Box<dyn Future<Item = Response, Error = Error> + Send> {
+        let url = request.url;
+        let client = match reqwest::async::Client::builder().build() {
+            Ok(client) => client,
+            Err(e) => return Box::new(future::err(e.into())),
+        };
+        let mut builder = match request.method {
+            Method::Get => client.get(&url),
+            Method::Post => client.post(&url),
+            Method::Put => client.put(&url),
+            Method::Delete => client.delete(&url),
+        };
+
+        for (name, value) in request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+
+        builder = match request.body {
+            Some(Body::Json(bytes)) => builder.header(CONTENT_TYPE, "application/json").body(bytes),
+            // the asynchronous client can't stream from a blocking reader, so uploads are read
+            // into memory first
+            Some(Body::Binary(mut reader)) => {
+                let mut bytes = Vec::new();
+                if let Err(e) = reader.read_to_end(&mut bytes) {
+                    return Box::new(future::err(e.into()));
+                }
+                builder.header(CONTENT_TYPE, "application/octet-stream").body(bytes)
+            }
+            None => builder,
+        };
+
+        let response = builder.send().and_then(|response| {
+            let status = response.status().as_u16();
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|l| l.to_str().ok())
+                .map(|l| l.to_string());
+
+            response.into_body().concat2().map(move |body| {
+                Response {
+                    status: status,
+                    location: location,
+                    body: Box::new(Cursor::new(body.to_vec())),
+                }
+            })
+        });
+
+        Box::new(response.map_err(move |e| {
+            Error::with_chain(e, format!("Can't send request to {}", url))
+        }))
+    }
+}
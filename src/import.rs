@@ -0,0 +1,409 @@
+//! This module recreates a project from a [ProjectExport](../export/struct.ProjectExport.html),
+//! possibly on a different redmine application. Ids of users, trackers, etc. are remapped via
+//! [ImportOptions](struct.ImportOptions.html), everything which can't be restored is reported.
+
+extern crate serde_json;
+
+use std::collections::HashMap;
+use super::errors::*;
+use super::export::ProjectExport;
+use super::issues::Issue;
+use super::{id_from_location, RedmineApi};
+
+/// Describes how the export is translated to the target redmine application. Ids which have no
+/// entry in one of the maps are taken over unchanged, except for users: unmapped users are
+/// skipped and reported.
+#[derive(Debug, Default, Clone)]
+pub struct ImportOptions {
+    /// Identifier of the new project. Defaults to the exported identifier.
+    pub identifier: Option<String>,
+    pub users: HashMap<u32, u32>,
+    pub trackers: HashMap<u32, u32>,
+    pub statuses: HashMap<u32, u32>,
+    pub priorities: HashMap<u32, u32>,
+    pub activities: HashMap<u32, u32>,
+    pub roles: HashMap<u32, u32>,
+}
+
+/// Holds the result of an import.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Id of the created project.
+    pub project_id: u32,
+    /// Maps exported issue ids to the ids of the created issues.
+    pub issues: HashMap<u32, u32>,
+    /// Maps exported version ids to the ids of the created versions.
+    pub versions: HashMap<u32, u32>,
+    /// Describes everything which couldn't be restored.
+    pub not_restored: Vec<String>,
+}
+
+/// Helper struct to provide a unified interface for importing a project. Is used as return type
+/// for [RedmineApi::import](../struct.RedmineApi.html#method.import).
+pub struct ProjectImporter<'a> {
+    api: &'a RedmineApi,
+    export: &'a ProjectExport,
+    options: ImportOptions,
+}
+impl<'a> ProjectImporter<'a> {
+    /// Creates a new instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - the redmine application the project is created in
+    /// * `export` - the exported project
+    /// * `options` - an [ImportOptions](struct.ImportOptions.html) holding the id mappings
+    pub fn new(api: &'a RedmineApi, export: &'a ProjectExport, options: ImportOptions) -> Self {
+        ProjectImporter {
+            api: api,
+            export: export,
+            options: options,
+        }
+    }
+
+    /// Performs requests to redmine application and recreates the project. Only failing to
+    /// create the project itself results in an error; everything else is reported.
+    pub fn execute(&self) -> Result<ImportReport> {
+        let mut report = ImportReport {
+            project_id: self.project()?,
+            ..Default::default()
+        };
+
+        self.versions(&mut report);
+        self.issues(&mut report);
+        self.wiki_pages(&mut report);
+        self.memberships(&mut report);
+        self.time_entries(&mut report);
+
+        Ok(report)
+    }
+
+    /// Creates the project and returns its id.
+    fn project(&self) -> Result<u32> {
+        let p = &self.export.project;
+        let identifier = self.options.identifier.clone().unwrap_or_else(
+            || p.identifier.clone(),
+        );
+
+        let mut builder = self.api.projects().create(&p.name, &identifier).is_public(
+            p.is_public
+                .unwrap_or(false),
+        );
+        if let Some(ref d) = p.description {
+            builder = builder.description(d);
+        }
+        if let Some(ref h) = p.homepage {
+            builder = builder.homepage(h);
+        }
+
+        Ok(builder.execute()?.id)
+    }
+
+    /// Creates the versions and records their new ids.
+    fn versions(&self, report: &mut ImportReport) {
+        let path = format!("/projects/{}/versions.json", report.project_id);
+
+        for v in &self.export.versions {
+            let mut version = serde_json::Map::new();
+            for key in &["name", "description", "status", "due_date", "sharing"] {
+                match v.get(*key) {
+                    Some(value) if !value.is_null() => {
+                        version.insert(key.to_string(), value.clone());
+                    }
+                    _ => {}
+                }
+            }
+            let mut wrapper = serde_json::Map::new();
+            wrapper.insert("version".to_string(), serde_json::Value::Object(version));
+
+            let result = self.api.client.create(&path, &wrapper).and_then(
+                |l| id_from_location(&l),
+            );
+            match (result, v.get("id").and_then(|i| i.as_u64())) {
+                (Ok(new_id), Some(old_id)) => {
+                    report.versions.insert(old_id as u32, new_id);
+                }
+                (Ok(_), None) => {}
+                (Err(e), _) => report.not_restored.push(format!("Version {}: {}", v["name"], e)),
+            }
+        }
+    }
+
+    /// Creates the issues including their notes and records their new ids.
+    fn issues(&self, report: &mut ImportReport) {
+        let mut issues: Vec<&Issue> = self.export.issues.iter().collect();
+        issues.sort_by_key(|i| i.id);
+
+        for issue in &issues {
+            match self.issue(issue, report) {
+                Ok(id) => {
+                    report.issues.insert(issue.id, id);
+                }
+                Err(e) => {
+                    report.not_restored.push(format!("Issue #{}: {}", issue.id, e));
+                    continue;
+                }
+            }
+
+            if issue.category.is_some() {
+                report.not_restored.push(
+                    format!("Category of issue #{}", issue.id),
+                );
+            }
+            for a in &issue.attachments {
+                report.not_restored.push(format!(
+                    "Attachment {} of issue #{} (content isn't exported)",
+                    a.filename,
+                    issue.id
+                ));
+            }
+            self.notes(issue, report);
+        }
+
+        // parents are set afterwards, since a parent may have a higher id than its child
+        for issue in &issues {
+            let parent_id = match issue.parent {
+                Some(ref p) => p.id,
+                None => continue,
+            };
+            let result = match (report.issues.get(&issue.id), report.issues.get(&parent_id)) {
+                (Some(id), Some(parent)) => {
+                    self.api
+                        .issues()
+                        .update(*id)
+                        .parent_issue_id(*parent)
+                        .execute()
+                        .map(|_| ())
+                }
+                _ => Err("Issue or parent hasn't been created".into()),
+            };
+            if let Err(e) = result {
+                report.not_restored.push(
+                    format!("Parent of issue #{}: {}", issue.id, e),
+                );
+            }
+        }
+    }
+
+    /// Creates a single issue and returns its id.
+    fn issue(&self, issue: &Issue, report: &mut ImportReport) -> Result<u32> {
+        let o = &self.options;
+        let description = issue.description.clone().unwrap_or_default();
+
+        let mut builder = self.api
+            .issues()
+            .create(
+                report.project_id,
+                map(&o.trackers, issue.tracker.id),
+                map(&o.statuses, issue.status.id),
+                map(&o.priorities, issue.priority.id),
+                &issue.subject,
+            )
+            .description(&description)
+            .done_ratio(issue.done_ratio)
+            .is_private(issue.is_private.unwrap_or(false));
+        if let Some(ref d) = issue.start_date {
+            builder = builder.start_date(d);
+        }
+        if let Some(ref d) = issue.due_date {
+            builder = builder.due_date(d);
+        }
+        if let Some(eh) = issue.estimated_hours {
+            builder = builder.estimated_hours(eh);
+        }
+        if let Some(ref a) = issue.assigned_to {
+            match o.users.get(&a.id) {
+                Some(id) => builder = builder.assigned_to_id(*id),
+                None => {
+                    report.not_restored.push(format!(
+                        "Assignee {} of issue #{} (unmapped user)",
+                        a.name,
+                        issue.id
+                    ))
+                }
+            }
+        }
+        if let Some(ref v) = issue.fixed_version {
+            match report.versions.get(&v.id) {
+                Some(id) => builder = builder.fixed_version_id(*id),
+                None => {
+                    report.not_restored.push(
+                        format!("Version of issue #{}", issue.id),
+                    )
+                }
+            }
+        }
+
+        Ok(builder.execute()?.id)
+    }
+
+    /// Restores the notes of an issue as comments. Attribute changes of the journal can't be
+    /// restored and are reported.
+    fn notes(&self, issue: &Issue, report: &mut ImportReport) {
+        let id = match report.issues.get(&issue.id) {
+            Some(id) => *id,
+            None => return,
+        };
+
+        let mut changes = 0;
+        for journal in &issue.journals {
+            changes += journal.details.len();
+
+            let notes = match journal.notes {
+                Some(ref n) if !n.is_empty() => n,
+                _ => continue,
+            };
+            let text = format!(
+                "{} wrote on {}:\n\n{}",
+                journal.user.name,
+                journal.created_on,
+                notes
+            );
+            if let Err(e) = self.api
+                .issues()
+                .comment(id, &text)
+                .private_notes(journal.private_notes)
+                .execute()
+            {
+                report.not_restored.push(
+                    format!("Note {} of issue #{}: {}", journal.id, issue.id, e),
+                );
+            }
+        }
+
+        if changes > 0 {
+            report.not_restored.push(format!(
+                "History of issue #{} ({} attribute changes)",
+                issue.id,
+                changes
+            ));
+        }
+    }
+
+    /// Creates the wiki pages.
+    fn wiki_pages(&self, report: &mut ImportReport) {
+        for page in &self.export.wiki_pages {
+            let title = page.get("title").and_then(|t| t.as_str()).unwrap_or("");
+            let text = page.get("text").and_then(|t| t.as_str()).unwrap_or("");
+            let comments = page.get("comments").and_then(|t| t.as_str()).unwrap_or("");
+
+            if let Err(e) = self.api
+                .wiki()
+                .update(report.project_id, title)
+                .text(text)
+                .comments(comments)
+                .execute()
+            {
+                report.not_restored.push(format!("Wiki page {}: {}", title, e));
+            }
+        }
+    }
+
+    /// Creates the memberships of users. Group memberships are reported.
+    fn memberships(&self, report: &mut ImportReport) {
+        #[derive(Serialize)]
+        struct Membership {
+            user_id: u32,
+            role_ids: Vec<u32>,
+        }
+        #[derive(Serialize)]
+        struct Wrapper {
+            membership: Membership,
+        }
+
+        let path = format!("/projects/{}/memberships.json", report.project_id);
+        for m in &self.export.memberships {
+            let user = match m.get("user") {
+                Some(u) => u,
+                None => {
+                    report.not_restored.push(format!("Membership {}", m["group"]["name"]));
+                    continue;
+                }
+            };
+            let user_id = match user.get("id").and_then(|i| i.as_u64()) {
+                Some(id) => id as u32,
+                None => continue,
+            };
+            let user_id = match self.options.users.get(&user_id) {
+                Some(id) => *id,
+                None => {
+                    report.not_restored.push(format!(
+                        "Membership of {} (unmapped user)",
+                        user["name"]
+                    ));
+                    continue;
+                }
+            };
+
+            let role_ids: Vec<u32> = m.get("roles")
+                .and_then(|r| r.as_array())
+                .map(|roles| {
+                    roles
+                        .iter()
+                        .filter(|r| r.get("inherited").is_none())
+                        .filter_map(|r| r.get("id").and_then(|i| i.as_u64()))
+                        .map(|id| map(&self.options.roles, id as u32))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            let wrapper = Wrapper {
+                membership: Membership {
+                    user_id: user_id,
+                    role_ids: role_ids,
+                },
+            };
+            if let Err(e) = self.api.client.create(&path, &wrapper) {
+                report.not_restored.push(
+                    format!("Membership of {}: {}", user["name"], e),
+                );
+            }
+        }
+    }
+
+    /// Creates the time entries of all restored issues. Entries of unmapped users are reported
+    /// instead of being booked on the user of the api key.
+    fn time_entries(&self, report: &mut ImportReport) {
+        for te in &self.export.time_entries {
+            let issue_id = match report.issues.get(&te.issue.id) {
+                Some(id) => *id,
+                None => {
+                    report.not_restored.push(format!("Time entry #{}", te.id));
+                    continue;
+                }
+            };
+
+            let mut builder = self.api
+                .time_entries()
+                .create(
+                    issue_id,
+                    te.hours,
+                    map(&self.options.activities, te.activity.id),
+                )
+                .spent_on(&te.spent_on)
+                .comments(&te.comments);
+            if let Some(ref user) = te.user {
+                match self.options.users.get(&user.id) {
+                    Some(id) => builder = builder.user_id(*id),
+                    None => {
+                        report.not_restored.push(format!(
+                            "Time entry #{} of {} (unmapped user)",
+                            te.id,
+                            user.name
+                        ));
+                        continue;
+                    }
+                }
+            }
+
+            if let Err(e) = builder.execute() {
+                report.not_restored.push(format!("Time entry #{}: {}", te.id, e));
+            }
+        }
+    }
+}
+
+/// Translates an id using a mapping, returning the id itself if there is no entry.
+fn map(mapping: &HashMap<u32, u32>, id: u32) -> u32 {
+    *mapping.get(&id).unwrap_or(&id)
+}
@@ -0,0 +1,220 @@
+//! This module holds everything needed to represent the redmine issue categories api as described
+//! by following link: http://www.redmine.org/projects/redmine/wiki/Rest_IssueCategories.
+
+use super::errors::*;
+use super::{ClientHandle, NamedObject, Tristate};
+
+/// This struct exposes all methods provided by the redmine issue categories api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns IssueCategoryListExecutor struct which offers an `execute` function which returns
+    /// the issue categories of the project specified by `project_id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.issue_categories().list(1).execute();
+    /// ```
+    pub fn list(&self, project_id: u32) -> IssueCategoryListExecutor {
+        IssueCategoryListExecutor::new(
+            self.client.clone(),
+            format!("/projects/{}/issue_categories.json", project_id),
+        )
+    }
+
+    /// Returns IssueCategoryShow struct which offers an `execute` function which returns the
+    /// issue category specified by `id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the issue category id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.issue_categories().show(1).execute();
+    /// ```
+    pub fn show(&self, id: u32) -> IssueCategoryShow {
+        IssueCategoryShow::new(self.client.clone(), id)
+    }
+
+    /// Returns an IssueCategoryBuilder (builder pattern) and ultimately creates a new issue
+    /// category in the project specified by `project_id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    /// * `name` - a string slice holding the name of the issue category
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.issue_categories().create(1, "Authentication")
+    ///     .assigned_to_id(5)
+    ///     .execute();
+    /// ```
+    pub fn create<'a>(&self, project_id: u32, name: &'a str) -> IssueCategoryBuilder<'a> {
+        IssueCategoryBuilder::for_create(
+            self.client.clone(),
+            format!("/projects/{}/issue_categories.json", project_id),
+        ).name(name)
+    }
+
+    /// Returns an IssueCategoryBuilder (builder pattern) and ultimately updates an existing issue
+    /// category.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the issue category id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.issue_categories().update(1)
+    ///     .name("Login")
+    ///     .clear_assigned_to_id()
+    ///     .execute();
+    /// ```
+    pub fn update<'a>(&self, id: u32) -> IssueCategoryBuilder<'a> {
+        IssueCategoryBuilder::for_update(
+            self.client.clone(),
+            format!("/issue_categories/{}.json", id),
+        )
+    }
+
+    /// Returns IssueCategoryDelete struct which offers an `execute` function which deletes the
+    /// issue category specified by `id` parameter. Issues of the category lose their category
+    /// unless they are moved to another one by
+    /// [reassign_to_id](struct.IssueCategoryDelete.html#method.reassign_to_id).
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the issue category id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.issue_categories().delete(1).reassign_to_id(2).execute();
+    /// ```
+    pub fn delete(&self, id: u32) -> IssueCategoryDelete {
+        IssueCategoryDelete {
+            client: self.client.clone(),
+            id: id,
+            reassign_to_id: None,
+        }
+    }
+}
+
+show_endpoint! {
+    /// Helper struct to provide a unified interface for all issue category api methods.
+    IssueCategoryShow => IssueCategory, issue_category, "/issue_categories/{}.json"
+}
+
+list_endpoint! {
+    /// Helper struct to provide a unified interface for all issue category api methods.
+    IssueCategoryListExecutor => IssueCategoryList<IssueCategory>, issue_categories
+}
+
+builder_endpoint! {
+    /// Struct to provide builder pattern for creation and update of issue categories. Can be
+    /// serialized to be used as json parameter for request to redmine application.
+    IssueCategoryBuilder [create, update] => IssueCategory, issue_category {
+        /// Sets name of the issue category.
+        name: &'a str,
+        /// Sets the user new issues of the category are assigned to.
+        assigned_to_id: u32,
+    }
+}
+impl<'a> IssueCategoryBuilder<'a> {
+    /// Removes the user new issues of the category are assigned to.
+    pub fn clear_assigned_to_id(mut self) -> Self {
+        self.assigned_to_id = Tristate::Clear;
+        self
+    }
+}
+
+/// Helper struct to provide a unified interface for all issue category api methods.
+pub struct IssueCategoryDelete {
+    client: ClientHandle,
+    id: u32,
+    reassign_to_id: Option<u32>,
+}
+impl IssueCategoryDelete {
+    /// Moves the issues of the deleted category to another category of the same project.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the id of the category the issues are moved to
+    pub fn reassign_to_id(&mut self, id: u32) -> &mut Self {
+        self.reassign_to_id = Some(id);
+        self
+    }
+
+    /// Performs request to redmine application and deletes the issue category.
+    pub fn execute(&self) -> Result<()> {
+        let path = match self.reassign_to_id {
+            Some(target) => {
+                format!("/issue_categories/{}.json?reassign_to_id={}", self.id, target)
+            }
+            None => format!("/issue_categories/{}.json", self.id),
+        };
+
+        self.client.delete(&path)
+    }
+}
+
+/// Represents an issue category of a project.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct IssueCategory {
+    pub id: u32,
+    pub project: NamedObject,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to: Option<NamedObject>,
+}
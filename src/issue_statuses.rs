@@ -0,0 +1,114 @@
+//! This module holds everything needed to represent the redmine issue statuses api as described
+//! by following link: http://www.redmine.org/projects/redmine/wiki/Rest_IssueStatuses.
+
+extern crate serde_json;
+
+use std::collections::HashMap;
+use super::errors::*;
+use super::ClientHandle;
+
+/// This struct exposes all methods provided by the redmine issue statuses api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns IssueStatusListExecutor struct which provides an `execute` function for retrieving
+    /// all issue statuses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(statuses) = redmine.issue_statuses().list().execute() {
+    ///     println!("Closing statuses: {:?}", statuses.closed_ids());
+    /// }
+    /// ```
+    pub fn list(&self) -> IssueStatusListExecutor {
+        IssueStatusListExecutor { client: self.client.clone() }
+    }
+}
+
+/// Helper struct to provide a unified interface for all issue status api methods.
+pub struct IssueStatusListExecutor {
+    client: ClientHandle,
+}
+impl IssueStatusListExecutor {
+    /// Performs request to redmine application and returns a list of issue statuses.
+    pub fn execute(&self) -> Result<IssueStatusList> {
+        let result = self.client.get("/issue_statuses.json", &HashMap::new())?;
+
+        serde_json::from_str(&result).chain_err(|| "Can't parse json")
+    }
+}
+
+/// Holds a vector of [IssueStatus](struct.IssueStatus.html)es in the order configured in
+/// redmine application. Implements IntoIterator trait for easy iteration.
+#[derive(Deserialize, Debug, Default)]
+pub struct IssueStatusList {
+    issue_statuses: Vec<IssueStatus>,
+}
+impl IssueStatusList {
+    /// Returns the issue status with the given id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the issue status id
+    pub fn by_id(&self, id: u32) -> Option<&IssueStatus> {
+        self.issue_statuses.iter().find(|s| s.id == id)
+    }
+
+    /// Returns the issue status with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the issue status
+    pub fn by_name(&self, name: &str) -> Option<&IssueStatus> {
+        self.issue_statuses.iter().find(|s| s.name == name)
+    }
+
+    /// Returns the ids of the statuses which close an issue.
+    pub fn closed_ids(&self) -> Vec<u32> {
+        self.issue_statuses
+            .iter()
+            .filter(|s| s.is_closed)
+            .map(|s| s.id)
+            .collect()
+    }
+
+    /// Returns the issue status flagged as default, if any.
+    pub fn default_status(&self) -> Option<&IssueStatus> {
+        self.issue_statuses.iter().find(|s| s.is_default)
+    }
+}
+impl IntoIterator for IssueStatusList {
+    type Item = IssueStatus;
+    type IntoIter = ::std::vec::IntoIter<IssueStatus>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.issue_statuses.into_iter()
+    }
+}
+
+/// Represents an issue status of a redmine application.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct IssueStatus {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub is_closed: bool,
+    /// Only reported by redmine before 3.0; newer versions define the default status per
+    /// tracker, see [Tracker](../trackers/struct.Tracker.html).
+    #[serde(default)]
+    pub is_default: bool,
+}
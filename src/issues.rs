@@ -4,17 +4,47 @@
 extern crate serde_json;
 
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use super::budget::{Budget, Continuation, Partial};
+use super::entity::{Creatable, Deletable, Listable, Page, Resource, Showable};
 use super::errors::*;
-use super::{Object, NamedObject, RedmineClient};
+use super::custom_fields::{self, CustomFieldDefinitionList, CustomFieldValue};
+use super::dates;
+use super::escalation::{Escalation, EscalationPolicy};
+use super::groups::Api as GroupApi;
+use super::assignment::{AssignmentStrategy, AutoAssign};
+use super::attachments::Attachment;
+#[cfg(feature = "outbox")]
+use super::outbox::{PendingWrite, WriteMethod};
+use super::query::Query;
+use super::relations::Relation;
+use super::resolver::Resolver;
+use super::saved_queries::SavedQuery;
+use super::tags::TagField;
+use super::triage::TriageInbox;
+use super::uploads::Upload;
+use super::users::UserRef;
+use super::{create_from_json, duration_to_hours, format_table, lenient_list, missing_includes,
+            Checked, ClientHandle, CustomField, ItemError, Lenient, NamedObject, Object, RedmineApi,
+            Tristate};
+
+/// Maximum number of characters of an issue subject accepted by redmine.
+pub const MAX_SUBJECT_LENGTH: usize = 255;
+
+/// Number of watchers added at once by [IssueAddWatchers](struct.IssueAddWatchers.html).
+const WATCHER_CONCURRENCY: usize = 4;
 
 /// This struct exposes all methods provided by the redmine issues api.
 pub struct Api {
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
 }
 impl Api {
     /// Creates a new instance. Should not be called externally.
-    pub fn new(client: Rc<RedmineClient>) -> Api {
+    pub fn new(client: ClientHandle) -> Api {
         Api { client: client }
     }
 
@@ -33,7 +63,7 @@ impl Api {
     /// let result = redmine.issues().list().status_id(1).execute();
     /// ```
     pub fn list(&self) -> IssueFilter {
-        IssueFilter::new(Rc::clone(&self.client))
+        IssueFilter::new(self.client.clone())
     }
 
     /// Returns a single issue by id.
@@ -56,7 +86,7 @@ impl Api {
     /// ```
     pub fn show(&self, id: u32) -> IssueShow {
         IssueShow {
-            client: Rc::clone(&self.client),
+            client: self.client.clone(),
             show_id: id,
             ..Default::default()
         }
@@ -100,7 +130,7 @@ impl Api {
         subject: &'a str,
     ) -> IssueBuilder<'a> {
         IssueBuilder::for_create(
-            Rc::clone(&self.client),
+            self.client.clone(),
             project_id,
             tracker_id,
             status_id,
@@ -132,7 +162,173 @@ impl Api {
     ///
     /// ```
     pub fn update(&self, id: u32) -> IssueBuilder {
-        IssueBuilder::for_update(Rc::clone(&self.client), id)
+        IssueBuilder::for_update(self.client.clone(), id)
+    }
+
+    /// Returns an IssueBuilder (builder pattern) which ultimately adds a public comment to an
+    /// existing issue. Shortcut for `update(id).notes(text)`. Only the note is sent, so the
+    /// privacy of the issue is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the issue id
+    /// * `text` - a string slice holding the comment
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let comment = redmine.issues().comment(1, "Deployed to staging.");
+    /// assert_eq!(
+    ///     comment.to_json().unwrap().to_string(),
+    ///     r#"{"issue":{"notes":"Deployed to staging."}}"#
+    /// );
+    /// let result = comment.execute();
+    /// ```
+    pub fn comment<'a>(&self, id: u32, text: &'a str) -> IssueBuilder<'a> {
+        IssueBuilder::for_update(self.client.clone(), id).notes(text)
+    }
+
+    /// Returns an IssueBuilder (builder pattern) which ultimately adds a private comment to an
+    /// existing issue. Shortcut for `update(id).notes(text).private_notes(true)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the issue id
+    /// * `text` - a string slice holding the comment
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let comment = redmine.issues().comment_private(1, "Customer is on vacation.");
+    /// assert_eq!(
+    ///     comment.to_json().unwrap().to_string(),
+    ///     r#"{"issue":{"notes":"Customer is on vacation.","private_notes":true}}"#
+    /// );
+    /// let result = comment.execute();
+    /// ```
+    pub fn comment_private<'a>(&self, id: u32, text: &'a str) -> IssueBuilder<'a> {
+        self.comment(id, text).private_notes(true)
+    }
+
+    /// Returns a Conversation (builder pattern) which ultimately adds several comments to an
+    /// existing issue in the given order, e.g. for mirroring a threaded discussion from another
+    /// tool. Redmine accepts one note per update, so an update is sent per comment.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the issue id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.issues().conversation(1)
+    ///     .note("Can you attach the log?")
+    ///     .note("Here it is.")
+    ///     .private_note("Log contains customer data.")
+    ///     .execute();
+    /// ```
+    pub fn conversation(&self, id: u32) -> Conversation {
+        Conversation {
+            client: self.client.clone(),
+            issue_id: id,
+            notes: Vec::new(),
+        }
+    }
+
+    /// Returns an Escalation (builder pattern) which ultimately escalates the issues matching
+    /// `filter` which are overdue or stale according to `policy`. Runs as dry run unless told
+    /// otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - an [IssueFilter](struct.IssueFilter.html) selecting the issues to check
+    /// * `policy` - an [EscalationPolicy](../escalation/struct.EscalationPolicy.html) describing
+    ///   which issues are escalated and how
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::escalation::EscalationPolicy;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let mut filter = redmine.issues().list();
+    /// filter.project_id(1);
+    ///
+    /// let policy = EscalationPolicy::new()
+    ///     .overdue()
+    ///     .priority_id(4)
+    ///     .note("Issue #{id} is overdue since {due_date}.");
+    ///
+    /// let report = redmine.issues().escalate_overdue(filter, policy)
+    ///     .dry_run(false)
+    ///     .max_updates(20)
+    ///     .execute();
+    /// ```
+    pub fn escalate_overdue(&self, filter: IssueFilter, policy: EscalationPolicy) -> Escalation {
+        Escalation::new(self.client.clone(), filter, policy)
+    }
+
+    /// Returns an AutoAssign (builder pattern) which ultimately distributes the unassigned issues
+    /// matching `filter` over a pool of users. Runs as dry run unless told otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - an [IssueFilter](struct.IssueFilter.html) selecting the issues to assign
+    /// * `pool` - a vector of [UserRef](../users/struct.UserRef.html)s the issues are assigned to
+    /// * `strategy` - an [AssignmentStrategy](../assignment/enum.AssignmentStrategy.html)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::assignment::AssignmentStrategy;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let mut filter = redmine.issues().list();
+    /// filter.project_id(1);
+    ///
+    /// let report = redmine.issues()
+    ///     .auto_assign(filter, vec![5.into(), 7.into()], AssignmentStrategy::LeastLoaded)
+    ///     .dry_run(false)
+    ///     .execute();
+    /// ```
+    pub fn auto_assign(
+        &self,
+        filter: IssueFilter,
+        pool: Vec<UserRef>,
+        strategy: AssignmentStrategy,
+    ) -> AutoAssign {
+        AutoAssign::new(self.client.clone(), filter, pool, strategy)
     }
 
     /// Returns IssueDelete struct which offers an `execute` function which deletes the issue
@@ -156,7 +352,7 @@ impl Api {
     /// ```
     pub fn delete(&self, id: u32) -> IssueDelete {
         IssueDelete {
-            client: Rc::clone(&self.client),
+            client: self.client.clone(),
             delete_id: id,
         }
     }
@@ -183,12 +379,125 @@ impl Api {
     /// ```
     pub fn add_watcher(&self, issue_id: u32, watcher_id: u32) -> IssueAddWatcher {
         IssueAddWatcher {
-            client: Rc::clone(&self.client),
+            client: self.client.clone(),
             issue_id: issue_id,
             watcher_id: watcher_id,
         }
     }
 
+    /// Returns IssueAddWatchers struct which offers an `execute` function which adds several
+    /// users as watchers to an issue. Redmine only accepts one watcher per request, so a request
+    /// is sent per user.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_id` - an integer holding the issue id
+    /// * `user_ids` - a vector holding the user ids
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.issues().add_watchers(1, vec![1, 2, 3]).execute();
+    /// ```
+    pub fn add_watchers(&self, issue_id: u32, user_ids: Vec<u32>) -> IssueAddWatchers {
+        IssueAddWatchers {
+            client: self.client.clone(),
+            issue_id: issue_id,
+            user_ids: user_ids,
+            group_id: None,
+        }
+    }
+
+    /// Returns IssueAddWatchers struct which offers an `execute` function which adds every
+    /// member of a group as watcher to an issue. Redmine doesn't accept groups as watchers, so
+    /// the group is resolved to its members first. Requires an api key of an administrator.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_id` - an integer holding the issue id
+    /// * `group_id` - an integer holding the group id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.issues().add_group_watchers(1, 5).execute();
+    /// ```
+    pub fn add_group_watchers(&self, issue_id: u32, group_id: u32) -> IssueAddWatchers {
+        IssueAddWatchers {
+            client: self.client.clone(),
+            issue_id: issue_id,
+            user_ids: Vec::new(),
+            group_id: Some(group_id),
+        }
+    }
+
+    /// Returns Journals struct which lazily pulls the journal entries of an issue on first
+    /// access and caches them. Redmine has no endpoint listing journals in pages, so the first
+    /// access pulls all of them at once; issues fetched without `journals` stay small, though.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the issue id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let journals = redmine.issues().journals(1);
+    /// let latest = journals.len().and_then(|n| journals.get(n.saturating_sub(1)));
+    /// ```
+    pub fn journals(&self, id: u32) -> Journals {
+        Journals {
+            client: self.client.clone(),
+            issue_id: id,
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Returns TriageInbox struct (builder pattern) which ultimately returns the open, unassigned
+    /// issues of a project together with the number of issues their authors reported before and
+    /// duplicate candidates, i.e. issues of the project with similar subjects.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.issues().triage_inbox(1).lookback_days(90).execute();
+    /// ```
+    pub fn triage_inbox(&self, project_id: u32) -> TriageInbox {
+        TriageInbox::new(self.client.clone(), project_id)
+    }
+
     /// Returns IssueRemoveWatcher struct which offers an `execute` function which removes an user
     /// as watcher of an issue.
     ///
@@ -211,46 +520,189 @@ impl Api {
     /// ```
     pub fn remove_watcher(&self, issue_id: u32, watcher_id: u32) -> IssueRemoveWatcher {
         IssueRemoveWatcher {
-            client: Rc::clone(&self.client),
+            client: self.client.clone(),
             issue_id: issue_id,
             watcher_id: watcher_id,
         }
     }
 }
+impl Resource for Api {
+    type Entity = Issue;
+
+    fn name(&self) -> &'static str {
+        "issues"
+    }
+}
+impl Listable for Api {
+    fn list_page(&self, offset: u32, limit: u32) -> Result<Page<Issue>> {
+        let list = self.list().offset(offset).limit(limit).execute()?;
+
+        Ok(Page {
+            total_count: list.total_count(),
+            items: list.into_iter().collect(),
+        })
+    }
+}
+impl Showable for Api {
+    fn fetch(&self, id: u32) -> Result<Issue> {
+        self.show(id).execute()
+    }
+}
+impl Creatable for Api {
+    fn create_from_json(&self, fields: &serde_json::Value) -> Result<u32> {
+        create_from_json(&self.client, "/issues.json", "issue", fields)
+    }
+}
+impl Deletable for Api {
+    fn remove(&self, id: u32) -> Result<()> {
+        self.delete(id).execute()
+    }
+}
 
 /// Holds parameters the issues in redmine application should be filtered by and implements a
 /// builder patern. Is used as return type for issues.list function.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct IssueFilter {
-    client: Rc<RedmineClient>,
-    assigned_to_id: Option<u32>,
+    client: ClientHandle,
+    assigned_to_id: Option<String>,
+    author_id: Option<String>,
+    member_of_group: Option<u32>,
     issue_id: Vec<u32>,
+    issue_id_from: Option<u32>,
+    is_private: Option<bool>,
     parent_id: Option<u32>,
     project_id: Option<u32>,
-    status_id: Option<u32>,
+    status_id: Option<String>,
     subproject_id: Option<u32>,
     tracker_id: Option<u32>,
+    fixed_version_id: Option<u32>,
+    updated_on: Option<String>,
+    custom_fields: Vec<(String, String)>,
+    queries: Vec<Query>,
+    query_id: Option<u32>,
+    include: Vec<String>,
+    sort: Option<String>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+    prefetch: bool,
 }
 impl IssueFilter {
     /// Creates a new instance.
     ///
     /// # Arguments
     ///
-    /// * `client` - a Rc boxed RedmineClient
-    fn new(client: Rc<RedmineClient>) -> IssueFilter {
+    /// * `client` - a handle to the RedmineClient
+    fn new(client: ClientHandle) -> IssueFilter {
         IssueFilter {
             client: client,
             ..Default::default()
         }
     }
 
-    /// Sets filter to get only issues which are assigned to a specific user.
+    /// Creates a new instance which isn't bound to a redmine application, e.g. for building
+    /// filters in code without access to one. Has to be bound with [bind](#method.bind) before
+    /// execution.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::issues::IssueFilter;
+    ///
+    /// let mut filter = IssueFilter::detached();
+    /// filter.project_id(1).unassigned();
+    /// let pairs = filter.query_pairs().unwrap();
+    /// assert!(pairs.contains(&("assigned_to_id".to_string(), "!*".to_string())));
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    /// let result = filter.bind(&redmine).execute();
+    /// ```
+    pub fn detached() -> IssueFilter {
+        IssueFilter::new(ClientHandle::default())
+    }
+
+    /// Binds the filter to a redmine application, which the request is sent to on execution.
     ///
     /// # Arguments
     ///
-    /// * `id` - an integer holding a user id
+    /// * `api` - the api pointing to the redmine application
+    pub fn bind(&mut self, api: &RedmineApi) -> &mut IssueFilter {
+        self.client = api.client.clone();
+        self
+    }
+
+    /// Returns the query parameters which are sent to redmine application on execution, sorted
+    /// by name, e.g. for checking filters in tests. Fails like execution if the conditions of
+    /// [queries](#method.query) contradict each other or other filter parameters.
+    pub fn query_pairs(&self) -> Result<Vec<(String, String)>> {
+        let mut pairs: Vec<(String, String)> = self.params()?
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect();
+        pairs.sort();
+        Ok(pairs)
+    }
+
+    /// Sets filter to get only issues which are assigned to a specific user or group. Issues
+    /// assigned to the members of a group aren't included, see
+    /// [member_of_group](#method.member_of_group).
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding a user or group id
     pub fn assigned_to_id(&mut self, id: u32) -> &mut IssueFilter {
-        self.assigned_to_id = Some(id);
+        self.assigned_to_id = Some(id.to_string());
+        self
+    }
+
+    /// Sets filter to get only issues which are assigned to the user the api key belongs to.
+    pub fn assigned_to_me(&mut self) -> &mut IssueFilter {
+        self.assigned_to_id = Some("me".to_string());
+        self
+    }
+
+    /// Sets filter to get only issues which aren't assigned to anyone (`assigned_to_id=!*`),
+    /// e.g. for triage.
+    pub fn unassigned(&mut self) -> &mut IssueFilter {
+        self.assigned_to_id = Some("!*".to_string());
+        self
+    }
+
+    /// Sets filter to get only issues which are assigned to any user or group
+    /// (`assigned_to_id=*`).
+    pub fn assigned(&mut self) -> &mut IssueFilter {
+        self.assigned_to_id = Some("*".to_string());
+        self
+    }
+
+    /// Sets filter to get only issues which are assigned to a member of a group, e.g. the queue
+    /// of a team.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding a group id
+    pub fn member_of_group(&mut self, id: u32) -> &mut IssueFilter {
+        self.member_of_group = Some(id);
+        self
+    }
+
+    /// Sets filter to get only issues which have been created by a specific user.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding a user id
+    pub fn author_id(&mut self, id: u32) -> &mut IssueFilter {
+        self.author_id = Some(id.to_string());
+        self
+    }
+
+    /// Sets filter to get only issues which have been created by the user the api key belongs
+    /// to.
+    pub fn author_me(&mut self) -> &mut IssueFilter {
+        self.author_id = Some("me".to_string());
         self
     }
 
@@ -277,6 +729,28 @@ impl IssueFilter {
         self
     }
 
+    /// Sets filter to get only issues whose id is at least the given one (`issue_id=>=id`). Is
+    /// ignored if single ids are set by [issue_id](#method.issue_id) or
+    /// [issue_ids](#method.issue_ids).
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the lowest issue id
+    pub fn issue_id_from(&mut self, id: u32) -> &mut IssueFilter {
+        self.issue_id_from = Some(id);
+        self
+    }
+
+    /// Sets filter to get only private or only public issues.
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - a boolean: true means only private issues, false means only public issues
+    pub fn is_private(&mut self, b: bool) -> &mut IssueFilter {
+        self.is_private = Some(b);
+        self
+    }
+
     /// Sets filter to get only issues which belong to a parent issue specified by `id`.
     ///
     /// # Arguments
@@ -303,15 +777,28 @@ impl IssueFilter {
     ///
     /// * `id` - an integer holding the id of the status
     pub fn status_id(&mut self, id: u32) -> &mut IssueFilter {
-        self.status_id = Some(id);
+        self.status_id = Some(id.to_string());
         self
     }
 
-    /// Sets filter to get only issues of a specific subproject.
-    ///
-    /// # Arguments
-    ///
-    /// * `id` - an integer holding the id of the status
+    /// Sets filter to get open as well as closed issues. By default redmine only returns open
+    /// issues.
+    pub fn any_status(&mut self) -> &mut IssueFilter {
+        self.status_id = Some("*".to_string());
+        self
+    }
+
+    /// Sets filter to get only closed issues.
+    pub fn closed(&mut self) -> &mut IssueFilter {
+        self.status_id = Some("closed".to_string());
+        self
+    }
+
+    /// Sets filter to get only issues of a specific subproject.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the id of the status
     pub fn subproject_id(&mut self, id: u32) -> &mut IssueFilter {
         self.subproject_id = Some(id);
         self
@@ -327,13 +814,347 @@ impl IssueFilter {
         self
     }
 
+    /// Sets filter to get only issues with a specific target version.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the id of the version
+    pub fn fixed_version_id(&mut self, id: u32) -> &mut IssueFilter {
+        self.fixed_version_id = Some(id);
+        self
+    }
+
+    /// Sets filter to get only issues which have been updated on or after a specific date.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - a string slice holding the date in `YYYY-MM-DD` format
+    pub fn updated_since(&mut self, date: &str) -> &mut IssueFilter {
+        self.updated_on = Some(format!(">={}", date));
+        self
+    }
+
+    /// Sets filter to get only issues with a specific custom field value. Setting several values
+    /// of the same custom field gets the issues having any of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the custom field id
+    /// * `value` - a string slice holding the value
+    pub fn custom_field(&mut self, id: u32, value: &str) -> &mut IssueFilter {
+        self.custom_fields.push((format!("cf_{}", id), value.to_string()));
+        self
+    }
+
+    /// Sets filter to get only issues with a specific tag. Setting several tags gets the issues
+    /// having any of them (`cf_5=infra|urgent`).
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - a [TagField](../tags/struct.TagField.html) referring to the tags custom field
+    /// * `tag` - a string slice holding the tag
+    pub fn tag(&mut self, field: &TagField, tag: &str) -> &mut IssueFilter {
+        self.custom_field(field.id(), tag)
+    }
+
+    /// Sets filter to get only issues matching a [Query](../query/struct.Query.html) as built by
+    /// the [query!](../macro.query.html) macro. Conditions of several queries are combined like
+    /// the ones of a single query. Execution fails if a condition can't be combined with another
+    /// one or with a filter parameter set for the same field, e.g. by
+    /// [project_id](#method.project_id), instead of silently dropping one of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - a query holding the conditions
+    pub fn query(&mut self, q: &Query) -> &mut IssueFilter {
+        self.queries.push(q.clone());
+        self
+    }
+
+    /// Sets filter to get only issues matching a query saved in redmine application. Queries
+    /// saved for a project are only applied if the project is set by
+    /// [project_id](#method.project_id) as well; [saved_query](#method.saved_query) takes care
+    /// of that.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the id of the saved query
+    pub fn query_id(&mut self, id: u32) -> &mut IssueFilter {
+        self.query_id = Some(id);
+        self
+    }
+
+    /// Sets filter to get only issues matching a query saved in redmine application, together
+    /// with the project the query has been saved for, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - a [SavedQuery](../saved_queries/struct.SavedQuery.html) as returned by the saved
+    ///   queries api
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(queries) = redmine.saved_queries().list().execute() {
+    ///     if let Some(q) = queries.into_iter().find(|q| q.name == "Open bugs") {
+    ///         let result = redmine.issues().list().saved_query(&q).execute();
+    ///     }
+    /// }
+    /// ```
+    pub fn saved_query(&mut self, q: &SavedQuery) -> &mut IssueFilter {
+        if let Some(id) = q.project_id {
+            self.project_id = Some(id);
+        }
+        self.query_id(q.id)
+    }
+
+    /// Requests additional associated data to be included in every issue of the list, e.g.
+    /// `attachments` or `relations`.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the name of the associated data
+    pub fn include(&mut self, s: &str) -> &mut IssueFilter {
+        self.include.push(s.to_string());
+        self
+    }
+
+    /// Sets the order of the issues, e.g. `id` for ascending ids or `updated_on:desc` for the
+    /// most recently updated issues first. Redmine orders by descending ids by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the column and optionally `:desc`
+    pub fn sort(&mut self, s: &str) -> &mut IssueFilter {
+        self.sort = Some(s.to_string());
+        self
+    }
+
+    /// Sets the number of issues which should be skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the offset
+    pub fn offset(&mut self, n: u32) -> &mut IssueFilter {
+        self.offset = Some(n);
+        self
+    }
+
+    /// Sets the maximum number of issues which should be returned (redmine caps this at 100).
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the limit
+    pub fn limit(&mut self, n: u32) -> &mut IssueFilter {
+        self.limit = Some(n);
+        self
+    }
+
+    /// Requests the users, versions and categories referenced by the resulting issues to be
+    /// fetched in bulk, each distinct user only once. They are available via
+    /// [IssueList::resolver](struct.IssueList.html#method.resolver) afterwards.
+    pub fn prefetch(&mut self) -> &mut IssueFilter {
+        self.prefetch = true;
+        self
+    }
+
     /// Performs request to redmine application and returns a list of issues matching the filter
     /// parameters.
     pub fn execute(&self) -> Result<IssueList> {
+        let result = self.client.get("/issues.json", &self.params()?)?;
+        let list: IssueList = serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+
+        self.resolve(list)
+    }
+
+    /// Performs request to redmine application like [execute](#method.execute), but
+    /// deserializes the issues one by one. Issues which can't be deserialized are left out of the
+    /// list and reported as errors instead of failing the whole request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(lenient) = redmine.issues().list().execute_lenient() {
+    ///     for e in &lenient.errors {
+    ///         println!("Skipped {}", e);
+    ///     }
+    /// }
+    /// ```
+    pub fn execute_lenient(&self) -> Result<Lenient<IssueList>> {
+        let result = self.client.get("/issues.json", &self.params()?)?;
+        let offset = self.offset.unwrap_or(0) as usize;
+        let (issues, total_count) = lenient_list(&result, "issues", offset)?;
+
+        Ok(Lenient {
+            value: self.resolve(IssueList {
+                issues: issues.value,
+                total_count: total_count,
+                resolver: None,
+            })?,
+            errors: issues.errors,
+        })
+    }
+
+    /// Performs as many requests to redmine application as needed to walk through all pages of
+    /// issues matching the filter parameters and returns all of them. Offset and limit set on the
+    /// filter are ignored.
+    pub fn execute_all(&self) -> Result<IssueList> {
+        let (issues, _) = self.pages(&Budget::unlimited(), 0, None)?;
+
+        self.resolve(IssueList {
+            total_count: Some(issues.len() as u32),
+            issues: issues,
+            resolver: None,
+        })
+    }
+
+    /// Walks through all pages of issues like [execute_all](#method.execute_all), but
+    /// deserializes the issues one by one, so a single malformed issue doesn't break an export.
+    /// Issues which can't be deserialized are reported as errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(lenient) = redmine.issues().list().execute_all_lenient() {
+    ///     println!("Exported {} issues", lenient.value.issues().len());
+    ///     for e in &lenient.errors {
+    ///         println!("Skipped {}", e);
+    ///     }
+    /// }
+    /// ```
+    pub fn execute_all_lenient(&self) -> Result<Lenient<IssueList>> {
+        let mut errors = Vec::new();
+        let (issues, _) = self.pages(&Budget::unlimited(), 0, Some(&mut errors))?;
+
+        Ok(Lenient {
+            value: self.resolve(IssueList {
+                total_count: Some(issues.len() as u32),
+                issues: issues,
+                resolver: None,
+            })?,
+            errors: errors,
+        })
+    }
+
+    /// Walks through the pages of issues matching the filter parameters like
+    /// [execute_all](#method.execute_all), but doesn't request another page once `budget` is
+    /// exhausted. At least one page is requested, so every call makes progress. If issues are
+    /// left, the result holds a continuation which resumes at the next page when passed to a later
+    /// call of this method with the same filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - the time the pages may take to be requested
+    /// * `from` - a continuation returned by a previous call or `None` to start at the first page
+    pub fn execute_within(
+        &self,
+        budget: &Budget,
+        from: Option<&Continuation>,
+    ) -> Result<Partial<IssueList>> {
+        let offset = from.map(|c| c.position()).unwrap_or(0);
+        let (issues, next) = self.pages(budget, offset, None)?;
+
+        Ok(Partial {
+            result: self.resolve(IssueList {
+                total_count: Some(issues.len() as u32),
+                issues: issues,
+                resolver: None,
+            })?,
+            continuation: next.map(Continuation::new),
+        })
+    }
+
+    /// Requests pages of issues starting at `offset` until all issues have been returned or the
+    /// budget is exhausted. Returns the issues and the offset of the next page if issues are left.
+    /// If `errors` is given, issues are deserialized one by one and malformed ones are added to
+    /// it instead of failing.
+    fn pages(
+        &self,
+        budget: &Budget,
+        offset: u32,
+        mut errors: Option<&mut Vec<ItemError>>,
+    ) -> Result<(Vec<Issue>, Option<u32>)> {
+        let mut params = self.params()?;
+        params.insert("limit", "100".to_string());
+
+        let mut issues: Vec<Issue> = Vec::new();
+        let mut next = offset as usize;
+        loop {
+            params.insert("offset", next.to_string());
+            let result = self.client.get("/issues.json", &params)?;
+            // count malformed issues as well, they take up their place in the pages
+            let (page, count, total_count) = match errors {
+                Some(ref mut errors) => {
+                    let (page, total_count) = lenient_list(&result, "issues", next)?;
+                    let count = page.value.len() + page.errors.len();
+                    errors.extend(page.errors);
+                    (page.value, count, total_count)
+                }
+                None => {
+                    let list: IssueList =
+                        serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+                    let count = list.issues.len();
+                    (list.issues, count, list.total_count)
+                }
+            };
+
+            let total_count = total_count.unwrap_or(0) as usize;
+            next += count;
+            issues.extend(page);
+
+            if count == 0 || next >= total_count {
+                return Ok((issues, None));
+            }
+            if budget.is_exhausted() {
+                return Ok((issues, Some(next as u32)));
+            }
+        }
+    }
+
+    /// Prefetches the references of the issues if requested.
+    fn resolve(&self, mut list: IssueList) -> Result<IssueList> {
+        if self.prefetch {
+            list.resolver = Some(Resolver::prefetch(self.client.clone(), &list.issues)?);
+        }
+
+        Ok(list)
+    }
+
+    /// Transcodes the filter parameters to query parameters.
+    fn params(&self) -> Result<HashMap<&str, String>> {
         let mut params: HashMap<&str, String> = HashMap::new();
 
-        if let Some(id) = self.assigned_to_id {
-            params.insert("assigned_to_id", id.to_string());
+        if let Some(ref id) = self.assigned_to_id {
+            params.insert("assigned_to_id", id.clone());
+        }
+
+        if let Some(ref id) = self.author_id {
+            params.insert("author_id", id.clone());
+        }
+
+        if let Some(id) = self.member_of_group {
+            params.insert("member_of_group", id.to_string());
         }
 
         if self.issue_id.len() > 0 {
@@ -344,6 +1165,12 @@ impl IssueFilter {
                 .collect::<Vec<String>>()
                 .join(",");
             params.insert("issue_id", issue_id);
+        } else if let Some(id) = self.issue_id_from {
+            params.insert("issue_id", format!(">={}", id));
+        }
+
+        if let Some(b) = self.is_private {
+            params.insert("is_private", if b { "1" } else { "0" }.to_string());
         }
 
         if let Some(id) = self.parent_id {
@@ -354,8 +1181,8 @@ impl IssueFilter {
             params.insert("project_id", id.to_string());
         }
 
-        if let Some(id) = self.status_id {
-            params.insert("status_id", id.to_string());
+        if let Some(ref id) = self.status_id {
+            params.insert("status_id", id.clone());
         }
 
         if let Some(id) = self.subproject_id {
@@ -366,9 +1193,62 @@ impl IssueFilter {
             params.insert("tracker_id", id.to_string());
         }
 
-        let result = self.client.get("/issues.json", &params)?;
+        if let Some(id) = self.fixed_version_id {
+            params.insert("fixed_version_id", id.to_string());
+        }
+
+        if let Some(ref s) = self.updated_on {
+            params.insert("updated_on", s.clone());
+        }
+
+        // redmine takes several values of a field separated by `|` and matches any of them
+        for (key, value) in &self.custom_fields {
+            let value = match params.get(key.as_str()) {
+                Some(existing) => format!("{}|{}", existing, value),
+                None => value.clone(),
+            };
+            params.insert(key, value);
+        }
+
+        let mut query = Query::new();
+        for condition in self.queries.iter().flat_map(|q| q.conditions()) {
+            query.push(condition.clone())?;
+        }
+        for condition in query.conditions() {
+            if let Some(value) = params.get(condition.key()) {
+                if value != condition.value() {
+                    bail!(
+                        "Condition {} conflicts with filter parameter `{}={}`",
+                        condition,
+                        condition.key(),
+                        value
+                    );
+                }
+            }
+            params.insert(condition.key(), condition.value().to_string());
+        }
+
+        if let Some(id) = self.query_id {
+            params.insert("query_id", id.to_string());
+        }
 
-        serde_json::from_str(&result).chain_err(|| "Can't parse json")
+        if !self.include.is_empty() {
+            params.insert("include", self.include.join(","));
+        }
+
+        if let Some(ref s) = self.sort {
+            params.insert("sort", s.clone());
+        }
+
+        if let Some(n) = self.offset {
+            params.insert("offset", n.to_string());
+        }
+
+        if let Some(n) = self.limit {
+            params.insert("limit", n.to_string());
+        }
+
+        Ok(params)
     }
 }
 
@@ -377,6 +1257,49 @@ impl IssueFilter {
 #[derive(Deserialize, Debug)]
 pub struct IssueList {
     issues: Vec<Issue>,
+    total_count: Option<u32>,
+    #[serde(skip_deserializing)]
+    resolver: Option<Resolver>,
+}
+impl IssueList {
+    /// Returns the issues of the list.
+    pub fn issues(&self) -> &[Issue] {
+        &self.issues
+    }
+
+    /// Returns the prefetched users, versions and categories if the list has been requested with
+    /// [IssueFilter::prefetch](struct.IssueFilter.html#method.prefetch).
+    pub fn resolver(&self) -> Option<&Resolver> {
+        self.resolver.as_ref()
+    }
+
+    /// Returns the total number of issues matching the filter, which may be more than the list
+    /// holds.
+    pub fn total_count(&self) -> Option<u32> {
+        self.total_count
+    }
+
+    /// Formats the issues as plain text table, e.g. for command line tools.
+    pub fn table(&self) -> String {
+        let rows: Vec<Vec<String>> = self.issues
+            .iter()
+            .map(|i| {
+                vec![
+                    i.id.to_string(),
+                    i.tracker.name.clone(),
+                    i.status.name.clone(),
+                    i.priority.name.clone(),
+                    i.subject.clone(),
+                    i.assigned_to.as_ref().map_or(String::new(), |a| a.name.clone()),
+                ]
+            })
+            .collect();
+
+        format_table(
+            &["ID", "Tracker", "Status", "Priority", "Subject", "Assignee"],
+            &rows,
+        )
+    }
 }
 impl IntoIterator for IssueList {
     type Item = Issue;
@@ -391,19 +1314,32 @@ impl IntoIterator for IssueList {
 #[derive(Deserialize, Debug, Default)]
 pub struct IssueShow {
     #[serde(skip_deserializing)]
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     #[serde(skip_deserializing)]
     show_id: u32,
+    #[serde(skip_deserializing)]
+    include: Vec<String>,
 
     // fields used for deserialization
     issue: Issue,
 }
 impl IssueShow {
+    /// Requests additional associated data to be included in the issue, e.g. `journals`,
+    /// `attachments`, `children`, `relations`, `changesets` or `watchers`.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the name of the associated data
+    pub fn include(mut self, s: &str) -> Self {
+        self.include.push(s.to_string());
+        self
+    }
+
     /// Performs request to redmine application and returns a single issue.
     pub fn execute(&self) -> Result<Issue> {
         let result = self.client.get(
             &(format!("/issues/{}.json", self.show_id)),
-            &HashMap::new(),
+            &self.params(),
         )?;
 
         Ok(
@@ -412,16 +1348,152 @@ impl IssueShow {
                 .into(),
         )
     }
+
+    /// Performs request to redmine application like [execute](#method.execute), but reports
+    /// requested includes missing in the response as warnings, e.g. `watchers` if the user isn't
+    /// allowed to view them. `children` are never reported, since redmine omits them for issues
+    /// without children.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(checked) = redmine.issues().show(1).include("watchers").execute_checked() {
+    ///     for warning in &checked.warnings {
+    ///         println!("Warning: {}", warning);
+    ///     }
+    /// }
+    /// ```
+    pub fn execute_checked(&self) -> Result<Checked<Issue>> {
+        let result = self.client.get(
+            &(format!("/issues/{}.json", self.show_id)),
+            &self.params(),
+        )?;
+
+        let value: serde_json::Value =
+            serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+        let include: Vec<String> = self.include
+            .iter()
+            .filter(|name| *name != "children")
+            .cloned()
+            .collect();
+        let warnings = missing_includes(&value["issue"], &include);
+        let show: IssueShow = serde_json::from_value(value).chain_err(|| "Can't parse json")?;
+
+        Ok(Checked {
+            value: show.into(),
+            warnings: warnings,
+        })
+    }
+
+    /// Performs request to redmine application and returns a single issue as
+    /// [IssueSnapshot](struct.IssueSnapshot.html), i.e. the typed issue together with the raw
+    /// json it was parsed from.
+    pub fn snapshot(&self) -> Result<IssueSnapshot> {
+        let result = self.client.get(
+            &(format!("/issues/{}.json", self.show_id)),
+            &self.params(),
+        )?;
+
+        let mut value: serde_json::Value =
+            serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+        match value.get_mut("issue") {
+            Some(raw) => IssueSnapshot::from_value(raw.take()),
+            None => bail!("Can't find issue in response"),
+        }
+    }
+
+    /// Transcodes the requested associated data to query parameters.
+    fn params(&self) -> HashMap<&str, String> {
+        let mut params: HashMap<&str, String> = HashMap::new();
+
+        if !self.include.is_empty() {
+            params.insert("include", self.include.join(","));
+        }
+
+        params
+    }
+}
+
+/// Holds an issue as pulled from redmine application together with the raw json it was parsed
+/// from. The raw json also contains values the [Issue](struct.Issue.html) struct doesn't model
+/// (e.g. fields added by plugins), so it can be used for lossless backups. Two snapshots are
+/// equal if their raw json is equal.
+#[derive(Debug)]
+pub struct IssueSnapshot {
+    raw: serde_json::Value,
+    issue: Issue,
+}
+impl IssueSnapshot {
+    /// Creates new instance from the raw json object of an issue, e.g. as loaded from a backup.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - a json value holding a single issue object
+    pub fn from_value(raw: serde_json::Value) -> Result<Self> {
+        let issue = serde_json::from_value(raw.clone()).chain_err(|| "Can't parse json")?;
+
+        Ok(IssueSnapshot {
+            raw: raw,
+            issue: issue,
+        })
+    }
+
+    /// Returns the raw json object of the issue.
+    pub fn raw(&self) -> &serde_json::Value {
+        &self.raw
+    }
+
+    /// Returns the typed issue.
+    pub fn issue(&self) -> &Issue {
+        &self.issue
+    }
+
+    /// Returns the names of all top level fields whose raw values differ between this and another
+    /// snapshot, which is helpful for detecting drift against a backup.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - another snapshot, e.g. of the same issue at a later point in time
+    pub fn changed_fields(&self, other: &IssueSnapshot) -> Vec<String> {
+        let empty = serde_json::Map::new();
+        let a = self.raw.as_object().unwrap_or(&empty);
+        let b = other.raw.as_object().unwrap_or(&empty);
+
+        let mut fields = a.keys()
+            .chain(b.keys().filter(|k| !a.contains_key(*k)))
+            .filter(|k| a.get(*k) != b.get(*k))
+            .cloned()
+            .collect::<Vec<String>>();
+        fields.sort();
+        fields
+    }
+}
+impl PartialEq for IssueSnapshot {
+    fn eq(&self, other: &IssueSnapshot) -> bool {
+        self.raw == other.raw
+    }
+}
+impl From<IssueSnapshot> for Issue {
+    fn from(item: IssueSnapshot) -> Self {
+        item.issue
+    }
 }
 
 /// Helper struct to provide a unified interface for all issue api methods.
 pub struct IssueDelete {
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     delete_id: u32,
 }
 impl IssueDelete {
     /// Performs request to redmine application and deletes an issue.
-    pub fn execute(&self) -> Result<bool> {
+    pub fn execute(&self) -> Result<()> {
         self.client.delete(
             &(format!("/issues/{}.json", self.delete_id)),
         )
@@ -430,13 +1502,13 @@ impl IssueDelete {
 
 /// Helper struct to provide a unified interface for all issue api methods.
 pub struct IssueAddWatcher {
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     issue_id: u32,
     watcher_id: u32,
 }
 impl IssueAddWatcher {
     /// Performs request to redmine application and adds a user as watcher to an issue.
-    pub fn execute(&self) -> Result<bool> {
+    pub fn execute(&self) -> Result<()> {
         #[derive(Serialize)]
         struct Wrapper {
             user_id: u32,
@@ -450,23 +1522,175 @@ impl IssueAddWatcher {
             &Wrapper { user_id: self.watcher_id },
         )?;
 
-        if !response.status().is_success() {
-            bail!("Error: {}", response.status());
+        if !response.is_success() {
+            bail!("Error: {}", response.status);
         }
 
-        Ok(true)
+        Ok(())
+    }
+}
+
+/// Helper struct to provide a unified interface for all issue api methods.
+pub struct IssueAddWatchers {
+    client: ClientHandle,
+    issue_id: u32,
+    user_ids: Vec<u32>,
+    group_id: Option<u32>,
+}
+impl IssueAddWatchers {
+    /// Performs requests to redmine application and adds the users as watchers to an issue. Up to
+    /// four requests are sent at once. A failing user doesn't stop the others; failures are
+    /// collected in the report, which lists the users in the given order.
+    pub fn execute(&self) -> Result<WatcherReport> {
+        let mut user_ids = self.user_ids.clone();
+        if let Some(group_id) = self.group_id {
+            let members = GroupApi::new(self.client.clone()).members(group_id)?;
+            user_ids.extend(members.iter().map(|m| m.id));
+        }
+
+        // every worker takes the next user until none is left
+        let next = AtomicUsize::new(0);
+        let add = || {
+            let mut results = Vec::new();
+            while let Some(&user_id) = user_ids.get(next.fetch_add(1, Ordering::SeqCst)) {
+                let watcher = IssueAddWatcher {
+                    client: self.client.clone(),
+                    issue_id: self.issue_id,
+                    watcher_id: user_id,
+                };
+                results.push((user_id, watcher.execute().map_err(|e| e.to_string())));
+            }
+            results
+        };
+        let mut results = Vec::new();
+        let mut panicked = false;
+        thread::scope(|s| {
+            let workers: Vec<_> = (0..WATCHER_CONCURRENCY.min(user_ids.len()))
+                .map(|_| s.spawn(add))
+                .collect();
+            for worker in workers {
+                match worker.join() {
+                    Ok(r) => results.extend(r),
+                    Err(_) => panicked = true,
+                }
+            }
+        });
+        if panicked {
+            bail!("A watcher worker panicked");
+        }
+
+        let mut report = WatcherReport::default();
+        for &user_id in &user_ids {
+            if let Some(i) = results.iter().position(|&(id, _)| id == user_id) {
+                let (_, result) = results.swap_remove(i);
+                report.push(user_id, result);
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Struct to provide builder pattern for adding several comments to an issue. Is used as return
+/// type for [issues.conversation](struct.Api.html#method.conversation).
+pub struct Conversation {
+    client: ClientHandle,
+    issue_id: u32,
+    notes: Vec<(String, bool)>,
+}
+impl Conversation {
+    /// Adds a public comment.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - a string slice holding the comment
+    pub fn note(mut self, text: &str) -> Self {
+        self.notes.push((text.to_string(), false));
+        self
+    }
+
+    /// Adds a private comment.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - a string slice holding the comment
+    pub fn private_note(mut self, text: &str) -> Self {
+        self.notes.push((text.to_string(), true));
+        self
+    }
+
+    /// Adds several comments with the same privacy.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - a vector holding the comments
+    /// * `private` - a boolean: true means the comments are private
+    pub fn notes_many(mut self, texts: Vec<&str>, private: bool) -> Self {
+        self.notes.extend(texts.into_iter().map(|t| (t.to_string(), private)));
+        self
+    }
+
+    /// Performs requests to redmine application and adds the comments one after another. Stops
+    /// at the first failing comment, so the order is kept when the remaining comments are posted
+    /// again; the error tells how many comments have been added. Returns the issue as of the
+    /// last comment.
+    pub fn execute(&self) -> Result<Issue> {
+        if self.notes.is_empty() {
+            bail!(ErrorKind::NothingToUpdate(self.issue_id));
+        }
+
+        let mut issue = Issue::default();
+        for (i, &(ref text, private)) in self.notes.iter().enumerate() {
+            issue = IssueBuilder::for_update(self.client.clone(), self.issue_id)
+                .notes(text)
+                .private_notes(private)
+                .execute()
+                .chain_err(|| {
+                    format!(
+                        "Added {} of {} comments to issue {}",
+                        i,
+                        self.notes.len(),
+                        self.issue_id
+                    )
+                })?;
+        }
+
+        Ok(issue)
+    }
+}
+
+/// Holds the result of adding several watchers to an issue.
+#[derive(Debug, Default, Clone)]
+pub struct WatcherReport {
+    /// Ids of the users which have been added as watchers.
+    pub added: Vec<u32>,
+    /// Ids of the users which couldn't be added together with the error message.
+    pub failed: Vec<(u32, String)>,
+}
+impl WatcherReport {
+    /// Adds the result of a single watcher addition to the report.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - an integer holding the user id
+    /// * `result` - the result of the addition
+    pub fn push(&mut self, user_id: u32, result: ::std::result::Result<(), String>) {
+        match result {
+            Ok(_) => self.added.push(user_id),
+            Err(e) => self.failed.push((user_id, e)),
+        }
     }
 }
 
 /// Helper struct to provide a unified interface for all issue api methods.
 pub struct IssueRemoveWatcher {
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     issue_id: u32,
     watcher_id: u32,
 }
 impl IssueRemoveWatcher {
     /// Performs request to redmine application and removes a user as watcher from an issue.
-    pub fn execute(&self) -> Result<bool> {
+    pub fn execute(&self) -> Result<()> {
         self.client.delete(
             &(format!(
                 "/issues/{}/watchers/{}.json",
@@ -478,18 +1702,27 @@ impl IssueRemoveWatcher {
 }
 
 /// Represents an issue as pulled from redmine application.
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Issue {
     pub assigned_to: Option<NamedObject>,
     pub author: NamedObject,
     pub category: Option<NamedObject>,
+    pub closed_on: Option<String>,
     pub created_on: String,
     pub description: Option<String>,
     pub done_ratio: u32,
     pub due_date: Option<String>,
     pub estimated_hours: Option<f32>,
+    /// Estimated hours of the issue and its subtasks (redmine 3.3 or newer).
+    pub total_estimated_hours: Option<f32>,
+    /// Hours logged on the issue. Is only pulled when showing a single issue.
+    pub spent_hours: Option<f32>,
+    /// Hours logged on the issue and its subtasks. Is only pulled when showing a single issue
+    /// (redmine 3.3 or newer).
+    pub total_spent_hours: Option<f32>,
     pub fixed_version: Option<NamedObject>,
     pub id: u32,
+    pub is_private: Option<bool>,
     pub parent: Option<Object>,
     pub priority: NamedObject,
     pub project: NamedObject,
@@ -498,12 +1731,534 @@ pub struct Issue {
     pub subject: String,
     pub tracker: NamedObject,
     pub updated_on: String,
+    #[serde(default)]
+    pub custom_fields: Vec<CustomField>,
+    #[serde(default)]
+    pub journals: Vec<Journal>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    #[serde(default)]
+    pub relations: Vec<Relation>,
+}
+impl Issue {
+    /// Returns the url of the page showing this issue in the redmine application, e.g.
+    /// `http://www.redmine.org/issues/1`, for linking users to it. Redmine applications installed
+    /// in a sub path are supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - the api pointing to the redmine application
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(issue) = redmine.issues().show(1).execute() {
+    ///     println!("See {}", issue.web_url(&redmine));
+    /// }
+    /// ```
+    pub fn web_url(&self, api: &RedmineApi) -> String {
+        api.client.web_url(&format!("/issues/{}", self.id))
+    }
+
+    /// Returns the estimated hours minus the spent hours, which is negative if the estimate has
+    /// been exceeded. The totals including subtasks are used if redmine reports them, so the
+    /// remaining hours of a parent issue cover its children. Returns None if the issue has no
+    /// estimate; spent hours are only known for issues pulled by `show`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(issue) = redmine.issues().show(1).execute() {
+    ///     println!("{:?} hours left", issue.remaining_hours());
+    /// }
+    /// ```
+    pub fn remaining_hours(&self) -> Option<f32> {
+        let estimated = self.total_estimated_hours.or(self.estimated_hours)?;
+        let spent = self.total_spent_hours.or(self.spent_hours).unwrap_or(0.0);
+
+        Some(estimated - spent)
+    }
+
+    /// Returns the estimated hours minus the spent hours of the issue alone, without subtasks.
+    /// Returns None if the issue has no estimate.
+    pub fn own_remaining_hours(&self) -> Option<f32> {
+        let estimated = self.estimated_hours?;
+
+        Some(estimated - self.spent_hours.unwrap_or(0.0))
+    }
+
+    /// Returns the changes of attributes and custom fields between this and another state of the
+    /// issue, e.g. for notifications about what changed between two polls. Names and values are
+    /// the ones redmine displays, e.g. the name of the status instead of its id.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the later state of the issue
+    pub fn diff(&self, other: &Issue) -> Vec<FieldChange> {
+        fn named(o: &Option<NamedObject>) -> Option<String> {
+            o.as_ref().map(|o| o.name.clone())
+        }
+        fn text<T: ToString>(v: &Option<T>) -> Option<String> {
+            v.as_ref().map(|v| v.to_string()).and_then(
+                |v| if v.is_empty() { None } else { Some(v) },
+            )
+        }
+
+        let fields = vec![
+            ("Project", Some(self.project.name.clone()), Some(other.project.name.clone())),
+            ("Tracker", Some(self.tracker.name.clone()), Some(other.tracker.name.clone())),
+            ("Subject", Some(self.subject.clone()), Some(other.subject.clone())),
+            ("Description", text(&self.description), text(&other.description)),
+            ("Status", Some(self.status.name.clone()), Some(other.status.name.clone())),
+            ("Priority", Some(self.priority.name.clone()), Some(other.priority.name.clone())),
+            ("Assignee", named(&self.assigned_to), named(&other.assigned_to)),
+            ("Category", named(&self.category), named(&other.category)),
+            ("Target version", named(&self.fixed_version), named(&other.fixed_version)),
+            (
+                "Parent task",
+                self.parent.as_ref().map(|p| format!("#{}", p.id)),
+                other.parent.as_ref().map(|p| format!("#{}", p.id)),
+            ),
+            ("Start date", text(&self.start_date), text(&other.start_date)),
+            ("Due date", text(&self.due_date), text(&other.due_date)),
+            ("% Done", Some(self.done_ratio.to_string()), Some(other.done_ratio.to_string())),
+            ("Estimated time", text(&self.estimated_hours), text(&other.estimated_hours)),
+            ("Private", text(&self.is_private), text(&other.is_private)),
+        ];
+
+        let mut changes: Vec<FieldChange> = fields
+            .into_iter()
+            .filter(|(_, old, new)| old != new)
+            .map(|(field, old, new)| {
+                FieldChange {
+                    field: field.to_string(),
+                    old: old,
+                    new: new,
+                }
+            })
+            .collect();
+
+        // custom fields are matched by id, but reported by name
+        let value = |fields: &[CustomField], id: u32| {
+            fields.iter().find(|cf| cf.id == id).and_then(
+                |cf| custom_field_text(&cf.value),
+            )
+        };
+        let mut seen = Vec::new();
+        for cf in self.custom_fields.iter().chain(other.custom_fields.iter()) {
+            if seen.contains(&cf.id) {
+                continue;
+            }
+            seen.push(cf.id);
+
+            let old = value(&self.custom_fields, cf.id);
+            let new = value(&other.custom_fields, cf.id);
+            if old != new {
+                changes.push(FieldChange {
+                    field: cf.name.clone(),
+                    old: old,
+                    new: new,
+                });
+            }
+        }
+
+        changes
+    }
+
+    /// Returns the custom field with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the custom field
+    pub fn custom_field(&self, name: &str) -> Option<&CustomField> {
+        self.custom_fields.iter().find(|cf| cf.name == name)
+    }
+
+    /// Returns the typed value of the custom field with the given name, converted according to
+    /// its definition.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the custom field
+    /// * `definitions` - the custom field definitions as returned by the custom fields api
+    pub fn custom_field_value(
+        &self,
+        name: &str,
+        definitions: &CustomFieldDefinitionList,
+    ) -> Result<Option<CustomFieldValue>> {
+        let cf = match self.custom_field(name) {
+            Some(cf) => cf,
+            None => return Ok(None),
+        };
+
+        match definitions.by_id(cf.id) {
+            Some(definition) => cf.typed(definition),
+            None => bail!("Can't find definition of custom field: {}", name),
+        }
+    }
+
+    /// Returns the value of a date custom field in `YYYY-MM-DD` format.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the custom field
+    pub fn custom_field_date(&self, name: &str) -> Result<Option<String>> {
+        match self.custom_field_as(name, "date")? {
+            Some(CustomFieldValue::Date(d)) => Ok(Some(d)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the value of a boolean custom field.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the custom field
+    pub fn custom_field_bool(&self, name: &str) -> Result<Option<bool>> {
+        match self.custom_field_as(name, "bool")? {
+            Some(CustomFieldValue::Bool(b)) => Ok(Some(b)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the value of an integer custom field.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the custom field
+    pub fn custom_field_int(&self, name: &str) -> Result<Option<i64>> {
+        match self.custom_field_as(name, "int")? {
+            Some(CustomFieldValue::Int(i)) => Ok(Some(i)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the value of a float custom field.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the custom field
+    pub fn custom_field_float(&self, name: &str) -> Result<Option<f64>> {
+        match self.custom_field_as(name, "float")? {
+            Some(CustomFieldValue::Float(f)) => Ok(Some(f)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Converts the value of the custom field with the given name assuming the given format.
+    fn custom_field_as(&self, name: &str, field_format: &str) -> Result<Option<CustomFieldValue>> {
+        match self.custom_field(name) {
+            Some(cf) => CustomFieldValue::parse(field_format, &cf.value),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the tags of the issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - a [TagField](../tags/struct.TagField.html) referring to the tags custom field
+    pub fn tags(&self, field: &TagField) -> Vec<String> {
+        field.values(&self.custom_fields)
+    }
+
+    /// Returns how long the issue has been in each status, summed up over all periods. The
+    /// calculation is based on the journals, so they have to be included when fetching the
+    /// issue; the current period is counted up to now. Returns None if a timestamp can't be
+    /// parsed.
+    pub fn time_per_status(&self) -> Option<HashMap<u32, Duration>> {
+        let changes = self.attribute_changes("status_id")?;
+
+        // the status the issue has been created with
+        let mut status = changes
+            .first()
+            .and_then(|&(_, d)| d.old_value.as_ref())
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(self.status.id);
+        let mut since = dates::parse_timestamp(&self.created_on)?;
+
+        let mut result: HashMap<u32, Duration> = HashMap::new();
+        for (time, detail) in changes {
+            *result.entry(status).or_default() += seconds(time - since);
+
+            status = match detail.new_value.as_ref().and_then(|v| v.parse::<u32>().ok()) {
+                Some(s) => s,
+                None => continue,
+            };
+            since = time;
+        }
+        *result.entry(status).or_default() += seconds(dates::now() - since);
+
+        Some(result)
+    }
+
+    /// Returns how long the issue has been in the given status, summed up over all periods. See
+    /// [time_per_status](#method.time_per_status).
+    ///
+    /// # Arguments
+    ///
+    /// * `status_id` - an integer holding the status id
+    pub fn time_in_status(&self, status_id: u32) -> Option<Duration> {
+        self.time_per_status().map(|t| {
+            t.get(&status_id).cloned().unwrap_or_default()
+        })
+    }
+
+    /// Returns the time from creation of the issue to the first note written by someone other
+    /// than the author. Returns None if there is no such note (or journals haven't been
+    /// included).
+    pub fn first_response_time(&self) -> Option<Duration> {
+        let created_on = dates::parse_timestamp(&self.created_on)?;
+
+        self.journals
+            .iter()
+            .filter(|j| j.user.id != self.author.id)
+            .filter(|j| j.notes.as_ref().is_some_and(|n| !n.is_empty()))
+            .filter_map(|j| dates::parse_timestamp(&j.created_on))
+            .next()
+            .map(|t| seconds(t - created_on))
+    }
+
+    /// Returns the time from creation of the issue to its first assignment, which is zero for
+    /// issues assigned on creation. Returns None if the issue has never been assigned.
+    pub fn time_to_first_assignment(&self) -> Option<Duration> {
+        let changes = self.attribute_changes("assigned_to_id")?;
+
+        match changes.first() {
+            Some(&(_, d)) if d.old_value.as_ref().is_some_and(|v| !v.is_empty()) => {
+                Some(Duration::default())
+            }
+            Some(&(time, _)) => {
+                dates::parse_timestamp(&self.created_on).map(|c| seconds(time - c))
+            }
+            None if self.assigned_to.is_some() => Some(Duration::default()),
+            None => None,
+        }
+    }
+
+    /// Returns the changes of an attribute together with the time they have been made, in
+    /// chronological order. Returns None if a timestamp can't be parsed.
+    fn attribute_changes(&self, name: &str) -> Option<Vec<(i64, &JournalDetail)>> {
+        let mut changes = Vec::new();
+        for journal in &self.journals {
+            let time = dates::parse_timestamp(&journal.created_on)?;
+            for detail in &journal.details {
+                if detail.property == "attr" && detail.name == name {
+                    changes.push((time, detail));
+                }
+            }
+        }
+        changes.sort_by_key(|&(time, _)| time);
+
+        Some(changes)
+    }
 }
 impl From<IssueShow> for Issue {
     fn from(item: IssueShow) -> Self {
         item.issue
     }
 }
+// e.g. `#123 [Bug/High] Login fails (assigned to John Doe)`
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "#{} [{}/{}] {}",
+            self.id,
+            self.tracker.name,
+            self.priority.name,
+            self.subject
+        )?;
+
+        match self.assigned_to {
+            Some(ref a) => write!(f, " (assigned to {})", a.name),
+            None => write!(f, " (unassigned)"),
+        }
+    }
+}
+
+/// Describes the change of a single field of an issue as returned by
+/// [Issue::diff](struct.Issue.html#method.diff). Empty values are None.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+// e.g. `Status changed from New to In Progress`
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => {
+                write!(f, "{} changed from {} to {}", self.field, old, new)
+            }
+            (None, Some(new)) => write!(f, "{} set to {}", self.field, new),
+            (Some(old), None) => write!(f, "{} deleted ({})", self.field, old),
+            (None, None) => write!(f, "{} unchanged", self.field),
+        }
+    }
+}
+
+/// Returns the value of a custom field as displayed by redmine, joining multiple values by comma.
+/// Empty values result in None.
+fn custom_field_text(value: &serde_json::Value) -> Option<String> {
+    let text = match *value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(ref s) => s.clone(),
+        serde_json::Value::Array(ref values) => {
+            values
+                .iter()
+                .filter_map(|v| v.as_str())
+                .filter(|v| !v.is_empty())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        }
+        ref other => other.to_string(),
+    };
+
+    if text.is_empty() { None } else { Some(text) }
+}
+
+/// Shortens a subject exceeding [MAX_SUBJECT_LENGTH](constant.MAX_SUBJECT_LENGTH.html)
+/// characters to the maximum length, ending with an ellipsis. Returns the shortened subject and
+/// the cut off remainder, if any. Counts characters rather than bytes like redmine does, so
+/// multi-byte characters are never split.
+///
+/// # Arguments
+///
+/// * `subject` - a string slice holding the subject
+///
+/// # Example
+///
+/// ```
+/// use redmine_api::issues::{truncate_subject, MAX_SUBJECT_LENGTH};
+///
+/// let subject = "ä".repeat(300);
+/// let (short, rest) = truncate_subject(&subject);
+/// assert_eq!(short.chars().count(), MAX_SUBJECT_LENGTH);
+/// assert!(short.ends_with('…'));
+/// assert_eq!(rest.unwrap().chars().count(), 300 - MAX_SUBJECT_LENGTH + 1);
+/// ```
+pub fn truncate_subject(subject: &str) -> (String, Option<String>) {
+    if subject.chars().count() <= MAX_SUBJECT_LENGTH {
+        return (subject.to_string(), None);
+    }
+
+    // one character is left for the ellipsis
+    let (cut, _) = subject.char_indices().nth(MAX_SUBJECT_LENGTH - 1).unwrap_or(
+        (subject.len(), ' '),
+    );
+    let (short, rest) = subject.split_at(cut);
+
+    (format!("{}…", short), Some(rest.to_string()))
+}
+
+/// Converts a number of seconds to a duration, negative numbers (e.g. due to clock skew) result
+/// in zero.
+fn seconds(secs: i64) -> Duration {
+    Duration::from_secs(if secs > 0 { secs as u64 } else { 0 })
+}
+
+/// Journal entries of an issue which are pulled from redmine application on first access and
+/// cached afterwards. Can be shared between threads. Is used as return type for issues.journals
+/// function.
+pub struct Journals {
+    client: ClientHandle,
+    issue_id: u32,
+    cache: Mutex<Option<Arc<Vec<Journal>>>>,
+}
+impl Journals {
+    /// Returns the number of journal entries.
+    pub fn len(&self) -> Result<usize> {
+        Ok(self.load()?.len())
+    }
+
+    /// Returns true if the issue has no journal entries.
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.load()?.is_empty())
+    }
+
+    /// Returns the journal entry at `index`, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - an integer holding the position of the journal entry
+    pub fn get(&self, index: usize) -> Result<Option<Journal>> {
+        Ok(self.load()?.get(index).cloned())
+    }
+
+    /// Returns all journal entries, oldest first.
+    pub fn all(&self) -> Result<Vec<Journal>> {
+        Ok(self.load()?.to_vec())
+    }
+
+    /// Drops the cached journal entries, so they are pulled again on next access.
+    pub fn reload(&self) {
+        *self.cache.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// Pulls the journal entries unless they are cached and returns them. The lock is held while
+    /// pulling, so concurrent first accesses send a single request.
+    fn load(&self) -> Result<Arc<Vec<Journal>>> {
+        // the cache is replaced as a whole, so poisoning is ignored
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(ref journals) = *cache {
+            return Ok(journals.clone());
+        }
+
+        let issue = Api::new(self.client.clone())
+            .show(self.issue_id)
+            .include("journals")
+            .execute()?;
+        let journals = Arc::new(issue.journals);
+        *cache = Some(journals.clone());
+        Ok(journals)
+    }
+}
+
+/// Represents a journal entry of an issue, i.e. a note and/or a set of changed attributes. Is only
+/// pulled from redmine application if `journals` are included.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Journal {
+    pub id: u32,
+    pub user: NamedObject,
+    pub notes: Option<String>,
+    pub created_on: String,
+    #[serde(default)]
+    pub private_notes: bool,
+    #[serde(default)]
+    pub details: Vec<JournalDetail>,
+}
+
+/// Represents a single attribute change of a [Journal](struct.Journal.html) entry.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct JournalDetail {
+    pub property: String,
+    pub name: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+/// Helper struct for serialization of custom field values.
+#[derive(Serialize, Debug, Clone)]
+struct CustomFieldUpdate {
+    id: u32,
+    value: serde_json::Value,
+}
 
 /// Helper struct for serialization.
 #[derive(Serialize)]
@@ -530,7 +2285,7 @@ impl Default for IssueBuilderKind {
 pub struct IssueBuilder<'a> {
     // internal
     #[serde(skip_serializing)]
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     #[serde(skip_serializing)]
     kind: IssueBuilderKind,
 
@@ -543,10 +2298,10 @@ pub struct IssueBuilder<'a> {
     status_id: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     priority_id: Option<u32>,
-    #[serde(skip_serializing_if = "str::is_empty")]
-    subject: &'a str,
-    #[serde(skip_serializing_if = "str::is_empty")]
-    description: &'a str,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    subject: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    description: Tristate<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     category_id: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -557,16 +2312,36 @@ pub struct IssueBuilder<'a> {
     parent_issue_id: Option<u32>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     watcher_user_ids: Vec<u32>,
-    is_private: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_private: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     estimated_hours: Option<f32>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    start_date: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    due_date: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    done_ratio: Option<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    uploads: Vec<Upload>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    custom_fields: Vec<CustomFieldUpdate>,
+    #[serde(skip_serializing)]
+    tag_field: Option<TagField>,
+    #[serde(skip_serializing)]
+    tags: Vec<&'a str>,
+    #[serde(skip_serializing)]
+    named_custom_fields: Vec<(&'a str, &'a str)>,
+    #[serde(skip_serializing)]
+    truncate_subject: bool,
 
     // additional fields used for serialization needed for update
     #[serde(skip_serializing)]
     update_id: u32,
-    #[serde(skip_serializing_if = "str::is_empty")]
-    notes: &'a str,
-    private_notes: bool,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    notes: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    private_notes: Option<bool>,
 }
 impl<'a> IssueBuilder<'a> {
     /// Creates new instance for creation of an issue. Function takes all mandatory parameters for
@@ -574,46 +2349,110 @@ impl<'a> IssueBuilder<'a> {
     ///
     /// # Arguments
     ///
-    /// * `client` - an Rc boxed [RedmineClient](struct.RedmineClient.html)
+    /// * `client` - a [ClientHandle](../struct.ClientHandle.html)
+    /// * `project_id` - an integer holding the project id
+    /// * `tracker_id` - an integer holding the tracker id
+    /// * `status_id` - an integer holding the status id
+    /// * `priority_id` - an integer holding the priority id
+    /// * `subject` - a string slice holding the subject
+    pub fn for_create(
+        client: ClientHandle,
+        project_id: u32,
+        tracker_id: u32,
+        status_id: u32,
+        priority_id: u32,
+        subject: &'a str,
+    ) -> Self {
+        IssueBuilder {
+            client: client,
+            kind: IssueBuilderKind::Create,
+
+            project_id: Some(project_id),
+            tracker_id: Some(tracker_id),
+            status_id: Some(status_id),
+            priority_id: Some(priority_id),
+            subject: Tristate::Set(subject),
+            ..Default::default()
+        }
+    }
+
+    /// Creates new instance for update of an issue. Function takes mandatory parameter for update:
+    /// an id of an issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the issue id
+    pub fn for_update(client: ClientHandle, id: u32) -> Self {
+        IssueBuilder {
+            client: client,
+            kind: IssueBuilderKind::Update,
+            update_id: id,
+            ..Default::default()
+        }
+    }
+
+    /// Creates new instance for creation of an issue which isn't bound to a redmine application,
+    /// e.g. for building requests in code without access to one. Has to be bound with
+    /// [bind](#method.bind) before execution.
+    ///
+    /// # Arguments
+    ///
     /// * `project_id` - an integer holding the project id
     /// * `tracker_id` - an integer holding the tracker id
     /// * `status_id` - an integer holding the status id
     /// * `priority_id` - an integer holding the priority id
     /// * `subject` - a string slice holding the subject
-    pub fn for_create(
-        client: Rc<RedmineClient>,
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::issues::IssueBuilder;
+    ///
+    /// let builder = IssueBuilder::detached(1, 1, 1, 1, "Subject").description("Details");
+    /// assert_eq!(builder.to_json().unwrap()["issue"]["description"], "Details");
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    /// let result = builder.bind(&redmine).execute();
+    /// ```
+    pub fn detached(
         project_id: u32,
         tracker_id: u32,
         status_id: u32,
         priority_id: u32,
         subject: &'a str,
     ) -> Self {
-        IssueBuilder {
-            client: client,
-            kind: IssueBuilderKind::Create,
-
-            project_id: Some(project_id),
-            tracker_id: Some(tracker_id),
-            status_id: Some(status_id),
-            priority_id: Some(priority_id),
-            subject: subject,
-            ..Default::default()
-        }
+        IssueBuilder::for_create(
+            ClientHandle::default(),
+            project_id,
+            tracker_id,
+            status_id,
+            priority_id,
+            subject,
+        )
     }
 
-    /// Creates new instance for update of an issue. Function takes mandatory parameter for update:
-    /// an id of an issue.
+    /// Creates new instance for update of an issue which isn't bound to a redmine application.
+    /// Has to be bound with [bind](#method.bind) before execution.
     ///
     /// # Arguments
     ///
     /// * `id` - an integer holding the issue id
-    pub fn for_update(client: Rc<RedmineClient>, id: u32) -> Self {
-        IssueBuilder {
-            client: client,
-            kind: IssueBuilderKind::Update,
-            update_id: id,
-            ..Default::default()
-        }
+    pub fn detached_update(id: u32) -> Self {
+        IssueBuilder::for_update(ClientHandle::default(), id)
+    }
+
+    /// Binds the builder to a redmine application, which the request is sent to on execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - the api pointing to the redmine application
+    pub fn bind(mut self, api: &RedmineApi) -> Self {
+        self.client = api.client.clone();
+        self
     }
 
     /// Sets project id for issue.
@@ -662,7 +2501,7 @@ impl<'a> IssueBuilder<'a> {
     ///
     /// * `s` - a string slice holding the subject
     pub fn subject(mut self, s: &'a str) -> Self {
-        self.subject = s;
+        self.subject = Tristate::Set(s);
         self
     }
 
@@ -672,7 +2511,39 @@ impl<'a> IssueBuilder<'a> {
     ///
     /// * `s` - a string slice holding the description
     pub fn description(mut self, s: &'a str) -> Self {
-        self.description = s;
+        self.description = Tristate::Set(s);
+        self
+    }
+
+    /// Sets whether a subject exceeding
+    /// [MAX_SUBJECT_LENGTH](constant.MAX_SUBJECT_LENGTH.html) characters is shortened (true) or
+    /// fails execution with [ErrorKind::Validation](../errors/enum.ErrorKind.html) before any
+    /// request is sent (false, default). The cut off remainder is put in front of the
+    /// description, or of the notes if an update doesn't set a description, so no text is lost.
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - a boolean: true means long subjects are shortened
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::issues::IssueBuilder;
+    ///
+    /// let subject = format!("Disk usage alert: {}", "/var/lib/redmine ".repeat(20));
+    /// let builder = IssueBuilder::detached(1, 1, 1, 1, &subject)
+    ///     .description("Raised by monitoring")
+    ///     .truncate_subject(true);
+    /// assert!(builder.to_json().unwrap()["issue"]["subject"].as_str().unwrap().ends_with('…'));
+    /// ```
+    pub fn truncate_subject(mut self, b: bool) -> Self {
+        self.truncate_subject = b;
+        self
+    }
+
+    /// Clears the description of the issue.
+    pub fn clear_description(mut self) -> Self {
+        self.description = Tristate::Clear;
         self
     }
 
@@ -736,13 +2607,23 @@ impl<'a> IssueBuilder<'a> {
         self
     }
 
-    /// Sets privacy status for issue.
+    /// Sets privacy status for issue. Updates keep the privacy status of the issue unless it's
+    /// set.
     ///
     /// # Arguments
     ///
     /// * `b` - a boolean: true means private, false means public
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::issues::IssueBuilder;
+    ///
+    /// let body = IssueBuilder::detached_update(1).is_private(true).to_json().unwrap();
+    /// assert_eq!(body.to_string(), r#"{"issue":{"is_private":true}}"#);
+    /// ```
     pub fn is_private(mut self, b: bool) -> Self {
-        self.is_private = b;
+        self.is_private = Some(b);
         self
     }
 
@@ -756,37 +2637,612 @@ impl<'a> IssueBuilder<'a> {
         self
     }
 
+    /// Sets start date of the issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the date in `YYYY-MM-DD` format
+    pub fn start_date(mut self, s: &'a str) -> Self {
+        self.start_date = Tristate::Set(s);
+        self
+    }
+
+    /// Clears the start date of the issue.
+    pub fn clear_start_date(mut self) -> Self {
+        self.start_date = Tristate::Clear;
+        self
+    }
+
+    /// Sets due date of the issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the date in `YYYY-MM-DD` format
+    pub fn due_date(mut self, s: &'a str) -> Self {
+        self.due_date = Tristate::Set(s);
+        self
+    }
+
+    /// Clears the due date of the issue.
+    pub fn clear_due_date(mut self) -> Self {
+        self.due_date = Tristate::Clear;
+        self
+    }
+
+    /// Sets done ratio of the issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the done ratio in percent
+    pub fn done_ratio(mut self, n: u32) -> Self {
+        self.done_ratio = Some(n);
+        self
+    }
+
+    /// Sets estimated hours of the issue from a duration, which avoids mixing up minutes and hours.
+    /// The value is rounded to two decimal places.
+    ///
+    /// # Arguments
+    ///
+    /// * `d` - a duration holding the estimated time
+    pub fn estimated_hours_from_duration(self, d: Duration) -> Self {
+        self.estimated_hours(duration_to_hours(d))
+    }
+
+    /// Sets the value of a custom field of the issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the custom field id
+    /// * `value` - a string slice holding the value
+    pub fn custom_field(mut self, id: u32, value: &str) -> Self {
+        self.custom_fields.push(CustomFieldUpdate {
+            id: id,
+            value: serde_json::Value::String(value.to_string()),
+        });
+        self
+    }
+
+    /// Sets the value of a custom field of the issue, referring to the custom field by name. The
+    /// id is looked up in the cached custom field definitions on execution, see
+    /// [custom_fields.by_name](../custom_fields/struct.Api.html#method.by_name), which requires
+    /// administrator privileges. Execution fails before any change is sent if there is no such
+    /// custom field ([ErrorKind::UnknownCustomField](../errors/enum.ErrorKind.html)) or the value
+    /// isn't one of its possible values
+    /// ([ErrorKind::InvalidCustomFieldValue](../errors/enum.ErrorKind.html)). Labels of possible
+    /// values are accepted, too.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the custom field
+    /// * `value` - a string slice holding the value
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.issues().update(1)
+    ///     .custom_field_by_name("Severity", "High")
+    ///     .execute();
+    /// ```
+    pub fn custom_field_by_name(mut self, name: &'a str, value: &'a str) -> Self {
+        self.named_custom_fields.push((name, value));
+        self
+    }
+
+    /// Adds a tag to the issue. On update, the tags the issue already has are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - a [TagField](../tags/struct.TagField.html) referring to the tags custom field
+    /// * `tag` - a string slice holding the tag
+    pub fn add_tag(mut self, field: &TagField, tag: &'a str) -> Self {
+        self.tag_field = Some(*field);
+        self.tags.push(tag);
+        self
+    }
+
+    /// Attaches an uploaded file to the issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `upload` - an [Upload](../uploads/struct.Upload.html) as returned by the uploads api
+    pub fn add_upload(mut self, upload: Upload) -> Self {
+        self.uploads.push(upload);
+        self
+    }
+
+    /// Attaches several uploaded files to the issue, e.g. all files uploaded by a loop.
+    ///
+    /// # Arguments
+    ///
+    /// * `uploads` - a vector holding [Upload](../uploads/struct.Upload.html)s as returned by the
+    ///   uploads api
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let uploads: Vec<_> = vec!["first.log", "second.log"]
+    ///     .into_iter()
+    ///     .filter_map(|name| redmine.uploads().bytes(name, b"log").execute().ok())
+    ///     .collect();
+    /// let result = redmine.issues().update(1).uploads(uploads).execute();
+    /// ```
+    pub fn uploads(mut self, uploads: Vec<Upload>) -> Self {
+        self.uploads.extend(uploads);
+        self
+    }
+
     /// Adds note to the issue.
     ///
     /// # Arguments
     ///
     /// * `n` - a string slice holding the note
     pub fn notes(mut self, n: &'a str) -> Self {
-        self.notes = n;
+        self.notes = Tristate::Set(n);
         self
     }
 
-    /// Adds privacy status to the issue note.
+    /// Adds privacy status to the issue note. Is ignored unless a note is set by
+    /// [notes](#method.notes).
     ///
     /// # Arguments
     ///
     /// * `b` - a boolean: true means it's a private note, false means it's a public note
     pub fn private_notes(mut self, b: bool) -> Self {
-        self.private_notes = b;
+        self.private_notes = Some(b);
         self
     }
 
-    /// Performs request to redmine application to create or update an issue.
-    pub fn execute(&self) -> Result<String> {
-        let issue = IssueBuilderWrapper { issue: self };
+    /// Performs request to redmine application to create or update an issue and returns it. An
+    /// update without any field or note set fails with
+    /// [ErrorKind::NothingToUpdate](../errors/enum.ErrorKind.html) instead of sending a request
+    /// redmine would silently accept.
+    pub fn execute(&self) -> Result<Issue> {
+        let tags = match self.tag_field {
+            Some(field) => Some(self.merged_tags(&field)?),
+            None => None,
+        };
+        let issue = self.body(tags)?;
+
+        if let IssueBuilderKind::Update = self.kind {
+            let changed = issue["issue"].as_object().is_some_and(|fields| !fields.is_empty());
+            if !changed {
+                bail!(ErrorKind::NothingToUpdate(self.update_id));
+            }
+        }
+
         match self.kind {
-            IssueBuilderKind::Create => self.client.create("/issues.json", &issue),
+            IssueBuilderKind::Create => self.client.create_entity("/issues.json", &issue, "issue"),
             IssueBuilderKind::Update => {
-                self.client.update(
+                self.client.update_entity(
                     &(format!("/issues/{}.json", self.update_id)),
                     &issue,
+                    "issue",
+                )
+            }
+        }
+    }
+
+    /// Returns the json body which is sent to redmine application on execution, e.g. for
+    /// checking requests built by detached builders in tests. On update, the tags the issue
+    /// already has are merged in on execution only. Custom fields set by name are looked up,
+    /// so detached builders using them fail.
+    pub fn to_json(&self) -> Result<serde_json::Value> {
+        let tags = self.tag_field.map(
+            |_| self.tags.iter().map(|t| t.to_string()).collect(),
+        );
+
+        self.body(tags)
+    }
+
+    /// Turns the builder into a write which can be queued in an
+    /// [Outbox](../outbox/struct.Outbox.html) and sent later. The body is built right away, see
+    /// [to_json](#method.to_json). Requires the `outbox` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::issues::IssueBuilder;
+    ///
+    /// let write = IssueBuilder::detached_update(1).status_id(5).to_pending_write().unwrap();
+    /// assert_eq!(write.path, "/issues/1.json");
+    /// ```
+    #[cfg(feature = "outbox")]
+    pub fn to_pending_write(&self) -> Result<PendingWrite> {
+        let body = Some(self.to_json()?);
+
+        Ok(match self.kind {
+            IssueBuilderKind::Create => {
+                PendingWrite::new(WriteMethod::Post, "/issues.json".to_string(), body)
+            }
+            IssueBuilderKind::Update => {
+                PendingWrite::new(
+                    WriteMethod::Put,
+                    format!("/issues/{}.json", self.update_id),
+                    body,
                 )
             }
+        })
+    }
+
+    /// Serializes the issue wrapped into `issue` and adds the tags, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `tags` - the tags which should be set if a tag field has been set
+    fn body(&self, tags: Option<Vec<String>>) -> Result<serde_json::Value> {
+        let mut issue = serde_json::to_value(IssueBuilderWrapper { issue: self })
+            .chain_err(|| "Can't serialize json")?;
+        // the privacy of a note doesn't change anything without a note
+        if !matches!(self.notes, Tristate::Set(_)) {
+            if let Some(fields) = issue["issue"].as_object_mut() {
+                fields.remove("private_notes");
+            }
+        }
+        if let Tristate::Set(subject) = self.subject {
+            self.shorten_subject(subject, &mut issue)?;
+        }
+
+        let mut added = self.resolve_custom_fields()?;
+        if let (Some(field), Some(tags)) = (self.tag_field, tags) {
+            added.push(CustomFieldUpdate {
+                id: field.id(),
+                value: tags.into(),
+            });
+        }
+        if !added.is_empty() {
+            let added = serde_json::to_value(added).chain_err(|| "Can't serialize json")?;
+
+            if let Some(fields) = issue["issue"].as_object_mut() {
+                let mut custom_fields = fields
+                    .remove("custom_fields")
+                    .and_then(|cf| cf.as_array().cloned())
+                    .unwrap_or_default();
+                custom_fields.extend(added.as_array().cloned().unwrap_or_default());
+                fields.insert("custom_fields".to_string(), custom_fields.into());
+            }
+        }
+
+        Ok(issue)
+    }
+
+    /// Looks up the ids of the custom fields set by name and checks their values.
+    fn resolve_custom_fields(&self) -> Result<Vec<CustomFieldUpdate>> {
+        if self.named_custom_fields.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let api = custom_fields::Api::new(self.client.clone());
+        let mut updates = Vec::new();
+        for &(name, value) in &self.named_custom_fields {
+            let definition = api.by_name(name)?;
+            match definition.accept(value) {
+                Some(v) => {
+                    updates.push(CustomFieldUpdate {
+                        id: definition.id,
+                        value: serde_json::Value::String(v),
+                    })
+                }
+                None => {
+                    bail!(ErrorKind::InvalidCustomFieldValue(
+                        name.to_string(),
+                        value.to_string(),
+                    ))
+                }
+            }
+        }
+
+        Ok(updates)
+    }
+
+    /// Checks the length of the subject and shortens it if requested, moving the remainder to the
+    /// description or the notes.
+    ///
+    /// # Arguments
+    ///
+    /// * `subject` - a string slice holding the subject
+    /// * `issue` - the serialized issue, which is changed in place
+    fn shorten_subject(&self, subject: &str, issue: &mut serde_json::Value) -> Result<()> {
+        let (short, rest) = match truncate_subject(subject) {
+            (_, None) => return Ok(()),
+            (short, Some(rest)) => (short, rest),
+        };
+        if !self.truncate_subject {
+            bail!(ErrorKind::Validation(ValidationError::new(vec![
+                format!(
+                    "Subject is too long (maximum is {} characters)",
+                    MAX_SUBJECT_LENGTH
+                ),
+            ])));
+        }
+
+        let (key, text) = match (&self.kind, self.description, self.notes) {
+            (_, Tristate::Set(d), _) => ("description", d),
+            (&IssueBuilderKind::Update, _, Tristate::Set(n)) => ("notes", n),
+            (&IssueBuilderKind::Update, _, _) => ("notes", ""),
+            (&IssueBuilderKind::Create, _, _) => ("description", ""),
+        };
+        let text = if text.is_empty() {
+            format!("…{}", rest)
+        } else {
+            format!("…{}\n\n{}", rest, text)
+        };
+
+        if let Some(fields) = issue["issue"].as_object_mut() {
+            fields.insert("subject".to_string(), short.into());
+            fields.insert(key.to_string(), text.into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the tags which should be set, i.e. the added tags and on update the tags the issue
+    /// already has.
+    fn merged_tags(&self, field: &TagField) -> Result<Vec<String>> {
+        let mut tags = match self.kind {
+            IssueBuilderKind::Create => Vec::new(),
+            IssueBuilderKind::Update => {
+                Api::new(self.client.clone())
+                    .show(self.update_id)
+                    .execute()?
+                    .tags(field)
+            }
+        };
+
+        for tag in &self.tags {
+            if !tags.iter().any(|t| t == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+
+        Ok(tags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use super::{Issue, IssueBuilder, IssueFilter, Journal, JournalDetail, Journals};
+    use super::super::errors::*;
+    use super::super::{http, NamedObject, RedmineApi};
+    use super::super::query::{fields, Condition, Date, Query};
+    use super::super::tags::TagField;
+
+    fn user(id: u32) -> NamedObject {
+        NamedObject {
+            id: id,
+            name: format!("User {}", id),
+        }
+    }
+
+    /// Returns an issue created by user 1 at 8:00 on 2017-09-14 (UTC) with status 3.
+    fn issue(journals: Vec<Journal>) -> Issue {
+        Issue {
+            author: user(1),
+            status: NamedObject {
+                id: 3,
+                name: "Resolved".to_string(),
+            },
+            created_on: "2017-09-14T08:00:00Z".to_string(),
+            journals: journals,
+            ..Default::default()
+        }
+    }
+
+    fn journal(
+        user_id: u32,
+        created_on: &str,
+        notes: &str,
+        details: Vec<JournalDetail>,
+    ) -> Journal {
+        Journal {
+            user: user(user_id),
+            notes: Some(notes.to_string()),
+            created_on: created_on.to_string(),
+            details: details,
+            ..Default::default()
+        }
+    }
+
+    fn change(name: &str, old_value: Option<&str>, new_value: &str) -> JournalDetail {
+        JournalDetail {
+            property: "attr".to_string(),
+            name: name.to_string(),
+            old_value: old_value.map(|v| v.to_string()),
+            new_value: Some(new_value.to_string()),
+        }
+    }
+
+    fn hours(n: u64) -> Duration {
+        Duration::from_secs(n * 3600)
+    }
+
+    #[test]
+    fn time_per_status_sums_periods() {
+        // journals are listed out of order and use different time zones
+        let issue = issue(vec![
+            journal(2, "2017-09-15T12:00:00+02:00", "", vec![change("status_id", Some("2"), "1")]),
+            journal(2, "2017-09-14T10:00:00Z", "", vec![change("status_id", Some("1"), "2")]),
+            journal(2, "2017-09-15T14:00:00Z", "", vec![change("status_id", Some("1"), "3")]),
+        ]);
+
+        let times = issue.time_per_status().unwrap();
+        assert_eq!(times[&1], hours(2) + hours(4));
+        assert_eq!(times[&2], hours(24));
+        // the current period is counted up to now
+        assert!(times[&3] > hours(24 * 365));
+        assert_eq!(issue.time_in_status(2), Some(hours(24)));
+        assert_eq!(issue.time_in_status(4), Some(Duration::default()));
+    }
+
+    #[test]
+    fn time_per_status_without_changes() {
+        let times = issue(vec![]).time_per_status().unwrap();
+        assert_eq!(times.len(), 1);
+        assert!(times[&3] > hours(24 * 365));
+    }
+
+    #[test]
+    fn unparseable_timestamps() {
+        let mut issue = issue(vec![
+            journal(2, "yesterday", "", vec![change("status_id", Some("1"), "3")]),
+        ]);
+        assert_eq!(issue.time_per_status(), None);
+
+        issue.journals.clear();
+        issue.created_on = "2017-09-14".to_string();
+        assert_eq!(issue.time_per_status(), None);
+        assert_eq!(issue.first_response_time(), None);
+    }
+
+    #[test]
+    fn first_response_time_ignores_author_and_empty_notes() {
+        let issue = issue(vec![
+            journal(1, "2017-09-14T09:00:00Z", "Any news?", vec![]),
+            journal(2, "2017-09-14T10:00:00Z", "", vec![change("status_id", Some("1"), "2")]),
+            journal(2, "2017-09-14T11:30:00Z", "Looking into it.", vec![]),
+        ]);
+        assert_eq!(issue.first_response_time(), Some(Duration::from_secs(3 * 3600 + 1800)));
+
+        let unanswered = self::issue(vec![journal(1, "2017-09-14T09:00:00Z", "Any news?", vec![])]);
+        assert_eq!(unanswered.first_response_time(), None);
+    }
+
+    #[test]
+    fn time_to_first_assignment() {
+        let later = issue(vec![
+            journal(2, "2017-09-14T13:00:00Z", "", vec![change("assigned_to_id", None, "5")]),
+            journal(2, "2017-09-15T13:00:00Z", "", vec![change("assigned_to_id", Some("5"), "6")]),
+        ]);
+        assert_eq!(later.time_to_first_assignment(), Some(hours(5)));
+
+        // reassigned, so assigned on creation
+        let reassigned = issue(vec![
+            journal(2, "2017-09-14T13:00:00Z", "", vec![change("assigned_to_id", Some("5"), "6")]),
+        ]);
+        assert_eq!(reassigned.time_to_first_assignment(), Some(Duration::default()));
+
+        let mut unchanged = issue(vec![]);
+        assert_eq!(unchanged.time_to_first_assignment(), None);
+        unchanged.assigned_to = Some(user(5));
+        assert_eq!(unchanged.time_to_first_assignment(), Some(Duration::default()));
+    }
+
+    #[test]
+    fn journals_can_be_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Journals>();
+    }
+
+    fn query(condition: Condition) -> Query {
+        let mut q = Query::new();
+        q.push(condition).unwrap();
+        q
+    }
+
+    #[test]
+    fn conditions_of_several_queries_are_combined() {
+        let mut filter = IssueFilter::detached();
+        filter
+            .query(&query(fields::updated_on().ge(Date::ymd(2020, 1, 1))))
+            .query(&query(fields::updated_on().le(Date::ymd(2020, 2, 1))));
+        let pairs = filter.query_pairs().unwrap();
+        assert!(pairs.contains(&("updated_on".to_string(), "><2020-01-01|2020-02-01".to_string())));
+
+        filter.query(&query(fields::updated_on().eq(Date::ymd(2020, 1, 5))));
+        assert!(filter.query_pairs().is_err());
+    }
+
+    #[test]
+    fn conditions_conflicting_with_filter_parameters_fail() {
+        let mut filter = IssueFilter::detached();
+        filter.project_id(1).query(&query(fields::project().eq(1)));
+        assert!(filter.query_pairs().is_ok());
+
+        filter.query(&query(fields::status().is_closed())).status_id(2);
+        let e = filter.query_pairs().unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "Condition `status_id=closed` conflicts with filter parameter `status_id=2`"
+        );
+    }
+
+    /// Accepts every watcher but user 3 and tracks how many requests are handled at once.
+    struct Watchers {
+        running: Arc<Mutex<(usize, usize)>>,
+    }
+    impl http::Transport for Watchers {
+        fn send(&self, request: http::Request) -> Result<http::Response> {
+            {
+                let mut running = self.running.lock().unwrap();
+                running.0 += 1;
+                running.1 = running.1.max(running.0);
+            }
+            thread::sleep(Duration::from_millis(50));
+            self.running.lock().unwrap().0 -= 1;
+
+            let rejected = match request.body {
+                Some(http::Body::Json(ref bytes)) => bytes == b"{\"user_id\":3}",
+                _ => false,
+            };
+            Ok(http::Response {
+                status: if rejected { 422 } else { 200 },
+                location: None,
+                body: Box::new(Cursor::new(Vec::new())),
+            })
         }
     }
+
+    #[test]
+    fn watchers_are_added_concurrently() {
+        let running = Arc::new(Mutex::new((0, 0)));
+        let api = RedmineApi::with_transport(
+            "http://localhost".to_string(),
+            "1234".to_string(),
+            Box::new(Watchers { running: running.clone() }),
+        );
+        let report = api.issues().add_watchers(1, vec![8, 7, 6, 5, 4, 3, 2, 1]).execute().unwrap();
+
+        assert_eq!(report.added, vec![8, 7, 6, 5, 4, 2, 1]);
+        assert_eq!(report.failed.iter().map(|f| f.0).collect::<Vec<_>>(), vec![3]);
+        let max = running.lock().unwrap().1;
+        assert!(max > 1 && max <= 4, "{} requests at once", max);
+    }
+
+    #[test]
+    fn private_notes_without_notes_are_ignored() {
+        let builder = IssueBuilder::detached_update(1).private_notes(true);
+        assert_eq!(builder.to_json().unwrap().to_string(), r#"{"issue":{}}"#);
+
+        let e = builder.execute().unwrap_err();
+        assert!(matches!(*e.kind(), ErrorKind::NothingToUpdate(1)));
+    }
+
+    #[test]
+    fn repeated_tags_are_joined() {
+        let field = TagField::new(5);
+        let mut filter = IssueFilter::detached();
+        filter.tag(&field, "infra").tag(&field, "urgent").custom_field(6, "yes");
+
+        let pairs = filter.query_pairs().unwrap();
+        assert!(pairs.contains(&("cf_5".to_string(), "infra|urgent".to_string())));
+        assert!(pairs.contains(&("cf_6".to_string(), "yes".to_string())));
+    }
 }
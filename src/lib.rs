@@ -1,29 +1,172 @@
 //! This library can be used to communicate with an existing redmine application. All you need is
 //! an up and running redmine application and a valid api key. See
 //! [RedmineApi](struct.RedmineApi.html) struct to get started.
+//!
+//! # Features
+//!
+//! The api modules can be disabled to cut compile time and binary size, e.g. for embedded or
+//! WASM consumers which only read issues. They are all enabled by default via the `full`
+//! feature; to pick some of them, disable the default features:
+//!
+//! ```toml
+//! [dependencies]
+//! redmine_api = { version = "0.0.1", default-features = false, features = ["issues"] }
+//! ```
+//!
+//! * `issues` - issues and everything built on them, e.g. triage, routing and mirroring; enables
+//!   `attachments` and `users`, which issues refer to
+//! * `projects` - projects and reconciliation of projects with a spec
+//! * `users` - users
+//! * `time_entries` - time entries
+//! * `wiki` - wiki pages
+//! * `attachments` - attachments
+//! * `plugins` - server info listing the installed plugins and api modules of other crates for
+//!   plugin endpoints
+//!
+//! Functions combining several modules, e.g. the workload of users, require all of them. The
+//! remaining modules, e.g. versions, groups, roles or enumerations, are always available; the
+//! requests themselves are sent by the blocking reqwest client of the `native-tls` or `rustls`
+//! feature or by a user supplied [Transport](http/trait.Transport.html).
 
 #![recursion_limit = "1024"]
 
+#[cfg(feature = "chrono")]
+extern crate chrono;
 #[macro_use]
 extern crate error_chain;
+#[cfg(feature = "cassette")]
+extern crate flate2;
+#[cfg(feature = "rate-limit-file")]
+extern crate fs2;
+#[cfg(feature = "async")]
+extern crate futures;
+#[cfg(feature = "async")]
+extern crate futures_cpupool;
+#[cfg(feature = "rate-limit-redis")]
+extern crate redis;
+#[cfg(feature = "blocking")]
 extern crate reqwest;
+#[cfg(any(feature = "outbox-sqlite", feature = "trends"))]
+#[macro_use]
+extern crate rusqlite;
 #[macro_use]
 extern crate serde_derive;
 extern crate serde;
+extern crate serde_json;
+#[cfg(feature = "async")]
+extern crate tokio;
+extern crate url;
+#[cfg(feature = "activity")]
+extern crate xml;
+
+#[macro_use]
+mod macros;
 
+#[cfg(feature = "activity")]
+pub mod activity;
+#[cfg(feature = "plugins")]
+pub mod admin;
+#[cfg(feature = "issues")]
+pub mod assignment;
+#[cfg(feature = "attachments")]
+pub mod attachments;
+pub mod budget;
+#[cfg(feature = "cassette")]
+pub mod cassette;
+#[cfg(all(feature = "issues", feature = "time_entries"))]
+pub mod cleanup;
+#[cfg(all(feature = "blocking", feature = "issues"))]
+pub mod crawler;
+pub mod custom_fields;
+mod dates;
+#[cfg(feature = "issues")]
+pub mod digest;
+pub mod entity;
+pub mod enumerations;
 pub mod errors;
+#[cfg(feature = "issues")]
+pub mod escalation;
+#[cfg(all(feature = "issues", feature = "projects", feature = "time_entries"))]
+pub mod export;
+#[cfg(feature = "plugins")]
+pub mod extension;
+#[cfg(all(feature = "issues", feature = "projects"))]
+pub mod federation;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod files;
+#[cfg(all(any(test, feature = "fixtures"), feature = "full"))]
+pub mod fixtures;
+pub mod groups;
+pub mod http;
+#[cfg(all(
+    feature = "issues",
+    feature = "projects",
+    feature = "time_entries",
+    feature = "wiki"
+))]
+pub mod import;
+pub mod issue_categories;
+pub mod issue_statuses;
+pub mod memberships;
+pub mod metadata;
+#[cfg(feature = "issues")]
+pub mod mirror;
+pub mod news;
+#[cfg(feature = "outbox")]
+pub mod outbox;
+#[cfg(feature = "async")]
+pub mod nonblocking;
+#[cfg(feature = "issues")]
 pub mod issues;
+#[cfg(feature = "projects")]
 pub mod projects;
+pub mod query;
+pub mod ratelimit;
+#[cfg(feature = "projects")]
+pub mod reconcile;
+pub mod relations;
+#[cfg(feature = "issues")]
+pub mod resolver;
+pub mod roles;
+#[cfg(feature = "issues")]
+pub mod routing;
+pub mod sanitize;
+pub mod saved_queries;
+pub mod search;
+#[cfg(feature = "store")]
+pub mod store;
+pub mod tags;
+#[cfg(feature = "time_entries")]
 pub mod time_entries;
+pub mod trackers;
+#[cfg(feature = "trends")]
+pub mod trends;
+#[cfg(feature = "issues")]
+pub mod triage;
+pub mod uploads;
+#[cfg(feature = "users")]
 pub mod users;
+pub mod versions;
+#[cfg(feature = "wiki")]
+pub mod wiki;
 
 use errors::*;
-use reqwest::header::Location;
-use reqwest::{Client, Response, Url};
-use serde::ser::Serialize;
+use serde::de::DeserializeOwned;
+use serde::ser::{Serialize, Serializer};
 use std::collections::HashMap;
+use std::fmt;
+#[cfg(feature = "attachments")]
+use std::io::{self, Write};
 use std::io::Read;
-use std::rc::Rc;
+use std::ops::Deref;
+#[cfg(feature = "chrono")]
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, RwLock};
+#[cfg(any(feature = "issues", feature = "time_entries"))]
+use std::time::Duration;
+use std::time::Instant;
+use url::Url;
 
 /// This struct represents the entry point to the stable redmine api. It gets a host url and an api
 /// key for instantiation and exposes all kind of different apis provided by redmine.
@@ -41,55 +184,740 @@ use std::rc::Rc;
 /// let result = redmine.issues().show(1).execute();
 /// ```
 pub struct RedmineApi {
+    client: ClientHandle,
+    #[cfg(feature = "attachments")]
+    attachments: attachments::Api,
+    custom_fields: custom_fields::Api,
+    enumerations: enumerations::Api,
+    files: files::Api,
+    groups: groups::Api,
+    issue_categories: issue_categories::Api,
+    issue_statuses: issue_statuses::Api,
+    #[cfg(feature = "issues")]
     issues: issues::Api,
+    memberships: memberships::Api,
+    news: news::Api,
+    #[cfg(feature = "projects")]
     projects: projects::Api,
+    relations: relations::Api,
+    roles: roles::Api,
+    saved_queries: saved_queries::Api,
+    search: search::Api,
+    #[cfg(feature = "time_entries")]
     time_entries: time_entries::Api,
+    trackers: trackers::Api,
+    uploads: uploads::Api,
+    #[cfg(feature = "users")]
     users: users::Api,
+    versions: versions::Api,
+    #[cfg(feature = "wiki")]
+    wiki: wiki::Api,
 }
 impl RedmineApi {
-    /// Creates a new instance.
+    /// Creates a new instance sending requests with the blocking reqwest client. Requires the
+    /// `blocking` feature, which is enabled by default.
     ///
     /// # Arguments
     ///
     /// * `host` - a string holding the url of a redmine application
     /// * `apikey` - a string holding the apikey provided by redmine
+    #[cfg(feature = "blocking")]
     pub fn new(host: String, apikey: String) -> RedmineApi {
-        let c = Rc::new(RedmineClient::new(host, apikey));
+        RedmineApi::with_transport(host, apikey, Box::new(http::BlockingTransport))
+    }
+
+    /// Creates a new instance sending requests via a user supplied transport, e.g. one based on
+    /// the fetch api when compiling for `wasm32-unknown-unknown`.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - a string holding the url of a redmine application
+    /// * `apikey` - a string holding the apikey provided by redmine
+    /// * `transport` - a boxed [Transport](http/trait.Transport.html) sending the requests
+    pub fn with_transport(
+        host: String,
+        apikey: String,
+        transport: Box<dyn http::Transport>,
+    ) -> RedmineApi {
+        RedmineApi::from_handle(ClientHandle {
+            client: Arc::new(RedmineClient::new(host, apikey, transport)),
+            scope: RequestScope::default(),
+        })
+    }
+
+    /// Creates a new instance whose apis send requests with `c`.
+    ///
+    /// # Arguments
+    ///
+    /// * `c` - the handle of the client, possibly carrying scoped headers or language
+    fn from_handle(c: ClientHandle) -> RedmineApi {
         RedmineApi {
-            issues: issues::Api::new(Rc::clone(&c)),
-            projects: projects::Api::new(Rc::clone(&c)),
-            time_entries: time_entries::Api::new(Rc::clone(&c)),
-            users: users::Api::new(Rc::clone(&c)),
+            #[cfg(feature = "attachments")]
+            attachments: attachments::Api::new(c.clone()),
+            custom_fields: custom_fields::Api::new(c.clone()),
+            enumerations: enumerations::Api::new(c.clone()),
+            files: files::Api::new(c.clone()),
+            groups: groups::Api::new(c.clone()),
+            issue_categories: issue_categories::Api::new(c.clone()),
+            issue_statuses: issue_statuses::Api::new(c.clone()),
+            #[cfg(feature = "issues")]
+            issues: issues::Api::new(c.clone()),
+            memberships: memberships::Api::new(c.clone()),
+            news: news::Api::new(c.clone()),
+            #[cfg(feature = "projects")]
+            projects: projects::Api::new(c.clone()),
+            relations: relations::Api::new(c.clone()),
+            roles: roles::Api::new(c.clone()),
+            saved_queries: saved_queries::Api::new(c.clone()),
+            search: search::Api::new(c.clone()),
+            #[cfg(feature = "time_entries")]
+            time_entries: time_entries::Api::new(c.clone()),
+            trackers: trackers::Api::new(c.clone()),
+            uploads: uploads::Api::new(c.clone()),
+            #[cfg(feature = "users")]
+            users: users::Api::new(c.clone()),
+            versions: versions::Api::new(c.clone()),
+            #[cfg(feature = "wiki")]
+            wiki: wiki::Api::new(c.clone()),
+            client: c,
         }
     }
 
+    /// Provides attachments api.
+    #[cfg(feature = "attachments")]
+    pub fn attachments(&self) -> &attachments::Api {
+        &self.attachments
+    }
+
+    /// Provides custom fields api.
+    pub fn custom_fields(&self) -> &custom_fields::Api {
+        &self.custom_fields
+    }
+
+    /// Provides enumerations api.
+    pub fn enumerations(&self) -> &enumerations::Api {
+        &self.enumerations
+    }
+
+    /// Provides project files api.
+    pub fn files(&self) -> &files::Api {
+        &self.files
+    }
+
+    /// Provides groups api.
+    pub fn groups(&self) -> &groups::Api {
+        &self.groups
+    }
+
+    /// Provides issue categories api.
+    pub fn issue_categories(&self) -> &issue_categories::Api {
+        &self.issue_categories
+    }
+
+    /// Provides issue statuses api.
+    pub fn issue_statuses(&self) -> &issue_statuses::Api {
+        &self.issue_statuses
+    }
+
     /// Provides issues api.
+    #[cfg(feature = "issues")]
     pub fn issues(&self) -> &issues::Api {
         &self.issues
     }
 
+    /// Provides project memberships api.
+    pub fn memberships(&self) -> &memberships::Api {
+        &self.memberships
+    }
+
+    /// Provides news api.
+    pub fn news(&self) -> &news::Api {
+        &self.news
+    }
+
     /// Provides projects api.
+    #[cfg(feature = "projects")]
     pub fn projects(&self) -> &projects::Api {
         &self.projects
     }
 
+    /// Provides issue relations api.
+    pub fn relations(&self) -> &relations::Api {
+        &self.relations
+    }
+
+    /// Provides roles api.
+    pub fn roles(&self) -> &roles::Api {
+        &self.roles
+    }
+
+    /// Provides saved queries api.
+    pub fn saved_queries(&self) -> &saved_queries::Api {
+        &self.saved_queries
+    }
+
+    /// Provides search api.
+    pub fn search(&self) -> &search::Api {
+        &self.search
+    }
+
     /// Provides time entries api.
+    #[cfg(feature = "time_entries")]
     pub fn time_entries(&self) -> &time_entries::Api {
         &self.time_entries
     }
 
+    /// Provides trackers api.
+    pub fn trackers(&self) -> &trackers::Api {
+        &self.trackers
+    }
+
+    /// Provides uploads api.
+    pub fn uploads(&self) -> &uploads::Api {
+        &self.uploads
+    }
+
     /// Provides users api.
+    #[cfg(feature = "users")]
     pub fn users(&self) -> &users::Api {
         &self.users
     }
+
+    /// Provides versions api.
+    pub fn versions(&self) -> &versions::Api {
+        &self.versions
+    }
+
+    /// Provides wiki pages api.
+    #[cfg(feature = "wiki")]
+    pub fn wiki(&self) -> &wiki::Api {
+        &self.wiki
+    }
+
+    /// Returns ServerInfoShow struct which offers an `execute` function which returns the redmine
+    /// version and the installed plugins of the redmine application. Requires an api key of an
+    /// administrator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(info) = redmine.server_info().execute() {
+    ///     println!("Redmine {}", info.redmine_version);
+    ///     println!("Agile plugin installed: {}", info.has_plugin("redmine_agile"));
+    /// }
+    /// ```
+    #[cfg(feature = "plugins")]
+    pub fn server_info(&self) -> admin::ServerInfoShow {
+        admin::ServerInfoShow::new(self.client.clone())
+    }
+
+    /// Provides an api module of another crate, e.g. for the endpoints of a redmine plugin. The
+    /// module sends its requests with the client of this instance, see
+    /// [extension](extension/index.html).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::extension::{Extension, ExtensionClient};
+    ///
+    /// struct AgileApi {
+    ///     client: ExtensionClient,
+    /// }
+    /// impl Extension for AgileApi {
+    ///     fn new(client: ExtensionClient) -> Self {
+    ///         AgileApi { client: client }
+    ///     }
+    /// }
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let agile = redmine.extension::<AgileApi>();
+    /// ```
+    #[cfg(feature = "plugins")]
+    pub fn extension<E: extension::Extension>(&self) -> E {
+        E::new(extension::ExtensionClient::new(self.client.clone()))
+    }
+
+    /// Returns an empty [Store](store/struct.Store.html) which keeps entities pulled with the
+    /// client of this instance and expands references to them. Requires the `store` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::projects::Project;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let store = redmine.store();
+    /// let project = store.get::<Project>(1);
+    /// ```
+    #[cfg(feature = "store")]
+    pub fn store(&self) -> store::Store {
+        store::Store::new(self.client.clone())
+    }
+
+    /// Returns a [Sanitizer](sanitize/struct.Sanitizer.html) cleaning HTML for embedding in web
+    /// pages, which resolves relative links against the redmine application of this instance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let html = redmine.sanitizer().clean_html("<img src=\"/attachments/download/1\">");
+    /// assert_eq!(html, "<img src=\"http://www.redmine.org/attachments/download/1\">");
+    /// ```
+    pub fn sanitizer(&self) -> sanitize::Sanitizer {
+        sanitize::Sanitizer::new(&self.client.host)
+    }
+
+    /// Allows deleting projects and users, which is irreversible in redmine. Without calling this
+    /// function or [confirm_destructive](#method.confirm_destructive), such deletions fail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    /// redmine.allow_destructive();
+    ///
+    /// let result = redmine.projects().delete(1).execute();
+    /// ```
+    pub fn allow_destructive(&self) {
+        *self.client.guard.write().unwrap_or_else(|e| e.into_inner()) = DestructiveGuard::Allow;
+    }
+
+    /// Sets a callback which is asked before deleting a project or user, which is irreversible in
+    /// redmine. The callback gets the kind of entity (`project` or `user`) and its id; the
+    /// deletion fails unless it returns true. Projects deleted by identifier instead of id can't
+    /// be confirmed, they require [allow_destructive](#method.allow_destructive).
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - a closure confirming the deletion
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    /// redmine.confirm_destructive(|resource, id| resource == "project" && id == 42);
+    ///
+    /// let result = redmine.projects().delete(42).execute();
+    /// ```
+    pub fn confirm_destructive<F>(&self, f: F)
+    where
+        F: Fn(&str, u32) -> bool + Send + Sync + 'static,
+    {
+        let guard = DestructiveGuard::Confirm(Arc::new(f));
+        *self.client.guard.write().unwrap_or_else(|e| e.into_inner()) = guard;
+    }
+
+    /// Sets a callback returning metadata headers which are added to each request, e.g. a
+    /// correlation id or the upstream ticket which caused a write, so platforms can trace it.
+    /// The callback gets the method and path of the request. Headers are echoed to the observer
+    /// set by [observe](#method.observe).
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - a closure returning pairs of header name and value
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::http::Method;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    /// redmine.request_metadata(|method, _path| if method == Method::Get {
+    ///     Vec::new()
+    /// } else {
+    ///     vec![("X-Origin".to_string(), "billing-service".to_string())]
+    /// });
+    ///
+    /// let result = redmine.issues().update(1).notes("Invoice sent").execute();
+    /// ```
+    pub fn request_metadata<F>(&self, f: F)
+    where
+        F: Fn(http::Method, &str) -> Vec<(String, String)> + Send + Sync + 'static,
+    {
+        *self.client.metadata.write().unwrap() = Some(Arc::new(f));
+    }
+
+    /// Runs `f` with an api adding `headers` to each request sent by it and the builders it
+    /// returns, e.g. to tag the writes caused by a single upstream action. Requests of this api,
+    /// e.g. by other threads, don't get the headers. Calls can be nested.
+    ///
+    /// # Arguments
+    ///
+    /// * `headers` - pairs of header name and value
+    /// * `f` - a closure building and executing requests with the scoped api
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.with_headers(&[("X-Correlation-Id", "4f1c2a")], |api| {
+    ///     api.issues().update(1).notes("Deployed to production").execute()
+    /// });
+    /// ```
+    pub fn with_headers<F, T>(&self, headers: &[(&str, &str)], f: F) -> T
+    where
+        F: FnOnce(&RedmineApi) -> T,
+    {
+        let mut client = self.client.clone();
+        client.scope.headers.extend(headers.iter().map(|&(n, v)| {
+            (n.to_string(), v.to_string())
+        }));
+
+        f(&RedmineApi::from_handle(client))
+    }
+
+    /// Sets a callback which is called after each request with its method, url (without api
+    /// key), headers and outcome, e.g. for metrics or an audit log of writes.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - a closure receiving a [RequestEvent](http/struct.RequestEvent.html)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    /// redmine.observe(|event| if event.is_write() {
+    ///     println!(
+    ///         "{:?} {} -> {:?} (correlation id {:?})",
+    ///         event.method,
+    ///         event.url,
+    ///         event.status,
+    ///         event.header("X-Correlation-Id")
+    ///     );
+    /// });
+    ///
+    /// let result = redmine.with_headers(&[("X-Correlation-Id", "4f1c2a")], |api| {
+    ///     api.issues().update(1).notes("Deployed to production").execute()
+    /// });
+    /// ```
+    pub fn observe<F>(&self, f: F)
+    where
+        F: Fn(&http::RequestEvent) + Send + Sync + 'static,
+    {
+        *self.client.observer.write().unwrap() = Some(Arc::new(f));
+    }
+
+    /// Limits the rate of requests sent by this instance, e.g. with a limiter shared by a fleet
+    /// of workers. See the [ratelimit](ratelimit/index.html) module for the provided backends.
+    ///
+    /// # Arguments
+    ///
+    /// * `limiter` - a [RateLimiter](ratelimit/trait.RateLimiter.html) asked before each request
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::ratelimit::RateLimiter;
+    /// use redmine_api::errors::Result;
+    ///
+    /// struct Unlimited;
+    /// impl RateLimiter for Unlimited {
+    ///     fn acquire(&self) -> Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    /// redmine.rate_limit(Unlimited);
+    ///
+    /// let result = redmine.issues().show(1).execute();
+    /// ```
+    pub fn rate_limit<L: ratelimit::RateLimiter + 'static>(&self, limiter: L) {
+        *self.client.limiter.write().unwrap() = Some(Arc::new(limiter));
+    }
+
+    /// Sets the time zone dates like `spent_on` of time entries are taken in when they are set
+    /// from a chrono `DateTime`. Redmine stores such dates without time zone, so it should match
+    /// the time zone of the team logging time; defaults to UTC, the default time zone of redmine
+    /// applications. Requires the `chrono` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - the offset of the time zone from UTC
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate chrono;
+    /// extern crate redmine_api;
+    ///
+    /// use chrono::{FixedOffset, Utc};
+    /// use redmine_api::RedmineApi;
+    ///
+    /// # fn main() {
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    /// redmine.set_timezone(FixedOffset::east_opt(2 * 3600).unwrap());
+    ///
+    /// let result = redmine.time_entries().create(1, 1.5, 9).spent_at(&Utc::now()).execute();
+    /// # }
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn set_timezone(&self, offset: chrono::FixedOffset) {
+        self.client.utc_offset.store(offset.local_minus_utc(), Ordering::Relaxed);
+    }
+
+    /// Sets the language redmine localizes names and error messages in (`Accept-Language`
+    /// header), e.g. to match status names regardless of the default locale of the redmine
+    /// application. None restores the default locale.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - an optional string slice holding the language code, e.g. `en`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    /// redmine.set_language(Some("en"));
+    ///
+    /// let result = redmine.issues().show(1).execute();
+    /// ```
+    pub fn set_language(&self, language: Option<&str>) {
+        *self.client.language.write().unwrap() = language.map(|l| l.to_string());
+    }
+
+    /// Runs `f` with an api whose requests are localized in `language`, regardless of the
+    /// language set by [set_language](#method.set_language). Requests of this api, e.g. by other
+    /// threads, aren't affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - a string slice holding the language code, e.g. `en`
+    /// * `f` - a closure building and executing requests with the scoped api
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.in_language("en", |api| api.issues().show(1).execute());
+    /// ```
+    pub fn in_language<F, T>(&self, language: &str, f: F) -> T
+    where
+        F: FnOnce(&RedmineApi) -> T,
+    {
+        let mut client = self.client.clone();
+        client.scope.language = Some(language.to_string());
+
+        f(&RedmineApi::from_handle(client))
+    }
+
+    /// Returns ProjectExporter struct which offers an `execute` function which exports issues
+    /// (with journals and attachment metadata), versions, wiki pages, memberships and time
+    /// entries of a project, and a `write_to` function which streams the same export as json.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let export = redmine.export_all(1).execute();
+    /// ```
+    #[cfg(all(feature = "issues", feature = "projects", feature = "time_entries"))]
+    pub fn export_all(&self, project_id: u32) -> export::ProjectExporter<'_> {
+        export::ProjectExporter::new(self, project_id)
+    }
+
+    /// Returns ProjectImporter struct which offers an `execute` function which recreates an
+    /// exported project in this redmine application. Users, trackers, statuses, etc. are remapped
+    /// as described by `options`; everything which can't be restored is listed in the returned
+    /// report.
+    ///
+    /// # Arguments
+    ///
+    /// * `export` - a [ProjectExport](export/struct.ProjectExport.html) as returned by
+    ///   `export_all`
+    /// * `options` - an [ImportOptions](import/struct.ImportOptions.html) holding the id mappings
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::import::ImportOptions;
+    ///
+    /// let source = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    /// let target = RedmineApi::new(
+    ///     "http://redmine.example.com/".to_string(),
+    ///     "5678".to_string()
+    /// );
+    ///
+    /// if let Ok(export) = source.export_all(1).execute() {
+    ///     let mut options = ImportOptions::default();
+    ///     options.users.insert(5, 12);
+    ///
+    ///     let report = target.import(&export, options).execute();
+    /// }
+    /// ```
+    #[cfg(all(
+        feature = "issues",
+        feature = "projects",
+        feature = "time_entries",
+        feature = "wiki"
+    ))]
+    pub fn import<'a>(
+        &'a self,
+        export: &'a export::ProjectExport,
+        options: import::ImportOptions,
+    ) -> import::ProjectImporter<'a> {
+        import::ProjectImporter::new(self, export, options)
+    }
+}
+
+/// Cheaply cloneable handle to the [RedmineClient](struct.RedmineClient.html) shared by all apis
+/// of a [RedmineApi](struct.RedmineApi.html) and the builders they return. It can be sent to
+/// other threads, so builders can be moved into threads or tasks and executed concurrently. Is
+/// only used internally.
+///
+/// # Example
+///
+/// ```
+/// use redmine_api::RedmineApi;
+/// use std::thread;
+///
+/// let redmine = RedmineApi::new(
+///     "http://www.redmine.org/".to_string(),
+///     "1234".to_string()
+/// );
+///
+/// let threads: Vec<_> = (1..4)
+///     .map(|id| {
+///         let show = redmine.issues().show(id);
+///         thread::spawn(move || show.execute())
+///     })
+///     .collect();
+/// for t in threads {
+///     let result = t.join().unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ClientHandle {
+    client: Arc<RedmineClient>,
+    scope: RequestScope,
 }
+impl Deref for ClientHandle {
+    type Target = RedmineClient;
 
-/// Holds host and api key and provides generic functions for get, post, delete, etc.. Is only used
-/// internally.
-#[derive(Debug, Default)]
+    fn deref(&self) -> &RedmineClient {
+        &self.client
+    }
+}
+
+/// Headers and language added to the requests of a single [ClientHandle](struct.ClientHandle.html)
+/// by [RedmineApi::with_headers](struct.RedmineApi.html#method.with_headers) and
+/// [RedmineApi::in_language](struct.RedmineApi.html#method.in_language). Unlike the settings of
+/// the shared client, they don't affect requests of other handles. The asynchronous executor
+/// sets the replay of the closure it runs.
+#[derive(Clone, Debug, Default)]
+struct RequestScope {
+    headers: Vec<(String, String)>,
+    language: Option<String>,
+    #[cfg(feature = "async")]
+    replay: Option<Arc<nonblocking::Replay>>,
+}
+
+/// Callback returning additional headers for a request.
+type MetadataFn = dyn Fn(http::Method, &str) -> Vec<(String, String)> + Send + Sync;
+
+/// Callback notified about every request.
+type ObserverFn = dyn Fn(&http::RequestEvent) + Send + Sync;
+
+/// Callback confirming the deletion of a project or user.
+type ConfirmFn = dyn Fn(&str, u32) -> bool + Send + Sync;
+
+/// Holds host and api key and provides generic functions for get, post, delete, etc.. Builds
+/// transport independent requests and sends them via a [Transport](http/trait.Transport.html).
+/// Is only used internally.
 pub struct RedmineClient {
     host: String,
     apikey: String,
+    transport: Box<dyn http::Transport>,
+    language: RwLock<Option<String>>,
+    guard: RwLock<DestructiveGuard>,
+    limiter: RwLock<Option<Arc<dyn ratelimit::RateLimiter>>>,
+    metadata: RwLock<Option<Arc<MetadataFn>>>,
+    observer: RwLock<Option<Arc<ObserverFn>>>,
+    cache: metadata::MetadataStore,
+    #[cfg(feature = "chrono")]
+    utc_offset: AtomicI32,
 }
 impl RedmineClient {
     /// Creates new instance.
@@ -98,13 +926,24 @@ impl RedmineClient {
     ///
     /// * `host` - a string holding the redmine host url
     /// * `apikey` - a string holding a valid redmine api key
-    fn new(host: String, apikey: String) -> RedmineClient {
+    /// * `transport` - a boxed transport sending the requests
+    fn new(host: String, apikey: String, transport: Box<dyn http::Transport>) -> RedmineClient {
         RedmineClient {
             host: host,
             apikey: apikey,
+            transport: transport,
+            language: RwLock::new(None),
+            guard: RwLock::new(DestructiveGuard::Deny),
+            limiter: RwLock::new(None),
+            metadata: RwLock::new(None),
+            observer: RwLock::new(None),
+            cache: metadata::MetadataStore::default(),
+            #[cfg(feature = "chrono")]
+            utc_offset: AtomicI32::new(0),
         }
     }
-
+}
+impl ClientHandle {
     /// Performs GET request to api endpoint specified by `path`, transcoding the `params` argument
     /// to query string. Returns the response body as string.
     ///
@@ -120,13 +959,7 @@ impl RedmineClient {
             url.query_pairs_mut().append_pair(key, value);
         }
 
-        let mut response = Client::new()?.get(url.as_str())?.send()?;
-
-        // read response body
-        let mut result = String::new();
-        response.read_to_string(&mut result)?;
-
-        Ok(result)
+        self.send(http::Method::Get, url, None)?.text()
     }
 
     /// Performs POST request to api endpoint specified by `path` for creating a new `object`.
@@ -136,19 +969,24 @@ impl RedmineClient {
     ///
     /// * `path` - a string slice holding the api endpoint, e.g. '/issues.json'
     /// * `object` - a struct implementing the serde Serialize trait
+    #[cfg(any(
+        feature = "issues",
+        feature = "projects",
+        feature = "time_entries",
+        feature = "users"
+    ))]
     fn create<T: Serialize>(&self, path: &str, object: &T) -> Result<String> {
         let mut response = self.post(path, object)?;
 
         // put response body in error message if request has failed
-        if !response.status().is_success() {
-            let mut body = String::new();
-            response.read_to_string(&mut body)?;
-            bail!("Error: {}, {}", response.status(), body);
+        if !response.is_success() {
+            let body = response.text()?;
+            return Err(failure(response.status, &body));
         }
 
         // return content of the location header, which holds the url of the created issue.
-        match response.headers().get::<Location>() {
-            Some(l) => Ok(l.to_string()),
+        match response.location {
+            Some(l) => Ok(l),
             _ => bail!("Can't create issue."),
         }
     }
@@ -160,20 +998,110 @@ impl RedmineClient {
     ///
     /// * `path` - a string slice holding the api endpoint, e.g. '/issues/1.json'
     /// * `object` - a struct implementing the serde Serialize trait
-    fn update<T: Serialize>(&self, path: &str, object: &T) -> Result<String> {
-        let mut response = Client::new()?
-            .put(self.get_base_url(path)?.as_str())?
-            .json(object)?
-            .send()?;
+    fn update<T: Serialize>(&self, path: &str, object: &T) -> Result<()> {
+        let mut response = self.send(
+            http::Method::Put,
+            self.get_base_url(path)?,
+            Some(json_body(object)?),
+        )?;
+
+        // put response body in error message if request has failed
+        if !response.is_success() {
+            let body = response.text()?;
+            return Err(failure(response.status, &body));
+        }
+
+        Ok(())
+    }
+
+    /// Performs POST request to api endpoint specified by `path` for creating a new `object`.
+    /// Returns the created entity, which redmine sends back wrapped into `key`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - a string slice holding the api endpoint, e.g. '/issues.json'
+    /// * `object` - a struct implementing the serde Serialize trait
+    /// * `key` - a string slice holding the name of the entity in the response, e.g. `issue`
+    fn create_entity<T, E>(&self, path: &str, object: &T, key: &str) -> Result<E>
+    where
+        T: Serialize,
+        E: DeserializeOwned,
+    {
+        let mut response = self.post(path, object)?;
+        let body = response.text()?;
 
         // put response body in error message if request has failed
-        if !response.status().is_success() {
-            let mut body = String::new();
-            response.read_to_string(&mut body)?;
-            bail!("Error: {}, {}", response.status(), body);
+        if !response.is_success() {
+            return Err(failure(response.status, &body));
+        }
+
+        entity_from_json(&body, key)
+    }
+
+    /// Performs PUT request to api endpoint specified by `path` for updating an entity with data
+    /// provided by `object`. Redmine doesn't send the updated entity back, so it's fetched from
+    /// the same endpoint afterwards and returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - a string slice holding the api endpoint, e.g. '/issues/1.json'
+    /// * `object` - a struct implementing the serde Serialize trait
+    /// * `key` - a string slice holding the name of the entity in the response, e.g. `issue`
+    fn update_entity<T, E>(&self, path: &str, object: &T, key: &str) -> Result<E>
+    where
+        T: Serialize,
+        E: DeserializeOwned,
+    {
+        self.update(path, object)?;
+
+        entity_from_json(&self.get(path, &HashMap::new())?, key)
+    }
+
+    /// Checks if a request is an irreversible deletion, i.e. deletes a project or user, and if so,
+    /// whether it has been allowed or confirmed by the callback. Returns an error otherwise.
+    /// Every request is checked, so deletions sent by extensions or outboxes are guarded, too.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - the http method
+    /// * `url` - the absolute url of the request
+    fn confirm_destructive(&self, method: http::Method, url: &Url) -> Result<()> {
+        if method != http::Method::Delete {
+            return Ok(());
+        }
+
+        // the path below the host, which may be installed in a sub path
+        let base = Url::parse(&self.host)
+            .map(|h| h.path().trim_end_matches('/').to_string())
+            .unwrap_or_default();
+        let path = url.path();
+        let path = if path.starts_with(&base) { &path[base.len()..] } else { path };
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let (resource, id) = match segments[..] {
+            ["projects", id] => ("project", id),
+            ["users", id] => ("user", id),
+            _ => return Ok(()),
+        };
+        let id = id.split('.').next().unwrap_or(id);
+
+        // the callback is called without holding the lock, so it may change the guard itself
+        let guard = self.guard.read().unwrap_or_else(|e| e.into_inner()).clone();
+        let confirmed = match (guard, id.parse()) {
+            (DestructiveGuard::Deny, _) => false,
+            (DestructiveGuard::Allow, _) => true,
+            (DestructiveGuard::Confirm(f), Ok(id)) => f(resource, id),
+            (DestructiveGuard::Confirm(_), Err(_)) => false,
+        };
+
+        if !confirmed {
+            bail!(
+                "Refusing to delete {} {} without confirmation, see RedmineApi::allow_destructive",
+                resource,
+                id
+            );
         }
 
-        Ok("Success".to_string())
+        Ok(())
     }
 
     /// Performs DELETE request to api endpoint specified by `path`.
@@ -181,35 +1109,198 @@ impl RedmineClient {
     /// # Arguments
     ///
     /// * `path` - a string slice holding the api endpoint, e.g. '/issues/1.json'
-    fn delete(&self, path: &str) -> Result<bool> {
-        let response = Client::new()?
-            .delete(self.get_base_url(path)?.as_str())?
-            .send()?;
+    fn delete(&self, path: &str) -> Result<()> {
+        let response = self.send(http::Method::Delete, self.get_base_url(path)?, None)?;
+
+        if !response.is_success() {
+            bail!("Error: {}", response.status);
+        }
+
+        Ok(())
+    }
+
+    /// Performs GET request to an absolute `url` pointing to the redmine application (e.g. the
+    /// content url of an attachment) and copies the response body to `sink`. Returns the number
+    /// of bytes written.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - a string slice holding the absolute url
+    /// * `sink` - a writer receiving the response body
+    #[cfg(feature = "attachments")]
+    fn download<W: Write>(&self, url: &str, sink: &mut W) -> Result<u64> {
+        let mut url = Url::parse(url).chain_err(|| format!("Can't parse url: {}", url))?;
+        url.query_pairs_mut().append_pair("key", &self.apikey);
+
+        let mut response = self.send(http::Method::Get, url, None)?;
+
+        if !response.is_success() {
+            bail!("Error: {}", response.status);
+        }
+
+        Ok(io::copy(&mut response.body, sink)?)
+    }
+
+    /// Performs POST request to api endpoint specified by `path` sending `body` as octet stream,
+    /// transcoding the `params` argument to query string. Returns the response body as string.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - a string slice holding the api endpoint, e.g. '/uploads.json'
+    /// * `params` - a hashmap holding query parameters
+    /// * `body` - a reader holding the content which should be uploaded
+    fn upload(
+        &self,
+        path: &str,
+        params: &HashMap<&str, String>,
+        body: Box<dyn Read + Send>,
+    ) -> Result<String> {
+        let mut url = self.get_base_url(path)?;
+
+        // transcode parameters to query string
+        for (key, value) in params {
+            url.query_pairs_mut().append_pair(key, value);
+        }
+
+        let mut response = self.send(http::Method::Post, url, Some(http::Body::Binary(body)))?;
+        let result = response.text()?;
 
-        if !response.status().is_success() {
-            bail!("Error: {}", response.status());
+        // put response body in error message if request has failed
+        if !response.is_success() {
+            return Err(failure(response.status, &result));
         }
 
-        Ok(true)
+        Ok(result)
     }
 
     /// Performs generic POST request to api endpoint specified by `path` and sends embedded
-    /// information of `object`. Returns reqwest response.
+    /// information of `object`. Returns the response.
     ///
     /// # Arguments
     ///
     /// * `path` - a string slice holding the api endpoint, e.g. '/issues.json'
     /// * `object` - a struct implementing the serde Serialize trait
-    fn post<T: Serialize>(&self, path: &str, object: &T) -> Result<Response> {
-        Client::new()?
-            .post(self.get_base_url(path)?.as_str())?
-            .json(object)?
-            .send()
-            .chain_err(|| format!("Can't post to {}", path))
+    fn post<T: Serialize>(&self, path: &str, object: &T) -> Result<http::Response> {
+        self.send(
+            http::Method::Post,
+            self.get_base_url(path)?,
+            Some(json_body(object)?),
+        ).chain_err(|| format!("Can't post to {}", path))
+    }
+
+    /// Sends a request via the transport of the client.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - the http method of the request
+    /// * `url` - the absolute url including query string
+    /// * `body` - the body of the request, if any
+    fn send(
+        &self,
+        method: http::Method,
+        url: Url,
+        body: Option<http::Body>,
+    ) -> Result<http::Response> {
+        #[cfg(feature = "async")]
+        {
+            if let Some(ref replay) = self.scope.replay {
+                return replay.send(method, url, body);
+            }
+        }
+
+        let (request, observation) = self.prepare(method, url, body)?;
+        let result = self.transport.send(request);
+        if let Some(observation) = observation {
+            observation.finish(&result);
+        }
+        result
+    }
+
+    /// Builds the request sent by [send](#method.send), i.e. adds language and metadata headers,
+    /// and waits for the rate limiter. Returns the observation which has to be finished once
+    /// the response has been received, too.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - the http method
+    /// * `url` - the absolute url of the request
+    /// * `body` - the body of the request or None
+    fn prepare(
+        &self,
+        method: http::Method,
+        url: Url,
+        body: Option<http::Body>,
+    ) -> Result<(http::Request, Option<Observation>)> {
+        self.confirm_destructive(method, &url)?;
+
+        let mut headers = Vec::new();
+        let language = match self.scope.language {
+            Some(ref language) => Some(language.clone()),
+            None => self.language.read().unwrap().clone(),
+        };
+        if let Some(language) = language {
+            headers.push(("Accept-Language".to_string(), language));
+        }
+        // callbacks are cloned out of their locks, so they may use the api themselves
+        let metadata = self.metadata.read().unwrap().clone();
+        if let Some(metadata) = metadata {
+            headers.extend(metadata(method, url.path()));
+        }
+        headers.extend(self.scope.headers.iter().cloned());
+        let limiter = self.limiter.read().unwrap().clone();
+        if let Some(limiter) = limiter {
+            limiter.acquire()?;
+        }
+
+        let observer = self.observer.read().unwrap().clone();
+        let observation = observer.map(|observer| {
+            Observation {
+                observer: observer,
+                method: method,
+                url: redact(&url),
+                headers: headers.clone(),
+                started: Instant::now(),
+            }
+        });
+        let request = http::Request {
+            method: method,
+            url: url.into_string(),
+            headers: headers,
+            body: body,
+        };
+
+        Ok((request, observation))
+    }
+
+    /// Returns the url of a page of the redmine application as shown to users, e.g. for
+    /// `/issues/1`. The path is appended to the host, so redmine applications installed in a sub
+    /// path like `http://example.com/redmine/` work, regardless of trailing slashes.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - a string slice holding the path of the page, e.g. '/issues/1'
+    fn web_url(&self, path: &str) -> String {
+        format!(
+            "{}/{}",
+            self.host.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+
+    /// Returns the date of `dt` in the time zone of the client in `YYYY-MM-DD` format.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - a date time in any time zone
+    #[cfg(all(feature = "chrono", feature = "time_entries"))]
+    fn local_date<Tz: chrono::TimeZone>(&self, dt: &chrono::DateTime<Tz>) -> String {
+        let offset = chrono::FixedOffset::east_opt(self.utc_offset.load(Ordering::Relaxed))
+            .expect("Offset is taken from a FixedOffset");
+        dt.with_timezone(&offset).format("%Y-%m-%d").to_string()
     }
 
     /// Returns fully qulaified url to a redmine api endpoint (assuming the host user provided
-    /// `host` parameter is valid). Returns reqwest Url.
+    /// `host` parameter is valid).
     ///
     /// # Arguments
     ///
@@ -224,17 +1315,632 @@ impl RedmineClient {
         Ok(url)
     }
 }
+// Builders implement Default trait, so RedmineClient has to implement Default, too. Default
+// clients are always replaced before sending requests.
+impl Default for RedmineClient {
+    fn default() -> Self {
+        RedmineClient::new(String::new(), String::new(), Box::new(NoTransport))
+    }
+}
+
+/// A request which is passed to the observer once its response has been received.
+struct Observation {
+    observer: Arc<ObserverFn>,
+    method: http::Method,
+    url: String,
+    headers: Vec<(String, String)>,
+    started: Instant,
+}
+impl Observation {
+    /// Passes the request and its outcome to the observer.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - the response or the error of the request
+    fn finish(self, result: &Result<http::Response>) {
+        (self.observer)(&http::RequestEvent {
+            method: self.method,
+            url: self.url,
+            headers: self.headers,
+            status: result.as_ref().ok().map(|r| r.status),
+            error: result.as_ref().err().map(|e| e.to_string()),
+            duration: self.started.elapsed(),
+        });
+    }
+}
+
+/// Decides whether irreversible deletions of projects and users are performed.
+#[derive(Clone)]
+enum DestructiveGuard {
+    Deny,
+    Allow,
+    Confirm(Arc<ConfirmFn>),
+}
+
+/// Transport of default constructed clients. Fails for every request.
+struct NoTransport;
+impl http::Transport for NoTransport {
+    fn send(&self, _: http::Request) -> Result<http::Response> {
+        bail!("No transport configured")
+    }
+}
+// The api key isn't printed.
+impl fmt::Debug for RedmineClient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RedmineClient")
+            .field("host", &self.host)
+            .finish()
+    }
+}
 
 /// Generic helper struct to wrap an id. Is used for deserialization of redmine json responses.
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Object {
     id: u32,
 }
 
 /// Generic helper struct to wrap an id and a name. Is used for deserialization of redmine json
 /// responses.
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct NamedObject {
     id: u32,
     name: String,
 }
+impl NamedObject {
+    /// Returns the id of the referenced entity.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the name of the referenced entity.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Tri-state value of a builder field. Redmine distinguishes between omitting a field (which keeps
+/// the current value) and sending an empty value (which clears it).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tristate<T> {
+    /// The field is omitted from the request.
+    Unset,
+    /// The field is sent with the given value.
+    Set(T),
+    /// The field is sent as empty string, which clears it.
+    Clear,
+}
+impl<T> Tristate<T> {
+    /// Returns true if the field is omitted from the request. Is used for serialization.
+    pub fn is_unset(&self) -> bool {
+        matches!(*self, Tristate::Unset)
+    }
+}
+// Builders implement Default trait, so Tristate has to implement Default, too.
+impl<T> Default for Tristate<T> {
+    fn default() -> Self {
+        Tristate::Unset
+    }
+}
+impl<T: Serialize> Serialize for Tristate<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+        match *self {
+            Tristate::Set(ref v) => v.serialize(serializer),
+            _ => serializer.serialize_str(""),
+        }
+    }
+}
+
+/// Represents a custom field value as pulled from redmine application. The value is kept as raw
+/// json since redmine returns a string for single value fields and an array for multiple value
+/// fields.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct CustomField {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub value: serde_json::Value,
+}
+impl CustomField {
+    /// Returns the value as string slice if it is a single value field.
+    pub fn as_str(&self) -> Option<&str> {
+        self.value.as_str()
+    }
+}
+
+/// Refers to a created entity by the url redmine returns in the location header, e.g. as found
+/// in the [location](http/struct.Response.html#structfield.location) of a response.
+///
+/// # Example
+///
+/// ```
+/// use redmine_api::CreatedRef;
+///
+/// let created = CreatedRef::from("http://www.redmine.org/issues/42");
+/// assert_eq!(created.id().unwrap(), 42);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct CreatedRef {
+    location: String,
+}
+impl CreatedRef {
+    /// Returns the url of the created entity, e.g. `http://localhost/issues/42`.
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Returns the id of the created entity, e.g. 42 for `http://localhost/issues/42`.
+    pub fn id(&self) -> Result<u32> {
+        id_from_location(&self.location)
+    }
+}
+impl From<String> for CreatedRef {
+    fn from(location: String) -> Self {
+        CreatedRef { location: location }
+    }
+}
+impl<'a> From<&'a str> for CreatedRef {
+    fn from(location: &'a str) -> Self {
+        CreatedRef { location: location.to_string() }
+    }
+}
+// e.g. `http://localhost/issues/42`
+impl fmt::Display for CreatedRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.location)
+    }
+}
+
+/// Holds an entity together with warnings about data redmine has omitted from the response, see
+/// e.g. [IssueShow::execute_checked](issues/struct.IssueShow.html#method.execute_checked).
+#[derive(Debug)]
+pub struct Checked<T> {
+    pub value: T,
+    pub warnings: Vec<Warning>,
+}
+impl<T> Checked<T> {
+    /// Checks if redmine has returned everything requested.
+    pub fn is_complete(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+/// Enumeration of the warnings a [Checked](struct.Checked.html) entity can hold.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    /// Holds the name of associated data which has been requested to be included, e.g.
+    /// `watchers`, but is missing in the response. Redmine silently omits includes the user
+    /// isn't permitted to see.
+    MissingInclude(String),
+}
+// e.g. `watchers have been requested but not included`
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Warning::MissingInclude(ref name) => {
+                write!(f, "{} have been requested but not included", name)
+            }
+        }
+    }
+}
+
+/// Holds the entities of a list which could be deserialized together with an error for each one
+/// which couldn't, see e.g.
+/// [IssueFilter::execute_all_lenient](issues/struct.IssueFilter.html#method.execute_all_lenient).
+#[derive(Debug)]
+pub struct Lenient<T> {
+    pub value: T,
+    pub errors: Vec<ItemError>,
+}
+impl<T> Lenient<T> {
+    /// Checks if all entities of the list have been deserialized.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Describes an entity of a list which couldn't be deserialized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemError {
+    /// Position of the entity in the list, counting from the first requested entity.
+    pub index: usize,
+    /// Id of the entity if the json object holds one.
+    pub id: Option<u32>,
+    pub message: String,
+}
+// e.g. `item 3 (id 42): missing field `subject``
+impl fmt::Display for ItemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "item {}", self.index)?;
+        if let Some(id) = self.id {
+            write!(f, " (id {})", id)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Deserializes the entities of a list response one by one, so a malformed entity doesn't fail
+/// the whole list. Returns the entities, an error for each malformed one and the total count. Is
+/// only used internally.
+///
+/// # Arguments
+///
+/// * `body` - a string slice holding the response
+/// * `key` - a string slice holding the name of the json array, e.g. `issues`
+/// * `offset` - the position of the first entity of the response, which errors count from
+fn lenient_list<T: DeserializeOwned>(
+    body: &str,
+    key: &str,
+    offset: usize,
+) -> Result<(Lenient<Vec<T>>, Option<u32>)> {
+    let mut value: serde_json::Value = serde_json::from_str(body).chain_err(|| "Can't parse json")?;
+    let total_count = value["total_count"].as_u64().map(|n| n as u32);
+    let items = match value.get_mut(key).map(|v| v.take()) {
+        Some(serde_json::Value::Array(items)) => items,
+        _ => bail!("Can't parse json: missing array `{}`", key),
+    };
+
+    let mut list = Lenient {
+        value: Vec::new(),
+        errors: Vec::new(),
+    };
+    for (i, item) in items.into_iter().enumerate() {
+        let id = item["id"].as_u64().map(|id| id as u32);
+        match serde_json::from_value(item) {
+            Ok(entity) => list.value.push(entity),
+            Err(e) => list.errors.push(ItemError {
+                index: offset + i,
+                id: id,
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    Ok((list, total_count))
+}
+
+/// Returns a warning for each requested include missing in the json object of an entity. Is
+/// only used internally.
+///
+/// # Arguments
+///
+/// * `object` - the json object of the entity
+/// * `include` - the names of the requested includes, which are the keys they're returned under
+#[cfg(any(feature = "issues", feature = "users"))]
+fn missing_includes(object: &serde_json::Value, include: &[String]) -> Vec<Warning> {
+    include
+        .iter()
+        .filter(|name| object.get(name.as_str()).is_none())
+        .map(|name| Warning::MissingInclude(name.clone()))
+        .collect()
+}
+
+/// Returns `url` without api key, e.g. for logging.
+///
+/// # Arguments
+///
+/// * `url` - the url of a request
+fn redact(url: &Url) -> String {
+    let pairs: Vec<(String, String)> = url.query_pairs()
+        .filter(|(k, _)| k != "key")
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+
+    let mut redacted = url.clone();
+    if pairs.is_empty() {
+        redacted.set_query(None);
+    } else {
+        redacted.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    redacted.into_string()
+}
+
+/// Extracts the id of a created entity from the url redmine returns in the location header, e.g.
+/// `http://localhost/issues/42` results in 42. Is only used internally.
+///
+/// # Arguments
+///
+/// * `location` - a string slice holding the url of the created entity
+fn id_from_location(location: &str) -> Result<u32> {
+    let last = location
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or("");
+    let id = last.split('.').next().unwrap_or("");
+
+    id.parse().chain_err(|| format!("Can't get id from location: {}", location))
+}
+
+/// Creates an entity from a json object, wrapped into `key` as expected by redmine, and returns the
+/// id of the created entity. Is only used internally.
+///
+/// # Arguments
+///
+/// * `client` - the client performing the request
+/// * `path` - a string slice holding the api endpoint, e.g. '/issues.json'
+/// * `key` - a string slice holding the name of the entity in the request, e.g. `issue`
+/// * `fields` - a json object holding the fields of the entity
+#[cfg(any(feature = "issues", feature = "projects", feature = "time_entries", feature = "users"))]
+fn create_from_json(
+    client: &ClientHandle,
+    path: &str,
+    key: &str,
+    fields: &serde_json::Value,
+) -> Result<u32> {
+    let mut wrapper = serde_json::Map::new();
+    wrapper.insert(key.to_string(), fields.clone());
+
+    id_from_location(&client.create(path, &wrapper)?)
+}
+
+/// Deserializes the entity wrapped into `key` of a response body, e.g. `{"issue": {...}}`. Is
+/// only used internally.
+///
+/// # Arguments
+///
+/// * `body` - a string slice holding the response body
+/// * `key` - a string slice holding the name of the entity, e.g. `issue`
+fn entity_from_json<E: DeserializeOwned>(body: &str, key: &str) -> Result<E> {
+    let mut value: serde_json::Value = serde_json::from_str(body).chain_err(
+        || "Can't parse json",
+    )?;
+
+    match value.get_mut(key) {
+        Some(entity) => serde_json::from_value(entity.take()).chain_err(|| "Can't parse json"),
+        None => bail!("Can't find {} in response", key),
+    }
+}
+
+/// Serializes `object` to a json request body. Is only used internally.
+///
+/// # Arguments
+///
+/// * `object` - a struct implementing the serde Serialize trait
+fn json_body<T: Serialize>(object: &T) -> Result<http::Body> {
+    Ok(http::Body::Json(
+        serde_json::to_vec(object).chain_err(|| "Can't serialize json")?,
+    ))
+}
+
+/// Turns the body of a failed request into an error. Validation errors (status 422) are parsed
+/// into [ErrorKind::Validation](errors/enum.ErrorKind.html), so callers can map them back to the
+/// offending fields. Is only used internally.
+///
+/// # Arguments
+///
+/// * `status` - the status code of the response
+/// * `body` - a string slice holding the response body
+fn failure(status: u16, body: &str) -> Error {
+    if status == 422 {
+        if let Ok(e) = serde_json::from_str::<ValidationError>(body) {
+            return ErrorKind::Validation(e).into();
+        }
+    }
+
+    format!("Error: {}, {}", status, body).into()
+}
+
+/// Converts a duration to fractional hours as expected by redmine, rounded to two decimal places
+/// (which is the precision redmine displays). Is only used internally.
+///
+/// # Arguments
+///
+/// * `d` - a duration, e.g. the time spent on an issue
+#[cfg(any(feature = "issues", feature = "time_entries"))]
+fn duration_to_hours(d: Duration) -> f32 {
+    let secs = d.as_secs() as f64 + d.subsec_nanos() as f64 / 1_000_000_000.0;
+
+    ((secs / 3600.0 * 100.0).round() / 100.0) as f32
+}
+
+/// Walks through all pages of a list endpoint and returns the raw json objects found under `key`.
+/// Is only used internally.
+///
+/// # Arguments
+///
+/// * `client` - the client performing the requests
+/// * `path` - a string slice holding the api endpoint
+/// * `key` - a string slice holding the name of the list in the response
+fn fetch_all(client: &ClientHandle, path: &str, key: &str) -> Result<Vec<serde_json::Value>> {
+    let mut params: HashMap<&str, String> = HashMap::new();
+    params.insert("limit", "100".to_string());
+
+    let mut items: Vec<serde_json::Value> = Vec::new();
+    loop {
+        params.insert("offset", items.len().to_string());
+        let result = client.get(path, &params)?;
+        let mut value: serde_json::Value =
+            serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+
+        let page = match value.get_mut(key).map(|v| v.take()) {
+            Some(serde_json::Value::Array(page)) => page,
+            _ => bail!("Can't find {} in response", key),
+        };
+        let count = page.len();
+        items.extend(page);
+
+        // endpoints without pagination don't return a total count
+        let total_count = value
+            .get("total_count")
+            .and_then(|t| t.as_u64())
+            .unwrap_or(0) as usize;
+        if count == 0 || items.len() >= total_count {
+            break;
+        }
+    }
+
+    Ok(items)
+}
+
+/// Formats rows as plain text table with left aligned columns and a header line, e.g. for the
+/// `table` functions of the lists. Is only used internally.
+///
+/// # Arguments
+///
+/// * `header` - the column titles
+/// * `rows` - the cells of each row, one per column
+#[cfg(any(feature = "issues", feature = "projects", feature = "time_entries", feature = "users"))]
+fn format_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{:<width$}", c, width = w))
+            .collect();
+        padded.join("  ").trim_end().to_string() + "\n"
+    };
+
+    let dashes: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    let mut table = line(header.to_vec());
+    table.push_str(&line(dashes.iter().map(|d| d.as_str()).collect()));
+    for row in rows {
+        table.push_str(&line(row.iter().map(|c| c.as_str()).collect()));
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{duration_to_hours, http, id_from_location, NoTransport, RedmineApi};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Returns an api failing every request and the events of all requests sent by it.
+    fn observed() -> (RedmineApi, Arc<Mutex<Vec<http::RequestEvent>>>) {
+        let api = RedmineApi::with_transport(
+            "http://www.redmine.org/".to_string(),
+            "1234".to_string(),
+            Box::new(NoTransport),
+        );
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let events = sent.clone();
+        api.observe(move |e: &http::RequestEvent| events.lock().unwrap().push(e.clone()));
+
+        (api, sent)
+    }
+
+    fn header(name: &str, value: &str) -> (String, String) {
+        (name.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn deletions_of_projects_and_users_are_guarded() {
+        let (api, sent) = observed();
+        for path in &["/projects/1.json", "/projects/infra.json", "/users/5.json"] {
+            let error = api.client.delete(path).unwrap_err().to_string();
+            assert!(error.starts_with("Refusing to delete"), "{}", error);
+        }
+        assert!(sent.lock().unwrap().is_empty());
+
+        // members of projects and groups aren't guarded
+        let _ = api.client.delete("/projects/1/memberships/3.json");
+        let _ = api.client.delete("/groups/1/users/5.json");
+        assert_eq!(sent.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn deletions_are_confirmed_by_callback() {
+        let (api, sent) = observed();
+        api.confirm_destructive(|resource, id| resource == "project" && id == 42);
+
+        assert!(api.client.delete("/projects/41.json").is_err());
+        assert!(api.client.delete("/projects/research.json").is_err());
+        assert!(api.client.delete("/users/42.json").is_err());
+        assert!(sent.lock().unwrap().is_empty());
+
+        let _ = api.client.delete("/projects/42.json");
+        assert_eq!(sent.lock().unwrap().len(), 1);
+
+        api.allow_destructive();
+        let _ = api.client.delete("/projects/research.json");
+        assert_eq!(sent.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn scoped_headers_are_only_sent_by_scoped_api() {
+        let (api, sent) = observed();
+        api.with_headers(&[("X-Outer", "1")], |outer| {
+            let _ = api.client.get("/issues.json", &HashMap::new());
+            outer.with_headers(&[("X-Inner", "2")], |inner| {
+                let _ = inner.client.get("/issues.json", &HashMap::new());
+            });
+            let _ = outer.client.get("/issues.json", &HashMap::new());
+        });
+        let _ = api.client.get("/issues.json", &HashMap::new());
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent[0].headers, vec![]);
+        assert_eq!(sent[1].headers, vec![header("X-Outer", "1"), header("X-Inner", "2")]);
+        assert_eq!(sent[2].headers, vec![header("X-Outer", "1")]);
+        assert_eq!(sent[3].headers, vec![]);
+    }
+
+    #[test]
+    fn scoped_language_overrides_language_of_api_only() {
+        let (api, sent) = observed();
+        api.set_language(Some("de"));
+        api.in_language("en", |scoped| {
+            let _ = scoped.client.get("/issues.json", &HashMap::new());
+            let _ = api.client.get("/issues.json", &HashMap::new());
+        });
+        let _ = api.client.get("/issues.json", &HashMap::new());
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent[0].headers, vec![header("Accept-Language", "en")]);
+        assert_eq!(sent[1].headers, vec![header("Accept-Language", "de")]);
+        assert_eq!(sent[2].headers, vec![header("Accept-Language", "de")]);
+    }
+
+    #[cfg(feature = "attachments")]
+    #[test]
+    fn attachment_download_url_is_joined_with_single_slash() {
+        let (api, sent) = observed();
+        let _ = api.attachments().download(3).execute();
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent[0].url, "http://www.redmine.org/attachments/download/3");
+    }
+
+    #[test]
+    fn durations_are_converted_to_hours() {
+        assert_eq!(duration_to_hours(Duration::from_secs(0)), 0.0);
+        assert_eq!(duration_to_hours(Duration::from_secs(5400)), 1.5);
+        assert_eq!(duration_to_hours(Duration::from_secs(36 * 3600)), 36.0);
+        assert_eq!(duration_to_hours(Duration::from_millis(900_500)), 0.25);
+    }
+
+    #[test]
+    fn durations_are_rounded_to_two_decimal_places() {
+        // 20 minutes are 0.333.. hours, 40 minutes 0.666.. hours
+        assert_eq!(duration_to_hours(Duration::from_secs(1200)), 0.33);
+        assert_eq!(duration_to_hours(Duration::from_secs(2400)), 0.67);
+        // below half of the precision
+        assert_eq!(duration_to_hours(Duration::from_secs(17)), 0.0);
+        assert_eq!(duration_to_hours(Duration::from_secs(18)), 0.01);
+    }
+
+    #[test]
+    fn ids_are_taken_from_location() {
+        assert_eq!(id_from_location("http://localhost/issues/42").unwrap(), 42);
+        assert_eq!(id_from_location("http://localhost/issues/42/").unwrap(), 42);
+        assert_eq!(id_from_location("http://localhost/issues/42.json").unwrap(), 42);
+        assert_eq!(id_from_location("https://example.com/redmine/projects/7").unwrap(), 7);
+    }
+
+    #[test]
+    fn locations_without_id_are_rejected() {
+        assert!(id_from_location("").is_err());
+        assert!(id_from_location("http://localhost/projects/redmine").is_err());
+        assert!(id_from_location("http://localhost/issues/").is_err());
+        assert!(id_from_location("http://localhost/issues/-1").is_err());
+    }
+}
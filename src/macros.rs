@@ -0,0 +1,324 @@
+//! Internal macros generating the boilerplate shared by all api endpoints, i.e. the structs which
+//! are returned by the `Api` methods of each module and offer an `execute` function. New
+//! endpoints should be declared with these macros to keep them consistent.
+//!
+//! The generated structs have private constructors, so they have to be declared in the module
+//! whose `Api` returns them. Modules declaring a builder need `Tristate` in scope, since serde
+//! resolves the `skip_serializing_if` path of its fields where the builder is declared.
+
+/// Generates a struct which shows a single entity. The entity is deserialized from the json
+/// object found under `key`.
+///
+/// ```ignore
+/// show_endpoint! {
+///     /// Helper struct to provide a unified interface for all attachment api methods.
+///     AttachmentShow => Attachment, attachment, "/attachments/{}.json"
+/// }
+/// ```
+macro_rules! show_endpoint {
+    ($(#[$attr:meta])* $name:ident => $entity:ty, $key:ident, $path:expr) => {
+        $(#[$attr])*
+        pub struct $name {
+            client: $crate::ClientHandle,
+            id: u32,
+        }
+        impl $name {
+            /// Creates a new instance.
+            ///
+            /// # Arguments
+            ///
+            /// * `client` - a handle to the RedmineClient
+            /// * `id` - an integer holding the id of the entity
+            fn new(client: $crate::ClientHandle, id: u32) -> Self {
+                $name {
+                    client: client,
+                    id: id,
+                }
+            }
+
+            /// Performs request to redmine application and returns a single entity.
+            pub fn execute(&self) -> $crate::errors::Result<$entity> {
+                use $crate::errors::ResultExt;
+
+                #[derive(Deserialize)]
+                struct Wrapper {
+                    $key: $entity,
+                }
+
+                let result = self.client.get(
+                    &(format!($path, self.id)),
+                    &::std::collections::HashMap::new(),
+                )?;
+                let wrapper: Wrapper =
+                    ::serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+
+                Ok(wrapper.$key)
+            }
+        }
+    };
+}
+
+/// Generates a struct which deletes a single entity.
+///
+/// ```ignore
+/// delete_endpoint! {
+///     /// Helper struct to provide a unified interface for all attachment api methods.
+///     AttachmentDelete, "/attachments/{}.json"
+/// }
+/// ```
+macro_rules! delete_endpoint {
+    ($(#[$attr:meta])* $name:ident, $path:expr) => {
+        $(#[$attr])*
+        pub struct $name {
+            client: $crate::ClientHandle,
+            id: u32,
+        }
+        impl $name {
+            /// Creates a new instance.
+            ///
+            /// # Arguments
+            ///
+            /// * `client` - a handle to the RedmineClient
+            /// * `id` - an integer holding the id of the entity
+            fn new(client: $crate::ClientHandle, id: u32) -> Self {
+                $name {
+                    client: client,
+                    id: id,
+                }
+            }
+
+            /// Performs request to redmine application and deletes a single entity.
+            pub fn execute(&self) -> $crate::errors::Result<()> {
+                self.client.delete(&(format!($path, self.id)))
+            }
+        }
+    };
+}
+
+/// Generates a struct which lists entities page by page together with the list struct holding
+/// the result. The entities are deserialized from the json array found under `key`.
+///
+/// ```ignore
+/// list_endpoint! {
+///     /// Helper struct to provide a unified interface for all version api methods.
+///     VersionListExecutor => VersionList<Version>, versions
+/// }
+/// ```
+macro_rules! list_endpoint {
+    ($(#[$attr:meta])* $name:ident => $list:ident<$entity:ty>, $key:ident) => {
+        $(#[$attr])*
+        pub struct $name {
+            client: $crate::ClientHandle,
+            path: String,
+            offset: Option<u32>,
+            limit: Option<u32>,
+        }
+        impl $name {
+            /// Creates a new instance.
+            ///
+            /// # Arguments
+            ///
+            /// * `client` - a handle to the RedmineClient
+            /// * `path` - a string holding the api endpoint
+            fn new(client: $crate::ClientHandle, path: String) -> Self {
+                $name {
+                    client: client,
+                    path: path,
+                    offset: None,
+                    limit: None,
+                }
+            }
+
+            /// Sets the number of entities to skip.
+            ///
+            /// # Arguments
+            ///
+            /// * `n` - an integer holding the offset
+            pub fn offset(&mut self, n: u32) -> &mut Self {
+                self.offset = Some(n);
+                self
+            }
+
+            /// Sets the maximum number of entities to return.
+            ///
+            /// # Arguments
+            ///
+            /// * `n` - an integer holding the limit
+            pub fn limit(&mut self, n: u32) -> &mut Self {
+                self.limit = Some(n);
+                self
+            }
+
+            /// Performs request to redmine application and returns a list of entities.
+            pub fn execute(&self) -> $crate::errors::Result<$list> {
+                use $crate::errors::ResultExt;
+
+                let result = self.client.get(&self.path, &self.params())?;
+
+                ::serde_json::from_str(&result).chain_err(|| "Can't parse json")
+            }
+
+            /// Performs request to redmine application like `execute`, but deserializes the
+            /// entities one by one. Entities which can't be deserialized are left out of the list
+            /// and reported as errors instead of failing the whole request.
+            pub fn execute_lenient(&self) -> $crate::errors::Result<$crate::Lenient<$list>> {
+                let result = self.client.get(&self.path, &self.params())?;
+                let offset = self.offset.unwrap_or(0) as usize;
+                let (list, total_count) =
+                    $crate::lenient_list(&result, stringify!($key), offset)?;
+
+                Ok($crate::Lenient {
+                    value: $list {
+                        $key: list.value,
+                        total_count: total_count,
+                    },
+                    errors: list.errors,
+                })
+            }
+
+            /// Transcodes offset and limit to query parameters.
+            fn params(&self) -> ::std::collections::HashMap<&str, String> {
+                let mut params = ::std::collections::HashMap::new();
+                if let Some(n) = self.offset {
+                    params.insert("offset", n.to_string());
+                }
+                if let Some(n) = self.limit {
+                    params.insert("limit", n.to_string());
+                }
+
+                params
+            }
+        }
+
+        /// Holds a vector of entities. Implements IntoIterator trait for easy iteration.
+        #[derive(Deserialize, Debug)]
+        pub struct $list {
+            $key: Vec<$entity>,
+            total_count: Option<u32>,
+        }
+        impl $list {
+            /// Returns the total number of entities, which may be more than the list holds.
+            /// Redmine doesn't report it for all resources.
+            pub fn total_count(&self) -> Option<u32> {
+                self.total_count
+            }
+        }
+        impl IntoIterator for $list {
+            type Item = $entity;
+            type IntoIter = ::std::vec::IntoIter<$entity>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.$key.into_iter()
+            }
+        }
+    };
+}
+
+/// Generates a struct providing builder pattern for creation and/or update of an entity. Every
+/// declared field gets a setter; fields which aren't set are omitted from the request. The
+/// entity is sent as json object under `key` and the created or updated entity is returned. The
+/// constructors `for_create` and `for_update` are generated as listed in brackets.
+///
+/// ```ignore
+/// builder_endpoint! {
+///     /// Struct to provide builder pattern for update of attachments.
+///     AttachmentBuilder [update] => Attachment, attachment {
+///         /// Sets filename of the attachment.
+///         filename: &'a str,
+///         /// Sets description of the attachment.
+///         description: &'a str,
+///     }
+/// }
+/// ```
+macro_rules! builder_endpoint {
+    (@constructor $name:ident create) => {
+        impl<'a> $name<'a> {
+            /// Creates new instance for creation of an entity.
+            ///
+            /// # Arguments
+            ///
+            /// * `client` - a handle to the RedmineClient
+            /// * `path` - a string holding the api endpoint
+            fn for_create(client: $crate::ClientHandle, path: String) -> Self {
+                $name {
+                    client: client,
+                    path: path,
+                    create: true,
+                    ..Default::default()
+                }
+            }
+        }
+    };
+    (@constructor $name:ident update) => {
+        impl<'a> $name<'a> {
+            /// Creates new instance for update of an entity.
+            ///
+            /// # Arguments
+            ///
+            /// * `client` - a handle to the RedmineClient
+            /// * `path` - a string holding the api endpoint
+            fn for_update(client: $crate::ClientHandle, path: String) -> Self {
+                $name {
+                    client: client,
+                    path: path,
+                    create: false,
+                    ..Default::default()
+                }
+            }
+        }
+    };
+    (
+        $(#[$attr:meta])*
+        $name:ident [$($constructor:ident),+] => $entity:ty, $key:ident {
+            $($(#[$field_attr:meta])* $field:ident: $ty:ty,)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Default, Serialize)]
+        pub struct $name<'a> {
+            // internal
+            #[serde(skip_serializing)]
+            client: $crate::ClientHandle,
+            #[serde(skip_serializing)]
+            path: String,
+            #[serde(skip_serializing)]
+            create: bool,
+            #[serde(skip_serializing)]
+            lifetime: ::std::marker::PhantomData<&'a ()>,
+
+            // fields used for serialization
+            $(
+                #[serde(skip_serializing_if = "Tristate::is_unset")]
+                $field: $crate::Tristate<$ty>,
+            )*
+        }
+        $(builder_endpoint!(@constructor $name $constructor);)+
+        impl<'a> $name<'a> {
+            $(
+                $(#[$field_attr])*
+                pub fn $field(mut self, value: $ty) -> Self {
+                    self.$field = $crate::Tristate::Set(value);
+                    self
+                }
+            )*
+
+            /// Performs request to redmine application to create or update the entity and
+            /// returns it.
+            pub fn execute(&self) -> $crate::errors::Result<$entity> {
+                use $crate::errors::ResultExt;
+
+                let mut wrapper = ::serde_json::Map::new();
+                wrapper.insert(
+                    stringify!($key).to_string(),
+                    ::serde_json::to_value(self).chain_err(|| "Can't serialize json")?,
+                );
+
+                if self.create {
+                    self.client.create_entity(&self.path, &wrapper, stringify!($key))
+                } else {
+                    self.client.update_entity(&self.path, &wrapper, stringify!($key))
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,196 @@
+//! This module holds everything needed to represent the redmine project memberships api as
+//! described by following link: http://www.redmine.org/projects/redmine/wiki/Rest_Memberships.
+
+use super::{ClientHandle, NamedObject, Tristate};
+
+/// This struct exposes all methods provided by the redmine project memberships api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns MembershipListExecutor struct which offers an `execute` function which returns
+    /// the memberships of the project specified by `project_id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.memberships().list(1).limit(100).execute();
+    /// ```
+    pub fn list(&self, project_id: u32) -> MembershipListExecutor {
+        MembershipListExecutor::new(
+            self.client.clone(),
+            format!("/projects/{}/memberships.json", project_id),
+        )
+    }
+
+    /// Returns MembershipShow struct which offers an `execute` function which returns the
+    /// membership specified by `id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the membership id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.memberships().show(1).execute();
+    /// ```
+    pub fn show(&self, id: u32) -> MembershipShow {
+        MembershipShow::new(self.client.clone(), id)
+    }
+
+    /// Returns a MembershipBuilder (builder pattern) and ultimately adds a user or a group to a
+    /// project with the given roles.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    /// * `user_id` - an integer holding the id of the user or group
+    /// * `role_ids` - a vector holding the ids of the roles
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.memberships().create(1, 5, vec![3, 4]).execute();
+    /// ```
+    pub fn create<'a>(
+        &self,
+        project_id: u32,
+        user_id: u32,
+        role_ids: Vec<u32>,
+    ) -> MembershipBuilder<'a> {
+        MembershipBuilder::for_create(
+            self.client.clone(),
+            format!("/projects/{}/memberships.json", project_id),
+        ).user_id(user_id)
+            .role_ids(role_ids)
+    }
+
+    /// Returns a MembershipBuilder (builder pattern) and ultimately replaces the roles of an
+    /// existing membership. Roles inherited from a group can't be removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the membership id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.memberships().update(1)
+    ///     .role_ids(vec![4])
+    ///     .execute();
+    /// ```
+    pub fn update<'a>(&self, id: u32) -> MembershipBuilder<'a> {
+        MembershipBuilder::for_update(self.client.clone(), format!("/memberships/{}.json", id))
+    }
+
+    /// Returns MembershipDelete struct which offers an `execute` function which removes the
+    /// membership specified by `id` parameter from its project.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the membership id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.memberships().delete(1).execute();
+    /// ```
+    pub fn delete(&self, id: u32) -> MembershipDelete {
+        MembershipDelete::new(self.client.clone(), id)
+    }
+}
+
+show_endpoint! {
+    /// Helper struct to provide a unified interface for all membership api methods.
+    MembershipShow => Membership, membership, "/memberships/{}.json"
+}
+
+list_endpoint! {
+    /// Helper struct to provide a unified interface for all membership api methods.
+    MembershipListExecutor => MembershipList<Membership>, memberships
+}
+
+builder_endpoint! {
+    /// Struct to provide builder pattern for creation and update of memberships. Can be
+    /// serialized to be used as json parameter for request to redmine application.
+    MembershipBuilder [create, update] => Membership, membership {
+        /// Sets the user or group which becomes member of the project. Is ignored by updates.
+        user_id: u32,
+        /// Sets the roles of the member.
+        role_ids: Vec<u32>,
+    }
+}
+
+delete_endpoint! {
+    /// Helper struct to provide a unified interface for all membership api methods.
+    MembershipDelete, "/memberships/{}.json"
+}
+
+/// Represents the membership of a user or group in a project. Memberships pulled as part of a
+/// user hold neither `user` nor `group`.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Membership {
+    pub id: u32,
+    pub project: NamedObject,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<NamedObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<NamedObject>,
+    #[serde(default)]
+    pub roles: Vec<Role>,
+}
+
+/// Represents a role of a member in a project.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Role {
+    pub id: u32,
+    pub name: String,
+    /// Is `Some(true)` if the role is given to the user as member of a group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inherited: Option<bool>,
+}
@@ -0,0 +1,234 @@
+//! This module caches the metadata of a redmine application which rarely changes but is needed
+//! by many requests, i.e. trackers, issue statuses, issue priorities and custom field
+//! definitions. A [MetadataCache](struct.MetadataCache.html) can be shared across threads, e.g.
+//! by the workers of a long-running daemon, and pulls each kind of metadata once per time to
+//! live. The snapshots are kept by the client of a [RedmineApi](../struct.RedmineApi.html), so
+//! all caches of an api share them, including the one
+//! [custom_fields.by_name](../custom_fields/struct.Api.html#method.by_name) looks up fields in.
+//!
+//! # Consistency
+//!
+//! * Each kind of metadata is cached as an immutable snapshot. A snapshot returned to the caller
+//!   never changes, even if the cache is refreshed while it's in use.
+//! * A snapshot is at most as old as the time to live when it's returned. Expired snapshots are
+//!   pulled again on next use; changes made by administrators in between aren't noticed.
+//! * Refreshing replaces a snapshot atomically. Readers see either the old or the new snapshot,
+//!   never a mix of both. Requests are sent without holding a lock, so several threads hitting
+//!   an expired snapshot at once may each pull it; the last one wins.
+//! * Snapshots of different kinds are pulled independently, so trackers and statuses may stem
+//!   from different points in time.
+//! * A failed request leaves the cached snapshot as it is and returns the error.
+//!
+//! # Example
+//!
+//! ```
+//! use redmine_api::RedmineApi;
+//! use redmine_api::metadata::MetadataCache;
+//! use std::time::Duration;
+//!
+//! let redmine = RedmineApi::new(
+//!     "http://www.redmine.org/".to_string(),
+//!     "1234".to_string()
+//! );
+//!
+//! let cache = MetadataCache::new(&redmine).ttl(Duration::from_secs(60));
+//! if let Ok(trackers) = cache.trackers() {
+//!     println!("Redmine knows {} trackers", trackers.len());
+//! }
+//!
+//! // an administrator has renamed a tracker
+//! cache.invalidate();
+//! ```
+
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, Instant};
+use super::errors::*;
+pub use super::custom_fields::CustomFieldDefinition;
+pub use super::enumerations::Priority;
+pub use super::issue_statuses::IssueStatus;
+pub use super::trackers::Tracker;
+use super::{entity_from_json, ClientHandle, RedmineApi};
+
+/// Time to live of cached metadata unless set otherwise.
+const DEFAULT_TTL: u64 = 300;
+
+/// Thread-safe cache of trackers, issue statuses, issue priorities and custom field definitions.
+/// Holds the client the metadata is pulled with, so it can be handed to other threads.
+#[derive(Debug, Clone)]
+pub struct MetadataCache {
+    client: ClientHandle,
+}
+impl MetadataCache {
+    /// Returns the cache of `redmine`. All caches of an api share the cached metadata and the time
+    /// to live, which defaults to five minutes.
+    ///
+    /// # Arguments
+    ///
+    /// * `redmine` - the api the requests are sent with
+    pub fn new(redmine: &RedmineApi) -> Self {
+        MetadataCache::with_client(redmine.client.clone())
+    }
+
+    /// Returns the cache of `client`. Should not be called externally.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - a handle to the RedmineClient
+    pub fn with_client(client: ClientHandle) -> Self {
+        MetadataCache { client: client }
+    }
+
+    /// Sets the time after which cached metadata is pulled again, for all caches of the api.
+    /// Defaults to five minutes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - the time to live of cached metadata
+    pub fn ttl(self, ttl: Duration) -> Self {
+        *write(&self.client.cache.ttl) = ttl;
+        self
+    }
+
+    /// Returns all trackers, pulled from redmine application if the cached ones have expired.
+    pub fn trackers(&self) -> Result<Arc<Vec<Tracker>>> {
+        self.cached(&self.client.cache.trackers, "/trackers.json", "trackers")
+    }
+
+    /// Returns all issue statuses, pulled from redmine application if the cached ones have
+    /// expired.
+    pub fn issue_statuses(&self) -> Result<Arc<Vec<IssueStatus>>> {
+        self.cached(&self.client.cache.issue_statuses, "/issue_statuses.json", "issue_statuses")
+    }
+
+    /// Returns all issue priorities, pulled from redmine application if the cached ones have
+    /// expired.
+    pub fn priorities(&self) -> Result<Arc<Vec<Priority>>> {
+        self.cached(
+            &self.client.cache.priorities,
+            "/enumerations/issue_priorities.json",
+            "issue_priorities",
+        )
+    }
+
+    /// Returns all custom field definitions, pulled from redmine application if the cached ones
+    /// have expired. Requires administrator privileges.
+    pub fn custom_fields(&self) -> Result<Arc<Vec<CustomFieldDefinition>>> {
+        self.cached(&self.client.cache.custom_fields, "/custom_fields.json", "custom_fields")
+    }
+
+    /// Returns the tracker with the given name, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the tracker
+    pub fn tracker_by_name(&self, name: &str) -> Result<Option<Tracker>> {
+        Ok(self.trackers()?.iter().find(|t| t.name == name).cloned())
+    }
+
+    /// Returns the issue status with the given name, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the issue status
+    pub fn issue_status_by_name(&self, name: &str) -> Result<Option<IssueStatus>> {
+        Ok(self.issue_statuses()?.iter().find(|s| s.name == name).cloned())
+    }
+
+    /// Drops all cached metadata, so it's pulled again on next use. Snapshots already returned
+    /// stay valid.
+    pub fn invalidate(&self) {
+        let store = &self.client.cache;
+        *write(&store.trackers) = None;
+        *write(&store.issue_statuses) = None;
+        *write(&store.priorities) = None;
+        *write(&store.custom_fields) = None;
+    }
+
+    /// Pulls all metadata from redmine application right away and replaces the cached
+    /// snapshots. Custom field definitions are only pulled if they have been cached before,
+    /// since they require administrator privileges. Stops at the first failing request;
+    /// snapshots pulled before are kept.
+    pub fn refresh(&self) -> Result<()> {
+        let custom_fields = read(&self.client.cache.custom_fields).is_some();
+        self.invalidate();
+        self.trackers()?;
+        self.issue_statuses()?;
+        self.priorities()?;
+        if custom_fields {
+            self.custom_fields()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the snapshot held by `slot` or pulls a new one if it's missing or has expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - the lock holding the snapshot
+    /// * `path` - a string slice holding the api endpoint
+    /// * `key` - a string slice holding the key the list is wrapped in
+    fn cached<T: DeserializeOwned>(
+        &self,
+        slot: &RwLock<Option<Snapshot<T>>>,
+        path: &str,
+        key: &str,
+    ) -> Result<Arc<Vec<T>>> {
+        let ttl = *read(&self.client.cache.ttl);
+        if let Some(ref snapshot) = *read(slot) {
+            if snapshot.pulled.elapsed() < ttl {
+                return Ok(Arc::clone(&snapshot.items));
+            }
+        }
+
+        let body = self.client.get(path, &HashMap::new())?;
+        let items: Arc<Vec<T>> = Arc::new(entity_from_json(&body, key)?);
+        *write(slot) = Some(Snapshot {
+            items: Arc::clone(&items),
+            pulled: Instant::now(),
+        });
+
+        Ok(items)
+    }
+}
+/// Cached metadata kept by the client of an api and shared by all its caches. Is only used
+/// internally.
+#[derive(Debug)]
+pub struct MetadataStore {
+    ttl: RwLock<Duration>,
+    trackers: RwLock<Option<Snapshot<Tracker>>>,
+    issue_statuses: RwLock<Option<Snapshot<IssueStatus>>>,
+    priorities: RwLock<Option<Snapshot<Priority>>>,
+    custom_fields: RwLock<Option<Snapshot<CustomFieldDefinition>>>,
+}
+impl Default for MetadataStore {
+    fn default() -> Self {
+        MetadataStore {
+            ttl: RwLock::new(Duration::from_secs(DEFAULT_TTL)),
+            trackers: RwLock::new(None),
+            issue_statuses: RwLock::new(None),
+            priorities: RwLock::new(None),
+            custom_fields: RwLock::new(None),
+        }
+    }
+}
+
+/// Immutable list of metadata together with the time it has been pulled.
+#[derive(Debug)]
+struct Snapshot<T> {
+    items: Arc<Vec<T>>,
+    pulled: Instant,
+}
+
+/// Locks `lock` for reading. Snapshots are replaced as a whole, so poisoning is ignored.
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Locks `lock` for writing. Snapshots are replaced as a whole, so poisoning is ignored.
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(|e| e.into_inner())
+}
+
@@ -0,0 +1,178 @@
+//! This module provides helpers to mirror entities from one redmine application to another, e.g.
+//! for bridging the trackers of a vendor and a customer.
+
+use std::collections::HashMap;
+use super::errors::*;
+use super::{NamedObject, RedmineApi};
+
+/// Describes how an issue is translated from the source to the destination redmine application.
+/// Ids which have no entry in one of the maps are taken over unchanged, except for users: an
+/// unmapped assignee isn't set on the counterpart but reported in
+/// [SyncReport::unmapped_assignee](struct.SyncReport.html#structfield.unmapped_assignee).
+#[derive(Debug, Default, Clone)]
+pub struct Mapping {
+    /// Id of the project the mirrored issue is created in.
+    pub project_id: u32,
+    /// Id of the custom field (on the destination) holding the id of the source issue. The custom
+    /// field has to be enabled as filter.
+    pub counterpart_field_id: u32,
+    pub trackers: HashMap<u32, u32>,
+    pub statuses: HashMap<u32, u32>,
+    pub priorities: HashMap<u32, u32>,
+    pub users: HashMap<u32, u32>,
+}
+impl Mapping {
+    /// Creates a new instance without any id translations.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the id of the destination project
+    /// * `counterpart_field_id` - an integer holding the id of the destination custom field which
+    ///   records the source issue id
+    pub fn new(project_id: u32, counterpart_field_id: u32) -> Self {
+        Mapping {
+            project_id: project_id,
+            counterpart_field_id: counterpart_field_id,
+            ..Default::default()
+        }
+    }
+}
+
+/// Holds the result of a mirroring operation.
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    /// Id of the issue on the destination.
+    pub id: u32,
+    /// True if the issue was created, false if an existing counterpart was updated.
+    pub created: bool,
+    pub notes: u32,
+    pub attachments: u32,
+    /// Assignee of the source issue which has no entry in the user mapping and hence hasn't been
+    /// assigned on the destination.
+    pub unmapped_assignee: Option<NamedObject>,
+}
+
+/// Mirrors an issue from `src` to `dst`. The first call creates the counterpart including all
+/// notes (as comments) and attachments, and records the source id in the counterpart custom
+/// field. Subsequent calls find the counterpart by that custom field and only update its fields.
+///
+/// # Arguments
+///
+/// * `src` - the redmine application holding the issue
+/// * `dst` - the redmine application the issue is mirrored to
+/// * `id` - an integer holding the id of the source issue
+/// * `mapping` - a [Mapping](struct.Mapping.html) translating ids between both applications
+///
+/// # Example
+///
+/// ```
+/// use redmine_api::RedmineApi;
+/// use redmine_api::mirror::{self, Mapping};
+///
+/// let vendor = RedmineApi::new("http://vendor.example.com/".to_string(), "1234".to_string());
+/// let customer = RedmineApi::new("http://customer.example.com/".to_string(), "5678".to_string());
+///
+/// let report = mirror::sync_issue(&vendor, &customer, 1, &Mapping::new(3, 7));
+/// ```
+pub fn sync_issue(
+    src: &RedmineApi,
+    dst: &RedmineApi,
+    id: u32,
+    mapping: &Mapping,
+) -> Result<SyncReport> {
+    let issue = src.issues()
+        .show(id)
+        .include("journals")
+        .include("attachments")
+        .execute()
+        .chain_err(|| format!("Can't fetch source issue {}", id))?;
+
+    let counterpart = id.to_string();
+    let existing = dst.issues()
+        .list()
+        .project_id(mapping.project_id)
+        .custom_field(mapping.counterpart_field_id, &counterpart)
+        .execute()?
+        .into_iter()
+        .next();
+
+    let map = |m: &HashMap<u32, u32>, id: u32| *m.get(&id).unwrap_or(&id);
+    let description = issue.description.clone().unwrap_or_default();
+
+    let mut builder = match existing {
+        Some(ref i) => dst.issues().update(i.id),
+        None => dst.issues().create(
+            mapping.project_id,
+            map(&mapping.trackers, issue.tracker.id),
+            map(&mapping.statuses, issue.status.id),
+            map(&mapping.priorities, issue.priority.id),
+            &issue.subject,
+        ),
+    };
+    builder = builder
+        .subject(&issue.subject)
+        .description(&description)
+        .tracker_id(map(&mapping.trackers, issue.tracker.id))
+        .status_id(map(&mapping.statuses, issue.status.id))
+        .priority_id(map(&mapping.priorities, issue.priority.id))
+        .custom_field(mapping.counterpart_field_id, &counterpart);
+    let mut report = SyncReport::default();
+    if let Some(ref a) = issue.assigned_to {
+        match mapping.users.get(&a.id) {
+            Some(user_id) => builder = builder.assigned_to_id(*user_id),
+            None => report.unmapped_assignee = Some(a.clone()),
+        }
+    }
+    if let Some(eh) = issue.estimated_hours {
+        builder = builder.estimated_hours(eh);
+    }
+
+    match existing {
+        Some(ref i) => {
+            builder.execute()?;
+            report.id = i.id;
+            return Ok(report);
+        }
+        None => {
+            report.id = builder.execute()?.id;
+            report.created = true;
+        }
+    }
+
+    for journal in &issue.journals {
+        let notes = match journal.notes {
+            Some(ref n) if !n.is_empty() => n,
+            _ => continue,
+        };
+        let text = format!(
+            "{} wrote on {}:\n\n{}",
+            journal.user.name,
+            journal.created_on,
+            notes
+        );
+        dst.issues()
+            .comment(report.id, &text)
+            .private_notes(journal.private_notes)
+            .execute()?;
+        report.notes += 1;
+    }
+
+    for attachment in &issue.attachments {
+        let mut content = Vec::new();
+        src.client.download(&attachment.content_url, &mut content)?;
+
+        let mut upload = dst.uploads().bytes(&attachment.filename, &content);
+        if let Some(ref ct) = attachment.content_type {
+            upload = upload.content_type(ct);
+        }
+        if let Some(ref d) = attachment.description {
+            upload = upload.description(d);
+        }
+        let upload = upload.execute()?;
+
+        dst.issues().update(report.id).add_upload(upload).execute()?;
+        report.attachments += 1;
+    }
+
+    Ok(report)
+}
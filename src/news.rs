@@ -0,0 +1,279 @@
+//! This module holds everything needed to represent the redmine news api as described by
+//! following link: http://www.redmine.org/projects/redmine/wiki/Rest_News. Showing, creating,
+//! updating and deleting news requires redmine 4.1 or newer.
+
+use super::errors::*;
+use super::{failure, ClientHandle, NamedObject, Tristate};
+
+/// This struct exposes all methods provided by the redmine news api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns NewsListExecutor struct which offers an `execute` function which returns the news
+    /// of all projects visible to the user.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.news().list().limit(10).execute();
+    /// ```
+    pub fn list(&self) -> NewsListExecutor {
+        NewsListExecutor::new(self.client.clone(), "/news.json".to_string())
+    }
+
+    /// Returns NewsListExecutor struct which offers an `execute` function which returns the news
+    /// of the project specified by `project_id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.news().list_for_project(1).execute();
+    /// ```
+    pub fn list_for_project(&self, project_id: u32) -> NewsListExecutor {
+        NewsListExecutor::new(
+            self.client.clone(),
+            format!("/projects/{}/news.json", project_id),
+        )
+    }
+
+    /// Returns NewsShow struct which offers an `execute` function which returns the news
+    /// specified by `id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the news id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.news().show(1).execute();
+    /// ```
+    pub fn show(&self, id: u32) -> NewsShow {
+        NewsShow::new(self.client.clone(), id)
+    }
+
+    /// Returns a NewsBuilder (builder pattern) and ultimately publishes news in the project
+    /// specified by `project_id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    /// * `title` - a string slice holding the title of the news
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.news().create(1, "Version 1.0 released")
+    ///     .summary("Fixes the LDAP login")
+    ///     .description("See the changelog for details.")
+    ///     .execute();
+    /// ```
+    pub fn create<'a>(&self, project_id: u32, title: &'a str) -> NewsBuilder<'a> {
+        NewsBuilder {
+            client: self.client.clone(),
+            path: format!("/projects/{}/news.json", project_id),
+            create: true,
+            ..Default::default()
+        }.title(title)
+    }
+
+    /// Returns a NewsBuilder (builder pattern) and ultimately updates existing news.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the news id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.news().update(1)
+    ///     .clear_summary()
+    ///     .execute();
+    /// ```
+    pub fn update<'a>(&self, id: u32) -> NewsBuilder<'a> {
+        NewsBuilder {
+            client: self.client.clone(),
+            path: format!("/news/{}.json", id),
+            create: false,
+            ..Default::default()
+        }
+    }
+
+    /// Returns NewsDelete struct which offers an `execute` function which deletes the news
+    /// specified by `id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the news id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.news().delete(1).execute();
+    /// ```
+    pub fn delete(&self, id: u32) -> NewsDelete {
+        NewsDelete::new(self.client.clone(), id)
+    }
+}
+
+show_endpoint! {
+    /// Helper struct to provide a unified interface for all news api methods.
+    NewsShow => News, news, "/news/{}.json"
+}
+
+list_endpoint! {
+    /// Helper struct to provide a unified interface for all news api methods.
+    NewsListExecutor => NewsList<News>, news
+}
+
+delete_endpoint! {
+    /// Helper struct to provide a unified interface for all news api methods.
+    NewsDelete, "/news/{}.json"
+}
+
+/// Helper struct for serialization.
+#[derive(Serialize)]
+struct NewsBuilderWrapper<'a> {
+    news: &'a NewsBuilder<'a>,
+}
+
+/// Struct to provide builder pattern for creation and update of news. Can be serialized to be
+/// used as json parameter for request to redmine application.
+#[derive(Debug, Default, Serialize)]
+pub struct NewsBuilder<'a> {
+    // internal
+    #[serde(skip_serializing)]
+    client: ClientHandle,
+    #[serde(skip_serializing)]
+    path: String,
+    #[serde(skip_serializing)]
+    create: bool,
+
+    // fields used for serialization
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    title: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    summary: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    description: Tristate<&'a str>,
+}
+impl<'a> NewsBuilder<'a> {
+    /// Sets title of the news.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the title
+    pub fn title(mut self, s: &'a str) -> Self {
+        self.title = Tristate::Set(s);
+        self
+    }
+
+    /// Sets summary of the news, which is shown in news lists.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the summary
+    pub fn summary(mut self, s: &'a str) -> Self {
+        self.summary = Tristate::Set(s);
+        self
+    }
+
+    /// Removes the summary of the news.
+    pub fn clear_summary(mut self) -> Self {
+        self.summary = Tristate::Clear;
+        self
+    }
+
+    /// Sets description of the news.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the description
+    pub fn description(mut self, s: &'a str) -> Self {
+        self.description = Tristate::Set(s);
+        self
+    }
+
+    /// Performs request to redmine application to create or update the news. Redmine responds
+    /// to both without the news, so nothing is returned.
+    pub fn execute(&self) -> Result<()> {
+        let news = NewsBuilderWrapper { news: self };
+        if !self.create {
+            return self.client.update(&self.path, &news);
+        }
+
+        let mut response = self.client.post(&self.path, &news)?;
+
+        // put response body in error message if request has failed
+        if !response.is_success() {
+            let body = response.text()?;
+            return Err(failure(response.status, &body));
+        }
+
+        Ok(())
+    }
+}
+
+/// Represents news of a project.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct News {
+    pub id: u32,
+    pub project: NamedObject,
+    pub author: NamedObject,
+    pub title: String,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub created_on: String,
+}
@@ -0,0 +1,481 @@
+//! This module provides an asynchronous executor for the apis, enabled by the `async` feature.
+//! Requests are built by the same builders as in blocking code; the executor sends them with the
+//! asynchronous reqwest client (or rather
+//! [Transport::send_async](../http/trait.Transport.html#method.send_async) of the client's
+//! transport) and returns a future resolving to the result.
+//!
+//! The builders themselves are blocking, so a closure passed to
+//! [run](struct.AsyncRedmineApi.html#method.run) is replayed: it's run on a thread pool until it
+//! sends a request without known response, then the request is sent asynchronously and the
+//! closure is run again, getting the recorded responses of all requests it has sent so far. No
+//! thread is blocked while waiting for redmine application. Requests are matched by method, url
+//! and body, so a closure has to send the same requests when it's run again and mustn't have
+//! side effects besides them, e.g. writing files.
+
+use futures::future::{self, Future, Loop};
+use futures::sync::oneshot;
+use futures_cpupool::CpuPool;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{Cursor, Read};
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use url::Url;
+use super::errors::*;
+use super::issues::WatcherReport;
+use super::{http, ClientHandle, RedmineApi};
+
+/// This struct represents the entry point to the asynchronous api. It wraps the client of a
+/// [RedmineApi](../struct.RedmineApi.html), so rate limiter, observer, metadata headers,
+/// transport and caches of the client apply to asynchronous requests, too.
+///
+/// # Example
+///
+/// ```
+/// extern crate futures;
+/// extern crate redmine_api;
+///
+/// use futures::Future;
+/// use redmine_api::RedmineApi;
+/// use redmine_api::nonblocking::AsyncRedmineApi;
+///
+/// # fn main() {
+/// let redmine = RedmineApi::new(
+///     "http://www.redmine.org/".to_string(),
+///     "1234".to_string()
+/// );
+/// let redmine = AsyncRedmineApi::new(&redmine).unwrap();
+///
+/// let issue = redmine.run(|api| api.issues().show(1).execute());
+/// let project = redmine.run(|api| api.projects().show(1).execute());
+///
+/// let result = issue.join(project).wait();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AsyncRedmineApi {
+    client: ClientHandle,
+    pool: CpuPool,
+    runtime: Arc<Runtime>,
+}
+impl AsyncRedmineApi {
+    /// Creates a new instance sending the requests of `redmine` with a thread pool of one thread
+    /// per cpu running the closures.
+    ///
+    /// # Arguments
+    ///
+    /// * `redmine` - the api whose client is used
+    pub fn new(redmine: &RedmineApi) -> Result<AsyncRedmineApi> {
+        AsyncRedmineApi::with_pool(redmine, CpuPool::new_num_cpus())
+    }
+
+    /// Creates a new instance running the closures on an existing thread pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `redmine` - the api whose client is used
+    /// * `pool` - the thread pool closures are run on
+    pub fn with_pool(redmine: &RedmineApi, pool: CpuPool) -> Result<AsyncRedmineApi> {
+        let runtime = Runtime::new().chain_err(|| "Can't start runtime")?;
+
+        Ok(AsyncRedmineApi {
+            client: redmine.client.clone(),
+            pool: pool,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Sets the language redmine localizes names and error messages in for all closures run by
+    /// this instance. See
+    /// [RedmineApi::in_language](../struct.RedmineApi.html#method.in_language).
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - a string slice holding the language code, e.g. `en`
+    pub fn language(mut self, language: &str) -> Self {
+        self.client.scope.language = Some(language.to_string());
+        self
+    }
+
+    /// Runs `f` until all its requests have been answered and returns a future resolving to its
+    /// result. `f` is run once per request, see the [module documentation](index.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - a closure building and executing requests with the given api
+    pub fn run<F, T>(&self, f: F) -> Box<dyn Future<Item = T, Error = Error> + Send>
+    where
+        F: Fn(&RedmineApi) -> Result<T> + Send + Sync + 'static,
+        T: Send + 'static,
+    {
+        let f = Arc::new(f);
+        let client = self.client.clone();
+        let pool = self.pool.clone();
+
+        let work = future::loop_fn(Responses::new(), move |responses| {
+            let f = f.clone();
+            let client = client.clone();
+            pool.spawn_fn(move || round(&client, &*f, responses))
+                .and_then(|round| -> Box<dyn Future<Item = _, Error = Error> + Send> {
+                    match round {
+                        Round::Done(result) => Box::new(future::result(result.map(Loop::Break))),
+                        Round::Sent(mut responses, key, response) => {
+                            Box::new(response.then(move |result| {
+                                responses.record(key, result)?;
+                                Ok(Loop::Continue(responses))
+                            }))
+                        }
+                    }
+                })
+        });
+
+        // the runtime drives the requests, so the future can be waited for on any thread
+        let (sender, receiver) = oneshot::channel();
+        self.runtime.executor().spawn(work.then(move |result| {
+            let _ = sender.send(result);
+            Ok(())
+        }));
+
+        Box::new(receiver.then(|result| match result {
+            Ok(result) => result,
+            Err(_) => bail!("Runtime has been shut down"),
+        }))
+    }
+
+    /// Adds several users as watchers to an issue. Redmine only accepts one watcher per request,
+    /// so the requests are sent concurrently. A failing user doesn't stop the others; failures
+    /// are collected in the report.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_id` - an integer holding the issue id
+    /// * `user_ids` - a vector holding the user ids
+    pub fn add_watchers(
+        &self,
+        issue_id: u32,
+        user_ids: Vec<u32>,
+    ) -> Box<dyn Future<Item = WatcherReport, Error = Error> + Send> {
+        let additions = user_ids
+            .into_iter()
+            .map(|user_id| {
+                self.run(move |api| {
+                    let result = api.issues().add_watcher(issue_id, user_id).execute();
+                    Ok((user_id, result.map_err(|e| e.to_string())))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Box::new(future::join_all(additions).map(|results| {
+            let mut report = WatcherReport::default();
+            for (user_id, result) in results {
+                report.push(user_id, result);
+            }
+            report
+        }))
+    }
+
+    /// Adds every member of a group as watcher to an issue. The group is resolved to its members
+    /// first, then the members are added concurrently like in
+    /// [add_watchers](struct.AsyncRedmineApi.html#method.add_watchers). Requires an api key of
+    /// an administrator.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_id` - an integer holding the issue id
+    /// * `group_id` - an integer holding the group id
+    pub fn add_group_watchers(
+        &self,
+        issue_id: u32,
+        group_id: u32,
+    ) -> Box<dyn Future<Item = WatcherReport, Error = Error> + Send> {
+        let this = self.clone();
+        let members = self.run(move |api| api.groups().members(group_id));
+
+        Box::new(members.and_then(move |members| {
+            this.add_watchers(issue_id, members.iter().map(|m| m.id).collect())
+        }))
+    }
+}
+
+/// Outcome of running a closure once.
+enum Round<T> {
+    /// All requests of the closure have been answered.
+    Done(Result<T>),
+    /// A request without known response is being sent.
+    Sent(
+        Responses,
+        String,
+        Box<dyn Future<Item = http::Response, Error = Error> + Send>,
+    ),
+}
+
+/// Runs `f` with the responses received so far. If it sends a request without known response,
+/// the request is prepared by the client, i.e. gets its headers and waits for the rate limiter,
+/// and passed to the transport.
+///
+/// # Arguments
+///
+/// * `client` - the client of the executor
+/// * `f` - the closure
+/// * `responses` - the responses received so far
+fn round<F, T>(client: &ClientHandle, f: &F, responses: Responses) -> Result<Round<T>>
+where
+    F: Fn(&RedmineApi) -> Result<T>,
+{
+    let replay = Arc::new(Replay {
+        responses: responses,
+        state: Mutex::new(ReplayState::default()),
+    });
+    let mut replaying = client.clone();
+    replaying.scope.replay = Some(replay.clone());
+
+    let result = f(&RedmineApi::from_handle(replaying));
+
+    let pending = replay.state.lock().unwrap_or_else(|e| e.into_inner()).pending.take();
+    let responses = replay.responses.clone();
+    match pending {
+        None => Ok(Round::Done(result)),
+        Some(pending) => {
+            let (request, observation) = client.prepare(pending.method, pending.url, pending.body)?;
+            let response = client.transport.send_async(request).then(move |result| {
+                if let Some(observation) = observation {
+                    observation.finish(&result);
+                }
+                result
+            });
+
+            Ok(Round::Sent(responses, pending.key, Box::new(response)))
+        }
+    }
+}
+
+/// Response of a request as recorded by the executor. Errors are kept as message, so they can
+/// be returned again.
+type Recorded = ::std::result::Result<(u16, Option<String>, Arc<Vec<u8>>), String>;
+
+/// Responses received for the requests of a closure, by request. A request sent several times
+/// gets its responses in order.
+#[derive(Clone, Default)]
+struct Responses {
+    by_request: HashMap<String, Vec<Recorded>>,
+}
+impl Responses {
+    /// Creates an empty instance.
+    fn new() -> Self {
+        Responses::default()
+    }
+
+    /// Adds the outcome of a request, reading its body.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string identifying the request
+    /// * `result` - the response or the error
+    fn record(&mut self, key: String, result: Result<http::Response>) -> Result<()> {
+        let recorded = match result {
+            Ok(mut response) => {
+                let mut body = Vec::new();
+                response.body.read_to_end(&mut body)?;
+                Ok((response.status, response.location, Arc::new(body)))
+            }
+            Err(e) => Err(e.to_string()),
+        };
+        self.by_request.entry(key).or_default().push(recorded);
+
+        Ok(())
+    }
+}
+
+/// Answers the requests of a closure run by the executor from the responses received so far and
+/// holds back the first request without known response. Is only used internally.
+pub struct Replay {
+    responses: Responses,
+    state: Mutex<ReplayState>,
+}
+impl Replay {
+    /// Returns the recorded response of the request or an error if there is none yet. Should
+    /// not be called externally.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - the http method
+    /// * `url` - the absolute url of the request
+    /// * `body` - the body of the request or None
+    pub fn send(
+        &self,
+        method: http::Method,
+        url: Url,
+        body: Option<http::Body>,
+    ) -> Result<http::Response> {
+        let key = match body {
+            Some(http::Body::Json(ref bytes)) => {
+                format!("{:?} {} {}", method, url, String::from_utf8_lossy(bytes))
+            }
+            _ => format!("{:?} {}", method, url),
+        };
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let n = {
+            let sent = state.sent.entry(key.clone()).or_insert(0);
+            *sent += 1;
+            *sent - 1
+        };
+
+        match self.responses.by_request.get(&key).and_then(|r| r.get(n)) {
+            Some(&Ok((status, ref location, ref body))) => {
+                Ok(http::Response {
+                    status: status,
+                    location: location.clone(),
+                    body: Box::new(Cursor::new(body.to_vec())),
+                })
+            }
+            Some(&Err(ref message)) => bail!("{}", message),
+            None => {
+                // only the first request is sent; later ones may depend on its failure here
+                if state.pending.is_none() {
+                    state.pending = Some(Pending {
+                        key: key,
+                        method: method,
+                        url: url,
+                        body: body,
+                    });
+                }
+                bail!("Request is sent asynchronously")
+            }
+        }
+    }
+}
+impl fmt::Debug for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Replay")
+    }
+}
+
+/// Requests a closure has sent in the current run.
+#[derive(Default)]
+struct ReplayState {
+    /// Number of times each request has been sent.
+    sent: HashMap<String, usize>,
+    /// The first request without known response.
+    pending: Option<Pending>,
+}
+
+/// A request which is held back to be sent asynchronously.
+struct Pending {
+    key: String,
+    method: http::Method,
+    url: Url,
+    body: Option<http::Body>,
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::Future;
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+    use url::Url;
+    use super::super::errors::*;
+    use super::super::{http, RedmineApi};
+    use super::AsyncRedmineApi;
+
+    /// Responds to the issue and project requests below and counts the requests sent.
+    struct Canned {
+        sent: Arc<Mutex<Vec<String>>>,
+    }
+    impl http::Transport for Canned {
+        fn send(&self, request: http::Request) -> Result<http::Response> {
+            let url = Url::parse(&request.url).unwrap();
+            self.sent.lock().unwrap().push(url.path().to_string());
+            let (status, body) = match url.path() {
+                "/issues/1.json" => (200, ISSUE),
+                "/projects/2.json" => (200, PROJECT),
+                _ => (404, ""),
+            };
+
+            Ok(http::Response {
+                status: status,
+                location: None,
+                body: Box::new(Cursor::new(body.as_bytes().to_vec())),
+            })
+        }
+    }
+
+    const ISSUE: &str = r#"{"issue": {
+        "id": 1,
+        "project": {"id": 2, "name": "Redmine"},
+        "tracker": {"id": 1, "name": "Bug"},
+        "status": {"id": 1, "name": "New"},
+        "priority": {"id": 2, "name": "Normal"},
+        "author": {"id": 3, "name": "A"},
+        "subject": "Printer is broken",
+        "description": "",
+        "start_date": "2017-09-14",
+        "done_ratio": 0,
+        "created_on": "2017-09-14T08:00:00Z",
+        "updated_on": "2017-09-14T08:00:00Z"
+    }}"#;
+
+    const PROJECT: &str = r#"{"project": {
+        "id": 2,
+        "name": "Redmine",
+        "identifier": "redmine",
+        "description": "",
+        "status": 1,
+        "created_on": "2017-01-02T09:00:00Z",
+        "updated_on": "2017-09-16T10:11:12Z"
+    }}"#;
+
+    /// Returns an executor with the canned transport, the requests it sends and the requests
+    /// passed to the observer.
+    fn executor() -> (AsyncRedmineApi, Arc<Mutex<Vec<String>>>, Arc<Mutex<Vec<String>>>) {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let api = RedmineApi::with_transport(
+            "http://localhost".to_string(),
+            "1234".to_string(),
+            Box::new(Canned { sent: sent.clone() }),
+        );
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        let events = observed.clone();
+        api.observe(move |e| events.lock().unwrap().push(e.url.clone()));
+
+        (AsyncRedmineApi::new(&api).unwrap(), sent, observed)
+    }
+
+    #[test]
+    fn dependent_requests_are_sent_once() {
+        let (redmine, sent, observed) = executor();
+        let name = redmine
+            .run(|api| {
+                let issue = api.issues().show(1).execute()?;
+                let project = api.projects().show(issue.project.id).execute()?;
+                Ok(project.name)
+            })
+            .wait()
+            .unwrap();
+
+        assert_eq!(name, "Redmine");
+        assert_eq!(*sent.lock().unwrap(), vec!["/issues/1.json", "/projects/2.json"]);
+        assert_eq!(observed.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn error_responses_are_replayed() {
+        let (redmine, sent, _) = executor();
+        let result = redmine.run(|api| api.issues().show(5).execute()).wait();
+
+        assert!(result.is_err());
+        assert_eq!(*sent.lock().unwrap(), vec!["/issues/5.json"]);
+    }
+
+    #[test]
+    fn closures_run_concurrently() {
+        let (redmine, sent, _) = executor();
+        let issue = redmine.run(|api| api.issues().show(1).execute());
+        let project = redmine.run(|api| api.projects().show(2).execute());
+
+        let (issue, project) = issue.join(project).wait().unwrap();
+        assert_eq!(issue.id, 1);
+        assert_eq!(project.id, 2);
+        assert_eq!(sent.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,713 @@
+//! This module queues write operations locally, so applications working offline don't lose them.
+//! Writes pushed to an [Outbox](struct.Outbox.html) are persisted right away and sent to redmine
+//! application by [flush](struct.Outbox.html#method.flush) once it's reachable, in the order they
+//! have been pushed. Requires the `outbox` feature.
+//!
+//! Delivery is at least once: a write is removed from the outbox only after redmine application
+//! has accepted it, so a crash between sending and removing a write sends it again on the next
+//! flush. Writes are persisted in an append-only file by default; with the `outbox-sqlite`
+//! feature they can be kept in an embedded SQLite database by a
+//! [SqliteStore](struct.SqliteStore.html) instead. Other stores can be plugged in by implementing
+//! [OutboxStore](trait.OutboxStore.html).
+//!
+//! # Example
+//!
+//! ```
+//! use redmine_api::RedmineApi;
+//! use redmine_api::outbox::Outbox;
+//!
+//! # let path = std::env::temp_dir().join("redmine-outbox-doc");
+//! let outbox = Outbox::open(&path).unwrap();
+//! outbox.comment(1, "Checked on site, the printer works again").unwrap();
+//!
+//! // later, when the network is back
+//! let redmine = RedmineApi::new(
+//!     "http://www.redmine.org/".to_string(),
+//!     "1234".to_string()
+//! );
+//! if let Ok(report) = outbox.flush(&redmine) {
+//!     println!("{} writes delivered, {} pending", report.delivered.len(), report.pending);
+//! }
+//! # let _ = std::fs::remove_file(&path);
+//! ```
+
+extern crate serde_json;
+
+#[cfg(feature = "outbox-sqlite")]
+use rusqlite::Connection;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use super::errors::*;
+use super::{http, json_body, RedmineApi};
+
+/// Default number of retries of a write within a flush.
+const DEFAULT_RETRIES: u32 = 3;
+
+/// Default time waited before the first retry, which doubles with every further retry.
+const DEFAULT_BACKOFF_MS: u64 = 500;
+
+/// Http method of a write.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WriteMethod {
+    Post,
+    Put,
+    Delete,
+}
+
+/// A write operation as sent to redmine application.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PendingWrite {
+    pub method: WriteMethod,
+    /// The api endpoint, e.g. `/issues/1.json`.
+    pub path: String,
+    /// The json body, e.g. `{"issue": {"notes": "Hello"}}`.
+    pub body: Option<serde_json::Value>,
+}
+impl PendingWrite {
+    /// Creates a new instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - the http method
+    /// * `path` - a string holding the api endpoint, e.g. `/issues/1.json`
+    /// * `body` - the json body or `None`
+    pub fn new(method: WriteMethod, path: String, body: Option<serde_json::Value>) -> Self {
+        PendingWrite {
+            method: method,
+            path: path,
+            body: body,
+        }
+    }
+
+    /// Creates a write adding a comment to an issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_id` - an integer holding the issue id
+    /// * `notes` - a string slice holding the comment
+    pub fn comment(issue_id: u32, notes: &str) -> Self {
+        let mut issue = serde_json::Map::new();
+        issue.insert("notes".to_string(), notes.into());
+        let mut body = serde_json::Map::new();
+        body.insert("issue".to_string(), issue.into());
+
+        PendingWrite::new(
+            WriteMethod::Put,
+            format!("/issues/{}.json", issue_id),
+            Some(body.into()),
+        )
+    }
+}
+
+/// A write queued in an outbox.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutboxEntry {
+    /// Position of the write in the outbox. Writes are sent in ascending order.
+    pub id: u64,
+    pub write: PendingWrite,
+}
+
+/// Persists the entries of an outbox. Every method has to be durable when it returns, i.e. a
+/// pushed entry has to survive a crash.
+pub trait OutboxStore {
+    /// Persists a new entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - the entry to persist
+    fn push(&mut self, entry: &OutboxEntry) -> Result<()>;
+
+    /// Removes an entry which has been delivered or rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the id of the entry
+    fn remove(&mut self, id: u64) -> Result<()>;
+
+    /// Returns all persisted entries ordered by id.
+    fn entries(&self) -> Result<Vec<OutboxEntry>>;
+}
+
+/// Store keeping the entries in an append-only file of json lines. The file is truncated once
+/// all entries have been removed.
+#[derive(Debug)]
+pub struct FileStore {
+    path: PathBuf,
+    file: File,
+    entries: BTreeMap<u64, OutboxEntry>,
+}
+impl FileStore {
+    /// Opens the store at `path`, which is created if it doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the file
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let (entries, complete) = read_records(&path)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .chain_err(|| format!("Can't open {}", path.display()))?;
+
+        // terminate a truncated line, so it doesn't swallow the next record
+        if !complete {
+            writeln!(file)?;
+        }
+
+        Ok(FileStore {
+            path: path,
+            file: file,
+            entries: entries,
+        })
+    }
+
+    /// Appends a record to the file and waits until it's written to disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - the record to append
+    fn append(&mut self, record: &Record) -> Result<()> {
+        let line = serde_json::to_string(record).chain_err(|| "Can't serialize json")?;
+        writeln!(self.file, "{}", line).chain_err(
+            || format!("Can't write {}", self.path.display()),
+        )?;
+        self.file.sync_data()?;
+
+        Ok(())
+    }
+}
+impl OutboxStore for FileStore {
+    fn push(&mut self, entry: &OutboxEntry) -> Result<()> {
+        self.append(&Record::Queued(entry.clone()))?;
+        self.entries.insert(entry.id, entry.clone());
+
+        Ok(())
+    }
+
+    fn remove(&mut self, id: u64) -> Result<()> {
+        if self.entries.remove(&id).is_none() {
+            return Ok(());
+        }
+
+        if self.entries.is_empty() {
+            self.file.set_len(0)?;
+            self.file.sync_data()?;
+            Ok(())
+        } else {
+            self.append(&Record::Done(id))
+        }
+    }
+
+    fn entries(&self) -> Result<Vec<OutboxEntry>> {
+        Ok(self.entries.values().cloned().collect())
+    }
+}
+
+/// Statements creating the schema of a [SqliteStore](struct.SqliteStore.html), if it doesn't
+/// exist yet.
+#[cfg(feature = "outbox-sqlite")]
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS outbox (
+    id INTEGER PRIMARY KEY,
+    write TEXT NOT NULL
+)";
+
+/// Store keeping the entries in a SQLite database. Unlike a [FileStore](struct.FileStore.html),
+/// removed entries don't take up space until the outbox is empty. Requires the `outbox-sqlite`
+/// feature.
+///
+/// # Example
+///
+/// ```
+/// use redmine_api::outbox::{Outbox, SqliteStore};
+///
+/// # let path = std::env::temp_dir().join("redmine-outbox-sqlite-doc");
+/// # let _ = std::fs::remove_file(&path);
+/// let outbox = Outbox::with_store(Box::new(SqliteStore::open(&path).unwrap()));
+/// outbox.comment(1, "Checked on site, the printer works again").unwrap();
+///
+/// // the write survives a restart
+/// let outbox = Outbox::with_store(Box::new(SqliteStore::open(&path).unwrap()));
+/// assert_eq!(outbox.pending().unwrap().len(), 1);
+/// # let _ = std::fs::remove_file(&path);
+/// ```
+#[cfg(feature = "outbox-sqlite")]
+pub struct SqliteStore {
+    conn: Connection,
+}
+#[cfg(feature = "outbox-sqlite")]
+impl SqliteStore {
+    /// Opens the database at `path`, creating it if it doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the database file
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let conn = Connection::open(path).chain_err(|| "Can't open outbox")?;
+        SqliteStore::init(conn)
+    }
+
+    /// Opens a database which is kept in memory and dropped together with the store, e.g. for
+    /// tests.
+    pub fn open_in_memory() -> Result<Self> {
+        let conn = Connection::open_in_memory().chain_err(|| "Can't open outbox")?;
+        SqliteStore::init(conn)
+    }
+
+    /// Creates the schema if needed.
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA).chain_err(|| "Can't create outbox")?;
+
+        Ok(SqliteStore { conn: conn })
+    }
+}
+#[cfg(feature = "outbox-sqlite")]
+impl OutboxStore for SqliteStore {
+    fn push(&mut self, entry: &OutboxEntry) -> Result<()> {
+        let write = serde_json::to_string(&entry.write).chain_err(|| "Can't serialize json")?;
+        self.conn
+            .execute(
+                "INSERT INTO outbox (id, write) VALUES (?1, ?2)",
+                params![entry.id as i64, write],
+            )
+            .chain_err(|| "Can't write outbox")?;
+
+        Ok(())
+    }
+
+    fn remove(&mut self, id: u64) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM outbox WHERE id = ?1", params![id as i64])
+            .chain_err(|| "Can't write outbox")?;
+
+        Ok(())
+    }
+
+    fn entries(&self) -> Result<Vec<OutboxEntry>> {
+        let mut stmt = self.conn
+            .prepare("SELECT id, write FROM outbox ORDER BY id")
+            .chain_err(|| "Can't read outbox")?;
+        let rows = stmt.query_map(params![], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        }).chain_err(|| "Can't read outbox")?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let (id, write) = row.chain_err(|| "Can't read outbox")?;
+            entries.push(OutboxEntry {
+                id: id as u64,
+                write: serde_json::from_str(&write).chain_err(|| "Can't parse json")?,
+            });
+        }
+
+        Ok(entries)
+    }
+}
+
+/// A line of the file of a [FileStore](struct.FileStore.html).
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+enum Record {
+    Queued(OutboxEntry),
+    Done(u64),
+}
+
+/// Reads the entries which haven't been removed yet. A truncated last line, e.g. of a write
+/// interrupted by a crash, is ignored. Returns whether the file ends with a complete line, too.
+///
+/// # Arguments
+///
+/// * `path` - the path of the file, which may not exist yet
+fn read_records(path: &Path) -> Result<(BTreeMap<u64, OutboxEntry>, bool)> {
+    let mut content = String::new();
+    match File::open(path) {
+        Ok(mut file) => {
+            file.read_to_string(&mut content).chain_err(
+                || format!("Can't read {}", path.display()),
+            )?;
+        }
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e).chain_err(|| format!("Can't open {}", path.display())),
+    };
+
+    let mut entries = BTreeMap::new();
+    for line in content.lines() {
+        match serde_json::from_str(line) {
+            Ok(Record::Queued(entry)) => {
+                entries.insert(entry.id, entry);
+            }
+            Ok(Record::Done(id)) => {
+                entries.remove(&id);
+            }
+            Err(_) => {}
+        }
+    }
+
+    Ok((entries, content.is_empty() || content.ends_with('\n')))
+}
+
+/// Queue of writes which are sent to redmine application when it's reachable.
+pub struct Outbox {
+    store: RefCell<Box<dyn OutboxStore>>,
+    retries: u32,
+    backoff: Duration,
+}
+impl Outbox {
+    /// Opens an outbox persisting its writes in the file at `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the file, which is created if it doesn't exist
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(Outbox::with_store(Box::new(FileStore::open(path)?)))
+    }
+
+    /// Creates an outbox persisting its writes in `store`.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - the store holding the entries
+    pub fn with_store(store: Box<dyn OutboxStore>) -> Self {
+        Outbox {
+            store: RefCell::new(store),
+            retries: DEFAULT_RETRIES,
+            backoff: Duration::from_millis(DEFAULT_BACKOFF_MS),
+        }
+    }
+
+    /// Sets how often a write failing because redmine application isn't reachable is retried
+    /// within a flush. Defaults to 3.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the number of retries
+    pub fn retries(mut self, n: u32) -> Self {
+        self.retries = n;
+        self
+    }
+
+    /// Sets the time waited before the first retry, which doubles with every further retry.
+    /// Defaults to half a second.
+    ///
+    /// # Arguments
+    ///
+    /// * `d` - the time to wait
+    pub fn backoff(mut self, d: Duration) -> Self {
+        self.backoff = d;
+        self
+    }
+
+    /// Persists a write and returns the id of its entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `write` - the write to queue
+    pub fn push(&self, write: PendingWrite) -> Result<u64> {
+        let mut store = self.store.borrow_mut();
+        let id = store.entries()?.last().map_or(1, |e| e.id + 1);
+        store.push(&OutboxEntry {
+            id: id,
+            write: write,
+        })?;
+
+        Ok(id)
+    }
+
+    /// Persists a comment on an issue and returns the id of its entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_id` - an integer holding the issue id
+    /// * `notes` - a string slice holding the comment
+    pub fn comment(&self, issue_id: u32, notes: &str) -> Result<u64> {
+        self.push(PendingWrite::comment(issue_id, notes))
+    }
+
+    /// Returns the writes which haven't been delivered yet.
+    pub fn pending(&self) -> Result<Vec<OutboxEntry>> {
+        self.store.borrow().entries()
+    }
+
+    /// Sends the queued writes to redmine application in order. Writes which redmine
+    /// application refuses (e.g. because the issue has been deleted or the data is invalid) are
+    /// removed and listed in the report, since sending them again wouldn't help. If redmine
+    /// application can't be reached even after retrying, flushing stops and the write and the
+    /// ones after it stay queued. The same happens if the api key is rejected or the first write
+    /// isn't found, which hints at a wrong host rather than a deleted issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `redmine` - the api the writes are sent with
+    pub fn flush(&self, redmine: &RedmineApi) -> Result<FlushReport> {
+        let entries = self.pending()?;
+        let mut report = FlushReport::default();
+
+        for (i, entry) in entries.iter().enumerate() {
+            match self.deliver(redmine, &entry.write) {
+                Delivery::Accepted => report.delivered.push(entry.id),
+                Delivery::Refused(404, ref message) if i == 0 => {
+                    report.pending = entries.len();
+                    report.error = Some(message.clone());
+                    break;
+                }
+                Delivery::Refused(_, message) => report.rejected.push((entry.clone(), message)),
+                Delivery::Halted(message) | Delivery::Unreachable(message) => {
+                    report.pending = entries.len() - i;
+                    report.error = Some(message);
+                    break;
+                }
+            }
+            self.store.borrow_mut().remove(entry.id)?;
+        }
+
+        Ok(report)
+    }
+
+    /// Sends a single write, retrying it as long as redmine application isn't reachable.
+    ///
+    /// # Arguments
+    ///
+    /// * `redmine` - the api the write is sent with
+    /// * `write` - the write to send
+    fn deliver(&self, redmine: &RedmineApi, write: &PendingWrite) -> Delivery {
+        let mut delay = self.backoff;
+        let mut attempt = 0;
+        loop {
+            let delivery = send(redmine, write);
+            match delivery {
+                Delivery::Unreachable(_) if attempt < self.retries => {
+                    thread::sleep(delay);
+                    delay *= 2;
+                    attempt += 1;
+                }
+                _ => return delivery,
+            }
+        }
+    }
+}
+
+/// Holds the result of a flush.
+#[derive(Debug, Default, Clone)]
+pub struct FlushReport {
+    /// Ids of the entries which have been delivered.
+    pub delivered: Vec<u64>,
+    /// Entries redmine application has refused together with the error message. They have been
+    /// removed from the outbox.
+    pub rejected: Vec<(OutboxEntry, String)>,
+    /// Number of entries left in the outbox.
+    pub pending: usize,
+    /// Message of the error which stopped the flush, if any.
+    pub error: Option<String>,
+}
+
+/// Outcome of sending a write.
+enum Delivery {
+    Accepted,
+    /// The write itself is refused, together with the status code.
+    Refused(u16, String),
+    /// Redmine application can't be reached for now, so the write is retried.
+    Unreachable(String),
+    /// No write can be delivered with the current configuration, e.g. due to an invalid api key.
+    Halted(String),
+}
+
+/// Sends a write and classifies the outcome. Requests which fail to be sent, time out or hit
+/// an overloaded or failing server are worth retrying. Only responses telling that the write
+/// itself is invalid or its entity is gone are final; any other error response, e.g. a rejected
+/// api key, halts the flush.
+///
+/// # Arguments
+///
+/// * `redmine` - the api the write is sent with
+/// * `write` - the write to send
+fn send(redmine: &RedmineApi, write: &PendingWrite) -> Delivery {
+    let request = || -> Result<http::Response> {
+        let method = match write.method {
+            WriteMethod::Post => http::Method::Post,
+            WriteMethod::Put => http::Method::Put,
+            WriteMethod::Delete => http::Method::Delete,
+        };
+        let body = match write.body {
+            Some(ref body) => Some(json_body(body)?),
+            None => None,
+        };
+
+        redmine.client.send(method, redmine.client.get_base_url(&write.path)?, body)
+    };
+
+    match request() {
+        Ok(ref response) if response.is_success() => Delivery::Accepted,
+        Ok(mut response) => {
+            let body = response.text().unwrap_or_default();
+            let message = format!("Error: {}, {}", response.status, body);
+            match response.status {
+                408 | 429 | 500..=599 => Delivery::Unreachable(message),
+                404 | 410 | 422 => Delivery::Refused(response.status, message),
+                _ => Delivery::Halted(message),
+            }
+        }
+        Err(e) => Delivery::Unreachable(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, OpenOptions};
+    use std::io::{Cursor, Write};
+    use std::path::PathBuf;
+    use std::time::Duration;
+    use url::Url;
+    use super::super::errors::*;
+    use super::super::{http, RedmineApi};
+    use super::{FileStore, Outbox, OutboxStore, PendingWrite};
+
+    /// Returns the path of a file in the temp directory, which doesn't exist yet.
+    fn path(name: &str) -> PathBuf {
+        let path = ::std::env::temp_dir().join(format!("redmine-outbox-test-{}", name));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    /// Responds to `/issues/{n}.json` with status n, or 204 for issues below 200.
+    struct Status;
+    impl http::Transport for Status {
+        fn send(&self, request: http::Request) -> Result<http::Response> {
+            let url = Url::parse(&request.url).unwrap();
+            let id: u16 = url.path()
+                .trim_start_matches("/issues/")
+                .trim_end_matches(".json")
+                .parse()
+                .unwrap();
+
+            Ok(http::Response {
+                status: if id < 200 { 204 } else { id },
+                location: None,
+                body: Box::new(Cursor::new(Vec::new())),
+            })
+        }
+    }
+
+    /// Queues a comment on each of the issues and flushes the outbox without waiting.
+    fn flush(name: &str, issue_ids: &[u32]) -> (super::FlushReport, Vec<u64>) {
+        let path = path(name);
+        let outbox = Outbox::open(&path).unwrap().backoff(Duration::from_millis(0));
+        for &id in issue_ids {
+            outbox.comment(id, "Hello").unwrap();
+        }
+
+        let api = RedmineApi::with_transport(
+            "http://localhost".to_string(),
+            "1234".to_string(),
+            Box::new(Status),
+        );
+        let report = outbox.flush(&api).unwrap();
+        let pending = outbox.pending().unwrap().iter().map(|e| e.id).collect();
+        let _ = fs::remove_file(&path);
+
+        (report, pending)
+    }
+
+    #[test]
+    fn file_store_replays_records() {
+        let path = path("replay");
+        {
+            let outbox = Outbox::open(&path).unwrap();
+            for id in 1..4 {
+                outbox.comment(id, "Hello").unwrap();
+            }
+        }
+        {
+            let mut store = FileStore::open(&path).unwrap();
+            store.remove(2).unwrap();
+        }
+
+        let store = FileStore::open(&path).unwrap();
+        let entries = store.entries().unwrap();
+        assert_eq!(entries.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(entries[1].write, PendingWrite::comment(3, "Hello"));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn file_store_is_truncated_when_empty() {
+        let path = path("truncate");
+        let mut store = FileStore::open(&path).unwrap();
+        store.push(&super::OutboxEntry { id: 1, write: PendingWrite::comment(1, "Hello") })
+            .unwrap();
+        store.remove(1).unwrap();
+
+        assert_eq!(fs::metadata(&path).unwrap().len(), 0);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn file_store_recovers_from_truncated_line() {
+        let path = path("recover");
+        {
+            let outbox = Outbox::open(&path).unwrap();
+            outbox.comment(1, "Hello").unwrap();
+        }
+        // a crash while appending the second entry
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(br#"{"queued":{"id":2,"write":{"method":"put","#)
+            .unwrap();
+
+        let outbox = Outbox::open(&path).unwrap();
+        assert_eq!(outbox.pending().unwrap().len(), 1);
+        outbox.comment(3, "World").unwrap();
+
+        let store = FileStore::open(&path).unwrap();
+        let entries = store.entries().unwrap();
+        assert_eq!(entries.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(entries[1].write, PendingWrite::comment(3, "World"));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn flush_removes_delivered_and_refused_writes() {
+        let (report, pending) = flush("refused", &[1, 404, 422, 410, 2]);
+        assert_eq!(report.delivered, vec![1, 5]);
+        let rejected: Vec<u64> = report.rejected.iter().map(|r| r.0.id).collect();
+        assert_eq!(rejected, vec![2, 3, 4]);
+        assert_eq!(report.pending, 0);
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn flush_keeps_writes_on_rejected_api_key() {
+        for &status in &[401, 403] {
+            let (report, pending) = flush("apikey", &[1, status, 2]);
+            assert_eq!(report.delivered, vec![1]);
+            assert!(report.rejected.is_empty());
+            assert_eq!(report.pending, 2);
+            assert!(report.error.is_some());
+            assert_eq!(pending, vec![2, 3]);
+        }
+    }
+
+    #[test]
+    fn flush_keeps_writes_if_first_is_not_found() {
+        let (report, pending) = flush("host", &[404, 1]);
+        assert!(report.delivered.is_empty());
+        assert!(report.rejected.is_empty());
+        assert_eq!(report.pending, 2);
+        assert_eq!(pending, vec![1, 2]);
+    }
+
+    #[test]
+    fn flush_keeps_writes_if_unreachable() {
+        let (report, pending) = flush("unreachable", &[503, 1]);
+        assert_eq!(report.pending, 2);
+        assert_eq!(pending, vec![1, 2]);
+    }
+}
@@ -4,17 +4,27 @@
 extern crate serde_json;
 
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::fmt;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+#[cfg(feature = "activity")]
+use super::activity::ActivityFeed;
+#[cfg(feature = "issues")]
+use super::digest::Digest;
+use super::entity::{Creatable, Deletable, Listable, Page, Resource, Showable};
 use super::errors::*;
-use super::RedmineClient;
+#[cfg(feature = "time_entries")]
+use super::time_entries;
+use super::{create_from_json, format_table, ClientHandle, CustomField, NamedObject, RedmineApi,
+            Tristate};
 
 /// This struct exposes all methods provided by the redmine projects api.
 pub struct Api {
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
 }
 impl Api {
     /// Creates a new instance. Should not be called externally.
-    pub fn new(client: Rc<RedmineClient>) -> Api {
+    pub fn new(client: ClientHandle) -> Api {
         Api { client: client }
     }
 
@@ -34,7 +44,7 @@ impl Api {
     /// let result = redmine.projects().list().execute();
     /// ```
     pub fn list(&self) -> ProjectListExecutor {
-        ProjectListExecutor::new(Rc::clone(&self.client))
+        ProjectListExecutor::new(self.client.clone())
     }
 
     /// Returns a single project by id.
@@ -57,7 +67,7 @@ impl Api {
     /// ```
     pub fn show(&self, id: u32) -> ProjectShow {
         ProjectShow {
-            client: Rc::clone(&self.client),
+            client: self.client.clone(),
             show_id: id,
             ..Default::default()
         }
@@ -86,7 +96,7 @@ impl Api {
     ///     .execute();
     /// ```
     pub fn create<'a>(&self, name: &'a str, identifier: &'a str) -> ProjectBuilder<'a> {
-        ProjectBuilder::for_create(Rc::clone(&self.client), name, identifier)
+        ProjectBuilder::for_create(self.client.clone(), name, identifier)
     }
 
     /// Returns an ProjectBuilder and ultimately updates an existing prpoject in the redmine
@@ -111,7 +121,7 @@ impl Api {
     ///     .execute();
     /// ```
     pub fn update(&self, id: u32) -> ProjectBuilder {
-        ProjectBuilder::for_update(Rc::clone(&self.client), id)
+        ProjectBuilder::for_update(self.client.clone(), id)
     }
 
     /// Returns ProjectDelete struct which offers an `execute` function which deletes the project
@@ -135,33 +145,191 @@ impl Api {
     /// ```
     pub fn delete(&self, id: u32) -> ProjectDelete {
         ProjectDelete {
-            client: Rc::clone(&self.client),
+            client: self.client.clone(),
             delete_id: id,
         }
     }
+
+    /// Returns an ActivityFeed (builder pattern) which ultimately reads the activity of a project
+    /// (issue edits, wiki edits, commits, etc.) from its Atom feed. Requires the `activity`
+    /// feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the project id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.projects().activity(1).from("2017-09-30").execute();
+    /// ```
+    #[cfg(feature = "activity")]
+    pub fn activity(&self, id: u32) -> ActivityFeed {
+        ActivityFeed::new(self.client.clone(), id)
+    }
+
+    /// Returns a Digest (builder pattern) which ultimately collects created and closed issues,
+    /// priority changes and top time loggers of a project over the last week. The result can be
+    /// rendered as Markdown or HTML. Requires the `issues` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the project id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::digest::DigestFormat;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(digest) = redmine.projects().digest(1).top_loggers(3).execute() {
+    ///     println!("{}", digest.render(DigestFormat::Markdown));
+    /// }
+    /// ```
+    #[cfg(feature = "issues")]
+    pub fn digest(&self, id: u32) -> Digest {
+        Digest::new(self.client.clone(), id)
+    }
+
+    /// Returns ProjectBudget struct which offers an `execute` function which compares the budget
+    /// of a project with the hours spent on it. Requires the `time_entries` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the project id
+    /// * `source` - a [BudgetSource](enum.BudgetSource.html) defining where the budget is read
+    ///   from
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::projects::BudgetSource;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.projects().budget_status(1, BudgetSource::CustomField(5))
+    ///     .warn_at(0.8)
+    ///     .warn_at(1.0)
+    ///     .execute();
+    /// ```
+    #[cfg(feature = "time_entries")]
+    pub fn budget_status(&self, id: u32, source: BudgetSource) -> ProjectBudget {
+        ProjectBudget {
+            client: self.client.clone(),
+            project_id: id,
+            source: source,
+            thresholds: Vec::new(),
+        }
+    }
+}
+impl Resource for Api {
+    type Entity = Project;
+
+    fn name(&self) -> &'static str {
+        "projects"
+    }
+}
+impl Listable for Api {
+    fn list_page(&self, offset: u32, limit: u32) -> Result<Page<Project>> {
+        let mut params: HashMap<&str, String> = HashMap::new();
+        params.insert("offset", offset.to_string());
+        params.insert("limit", limit.to_string());
+
+        let result = self.client.get("/projects.json", &params)?;
+        let list: ProjectList = serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+
+        Ok(Page {
+            total_count: list.total_count,
+            items: list.projects,
+        })
+    }
+}
+impl Showable for Api {
+    fn fetch(&self, id: u32) -> Result<Project> {
+        self.show(id).execute()
+    }
+}
+impl Creatable for Api {
+    fn create_from_json(&self, fields: &serde_json::Value) -> Result<u32> {
+        create_from_json(&self.client, "/projects.json", "project", fields)
+    }
+}
+impl Deletable for Api {
+    fn remove(&self, id: u32) -> Result<()> {
+        self.delete(id).execute()
+    }
 }
 
 /// Helper struct to provide a unified interface for all project api methods.
 #[derive(Default)]
 pub struct ProjectListExecutor {
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
+    status: Option<ProjectStatus>,
 }
 impl ProjectListExecutor {
     /// Creates a new instance.
     ///
     /// # Arguments
     ///
-    /// * `client` - a Rc boxed RedmineClient
-    fn new(client: Rc<RedmineClient>) -> Self {
+    /// * `client` - a handle to the RedmineClient
+    fn new(client: ClientHandle) -> Self {
         Self {
             client: client,
+            status: None,
         }
     }
 
+    /// Filters projects by status. Redmine lists active and closed projects by default; archived
+    /// projects are only listed to administrators when filtering for them (redmine 5.0 or
+    /// newer).
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - a [ProjectStatus](enum.ProjectStatus.html)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::projects::ProjectStatus;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.projects().list().status(ProjectStatus::Archived).execute();
+    /// ```
+    pub fn status(&mut self, status: ProjectStatus) -> &mut Self {
+        self.status = Some(status);
+        self
+    }
+
     /// Performs request to redmine application and returns a list of projects (accessible by the
     /// user)
     pub fn execute(&self) -> Result<ProjectList> {
-        let result = self.client.get("/projects.json", &HashMap::new())?;
+        let mut params: HashMap<&str, String> = HashMap::new();
+        if let Some(status) = self.status {
+            params.insert("status", status.code().to_string());
+        }
+
+        let result = self.client.get("/projects.json", &params)?;
 
         serde_json::from_str(&result).chain_err(|| "Can't parse json")
     }
@@ -172,6 +340,43 @@ impl ProjectListExecutor {
 #[derive(Deserialize, Debug)]
 pub struct ProjectList {
     projects: Vec<Project>,
+    total_count: Option<u32>,
+}
+impl ProjectList {
+    /// Formats the projects as plain text table, e.g. for command line tools.
+    pub fn table(&self) -> String {
+        let rows: Vec<Vec<String>> = self.projects
+            .iter()
+            .map(|p| vec![p.id.to_string(), p.identifier.clone(), p.name.clone()])
+            .collect();
+
+        format_table(&["ID", "Identifier", "Name"], &rows)
+    }
+
+    /// Returns the active projects of the list.
+    pub fn active(&self) -> Vec<&Project> {
+        self.with_status(ProjectStatus::Active)
+    }
+
+    /// Returns the closed projects of the list, which are read-only.
+    pub fn closed(&self) -> Vec<&Project> {
+        self.with_status(ProjectStatus::Closed)
+    }
+
+    /// Returns the archived projects of the list. Archived projects are only listed when
+    /// filtering for them, see [status](struct.ProjectListExecutor.html#method.status).
+    pub fn archived(&self) -> Vec<&Project> {
+        self.with_status(ProjectStatus::Archived)
+    }
+
+    /// Returns the projects of the list with the given status.
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - a [ProjectStatus](enum.ProjectStatus.html)
+    pub fn with_status(&self, status: ProjectStatus) -> Vec<&Project> {
+        self.projects.iter().filter(|p| p.status == status).collect()
+    }
 }
 impl IntoIterator for ProjectList {
     type Item = Project;
@@ -182,11 +387,78 @@ impl IntoIterator for ProjectList {
     }
 }
 
+/// Enumeration of the states of a project.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProjectStatus {
+    #[default]
+    Active,
+    /// The project is read-only.
+    Closed,
+    /// The project is hidden from everyone but administrators.
+    Archived,
+    /// The project is going to be deleted by a background job (redmine 5.1 or newer).
+    ScheduledForDeletion,
+    /// A status unknown to this library.
+    Other(u32),
+}
+impl ProjectStatus {
+    /// Returns the code redmine uses for the status, e.g. `9` for archived projects.
+    pub fn code(&self) -> u32 {
+        match *self {
+            ProjectStatus::Active => 1,
+            ProjectStatus::Closed => 5,
+            ProjectStatus::Archived => 9,
+            ProjectStatus::ScheduledForDeletion => 10,
+            ProjectStatus::Other(code) => code,
+        }
+    }
+
+    /// Creates the status from the code redmine uses.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - an integer holding the code, e.g. `5`
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => ProjectStatus::Active,
+            5 => ProjectStatus::Closed,
+            9 => ProjectStatus::Archived,
+            10 => ProjectStatus::ScheduledForDeletion,
+            _ => ProjectStatus::Other(code),
+        }
+    }
+}
+// e.g. `archived`
+impl fmt::Display for ProjectStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ProjectStatus::Active => write!(f, "active"),
+            ProjectStatus::Closed => write!(f, "closed"),
+            ProjectStatus::Archived => write!(f, "archived"),
+            ProjectStatus::ScheduledForDeletion => write!(f, "scheduled for deletion"),
+            ProjectStatus::Other(code) => write!(f, "status {}", code),
+        }
+    }
+}
+impl Serialize for ProjectStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.code())
+    }
+}
+impl<'de> Deserialize<'de> for ProjectStatus {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ProjectStatus::from_code(u32::deserialize(deserializer)?))
+    }
+}
+
 /// Wrapper struct for deserialization of a single Project pulled from redmine application.
 #[derive(Deserialize, Debug, Default)]
 pub struct ProjectShow {
     #[serde(skip_deserializing)]
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     #[serde(skip_deserializing)]
     show_id: u32,
 
@@ -211,36 +483,188 @@ impl ProjectShow {
 
 /// Helper struct to provide a unified interface for all project api methods.
 pub struct ProjectDelete {
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     delete_id: u32,
 }
 impl ProjectDelete {
-    /// Performs request to redmine application and deletes a project.
-    pub fn execute(&self) -> Result<bool> {
+    /// Performs request to redmine application and deletes a project. Fails unless deletions
+    /// have been allowed, see
+    /// [RedmineApi::allow_destructive](../struct.RedmineApi.html#method.allow_destructive).
+    pub fn execute(&self) -> Result<()> {
         self.client.delete(
             &(format!("/projects/{}.json", self.delete_id)),
         )
     }
 }
 
+/// Defines where the budget (in hours) of a project is read from.
+#[derive(Debug, Clone, Copy)]
+pub enum BudgetSource {
+    /// A project custom field, specified by its id, holding the budget in hours.
+    CustomField(u32),
+    /// The sum of the estimated hours of all versions of the project.
+    VersionEstimates,
+}
+
+/// Helper struct to provide a unified interface for all project api methods.
+#[cfg(feature = "time_entries")]
+pub struct ProjectBudget {
+    client: ClientHandle,
+    project_id: u32,
+    source: BudgetSource,
+    thresholds: Vec<f32>,
+}
+#[cfg(feature = "time_entries")]
+impl ProjectBudget {
+    /// Adds a threshold which results in a warning once crossed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ratio` - a floating point number holding the ratio of spent to budget hours, e.g. 0.8
+    pub fn warn_at(mut self, ratio: f32) -> Self {
+        self.thresholds.push(ratio);
+        self
+    }
+
+    /// Performs requests to redmine application and returns the budget status of the project.
+    pub fn execute(&self) -> Result<BudgetStatus> {
+        let budget = match self.source {
+            BudgetSource::CustomField(id) => self.custom_field_budget(id)?,
+            BudgetSource::VersionEstimates => self.version_budget()?,
+        };
+
+        let spent = time_entries::Api::new(self.client.clone())
+            .list()
+            .project_id(self.project_id)
+            .total_hours()?;
+
+        let mut warnings = self.thresholds
+            .iter()
+            .filter(|t| budget > 0.0 && spent / budget >= **t)
+            .map(|t| BudgetWarning { threshold: *t })
+            .collect::<Vec<BudgetWarning>>();
+        if budget <= 0.0 && spent > 0.0 {
+            warnings.push(BudgetWarning { threshold: 0.0 });
+        }
+
+        Ok(BudgetStatus {
+            budget: budget,
+            spent: spent,
+            remaining: budget - spent,
+            warnings: warnings,
+        })
+    }
+
+    /// Reads the budget from a project custom field.
+    fn custom_field_budget(&self, id: u32) -> Result<f32> {
+        let result = self.client.get(
+            &(format!("/projects/{}.json", self.project_id)),
+            &HashMap::new(),
+        )?;
+        let project: Project = serde_json::from_str::<ProjectShow>(&result)
+            .chain_err(|| "Can't parse json")?
+            .into();
+
+        let value = project
+            .custom_fields
+            .iter()
+            .find(|cf| cf.id == id)
+            .and_then(|cf| cf.as_str())
+            .unwrap_or("");
+
+        if value.is_empty() {
+            return Ok(0.0);
+        }
+
+        value.trim().parse::<f32>().chain_err(|| {
+            format!("Can't parse budget from custom field {}: {}", id, value)
+        })
+    }
+
+    /// Sums up the estimated hours of all versions of the project.
+    fn version_budget(&self) -> Result<f32> {
+        #[derive(Deserialize)]
+        struct Version {
+            estimated_hours: Option<f32>,
+        }
+        #[derive(Deserialize)]
+        struct Wrapper {
+            versions: Vec<Version>,
+        }
+
+        let result = self.client.get(
+            &(format!("/projects/{}/versions.json", self.project_id)),
+            &HashMap::new(),
+        )?;
+        let wrapper: Wrapper = serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+
+        Ok(
+            wrapper
+                .versions
+                .iter()
+                .filter_map(|v| v.estimated_hours)
+                .sum(),
+        )
+    }
+}
+
+/// Holds the result of a budget check of a project. All values are given in hours.
+#[derive(Debug, Default)]
+pub struct BudgetStatus {
+    pub budget: f32,
+    pub spent: f32,
+    pub remaining: f32,
+    pub warnings: Vec<BudgetWarning>,
+}
+
+/// Is issued when the ratio of spent to budget hours has reached a threshold. A threshold of 0
+/// means time has been spent although the project has no budget at all.
+#[derive(Debug, Clone, Copy)]
+pub struct BudgetWarning {
+    pub threshold: f32,
+}
+
 /// Represents a project as pulled from redmine application.
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Project {
     pub id: u32,
     pub name: String,
     pub identifier: String,
     pub description: Option<String>,
     pub homepage: Option<String>,
-    pub status: u32,
+    pub status: ProjectStatus,
     pub is_public: Option<bool>,
+    /// The version new issues of the project get by default.
+    pub default_version: Option<NamedObject>,
+    /// The user or group new issues of the project are assigned to by default.
+    pub default_assignee: Option<NamedObject>,
     pub created_on: String,
     pub updated_on: String,
+    #[serde(default)]
+    pub custom_fields: Vec<CustomField>,
 }
 impl From<ProjectShow> for Project {
     fn from(item: ProjectShow) -> Self {
         item.project
     }
 }
+// e.g. `Redmine (redmine)`
+impl fmt::Display for Project {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.identifier)
+    }
+}
+impl Project {
+    /// Returns the url of the page showing this project in the redmine application, e.g.
+    /// `http://www.redmine.org/projects/redmine`, for linking users to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - the api pointing to the redmine application
+    pub fn web_url(&self, api: &RedmineApi) -> String {
+        api.client.web_url(&format!("/projects/{}", self.identifier))
+    }
+}
 
 /// Helper struct for serialization.
 #[derive(Serialize)]
@@ -267,25 +691,29 @@ impl Default for ProjectBuilderKind {
 pub struct ProjectBuilder<'a> {
     // internal
     #[serde(skip_serializing)]
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     #[serde(skip_serializing)]
     kind: ProjectBuilderKind,
     #[serde(skip_serializing)]
     update_id: u32,
 
     // fields used for serialization
-    #[serde(skip_serializing_if = "str::is_empty")]
-    name: &'a str,
-    #[serde(skip_serializing_if = "str::is_empty")]
-    identifier: &'a str,
-    #[serde(skip_serializing_if = "str::is_empty")]
-    description: &'a str,
-    #[serde(skip_serializing_if = "str::is_empty")]
-    homepage: &'a str,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    name: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    identifier: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    description: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    homepage: Tristate<&'a str>,
     is_public: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     parent_id: Option<u32>,
     inherit_members: bool,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    default_version_id: Tristate<u32>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    default_assigned_to_id: Tristate<u32>,
 }
 impl<'a> ProjectBuilder<'a> {
     /// Creates new instance for creation of a project. Function takes all mandatory parameters for
@@ -296,7 +724,7 @@ impl<'a> ProjectBuilder<'a> {
     /// * `name` - a string slice holding the name of the project
     /// * `identifier` - a string slice holding the unique identifier of the project
     pub fn for_create(
-        client: Rc<RedmineClient>,
+        client: ClientHandle,
         name: &'a str,
         identifier: &'a str,
     ) -> Self {
@@ -304,8 +732,8 @@ impl<'a> ProjectBuilder<'a> {
             client: client,
             kind: ProjectBuilderKind::Create,
 
-            name: name,
-            identifier: identifier,
+            name: Tristate::Set(name),
+            identifier: Tristate::Set(identifier),
             ..Default::default()
         }
     }
@@ -316,7 +744,7 @@ impl<'a> ProjectBuilder<'a> {
     /// # Arguments
     ///
     /// * `id` - an integer holding the project id
-    pub fn for_update(client: Rc<RedmineClient>, id: u32) -> Self {
+    pub fn for_update(client: ClientHandle, id: u32) -> Self {
         ProjectBuilder {
             client: client,
             kind: ProjectBuilderKind::Update,
@@ -331,7 +759,7 @@ impl<'a> ProjectBuilder<'a> {
     ///
     /// * `s` - a string slice holding the subject
     pub fn name(mut self, s: &'a str) -> Self {
-        self.name = s;
+        self.name = Tristate::Set(s);
         self
     }
 
@@ -341,7 +769,7 @@ impl<'a> ProjectBuilder<'a> {
     ///
     /// * `s` - a string slice holding the subject
     pub fn identifier(mut self, s: &'a str) -> Self {
-        self.identifier = s;
+        self.identifier = Tristate::Set(s);
         self
     }
 
@@ -351,7 +779,13 @@ impl<'a> ProjectBuilder<'a> {
     ///
     /// * `s` - a string slice holding the subject
     pub fn description(mut self, s: &'a str) -> Self {
-        self.description = s;
+        self.description = Tristate::Set(s);
+        self
+    }
+
+    /// Clears the description of the project.
+    pub fn clear_description(mut self) -> Self {
+        self.description = Tristate::Clear;
         self
     }
 
@@ -361,7 +795,13 @@ impl<'a> ProjectBuilder<'a> {
     ///
     /// * `s` - a string slice holding the subject
     pub fn homepage(mut self, s: &'a str) -> Self {
-        self.homepage = s;
+        self.homepage = Tristate::Set(s);
+        self
+    }
+
+    /// Clears the homepage of the project.
+    pub fn clear_homepage(mut self) -> Self {
+        self.homepage = Tristate::Clear;
         self
     }
 
@@ -395,15 +835,52 @@ impl<'a> ProjectBuilder<'a> {
         self
     }
 
-    /// Performs request to redmine application to create or update a project.
-    pub fn execute(&self) -> Result<String> {
+    /// Sets the version new issues of the project get by default. The version has to be shared
+    /// with or belong to the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the version id
+    pub fn default_version_id(mut self, id: u32) -> Self {
+        self.default_version_id = Tristate::Set(id);
+        self
+    }
+
+    /// Clears the default version of the project.
+    pub fn clear_default_version_id(mut self) -> Self {
+        self.default_version_id = Tristate::Clear;
+        self
+    }
+
+    /// Sets the user or group new issues of the project are assigned to by default. The assignee
+    /// has to be a member of the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the user or group id
+    pub fn default_assigned_to_id(mut self, id: u32) -> Self {
+        self.default_assigned_to_id = Tristate::Set(id);
+        self
+    }
+
+    /// Clears the default assignee of the project.
+    pub fn clear_default_assigned_to_id(mut self) -> Self {
+        self.default_assigned_to_id = Tristate::Clear;
+        self
+    }
+
+    /// Performs request to redmine application to create or update a project and returns it.
+    pub fn execute(&self) -> Result<Project> {
         let project = ProjectBuilderWrapper { project: self };
         match self.kind {
-            ProjectBuilderKind::Create => self.client.create("/projects.json", &project),
+            ProjectBuilderKind::Create => {
+                self.client.create_entity("/projects.json", &project, "project")
+            }
             ProjectBuilderKind::Update => {
-                self.client.update(
+                self.client.update_entity(
                     &(format!("/projects/{}.json", self.update_id)),
                     &project,
+                    "project",
                 )
             }
         }
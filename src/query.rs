@@ -0,0 +1,499 @@
+//! This module provides a small typed query language for issue filters. The
+//! [query!](../macro.query.html) macro turns conditions joined by `&&` into a
+//! [Query](struct.Query.html) which is applied with
+//! [IssueFilter::query](../issues/struct.IssueFilter.html#method.query). Every field is a function
+//! of the [fields](fields/index.html) module and every comparison a method of the field's type,
+//! so misspelled fields and unsupported comparisons are compile errors instead of silently
+//! ignored query parameters.
+//!
+//! # Example
+//!
+//! ```
+//! #[macro_use]
+//! extern crate redmine_api;
+//!
+//! use redmine_api::RedmineApi;
+//!
+//! fn main() {
+//!     let redmine = RedmineApi::new(
+//!         "http://www.redmine.org/".to_string(),
+//!         "1234".to_string()
+//!     );
+//!
+//!     let q = query!(project == "infra" && status.is_open() && updated_on > 7.days_ago())
+//!         .unwrap();
+//!     let result = redmine.issues().list().query(&q).execute();
+//! }
+//! ```
+
+use std::fmt;
+use super::dates;
+use super::errors::*;
+
+/// Builds a [Query](query/struct.Query.html) from conditions joined by `&&`. A condition is
+/// either a comparison (`field == value`, `!=`, `>`, `>=`, `<` or `<=`) or a method call on a
+/// field (e.g. `status.is_open()`). See the [fields](query/fields/index.html) module for the
+/// available fields. Returns an error if conditions on the same field can't be combined, see
+/// [Query::push](query/struct.Query.html#method.push).
+#[macro_export]
+macro_rules! query {
+    ($($tokens:tt)+) => {{
+        #[allow(unused_imports)]
+        use $crate::query::DaysAgo;
+
+        let q: $crate::errors::Result<$crate::query::Query> = Ok($crate::query::Query::new());
+        __query_conditions!(q [] $($tokens)+);
+        q
+    }};
+}
+
+/// Splits the tokens passed to `query!` at `&&` and adds the conditions to the query until one
+/// of them fails. Is only used internally.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __query_conditions {
+    ($q:ident [$($cur:tt)+] && $($rest:tt)+) => {
+        let $q = $q.and_then(|mut q| q.push(__query_condition!($($cur)+)).map(|_| q));
+        __query_conditions!($q [] $($rest)+);
+    };
+    ($q:ident [$($cur:tt)*] $t:tt $($rest:tt)*) => {
+        __query_conditions!($q [$($cur)* $t] $($rest)*);
+    };
+    ($q:ident [$($cur:tt)+]) => {
+        let $q = $q.and_then(|mut q| q.push(__query_condition!($($cur)+)).map(|_| q));
+    };
+}
+
+/// Turns a single condition into a method call on a field. Is only used internally.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __query_condition {
+    ($field:ident . $method:ident ($($args:tt)*)) => {
+        $crate::query::fields::$field().$method($($args)*)
+    };
+    ($field:ident == $($value:tt)+) => {
+        $crate::query::fields::$field().eq($($value)+)
+    };
+    ($field:ident != $($value:tt)+) => {
+        $crate::query::fields::$field().ne($($value)+)
+    };
+    ($field:ident >= $($value:tt)+) => {
+        $crate::query::fields::$field().ge($($value)+)
+    };
+    ($field:ident <= $($value:tt)+) => {
+        $crate::query::fields::$field().le($($value)+)
+    };
+    ($field:ident > $($value:tt)+) => {
+        $crate::query::fields::$field().gt($($value)+)
+    };
+    ($field:ident < $($value:tt)+) => {
+        $crate::query::fields::$field().lt($($value)+)
+    };
+}
+
+/// Holds the conditions of a query as filter parameters.
+#[derive(Debug, Default, Clone)]
+pub struct Query {
+    conditions: Vec<Condition>,
+}
+impl Query {
+    /// Creates a new query without any condition.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a condition to the query. Redmine takes a single condition per field, so a lower and
+    /// an upper bound of the same date field are merged into a range. Returns an error if the
+    /// query already holds a condition on the same field which can't be merged with `condition`,
+    /// since one of them would be silently dropped otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - a condition as returned by the methods of the fields
+    pub fn push(&mut self, condition: Condition) -> Result<()> {
+        if let Some(existing) = self.conditions.iter_mut().find(|c| c.key == condition.key) {
+            match existing.merge(&condition) {
+                Some(merged) => *existing = merged,
+                None => bail!("Conditions {} and {} can't be combined", existing, condition),
+            }
+            return Ok(());
+        }
+
+        self.conditions.push(condition);
+        Ok(())
+    }
+
+    /// Returns the conditions of the query.
+    pub fn conditions(&self) -> &[Condition] {
+        &self.conditions
+    }
+}
+
+/// Represents a single condition of a query, i.e. a filter parameter and its value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    key: &'static str,
+    value: String,
+}
+impl Condition {
+    /// Returns the name of the filter parameter.
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    /// Returns the value of the filter parameter including the operator, e.g. `>=2017-09-01`.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Merges a lower and an upper bound of the same field into a range, e.g. `>=2020-01-01` and
+    /// `<=2020-02-01` into `><2020-01-01|2020-02-01`. Returns `None` for any other conditions.
+    fn merge(&self, other: &Condition) -> Option<Condition> {
+        let (lower, upper) = if self.value.starts_with(">=") {
+            (&self.value, &other.value)
+        } else {
+            (&other.value, &self.value)
+        };
+        if !lower.starts_with(">=") || !upper.starts_with("<=") {
+            return None;
+        }
+
+        Some(Condition {
+            key: self.key,
+            value: format!("><{}|{}", &lower[2..], &upper[2..]),
+        })
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}={}`", self.key, self.value)
+    }
+}
+
+/// Represents a date in queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    days: i64,
+}
+impl Date {
+    /// Creates a date from year, month and day.
+    ///
+    /// # Arguments
+    ///
+    /// * `y` - an integer holding the year
+    /// * `m` - an integer holding the month (1-12)
+    /// * `d` - an integer holding the day of the month
+    pub fn ymd(y: i32, m: u32, d: u32) -> Self {
+        Date { days: dates::days_from_civil(i64::from(y), i64::from(m), i64::from(d)) }
+    }
+
+    /// Returns the current date (UTC).
+    pub fn today() -> Self {
+        DaysAgo::days_ago(0)
+    }
+
+    /// Returns the date `n` days after this one.
+    fn add_days(&self, n: i64) -> Self {
+        Date { days: self.days + n }
+    }
+}
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", dates::format_days(self.days))
+    }
+}
+
+/// Provides relative dates like `7.days_ago()` in queries.
+pub trait DaysAgo {
+    /// Returns the date the given number of days before today (UTC).
+    fn days_ago(self) -> Date;
+}
+impl DaysAgo for u32 {
+    fn days_ago(self) -> Date {
+        Date { days: dates::now() / 86_400 - i64::from(self) }
+    }
+}
+
+/// Field holding the id of an associated record, e.g. the tracker of an issue.
+#[derive(Debug, Clone, Copy)]
+pub struct IdField {
+    key: &'static str,
+}
+impl IdField {
+    /// Matches issues referring to the record with the given id. Projects may also be given by
+    /// their identifier.
+    pub fn eq<T: fmt::Display>(self, id: T) -> Condition {
+        self.condition(id.to_string())
+    }
+
+    /// Matches issues not referring to the record with the given id.
+    pub fn ne<T: fmt::Display>(self, id: T) -> Condition {
+        self.condition(format!("!{}", id))
+    }
+
+    /// Matches issues referring to any record, e.g. assigned issues.
+    pub fn is_set(self) -> Condition {
+        self.condition("*".to_string())
+    }
+
+    /// Matches issues not referring to a record, e.g. unassigned issues.
+    pub fn is_none(self) -> Condition {
+        self.condition("!*".to_string())
+    }
+
+    fn condition(self, value: String) -> Condition {
+        Condition {
+            key: self.key,
+            value: value,
+        }
+    }
+}
+
+/// Field holding the status of an issue.
+#[derive(Debug, Clone, Copy)]
+pub struct StatusField;
+impl StatusField {
+    /// Matches issues with the given status id.
+    pub fn eq(self, id: u32) -> Condition {
+        self.condition(id.to_string())
+    }
+
+    /// Matches issues with any other status than the given one.
+    pub fn ne(self, id: u32) -> Condition {
+        self.condition(format!("!{}", id))
+    }
+
+    /// Matches issues with an open status.
+    pub fn is_open(self) -> Condition {
+        self.condition("open".to_string())
+    }
+
+    /// Matches issues with a closed status.
+    pub fn is_closed(self) -> Condition {
+        self.condition("closed".to_string())
+    }
+
+    /// Matches open as well as closed issues.
+    pub fn is_any(self) -> Condition {
+        self.condition("*".to_string())
+    }
+
+    fn condition(self, value: String) -> Condition {
+        Condition {
+            key: "status_id",
+            value: value,
+        }
+    }
+}
+
+/// Field holding a date or timestamp of an issue. Comparisons are made by day.
+#[derive(Debug, Clone, Copy)]
+pub struct DateField {
+    key: &'static str,
+}
+impl DateField {
+    /// Matches issues on the given day.
+    pub fn eq(self, date: Date) -> Condition {
+        self.condition(date.to_string())
+    }
+
+    /// Matches issues after the given day.
+    pub fn gt(self, date: Date) -> Condition {
+        self.ge(date.add_days(1))
+    }
+
+    /// Matches issues on or after the given day.
+    pub fn ge(self, date: Date) -> Condition {
+        self.condition(format!(">={}", date))
+    }
+
+    /// Matches issues before the given day.
+    pub fn lt(self, date: Date) -> Condition {
+        self.le(date.add_days(-1))
+    }
+
+    /// Matches issues on or before the given day.
+    pub fn le(self, date: Date) -> Condition {
+        self.condition(format!("<={}", date))
+    }
+
+    /// Matches issues having the date set.
+    pub fn is_set(self) -> Condition {
+        self.condition("*".to_string())
+    }
+
+    /// Matches issues not having the date set.
+    pub fn is_none(self) -> Condition {
+        self.condition("!*".to_string())
+    }
+
+    fn condition(self, value: String) -> Condition {
+        Condition {
+            key: self.key,
+            value: value,
+        }
+    }
+}
+
+/// Field holding a text of an issue.
+#[derive(Debug, Clone, Copy)]
+pub struct TextField {
+    key: &'static str,
+}
+impl TextField {
+    /// Matches issues whose text contains the given string.
+    pub fn contains(self, s: &str) -> Condition {
+        Condition {
+            key: self.key,
+            value: format!("~{}", s),
+        }
+    }
+}
+
+/// The fields of an issue which can be used in queries.
+pub mod fields {
+    use super::{DateField, IdField, StatusField, TextField};
+
+    /// The project of an issue, given by id or identifier.
+    pub fn project() -> IdField {
+        IdField { key: "project_id" }
+    }
+
+    /// The tracker of an issue.
+    pub fn tracker() -> IdField {
+        IdField { key: "tracker_id" }
+    }
+
+    /// The status of an issue.
+    pub fn status() -> StatusField {
+        StatusField
+    }
+
+    /// The priority of an issue.
+    pub fn priority() -> IdField {
+        IdField { key: "priority_id" }
+    }
+
+    /// The user or group an issue is assigned to.
+    pub fn assigned_to() -> IdField {
+        IdField { key: "assigned_to_id" }
+    }
+
+    /// The author of an issue.
+    pub fn author() -> IdField {
+        IdField { key: "author_id" }
+    }
+
+    /// The category of an issue.
+    pub fn category() -> IdField {
+        IdField { key: "category_id" }
+    }
+
+    /// The target version of an issue.
+    pub fn fixed_version() -> IdField {
+        IdField { key: "fixed_version_id" }
+    }
+
+    /// The parent of an issue.
+    pub fn parent() -> IdField {
+        IdField { key: "parent_id" }
+    }
+
+    /// The subject of an issue.
+    pub fn subject() -> TextField {
+        TextField { key: "subject" }
+    }
+
+    /// The creation time of an issue.
+    pub fn created_on() -> DateField {
+        DateField { key: "created_on" }
+    }
+
+    /// The time an issue has been updated last.
+    pub fn updated_on() -> DateField {
+        DateField { key: "updated_on" }
+    }
+
+    /// The time an issue has been closed.
+    pub fn closed_on() -> DateField {
+        DateField { key: "closed_on" }
+    }
+
+    /// The start date of an issue.
+    pub fn start_date() -> DateField {
+        DateField { key: "start_date" }
+    }
+
+    /// The due date of an issue.
+    pub fn due_date() -> DateField {
+        DateField { key: "due_date" }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Date, Query};
+
+    /// Returns the conditions of `q` as pairs of key and value.
+    fn pairs(q: &Query) -> Vec<(&str, &str)> {
+        q.conditions().iter().map(|c| (c.key(), c.value())).collect()
+    }
+
+    #[test]
+    fn comparisons() {
+        let q = query!(project == "infra" && tracker != 2 && subject.contains("login")).unwrap();
+        assert_eq!(
+            pairs(&q),
+            vec![("project_id", "infra"), ("tracker_id", "!2"), ("subject", "~login")]
+        );
+    }
+
+    #[test]
+    fn method_calls() {
+        let q = query!(status.is_open() && assigned_to.is_none() && due_date.is_set()).unwrap();
+        assert_eq!(
+            pairs(&q),
+            vec![("status_id", "open"), ("assigned_to_id", "!*"), ("due_date", "*")]
+        );
+    }
+
+    #[test]
+    fn strict_date_comparisons_shift_by_a_day() {
+        let q = query!(created_on > Date::ymd(2020, 1, 31) && due_date < Date::ymd(2020, 3, 1))
+            .unwrap();
+        assert_eq!(
+            pairs(&q),
+            vec![("created_on", ">=2020-02-01"), ("due_date", "<=2020-02-29")]
+        );
+    }
+
+    #[test]
+    fn relative_dates() {
+        let q = query!(updated_on >= 0.days_ago()).unwrap();
+        assert_eq!(pairs(&q), vec![("updated_on", format!(">={}", Date::today()).as_str())]);
+    }
+
+    #[test]
+    fn bounds_of_one_field_are_merged_into_range() {
+        let q = query!(updated_on >= Date::ymd(2020, 1, 1) && updated_on <= Date::ymd(2020, 2, 1))
+            .unwrap();
+        assert_eq!(pairs(&q), vec![("updated_on", "><2020-01-01|2020-02-01")]);
+
+        let q = query!(updated_on < Date::ymd(2020, 2, 2) && updated_on > Date::ymd(2019, 12, 31))
+            .unwrap();
+        assert_eq!(pairs(&q), vec![("updated_on", "><2020-01-01|2020-02-01")]);
+    }
+
+    #[test]
+    fn conflicting_conditions_of_one_field_fail() {
+        let e = query!(tracker == 1 && tracker == 2).unwrap_err();
+        assert_eq!(e.to_string(), "Conditions `tracker_id=1` and `tracker_id=2` can't be combined");
+    }
+
+    #[test]
+    fn two_lower_bounds_fail() {
+        let q = query!(updated_on >= Date::ymd(2020, 1, 1) && updated_on >= Date::ymd(2020, 2, 1));
+        assert!(q.is_err());
+    }
+}
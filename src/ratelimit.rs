@@ -0,0 +1,336 @@
+//! This module limits the rate of requests sent to a redmine application. The limit is a token
+//! bucket: every request takes a token, tokens are refilled at a fixed rate and up to `burst`
+//! tokens can be saved up. The backends store the bucket outside of the process, so a fleet of
+//! workers talking to one redmine application stays under the limit as a whole:
+//!
+//! * [FileRateLimiter](struct.FileRateLimiter.html) keeps the bucket in a file guarded by an
+//!   exclusive lock, for workers on one host. Requires the `rate-limit-file` feature.
+//! * [RedisRateLimiter](struct.RedisRateLimiter.html) keeps the bucket in redis, for workers on
+//!   several hosts. Requires the `rate-limit-redis` feature.
+//!
+//! Limiters are set with
+//! [RedmineApi::rate_limit](../struct.RedmineApi.html#method.rate_limit).
+
+#[cfg(feature = "rate-limit-file")]
+use fs2::FileExt;
+#[cfg(feature = "rate-limit-redis")]
+use redis;
+#[cfg(feature = "rate-limit-file")]
+use std::fs::{File, OpenOptions};
+#[cfg(feature = "rate-limit-file")]
+use std::io::{Read, Seek, SeekFrom, Write};
+#[cfg(feature = "rate-limit-file")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "rate-limit-redis")]
+use std::sync::Mutex;
+#[cfg(any(feature = "rate-limit-file", feature = "rate-limit-redis"))]
+use std::thread;
+#[cfg(any(feature = "rate-limit-file", feature = "rate-limit-redis"))]
+use std::time::Duration;
+#[cfg(feature = "rate-limit-file")]
+use std::time::{SystemTime, UNIX_EPOCH};
+use super::errors::*;
+
+/// Limits the rate of requests. Implement this trait to coordinate requests by other means than
+/// the provided backends. Is shared by all threads executing requests of the same
+/// [RedmineApi](../struct.RedmineApi.html), so it has to be `Send` and `Sync`.
+pub trait RateLimiter: Send + Sync {
+    /// Blocks until a request may be sent.
+    fn acquire(&self) -> Result<()>;
+}
+
+/// State of a token bucket as stored by the backends.
+#[cfg(feature = "rate-limit-file")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Bucket {
+    tokens: f64,
+    /// Time of the last refill in milliseconds since the unix epoch.
+    updated: u64,
+}
+#[cfg(feature = "rate-limit-file")]
+impl Bucket {
+    /// Refills the bucket and takes a token. Returns None if a token has been taken, otherwise
+    /// the time to wait for the next token.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - an integer holding the current time in milliseconds since the unix epoch
+    /// * `rate` - the number of tokens refilled per second
+    /// * `burst` - the maximum number of tokens
+    fn take(&mut self, now: u64, rate: f64, burst: u32) -> Option<Duration> {
+        let elapsed = now.saturating_sub(self.updated) as f64 / 1000.0;
+        self.tokens = (self.tokens + elapsed * rate).min(f64::from(burst));
+        self.updated = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            let millis = ((1.0 - self.tokens) / rate * 1000.0).ceil() as u64;
+            Some(Duration::from_millis(millis.max(1)))
+        }
+    }
+}
+
+/// Returns the milliseconds since the unix epoch.
+#[cfg(feature = "rate-limit-file")]
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() * 1000 + u64::from(d.subsec_millis()))
+        .unwrap_or(0)
+}
+
+/// Checks the parameters shared by all backends.
+///
+/// # Arguments
+///
+/// * `rate` - the number of requests per second
+/// * `burst` - the maximum number of requests sent at once
+#[cfg(any(feature = "rate-limit-file", feature = "rate-limit-redis"))]
+fn check_limit(rate: f64, burst: u32) -> Result<()> {
+    if rate.is_nan() || rate <= 0.0 {
+        bail!("Rate limit must be positive, got {}", rate);
+    }
+    if burst == 0 {
+        bail!("Burst of rate limit must be at least 1");
+    }
+
+    Ok(())
+}
+
+/// Rate limiter sharing its bucket with all processes using the same file. Requires the
+/// `rate-limit-file` feature.
+///
+/// # Example
+///
+/// ```
+/// use redmine_api::RedmineApi;
+/// use redmine_api::ratelimit::FileRateLimiter;
+///
+/// let redmine = RedmineApi::new(
+///     "http://www.redmine.org/".to_string(),
+///     "1234".to_string()
+/// );
+/// if let Ok(limiter) = FileRateLimiter::new("/tmp/redmine.bucket", 10.0, 20) {
+///     redmine.rate_limit(limiter);
+/// }
+///
+/// let result = redmine.issues().show(1).execute();
+/// ```
+#[cfg(feature = "rate-limit-file")]
+#[derive(Debug, Clone)]
+pub struct FileRateLimiter {
+    path: PathBuf,
+    rate: f64,
+    burst: u32,
+}
+#[cfg(feature = "rate-limit-file")]
+impl FileRateLimiter {
+    /// Creates a new instance. The file is created on first use.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the file holding the bucket
+    /// * `rate` - the number of requests per second
+    /// * `burst` - the maximum number of requests sent at once
+    pub fn new<P: AsRef<Path>>(path: P, rate: f64, burst: u32) -> Result<Self> {
+        check_limit(rate, burst)?;
+
+        Ok(FileRateLimiter {
+            path: path.as_ref().to_path_buf(),
+            rate: rate,
+            burst: burst,
+        })
+    }
+
+    /// Takes a token from the bucket in the file. Returns the time to wait if there is none.
+    fn try_acquire(&self) -> Result<Option<Duration>> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)
+            .chain_err(|| format!("Can't open {}", self.path.display()))?;
+        file.lock_exclusive().chain_err(|| {
+            format!("Can't lock {}", self.path.display())
+        })?;
+
+        let result = self.update(&mut file);
+        let _ = file.unlock();
+
+        result
+    }
+
+    /// Reads, updates and writes the bucket. The file has to be locked.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - the locked file holding the bucket
+    fn update(&self, file: &mut File) -> Result<Option<Duration>> {
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+
+        // the file holds `<tokens> <updated>`; a new or damaged file starts with a full bucket
+        let mut parts = content.split_whitespace();
+        let now = now_millis();
+        let mut bucket = match (parts.next(), parts.next()) {
+            (Some(tokens), Some(updated)) => {
+                match (tokens.parse::<f64>(), updated.parse::<u64>()) {
+                    (Ok(tokens), Ok(updated)) => Bucket {
+                        tokens: tokens,
+                        updated: updated,
+                    },
+                    _ => Bucket {
+                        tokens: f64::from(self.burst),
+                        updated: now,
+                    },
+                }
+            }
+            _ => Bucket {
+                tokens: f64::from(self.burst),
+                updated: now,
+            },
+        };
+
+        let wait = bucket.take(now, self.rate, self.burst);
+
+        file.seek(SeekFrom::Start(0))?;
+        file.set_len(0)?;
+        write!(file, "{} {}", bucket.tokens, bucket.updated)?;
+
+        Ok(wait)
+    }
+}
+#[cfg(feature = "rate-limit-file")]
+impl RateLimiter for FileRateLimiter {
+    fn acquire(&self) -> Result<()> {
+        while let Some(wait) = self.try_acquire()? {
+            thread::sleep(wait);
+        }
+
+        Ok(())
+    }
+}
+
+/// Takes a token from the bucket stored as hash under `KEYS[1]`. Returns 0 if a token has been
+/// taken, otherwise the milliseconds to wait for the next token. Mirrors `Bucket::take`, using the
+/// clock of the redis server, so the clocks of the workers don't matter. Replicating the effects
+/// instead of the script is required by redis < 5 for scripts calling `TIME`.
+#[cfg(feature = "rate-limit-redis")]
+const REDIS_SCRIPT: &str = r"
+redis.replicate_commands()
+local rate = tonumber(ARGV[1])
+local burst = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'updated')
+local tokens = tonumber(state[1]) or burst
+local updated = tonumber(state[2]) or now
+local elapsed = math.max(now - updated, 0) / 1000
+tokens = math.min(tokens + elapsed * rate, burst)
+local wait = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+else
+    wait = math.max(math.ceil((1 - tokens) / rate * 1000), 1)
+end
+redis.call('HMSET', KEYS[1], 'tokens', tostring(tokens), 'updated', now)
+redis.call('PEXPIRE', KEYS[1], math.ceil(burst / rate * 1000) + 1000)
+return wait
+";
+
+/// Rate limiter sharing its bucket with all processes using the same redis key. The bucket is
+/// updated atomically by a lua script. Keeps one connection, which is shared by all threads and
+/// reopened after an error. Requires the `rate-limit-redis` feature.
+///
+/// # Example
+///
+/// ```
+/// use redmine_api::RedmineApi;
+/// use redmine_api::ratelimit::RedisRateLimiter;
+///
+/// let redmine = RedmineApi::new(
+///     "http://www.redmine.org/".to_string(),
+///     "1234".to_string()
+/// );
+/// if let Ok(limiter) = RedisRateLimiter::new("redis://127.0.0.1/", "redmine", 10.0, 20) {
+///     redmine.rate_limit(limiter);
+/// }
+///
+/// let result = redmine.issues().show(1).execute();
+/// ```
+#[cfg(feature = "rate-limit-redis")]
+pub struct RedisRateLimiter {
+    client: redis::Client,
+    connection: Mutex<Option<redis::Connection>>,
+    script: redis::Script,
+    key: String,
+    rate: f64,
+    burst: u32,
+}
+#[cfg(feature = "rate-limit-redis")]
+impl RedisRateLimiter {
+    /// Creates a new instance. The connection is opened on first use.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - a string slice holding the url of the redis server, e.g. `redis://127.0.0.1/`
+    /// * `key` - a string slice holding the key the bucket is stored under
+    /// * `rate` - the number of requests per second
+    /// * `burst` - the maximum number of requests sent at once
+    pub fn new(url: &str, key: &str, rate: f64, burst: u32) -> Result<Self> {
+        check_limit(rate, burst)?;
+        let client = redis::Client::open(url).chain_err(
+            || format!("Can't parse redis url: {}", url),
+        )?;
+
+        Ok(RedisRateLimiter {
+            client: client,
+            connection: Mutex::new(None),
+            script: redis::Script::new(REDIS_SCRIPT),
+            key: key.to_string(),
+            rate: rate,
+            burst: burst,
+        })
+    }
+}
+#[cfg(feature = "rate-limit-redis")]
+impl RateLimiter for RedisRateLimiter {
+    fn acquire(&self) -> Result<()> {
+        while let Some(wait) = self.try_acquire()? {
+            thread::sleep(wait);
+        }
+
+        Ok(())
+    }
+}
+#[cfg(feature = "rate-limit-redis")]
+impl RedisRateLimiter {
+    /// Takes a token from the bucket. Returns None if a token has been taken, otherwise the time
+    /// to wait for the next token. The connection isn't locked while waiting.
+    fn try_acquire(&self) -> Result<Option<Duration>> {
+        let mut connection = self.connection.lock().unwrap();
+        if connection.is_none() {
+            *connection = Some(self.client.get_connection().chain_err(
+                || "Can't connect to redis",
+            )?);
+        }
+
+        let result: redis::RedisResult<u64> = self.script
+            .key(&self.key)
+            .arg(self.rate)
+            .arg(self.burst)
+            .invoke(connection.as_ref().unwrap());
+        match result {
+            Ok(0) => Ok(None),
+            Ok(wait) => Ok(Some(Duration::from_millis(wait))),
+            Err(e) => {
+                // the connection may be broken, e.g. by a restart of redis
+                *connection = None;
+                Err(e).chain_err(|| "Can't update rate limit in redis")
+            }
+        }
+    }
+}
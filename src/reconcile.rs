@@ -0,0 +1,1117 @@
+//! This module brings a redmine application into a declared state. A [Spec](struct.Spec.html),
+//! which can be loaded from any serde format, declares projects together with their custom field
+//! values, memberships and versions. [Reconciler::plan](struct.Reconciler.html#method.plan)
+//! compares it with the live application and returns the changes needed as a
+//! [Plan](struct.Plan.html), which can be reviewed and is applied by
+//! [Plan::apply](struct.Plan.html#method.apply).
+//!
+//! Reconciliation only adds and updates by default. Memberships and versions of a project which
+//! aren't declared are removed only if pruning is enabled and the project declares the list at
+//! all. Projects are never deleted; archived projects aren't seen and can't be reconciled.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate redmine_api;
+//! extern crate serde_json;
+//!
+//! use redmine_api::RedmineApi;
+//! use redmine_api::reconcile::{Reconciler, Spec};
+//!
+//! fn main() {
+//!     let spec: Spec = serde_json::from_str(r#"{
+//!       "projects": [{
+//!         "identifier": "infra",
+//!         "name": "Infrastructure",
+//!         "is_public": false,
+//!         "custom_fields": {"Customer": "ACME"},
+//!         "memberships": [{"user_id": 5, "role_ids": [3, 4]}],
+//!         "versions": [{"name": "1.0", "status": "open", "due_date": "2018-03-31"}]
+//!       }]
+//!     }"#).unwrap();
+//!
+//!     let redmine = RedmineApi::new(
+//!         "http://www.redmine.org/".to_string(),
+//!         "1234".to_string()
+//!     );
+//!
+//!     if let Ok(plan) = Reconciler::new(&redmine, spec).prune(true).plan() {
+//!         println!("{}", plan);
+//!         let report = plan.apply(&redmine);
+//!     }
+//! }
+//! ```
+
+extern crate serde_json;
+
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use super::errors::*;
+use super::memberships::Membership;
+use super::projects::Project;
+use super::versions::{Version, VersionSharing, VersionStatus};
+use super::{fetch_all, RedmineApi};
+
+/// Declared state of a redmine application.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Spec {
+    #[serde(default)]
+    pub projects: Vec<ProjectSpec>,
+}
+
+/// Declared state of a project. Fields which are `None` aren't managed, i.e. left as they are.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ProjectSpec {
+    /// Identifies the project; is never changed.
+    pub identifier: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    #[serde(default)]
+    pub is_public: Option<bool>,
+    /// Values of the project custom fields by name. Custom fields which aren't listed are left
+    /// as they are.
+    #[serde(default)]
+    pub custom_fields: BTreeMap<String, String>,
+    /// The members of the project. `None` leaves the memberships as they are.
+    #[serde(default)]
+    pub memberships: Option<Vec<MembershipSpec>>,
+    /// The versions of the project. `None` leaves the versions as they are.
+    #[serde(default)]
+    pub versions: Option<Vec<VersionSpec>>,
+}
+
+/// Declared membership of a user or group in a project.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct MembershipSpec {
+    /// Id of the user or group.
+    pub user_id: u32,
+    /// Ids of the roles given directly, i.e. not inherited from a group.
+    pub role_ids: Vec<u32>,
+}
+
+/// Declared version of a project, identified by its name. Fields which are `None` aren't managed.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct VersionSpec {
+    pub name: String,
+    #[serde(default)]
+    pub status: Option<VersionStatus>,
+    #[serde(default)]
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub sharing: Option<VersionSharing>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Computes the changes needed to bring a redmine application into a declared state.
+pub struct Reconciler<'a> {
+    api: &'a RedmineApi,
+    spec: Spec,
+    prune: bool,
+}
+impl<'a> Reconciler<'a> {
+    /// Creates a new instance which doesn't prune.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - the redmine application to reconcile
+    /// * `spec` - the declared state
+    pub fn new(api: &'a RedmineApi, spec: Spec) -> Self {
+        Reconciler {
+            api: api,
+            spec: spec,
+            prune: false,
+        }
+    }
+
+    /// Sets whether memberships and versions which aren't declared are removed. Only applies to
+    /// projects declaring the respective list. Defaults to false.
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - a boolean: true means undeclared entities are removed
+    pub fn prune(mut self, b: bool) -> Self {
+        self.prune = b;
+        self
+    }
+
+    /// Performs requests to redmine application and returns the changes needed to reach the
+    /// declared state. Nothing is changed.
+    pub fn plan(&self) -> Result<Plan> {
+        let mut live = HashMap::new();
+        for value in fetch_all(&self.api.client, "/projects.json", "projects")? {
+            let project: Project = serde_json::from_value(value).chain_err(
+                || "Can't parse json",
+            )?;
+            live.insert(project.identifier.clone(), project);
+        }
+
+        let mut plan = Plan::default();
+        for spec in &self.spec.projects {
+            match live.get(&spec.identifier) {
+                Some(project) => {
+                    plan.project_ids.insert(spec.identifier.clone(), project.id);
+                    self.plan_project(spec, Some(project), &mut plan);
+                    self.plan_memberships(spec, project.id, &mut plan)?;
+                    self.plan_versions(spec, project.id, &mut plan)?;
+                }
+                None => {
+                    self.plan_project(spec, None, &mut plan);
+                    self.plan_memberships(spec, 0, &mut plan)?;
+                    self.plan_versions(spec, 0, &mut plan)?;
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Adds the changes of the fields of a project.
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - the declared project
+    /// * `live` - the project in redmine application or `None` if it doesn't exist yet
+    /// * `plan` - the plan the changes are added to
+    fn plan_project(&self, spec: &ProjectSpec, live: Option<&Project>, plan: &mut Plan) {
+        let project = match live {
+            Some(project) => project,
+            None => {
+                let mut fields = vec![field("name", Value::Null, spec.name.as_str().into())];
+                if let Some(ref s) = spec.description {
+                    fields.push(field("description", Value::Null, s.as_str().into()));
+                }
+                if let Some(ref s) = spec.homepage {
+                    fields.push(field("homepage", Value::Null, s.as_str().into()));
+                }
+                if let Some(b) = spec.is_public {
+                    fields.push(field("is_public", Value::Null, b.into()));
+                }
+                plan.changes.push(Change::CreateProject {
+                    identifier: spec.identifier.clone(),
+                    fields: fields,
+                });
+
+                if !spec.custom_fields.is_empty() {
+                    plan.changes.push(Change::UpdateProject {
+                        identifier: spec.identifier.clone(),
+                        fields: Vec::new(),
+                        custom_fields: spec.custom_fields
+                            .iter()
+                            .map(|(name, value)| {
+                                CustomFieldChange {
+                                    id: None,
+                                    name: name.clone(),
+                                    old: Value::Null,
+                                    new: value.as_str().into(),
+                                }
+                            })
+                            .collect(),
+                    });
+                }
+                return;
+            }
+        };
+
+        let mut fields = Vec::new();
+        if project.name != spec.name {
+            fields.push(field(
+                "name",
+                project.name.as_str().into(),
+                spec.name.as_str().into(),
+            ));
+        }
+        text_change(&mut fields, "description", &project.description, &spec.description);
+        text_change(&mut fields, "homepage", &project.homepage, &spec.homepage);
+        if let Some(b) = spec.is_public {
+            if project.is_public != Some(b) {
+                let old = project.is_public.map_or(Value::Null, Value::from);
+                fields.push(field("is_public", old, b.into()));
+            }
+        }
+
+        let mut custom_fields = Vec::new();
+        for (name, value) in &spec.custom_fields {
+            let current = project.custom_fields.iter().find(|cf| cf.name == *name);
+            if current.and_then(|cf| cf.as_str()) != Some(value.as_str()) {
+                custom_fields.push(CustomFieldChange {
+                    id: current.map(|cf| cf.id),
+                    name: name.clone(),
+                    old: current.map_or(Value::Null, |cf| cf.value.clone()),
+                    new: value.as_str().into(),
+                });
+            }
+        }
+
+        if !fields.is_empty() || !custom_fields.is_empty() {
+            plan.changes.push(Change::UpdateProject {
+                identifier: spec.identifier.clone(),
+                fields: fields,
+                custom_fields: custom_fields,
+            });
+        }
+    }
+
+    /// Adds the changes of the memberships of a project.
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - the declared project
+    /// * `project_id` - an integer holding the project id or 0 if it doesn't exist yet
+    /// * `plan` - the plan the changes are added to
+    fn plan_memberships(&self, spec: &ProjectSpec, project_id: u32, plan: &mut Plan) -> Result<()> {
+        let declared = match spec.memberships {
+            Some(ref declared) => declared,
+            None => return Ok(()),
+        };
+
+        let mut live: Vec<Membership> = Vec::new();
+        if project_id != 0 {
+            let path = format!("/projects/{}/memberships.json", project_id);
+            for value in fetch_all(&self.api.client, &path, "memberships")? {
+                live.push(serde_json::from_value(value).chain_err(|| "Can't parse json")?);
+            }
+        }
+
+        for m in declared {
+            let mut role_ids = m.role_ids.clone();
+            role_ids.sort();
+            role_ids.dedup();
+
+            match live.iter().find(|l| member_id(l) == Some(m.user_id)) {
+                Some(membership) => {
+                    let current = direct_role_ids(membership);
+                    if current != role_ids {
+                        plan.changes.push(Change::UpdateMembership {
+                            identifier: spec.identifier.clone(),
+                            membership_id: membership.id,
+                            user_id: m.user_id,
+                            old_role_ids: current,
+                            role_ids: role_ids,
+                        });
+                    }
+                }
+                None => {
+                    plan.changes.push(Change::AddMembership {
+                        identifier: spec.identifier.clone(),
+                        user_id: m.user_id,
+                        role_ids: role_ids,
+                    });
+                }
+            }
+        }
+
+        if self.prune {
+            for membership in &live {
+                // memberships inherited from a group have no direct roles and can't be removed
+                let user_id = match member_id(membership) {
+                    Some(id) => id,
+                    None => continue,
+                };
+                if direct_role_ids(membership).is_empty() ||
+                    declared.iter().any(|m| m.user_id == user_id)
+                {
+                    continue;
+                }
+                plan.changes.push(Change::RemoveMembership {
+                    identifier: spec.identifier.clone(),
+                    membership_id: membership.id,
+                    user_id: user_id,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the changes of the versions of a project.
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - the declared project
+    /// * `project_id` - an integer holding the project id or 0 if it doesn't exist yet
+    /// * `plan` - the plan the changes are added to
+    fn plan_versions(&self, spec: &ProjectSpec, project_id: u32, plan: &mut Plan) -> Result<()> {
+        let declared = match spec.versions {
+            Some(ref declared) => declared,
+            None => return Ok(()),
+        };
+
+        let mut live: Vec<Version> = Vec::new();
+        if project_id != 0 {
+            // versions shared by other projects are listed, too, but belong to those
+            live = self.api
+                .versions()
+                .list(project_id)
+                .execute()?
+                .into_iter()
+                .filter(|v| v.project.id() == project_id)
+                .collect();
+        }
+
+        for v in declared {
+            let version = match live.iter().find(|l| l.name == v.name) {
+                Some(version) => version,
+                None => {
+                    plan.changes.push(Change::CreateVersion {
+                        identifier: spec.identifier.clone(),
+                        fields: version_fields(v, None),
+                    });
+                    continue;
+                }
+            };
+
+            let fields = version_fields(v, Some(version));
+            if !fields.is_empty() {
+                plan.changes.push(Change::UpdateVersion {
+                    identifier: spec.identifier.clone(),
+                    version_id: version.id,
+                    name: version.name.clone(),
+                    fields: fields,
+                });
+            }
+        }
+
+        if self.prune {
+            for version in live.iter().filter(|l| !declared.iter().any(|v| v.name == l.name)) {
+                plan.changes.push(Change::DeleteVersion {
+                    identifier: spec.identifier.clone(),
+                    version_id: version.id,
+                    name: version.name.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Change of a single field. `old` is `null` for new entities.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+/// Change of the value of a project custom field.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CustomFieldChange {
+    /// Id of the custom field, `None` if it's looked up by name when applied.
+    pub id: Option<u32>,
+    pub name: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+/// A single change of a plan. Projects are referred to by identifier.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Change {
+    CreateProject {
+        identifier: String,
+        fields: Vec<FieldChange>,
+    },
+    UpdateProject {
+        identifier: String,
+        fields: Vec<FieldChange>,
+        custom_fields: Vec<CustomFieldChange>,
+    },
+    AddMembership {
+        identifier: String,
+        user_id: u32,
+        role_ids: Vec<u32>,
+    },
+    UpdateMembership {
+        identifier: String,
+        membership_id: u32,
+        user_id: u32,
+        old_role_ids: Vec<u32>,
+        role_ids: Vec<u32>,
+    },
+    RemoveMembership {
+        identifier: String,
+        membership_id: u32,
+        user_id: u32,
+    },
+    CreateVersion {
+        identifier: String,
+        fields: Vec<FieldChange>,
+    },
+    UpdateVersion {
+        identifier: String,
+        version_id: u32,
+        name: String,
+        fields: Vec<FieldChange>,
+    },
+    DeleteVersion {
+        identifier: String,
+        version_id: u32,
+        name: String,
+    },
+}
+// e.g. `~ membership infra: user 5 roles [3] -> [3, 4]`
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Change::CreateProject {
+                ref identifier,
+                ref fields,
+            } => write!(f, "+ project {}{}", identifier, format_fields(fields)),
+            Change::UpdateProject {
+                ref identifier,
+                ref fields,
+                ref custom_fields,
+            } => {
+                let mut all = fields.clone();
+                all.extend(custom_fields.iter().map(|cf| {
+                    field(&cf.name, cf.old.clone(), cf.new.clone())
+                }));
+                write!(f, "~ project {}{}", identifier, format_fields(&all))
+            }
+            Change::AddMembership {
+                ref identifier,
+                user_id,
+                ref role_ids,
+            } => write!(f, "+ membership {}: user {} roles {:?}", identifier, user_id, role_ids),
+            Change::UpdateMembership {
+                ref identifier,
+                user_id,
+                ref old_role_ids,
+                ref role_ids,
+                ..
+            } => {
+                write!(
+                    f,
+                    "~ membership {}: user {} roles {:?} -> {:?}",
+                    identifier,
+                    user_id,
+                    old_role_ids,
+                    role_ids
+                )
+            }
+            Change::RemoveMembership {
+                ref identifier,
+                user_id,
+                ..
+            } => write!(f, "- membership {}: user {}", identifier, user_id),
+            Change::CreateVersion {
+                ref identifier,
+                ref fields,
+            } => write!(f, "+ version {}{}", identifier, format_fields(fields)),
+            Change::UpdateVersion {
+                ref identifier,
+                ref name,
+                ref fields,
+                ..
+            } => write!(f, "~ version {} {}{}", identifier, name, format_fields(fields)),
+            Change::DeleteVersion {
+                ref identifier,
+                ref name,
+                ..
+            } => write!(f, "- version {}: {}", identifier, name),
+        }
+    }
+}
+
+/// Changes needed to bring a redmine application into a declared state, in the order they are
+/// applied. Is returned by [Reconciler::plan](struct.Reconciler.html#method.plan).
+#[derive(Debug, Default, Clone)]
+pub struct Plan {
+    pub changes: Vec<Change>,
+    /// Ids of the projects which existed when planning by identifier.
+    project_ids: HashMap<String, u32>,
+}
+impl Plan {
+    /// Checks if the redmine application is in the declared state already.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Performs requests to redmine application applying the changes in order. Stops at the
+    /// first failing change, since later changes may depend on it; planning again shows what's
+    /// left.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - the redmine application the plan has been computed for
+    pub fn apply(&self, api: &RedmineApi) -> Result<ApplyReport> {
+        let mut project_ids = self.project_ids.clone();
+        let mut report = ApplyReport::default();
+
+        for change in &self.changes {
+            match apply_change(api, change, &mut project_ids) {
+                Ok(()) => report.applied.push(change.clone()),
+                Err(e) => {
+                    report.failed = Some((change.clone(), e.to_string()));
+                    break;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.changes.is_empty() {
+            return write!(f, "No changes");
+        }
+
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        write!(f, "{} changes", self.changes.len())
+    }
+}
+
+/// Holds the result of applying a plan.
+#[derive(Debug, Default, Clone)]
+pub struct ApplyReport {
+    /// Changes which have been applied.
+    pub applied: Vec<Change>,
+    /// The change which failed together with the error message, if any. Changes after it
+    /// haven't been applied.
+    pub failed: Option<(Change, String)>,
+}
+impl ApplyReport {
+    /// Checks if all changes have been applied.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_none()
+    }
+}
+
+/// Applies a single change.
+///
+/// # Arguments
+///
+/// * `api` - the redmine application
+/// * `change` - the change to apply
+/// * `project_ids` - the ids of the projects by identifier; created projects are added
+fn apply_change(
+    api: &RedmineApi,
+    change: &Change,
+    project_ids: &mut HashMap<String, u32>,
+) -> Result<()> {
+    let project_id = |identifier: &String| match project_ids.get(identifier) {
+        Some(&id) => Ok(id),
+        None => Err(Error::from(format!("Unknown project: {}", identifier))),
+    };
+
+    match *change {
+        Change::CreateProject {
+            ref identifier,
+            ref fields,
+        } => {
+            let mut body = fields_body(fields);
+            body.insert("identifier".to_string(), identifier.as_str().into());
+            let project: Project = api.client.create_entity(
+                "/projects.json",
+                &wrap("project", body),
+                "project",
+            )?;
+            project_ids.insert(identifier.clone(), project.id);
+        }
+        Change::UpdateProject {
+            ref identifier,
+            ref fields,
+            ref custom_fields,
+        } => {
+            let id = project_id(identifier)?;
+            let mut body = fields_body(fields);
+            if !custom_fields.is_empty() {
+                body.insert(
+                    "custom_fields".to_string(),
+                    custom_fields_body(api, id, custom_fields)?,
+                );
+            }
+            api.client.update(&format!("/projects/{}.json", id), &wrap("project", body))?;
+        }
+        Change::AddMembership {
+            ref identifier,
+            user_id,
+            ref role_ids,
+        } => {
+            api.memberships()
+                .create(project_id(identifier)?, user_id, role_ids.clone())
+                .execute()?;
+        }
+        Change::UpdateMembership {
+            membership_id,
+            ref role_ids,
+            ..
+        } => {
+            api.memberships()
+                .update(membership_id)
+                .role_ids(role_ids.clone())
+                .execute()?;
+        }
+        Change::RemoveMembership { membership_id, .. } => {
+            api.memberships().delete(membership_id).execute()?;
+        }
+        Change::CreateVersion {
+            ref identifier,
+            ref fields,
+        } => {
+            let path = format!("/projects/{}/versions.json", project_id(identifier)?);
+            let _: Version = api.client.create_entity(
+                &path,
+                &wrap("version", fields_body(fields)),
+                "version",
+            )?;
+        }
+        Change::UpdateVersion {
+            version_id,
+            ref fields,
+            ..
+        } => {
+            api.client.update(
+                &format!("/versions/{}.json", version_id),
+                &wrap("version", fields_body(fields)),
+            )?;
+        }
+        Change::DeleteVersion { version_id, .. } => {
+            api.versions().delete(version_id).execute()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the custom field values of a project update. Ids unknown when planning are looked up
+/// by name in the project.
+///
+/// # Arguments
+///
+/// * `api` - the redmine application
+/// * `project_id` - an integer holding the project id
+/// * `changes` - the changes of the custom field values
+fn custom_fields_body(
+    api: &RedmineApi,
+    project_id: u32,
+    changes: &[CustomFieldChange],
+) -> Result<Value> {
+    let mut project: Option<Project> = None;
+    let mut values = Vec::new();
+    for change in changes {
+        let id = match change.id {
+            Some(id) => id,
+            None => {
+                if project.is_none() {
+                    project = Some(api.projects().show(project_id).execute()?);
+                }
+                match project.as_ref().and_then(|p| {
+                    p.custom_fields.iter().find(|cf| cf.name == change.name)
+                }) {
+                    Some(cf) => cf.id,
+                    None => bail!(ErrorKind::UnknownCustomField(change.name.clone())),
+                }
+            }
+        };
+
+        let mut value = serde_json::Map::new();
+        value.insert("id".to_string(), id.into());
+        value.insert("value".to_string(), change.new.clone());
+        values.push(Value::Object(value));
+    }
+
+    Ok(Value::Array(values))
+}
+
+/// Returns the id of the user or group of a membership.
+fn member_id(membership: &Membership) -> Option<u32> {
+    membership
+        .user
+        .as_ref()
+        .or(membership.group.as_ref())
+        .map(|m| m.id())
+}
+
+/// Returns the sorted ids of the roles given to a member directly.
+fn direct_role_ids(membership: &Membership) -> Vec<u32> {
+    let mut ids: Vec<u32> = membership
+        .roles
+        .iter()
+        .filter(|r| r.inherited != Some(true))
+        .map(|r| r.id)
+        .collect();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+/// Returns the field changes of a declared version, compared with the live version if any.
+///
+/// # Arguments
+///
+/// * `spec` - the declared version
+/// * `live` - the version in redmine application or `None` if it doesn't exist yet
+fn version_fields(spec: &VersionSpec, live: Option<&Version>) -> Vec<FieldChange> {
+    let mut fields = Vec::new();
+    let version = match live {
+        Some(version) => version,
+        None => {
+            fields.push(field("name", Value::Null, spec.name.as_str().into()));
+            &Version::default()
+        }
+    };
+    let new = live.is_none();
+
+    if let Some(status) = spec.status {
+        if new || version.status != status {
+            fields.push(field("status", to_value(&version.status, new), to_value(&status, false)));
+        }
+    }
+    if let Some(sharing) = spec.sharing {
+        if new || version.sharing != sharing {
+            fields.push(field(
+                "sharing",
+                to_value(&version.sharing, new),
+                to_value(&sharing, false),
+            ));
+        }
+    }
+    text_change(&mut fields, "due_date", &version.due_date, &spec.due_date);
+    text_change(&mut fields, "description", &version.description, &spec.description);
+
+    fields
+}
+
+/// Serializes a value of a live entity, which is `null` for new entities.
+///
+/// # Arguments
+///
+/// * `value` - the value to serialize
+/// * `new` - a boolean: true if the entity doesn't exist yet
+fn to_value<T: ::serde::Serialize>(value: &T, new: bool) -> Value {
+    if new {
+        Value::Null
+    } else {
+        serde_json::to_value(value).unwrap_or(Value::Null)
+    }
+}
+
+/// Adds the change of an optional text field if it's declared and differs. Missing texts equal
+/// empty ones, since redmine doesn't distinguish them.
+///
+/// # Arguments
+///
+/// * `fields` - the changes the change is added to
+/// * `name` - a string slice holding the name of the field
+/// * `live` - the current value
+/// * `declared` - the declared value or `None` if the field isn't managed
+fn text_change(
+    fields: &mut Vec<FieldChange>,
+    name: &str,
+    live: &Option<String>,
+    declared: &Option<String>,
+) {
+    if let Some(ref new) = *declared {
+        let old = live.as_ref().map_or("", |s| s.as_str());
+        if old != new {
+            let old = live.as_ref().map_or(Value::Null, |s| s.as_str().into());
+            fields.push(field(name, old, new.as_str().into()));
+        }
+    }
+}
+
+/// Creates a field change.
+fn field(name: &str, old: Value, new: Value) -> FieldChange {
+    FieldChange {
+        field: name.to_string(),
+        old: old,
+        new: new,
+    }
+}
+
+/// Returns the new values of field changes as json object.
+fn fields_body(fields: &[FieldChange]) -> serde_json::Map<String, Value> {
+    fields
+        .iter()
+        .map(|f| (f.field.clone(), f.new.clone()))
+        .collect()
+}
+
+/// Wraps a json object into `key`, e.g. `{"project": {...}}`.
+fn wrap(key: &str, body: serde_json::Map<String, Value>) -> Value {
+    let mut wrapper = serde_json::Map::new();
+    wrapper.insert(key.to_string(), Value::Object(body));
+    Value::Object(wrapper)
+}
+
+/// Formats field changes, e.g. `: name "Infra" -> "Infrastructure", is_public true`.
+fn format_fields(fields: &[FieldChange]) -> String {
+    let formatted: Vec<String> = fields
+        .iter()
+        .map(|f| if f.old.is_null() {
+            format!("{} {}", f.field, f.new)
+        } else {
+            format!("{} {} -> {}", f.field, f.old, f.new)
+        })
+        .collect();
+
+    if formatted.is_empty() {
+        String::new()
+    } else {
+        format!(": {}", formatted.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{self, Value};
+    use std::io::Cursor;
+    use url::Url;
+    use super::super::errors::*;
+    use super::super::{http, RedmineApi};
+    use super::{field, Change, CustomFieldChange, Reconciler, Spec};
+
+    const PROJECTS: &str = r#"{"projects": [{
+        "id": 1,
+        "name": "Redmine",
+        "identifier": "redmine",
+        "description": "Project management",
+        "homepage": "http://www.redmine.org",
+        "status": 1,
+        "is_public": true,
+        "custom_fields": [{"id": 3, "name": "Customer", "value": "ACME"}],
+        "created_on": "2017-01-02T09:00:00Z",
+        "updated_on": "2017-09-16T10:11:12Z"
+    }], "total_count": 1}"#;
+
+    const MEMBERSHIPS: &str = r#"{"memberships": [
+        {"id": 11, "project": {"id": 1, "name": "Redmine"}, "user": {"id": 5, "name": "A"},
+         "roles": [{"id": 3, "name": "Manager"}]},
+        {"id": 12, "project": {"id": 1, "name": "Redmine"}, "user": {"id": 6, "name": "B"},
+         "roles": [{"id": 4, "name": "Developer"}]},
+        {"id": 13, "project": {"id": 1, "name": "Redmine"}, "user": {"id": 7, "name": "C"},
+         "roles": [{"id": 4, "name": "Developer", "inherited": true}]},
+        {"id": 14, "project": {"id": 1, "name": "Redmine"}, "group": {"id": 9, "name": "D"},
+         "roles": [{"id": 4, "name": "Developer"}]}
+    ], "total_count": 4}"#;
+
+    const VERSIONS: &str = r#"{"versions": [
+        {"id": 21, "project": {"id": 1, "name": "Redmine"}, "name": "1.0", "status": "open",
+         "sharing": "none", "created_on": "", "updated_on": ""},
+        {"id": 22, "project": {"id": 1, "name": "Redmine"}, "name": "2.0", "status": "open",
+         "sharing": "none", "due_date": "2018-01-31", "created_on": "", "updated_on": ""},
+        {"id": 23, "project": {"id": 2, "name": "Shared"}, "name": "Shared", "status": "open",
+         "sharing": "system", "created_on": "", "updated_on": ""}
+    ], "total_count": 3}"#;
+
+    /// Responds to the list requests of planning with the samples above.
+    struct Canned;
+    impl http::Transport for Canned {
+        fn send(&self, request: http::Request) -> Result<http::Response> {
+            let url = Url::parse(&request.url).unwrap();
+            let body = match url.path() {
+                "/projects.json" => PROJECTS,
+                "/projects/1/memberships.json" => MEMBERSHIPS,
+                "/projects/1/versions.json" => VERSIONS,
+                path => bail!("Unexpected request: {}", path),
+            };
+
+            Ok(http::Response {
+                status: 200,
+                location: None,
+                body: Box::new(Cursor::new(body.as_bytes().to_vec())),
+            })
+        }
+    }
+
+    fn plan(spec: &str, prune: bool) -> Vec<Change> {
+        let api = RedmineApi::with_transport(
+            "http://localhost".to_string(),
+            "1234".to_string(),
+            Box::new(Canned),
+        );
+        let spec: Spec = serde_json::from_str(spec).unwrap();
+
+        Reconciler::new(&api, spec).prune(prune).plan().unwrap().changes
+    }
+
+    #[test]
+    fn missing_project_is_created() {
+        let changes = plan(
+            r#"{"projects": [{
+                "identifier": "infra",
+                "name": "Infrastructure",
+                "is_public": false,
+                "custom_fields": {"Customer": "ACME"},
+                "memberships": [{"user_id": 5, "role_ids": [4, 3, 4]}],
+                "versions": [{"name": "1.0", "status": "locked"}]
+            }]}"#,
+            true,
+        );
+
+        assert_eq!(
+            changes,
+            vec![
+                Change::CreateProject {
+                    identifier: "infra".to_string(),
+                    fields: vec![
+                        field("name", Value::Null, "Infrastructure".into()),
+                        field("is_public", Value::Null, false.into()),
+                    ],
+                },
+                Change::UpdateProject {
+                    identifier: "infra".to_string(),
+                    fields: vec![],
+                    custom_fields: vec![
+                        CustomFieldChange {
+                            id: None,
+                            name: "Customer".to_string(),
+                            old: Value::Null,
+                            new: "ACME".into(),
+                        },
+                    ],
+                },
+                Change::AddMembership {
+                    identifier: "infra".to_string(),
+                    user_id: 5,
+                    role_ids: vec![3, 4],
+                },
+                Change::CreateVersion {
+                    identifier: "infra".to_string(),
+                    fields: vec![
+                        field("name", Value::Null, "1.0".into()),
+                        field("status", Value::Null, "locked".into()),
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn declared_state_is_reached_already() {
+        let changes = plan(
+            r#"{"projects": [{
+                "identifier": "redmine",
+                "name": "Redmine",
+                "homepage": "http://www.redmine.org",
+                "is_public": true,
+                "custom_fields": {"Customer": "ACME"},
+                "memberships": [
+                    {"user_id": 5, "role_ids": [3]},
+                    {"user_id": 6, "role_ids": [4]},
+                    {"user_id": 9, "role_ids": [4]}
+                ],
+                "versions": [{"name": "1.0", "status": "open"}, {"name": "2.0"}]
+            }]}"#,
+            true,
+        );
+
+        assert_eq!(changes, vec![]);
+    }
+
+    #[test]
+    fn fields_are_diffed() {
+        let changes = plan(
+            r#"{"projects": [{
+                "identifier": "redmine",
+                "name": "Redmine",
+                "description": "",
+                "homepage": "http://www.redmine.org",
+                "is_public": false,
+                "custom_fields": {"Customer": "ACME", "Tier": "Gold"}
+            }]}"#,
+            true,
+        );
+
+        assert_eq!(
+            changes,
+            vec![
+                Change::UpdateProject {
+                    identifier: "redmine".to_string(),
+                    fields: vec![
+                        field("description", "Project management".into(), "".into()),
+                        field("is_public", true.into(), false.into()),
+                    ],
+                    custom_fields: vec![
+                        CustomFieldChange {
+                            id: None,
+                            name: "Tier".to_string(),
+                            old: Value::Null,
+                            new: "Gold".into(),
+                        },
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn memberships_are_diffed() {
+        let spec = r#"{"projects": [{
+            "identifier": "redmine",
+            "name": "Redmine",
+            "memberships": [{"user_id": 5, "role_ids": [4, 3]}, {"user_id": 8, "role_ids": [3]}]
+        }]}"#;
+        let update = Change::UpdateMembership {
+            identifier: "redmine".to_string(),
+            membership_id: 11,
+            user_id: 5,
+            old_role_ids: vec![3],
+            role_ids: vec![3, 4],
+        };
+        let add = Change::AddMembership {
+            identifier: "redmine".to_string(),
+            user_id: 8,
+            role_ids: vec![3],
+        };
+
+        assert_eq!(plan(spec, false), vec![update.clone(), add.clone()]);
+
+        // memberships with inherited roles only are kept
+        let remove = |membership_id, user_id| {
+            Change::RemoveMembership {
+                identifier: "redmine".to_string(),
+                membership_id: membership_id,
+                user_id: user_id,
+            }
+        };
+        assert_eq!(plan(spec, true), vec![update, add, remove(12, 6), remove(14, 9)]);
+    }
+
+    #[test]
+    fn versions_are_diffed() {
+        let spec = r#"{"projects": [{
+            "identifier": "redmine",
+            "name": "Redmine",
+            "versions": [
+                {"name": "1.0", "status": "closed", "due_date": "2017-10-31"},
+                {"name": "3.0", "sharing": "descendants"}
+            ]
+        }]}"#;
+        let update = Change::UpdateVersion {
+            identifier: "redmine".to_string(),
+            version_id: 21,
+            name: "1.0".to_string(),
+            fields: vec![
+                field("status", "open".into(), "closed".into()),
+                field("due_date", Value::Null, "2017-10-31".into()),
+            ],
+        };
+        let create = Change::CreateVersion {
+            identifier: "redmine".to_string(),
+            fields: vec![
+                field("name", Value::Null, "3.0".into()),
+                field("sharing", Value::Null, "descendants".into()),
+            ],
+        };
+
+        assert_eq!(plan(spec, false), vec![update.clone(), create.clone()]);
+
+        // versions shared by other projects aren't deleted
+        let delete = Change::DeleteVersion {
+            identifier: "redmine".to_string(),
+            version_id: 22,
+            name: "2.0".to_string(),
+        };
+        assert_eq!(plan(spec, true), vec![update, create, delete]);
+    }
+}
@@ -0,0 +1,306 @@
+//! This module holds everything needed to represent the redmine issue relations api as described
+//! by following link: http://www.redmine.org/projects/redmine/wiki/Rest_IssueRelations.
+
+extern crate serde_json;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use super::errors::*;
+use super::{failure, ClientHandle};
+
+/// This struct exposes all methods provided by the redmine issue relations api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns RelationListExecutor struct which offers an `execute` function which returns the
+    /// relations of the issue specified by `issue_id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_id` - an integer holding the issue id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.relations().list(1).execute();
+    /// ```
+    pub fn list(&self, issue_id: u32) -> RelationListExecutor {
+        RelationListExecutor::new(
+            self.client.clone(),
+            format!("/issues/{}/relations.json", issue_id),
+        )
+    }
+
+    /// Returns RelationShow struct which offers an `execute` function which returns the relation
+    /// specified by `id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the relation id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.relations().show(1).execute();
+    /// ```
+    pub fn show(&self, id: u32) -> RelationShow {
+        RelationShow::new(self.client.clone(), id)
+    }
+
+    /// Returns RelationCreate struct which offers an `execute` function which relates the issue
+    /// specified by `issue_id` to the one specified by `issue_to_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_id` - an integer holding the id of the issue the relation starts from
+    /// * `issue_to_id` - an integer holding the id of the related issue
+    /// * `relation_type` - a [RelationType](enum.RelationType.html)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::relations::RelationType;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.relations()
+    ///     .create(1, 2, RelationType::Precedes { delay: Some(3) })
+    ///     .execute();
+    /// ```
+    pub fn create(
+        &self,
+        issue_id: u32,
+        issue_to_id: u32,
+        relation_type: RelationType,
+    ) -> RelationCreate {
+        RelationCreate {
+            client: self.client.clone(),
+            issue_id: issue_id,
+            issue_to_id: issue_to_id,
+            relation_type: relation_type,
+        }
+    }
+
+    /// Returns RelationDelete struct which offers an `execute` function which deletes the
+    /// relation specified by `id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the relation id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.relations().delete(1).execute();
+    /// ```
+    pub fn delete(&self, id: u32) -> RelationDelete {
+        RelationDelete::new(self.client.clone(), id)
+    }
+}
+
+show_endpoint! {
+    /// Helper struct to provide a unified interface for all relation api methods.
+    RelationShow => Relation, relation, "/relations/{}.json"
+}
+
+list_endpoint! {
+    /// Helper struct to provide a unified interface for all relation api methods.
+    RelationListExecutor => RelationList<Relation>, relations
+}
+
+delete_endpoint! {
+    /// Helper struct to provide a unified interface for all relation api methods.
+    RelationDelete, "/relations/{}.json"
+}
+
+/// Helper struct to provide a unified interface for all relation api methods.
+pub struct RelationCreate {
+    client: ClientHandle,
+    issue_id: u32,
+    issue_to_id: u32,
+    relation_type: RelationType,
+}
+impl RelationCreate {
+    /// Performs request to redmine application and returns the created relation.
+    pub fn execute(&self) -> Result<Relation> {
+        #[derive(Serialize)]
+        struct Fields<'a> {
+            issue_to_id: u32,
+            relation_type: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            delay: Option<i32>,
+        }
+        #[derive(Serialize)]
+        struct Wrapper<'a> {
+            relation: Fields<'a>,
+        }
+        #[derive(Deserialize)]
+        struct Created {
+            relation: Relation,
+        }
+
+        let wrapper = Wrapper {
+            relation: Fields {
+                issue_to_id: self.issue_to_id,
+                relation_type: self.relation_type.name(),
+                delay: self.relation_type.delay(),
+            },
+        };
+        let mut response = self.client.post(
+            &format!("/issues/{}/relations.json", self.issue_id),
+            &wrapper,
+        )?;
+        let body = response.text()?;
+        if !response.is_success() {
+            return Err(failure(response.status, &body));
+        }
+
+        let created: Created = serde_json::from_str(&body).chain_err(|| "Can't parse json")?;
+        Ok(created.relation)
+    }
+}
+
+/// Enumeration of the types of relations between issues. Relations are directed: the issue a
+/// relation starts from e.g. blocks or precedes the related issue.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum RelationType {
+    #[default]
+    Relates,
+    Duplicates,
+    Duplicated,
+    Blocks,
+    Blocked,
+    /// The related issue can't start before this one is finished, optionally `delay` days later.
+    Precedes { delay: Option<i32> },
+    Follows { delay: Option<i32> },
+    CopiedTo,
+    CopiedFrom,
+    /// A relation type unknown to this library, e.g. added by a plugin.
+    Other(String),
+}
+impl RelationType {
+    /// Returns the name redmine uses for the relation type, e.g. `copied_to`.
+    pub fn name(&self) -> &str {
+        match *self {
+            RelationType::Relates => "relates",
+            RelationType::Duplicates => "duplicates",
+            RelationType::Duplicated => "duplicated",
+            RelationType::Blocks => "blocks",
+            RelationType::Blocked => "blocked",
+            RelationType::Precedes { .. } => "precedes",
+            RelationType::Follows { .. } => "follows",
+            RelationType::CopiedTo => "copied_to",
+            RelationType::CopiedFrom => "copied_from",
+            RelationType::Other(ref name) => name,
+        }
+    }
+
+    /// Returns the delay in days of `Precedes` and `Follows` relations.
+    pub fn delay(&self) -> Option<i32> {
+        match *self {
+            RelationType::Precedes { delay } |
+            RelationType::Follows { delay } => delay,
+            _ => None,
+        }
+    }
+
+    /// Creates the relation type from the name redmine uses and the delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name, e.g. `blocks`
+    /// * `delay` - the delay in days, only used by `precedes` and `follows`
+    pub fn from_name(name: &str, delay: Option<i32>) -> Self {
+        match name {
+            "relates" => RelationType::Relates,
+            "duplicates" => RelationType::Duplicates,
+            "duplicated" => RelationType::Duplicated,
+            "blocks" => RelationType::Blocks,
+            "blocked" => RelationType::Blocked,
+            "precedes" => RelationType::Precedes { delay: delay },
+            "follows" => RelationType::Follows { delay: delay },
+            "copied_to" => RelationType::CopiedTo,
+            "copied_from" => RelationType::CopiedFrom,
+            _ => RelationType::Other(name.to_string()),
+        }
+    }
+}
+
+/// Represents a relation between two issues as pulled from redmine application.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Relation {
+    pub id: u32,
+    pub issue_id: u32,
+    pub issue_to_id: u32,
+    pub relation_type: RelationType,
+}
+
+/// Representation of a relation in json, which holds the delay next to the relation type.
+#[derive(Serialize, Deserialize)]
+struct RawRelation {
+    id: u32,
+    issue_id: u32,
+    issue_to_id: u32,
+    relation_type: String,
+    delay: Option<i32>,
+}
+impl Serialize for Relation {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        RawRelation {
+            id: self.id,
+            issue_id: self.issue_id,
+            issue_to_id: self.issue_to_id,
+            relation_type: self.relation_type.name().to_string(),
+            delay: self.relation_type.delay(),
+        }.serialize(serializer)
+    }
+}
+impl<'de> Deserialize<'de> for Relation {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = RawRelation::deserialize(deserializer)?;
+
+        Ok(Relation {
+            id: raw.id,
+            issue_id: raw.issue_id,
+            issue_to_id: raw.issue_to_id,
+            relation_type: RelationType::from_name(&raw.relation_type, raw.delay),
+        })
+    }
+}
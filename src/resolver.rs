@@ -0,0 +1,158 @@
+//! This module provides a [Resolver](struct.Resolver.html) which holds the full records of users,
+//! versions and categories referenced by a list of issues. The records are fetched once per
+//! distinct id (or once per project for versions and categories), so rendering code can expand
+//! references like `issue.assigned_to` without a show request per issue.
+
+extern crate serde_json;
+
+use std::collections::{BTreeSet, HashMap};
+use super::errors::*;
+use super::issues::Issue;
+use super::users::User;
+use super::{failure, fetch_all, http, ClientHandle};
+
+/// Holds prefetched users, versions and categories. Is returned by
+/// [IssueList::resolver](../issues/struct.IssueList.html#method.resolver) if the list has been
+/// requested with `prefetch`. Versions and categories are kept as raw json.
+#[derive(Debug, Default)]
+pub struct Resolver {
+    users: HashMap<u32, User>,
+    versions: HashMap<u32, serde_json::Value>,
+    categories: HashMap<u32, serde_json::Value>,
+}
+impl Resolver {
+    /// Collects all users, versions and categories referenced by `issues` and fetches them in as
+    /// few requests as possible. Users which aren't found, e.g. groups an issue is assigned to or
+    /// users the api key isn't allowed to see, are left out; any other failure is returned.
+    /// Should not be called externally.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - the client performing the requests
+    /// * `issues` - the issues whose references should be resolved
+    pub fn prefetch(client: ClientHandle, issues: &[Issue]) -> Result<Self> {
+        let mut user_ids = BTreeSet::new();
+        let mut project_ids = BTreeSet::new();
+        for issue in issues {
+            user_ids.insert(issue.author.id);
+            if let Some(ref a) = issue.assigned_to {
+                user_ids.insert(a.id);
+            }
+            if issue.fixed_version.is_some() || issue.category.is_some() {
+                project_ids.insert(issue.project.id);
+            }
+        }
+
+        let mut resolver = Resolver::default();
+
+        #[derive(Deserialize)]
+        struct Wrapper {
+            user: User,
+        }
+
+        for id in user_ids {
+            let url = client.get_base_url(&format!("/users/{}.json", id))?;
+            let mut response = client.send(http::Method::Get, url, None)?;
+            if response.status == 404 {
+                continue;
+            }
+
+            let body = response.text()?;
+            if !response.is_success() {
+                return Err(failure(response.status, &body));
+            }
+            let wrapper: Wrapper = serde_json::from_str(&body).chain_err(|| "Can't parse json")?;
+            resolver.users.insert(id, wrapper.user);
+        }
+
+        for id in project_ids {
+            // versions shared with the project are included in its list
+            let versions = fetch_all(
+                &client,
+                &(format!("/projects/{}/versions.json", id)),
+                "versions",
+            )?;
+            for v in versions {
+                if let Some(id) = v.get("id").and_then(|i| i.as_u64()) {
+                    resolver.versions.insert(id as u32, v);
+                }
+            }
+
+            let categories = fetch_all(
+                &client,
+                &(format!("/projects/{}/issue_categories.json", id)),
+                "issue_categories",
+            )?;
+            for c in categories {
+                if let Some(id) = c.get("id").and_then(|i| i.as_u64()) {
+                    resolver.categories.insert(id as u32, c);
+                }
+            }
+        }
+
+        Ok(resolver)
+    }
+
+    /// Returns a prefetched user.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the user id
+    pub fn user(&self, id: u32) -> Option<&User> {
+        self.users.get(&id)
+    }
+
+    /// Returns a prefetched version as raw json.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the version id
+    pub fn version(&self, id: u32) -> Option<&serde_json::Value> {
+        self.versions.get(&id)
+    }
+
+    /// Returns a prefetched category as raw json.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the category id
+    pub fn category(&self, id: u32) -> Option<&serde_json::Value> {
+        self.categories.get(&id)
+    }
+
+    /// Expands the assignee of an issue to the full user record.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue` - the issue whose assignee should be resolved
+    pub fn assigned_to(&self, issue: &Issue) -> Option<&User> {
+        issue.assigned_to.as_ref().and_then(|a| self.user(a.id))
+    }
+
+    /// Expands the author of an issue to the full user record.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue` - the issue whose author should be resolved
+    pub fn author(&self, issue: &Issue) -> Option<&User> {
+        self.user(issue.author.id)
+    }
+
+    /// Expands the target version of an issue to the full version record.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue` - the issue whose version should be resolved
+    pub fn fixed_version(&self, issue: &Issue) -> Option<&serde_json::Value> {
+        issue.fixed_version.as_ref().and_then(|v| self.version(v.id))
+    }
+
+    /// Expands the category of an issue to the full category record.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue` - the issue whose category should be resolved
+    pub fn category_of(&self, issue: &Issue) -> Option<&serde_json::Value> {
+        issue.category.as_ref().and_then(|c| self.category(c.id))
+    }
+}
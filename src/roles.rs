@@ -0,0 +1,154 @@
+//! This module holds everything needed to represent the redmine roles api as described by
+//! following link: http://www.redmine.org/projects/redmine/wiki/Rest_Roles.
+
+extern crate serde_json;
+
+use std::collections::HashMap;
+use super::errors::*;
+use super::ClientHandle;
+
+/// This struct exposes all methods provided by the redmine roles api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns RoleListExecutor struct which provides an `execute` function for retrieving all
+    /// roles. Listed roles hold neither permissions nor visibilities, see
+    /// [show](#method.show).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(roles) = redmine.roles().list().execute() {
+    ///     if let Some(developer) = roles.by_name("Developer") {
+    ///         let result = redmine.memberships().create(1, 5, vec![developer.id]).execute();
+    ///     }
+    /// }
+    /// ```
+    pub fn list(&self) -> RoleListExecutor {
+        RoleListExecutor { client: self.client.clone() }
+    }
+
+    /// Returns RoleShow struct which offers an `execute` function which returns the role
+    /// specified by `id` parameter together with its permissions.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the role id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(role) = redmine.roles().show(1).execute() {
+    ///     println!("May edit issues: {}", role.has_permission("edit_issues"));
+    /// }
+    /// ```
+    pub fn show(&self, id: u32) -> RoleShow {
+        RoleShow::new(self.client.clone(), id)
+    }
+}
+
+/// Helper struct to provide a unified interface for all role api methods.
+pub struct RoleListExecutor {
+    client: ClientHandle,
+}
+impl RoleListExecutor {
+    /// Performs request to redmine application and returns a list of roles.
+    pub fn execute(&self) -> Result<RoleList> {
+        let result = self.client.get("/roles.json", &HashMap::new())?;
+
+        serde_json::from_str(&result).chain_err(|| "Can't parse json")
+    }
+}
+
+show_endpoint! {
+    /// Helper struct to provide a unified interface for all role api methods.
+    RoleShow => Role, role, "/roles/{}.json"
+}
+
+/// Holds a vector of [Role](struct.Role.html)s in the order configured in redmine application.
+/// Implements IntoIterator trait for easy iteration.
+#[derive(Deserialize, Debug, Default)]
+pub struct RoleList {
+    roles: Vec<Role>,
+}
+impl RoleList {
+    /// Returns the role with the given id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the role id
+    pub fn by_id(&self, id: u32) -> Option<&Role> {
+        self.roles.iter().find(|r| r.id == id)
+    }
+
+    /// Returns the role with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the role
+    pub fn by_name(&self, name: &str) -> Option<&Role> {
+        self.roles.iter().find(|r| r.name == name)
+    }
+}
+impl IntoIterator for RoleList {
+    type Item = Role;
+    type IntoIter = ::std::vec::IntoIter<Role>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.roles.into_iter()
+    }
+}
+
+/// Represents a role of a redmine application. Everything but id and name is only reported if
+/// the role is pulled by [show](struct.Api.html#method.show).
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Role {
+    pub id: u32,
+    pub name: String,
+    /// Is `Some(false)` if issues can't be assigned to members with this role.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignable: Option<bool>,
+    /// Which issues members with this role can see, e.g. `default`, `all` or `own`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issues_visibility: Option<String>,
+    /// Which time entries members with this role can see, e.g. `all` or `own`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_entries_visibility: Option<String>,
+    /// Which users members with this role can see, e.g. `all` or `members_of_visible_projects`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub users_visibility: Option<String>,
+    /// The permissions of the role, e.g. `add_issues` or `edit_issues`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub permissions: Vec<String>,
+}
+impl Role {
+    /// Returns true if the role has the permission `name`, e.g. `edit_issues`. Requires the role
+    /// to be pulled by [show](struct.Api.html#method.show).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the permission
+    pub fn has_permission(&self, name: &str) -> bool {
+        self.permissions.iter().any(|p| p == name)
+    }
+}
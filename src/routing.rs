@@ -0,0 +1,210 @@
+//! This module routes issue changes to notification targets on the client side, e.g. for
+//! chat-ops bots polling redmine application. [RoutingRules](struct.RoutingRules.html) are loaded
+//! from any serde format and evaluated in order against an issue and the changes pulled for it,
+//! usually computed by [Issue::diff](../issues/struct.Issue.html#method.diff).
+//!
+//! A rule matches if all of its conditions match; a condition listing several values matches if
+//! any of them does. Projects, trackers, priorities and statuses are given by id or name. The
+//! first matching rule decides unless it's marked `continue`, in which case later rules are
+//! evaluated, too. An `ignore` rule drops all decisions made so far.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate redmine_api;
+//! extern crate serde_json;
+//!
+//! use redmine_api::issues::Issue;
+//! use redmine_api::routing::{Action, RoutingRules};
+//!
+//! fn main() {
+//!     let rules: RoutingRules = serde_json::from_str(r##"{
+//!       "rules": [
+//!         {"when": {"tracker": ["Support"], "changed": ["Description"]}, "then": "ignore"},
+//!         {"when": {"priority": ["Urgent", "Immediate"]}, "then": "escalate", "continue": true},
+//!         {"when": {"project": ["Infrastructure", 12]}, "then": {"notify": "#ops"}},
+//!         {"when": {"custom_fields": {"Customer": ["ACME"]}}, "then": {"notify": "#acme"}}
+//!       ]
+//!     }"##).unwrap();
+//!
+//!     let before = Issue::default();
+//!     let after = Issue::default();
+//!     for action in rules.route(&after, &before.diff(&after)) {
+//!         match action {
+//!             Action::Notify(channel) => println!("Notify {}", channel),
+//!             Action::Escalate => println!("Escalate #{}", after.id),
+//!             Action::Ignore => {}
+//!         }
+//!     }
+//! }
+//! ```
+
+extern crate serde_json;
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use super::issues::{FieldChange, Issue};
+use super::NamedObject;
+
+/// Ordered list of routing rules.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct RoutingRules {
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+impl RoutingRules {
+    /// Returns the decisions of the rules matching an issue, in the order of the rules. Is empty
+    /// if no rule matches or an `ignore` rule does.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue` - the current state of the issue
+    /// * `changes` - the changes pulled for the issue, empty for new issues
+    pub fn route(&self, issue: &Issue, changes: &[FieldChange]) -> Vec<Action> {
+        self.decide(issue, changes)
+            .into_iter()
+            .map(|d| d.action)
+            .collect()
+    }
+
+    /// Returns the decisions of the rules matching an issue together with the names of the
+    /// rules, e.g. for logging why a notification has been sent. Is empty if no rule matches or
+    /// an `ignore` rule does.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue` - the current state of the issue
+    /// * `changes` - the changes pulled for the issue, empty for new issues
+    pub fn decide(&self, issue: &Issue, changes: &[FieldChange]) -> Vec<Decision> {
+        let mut decisions = Vec::new();
+        for rule in self.rules.iter().filter(|r| r.when.matches(issue, changes)) {
+            if rule.then == Action::Ignore {
+                return Vec::new();
+            }
+
+            decisions.push(Decision {
+                rule: rule.name.clone(),
+                action: rule.then.clone(),
+            });
+            if !rule.fallthrough {
+                break;
+            }
+        }
+
+        decisions
+    }
+}
+
+/// A single routing rule.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Rule {
+    /// Optional name, reported in [Decision](struct.Decision.html)s.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub when: Conditions,
+    pub then: Action,
+    /// Whether later rules are evaluated if this one matches. Is named `continue` when
+    /// serialized. Defaults to false.
+    #[serde(default, rename = "continue")]
+    pub fallthrough: bool,
+}
+
+/// Conditions of a rule, all of which have to match. Empty conditions always match.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Conditions {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub project: Vec<Ref>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tracker: Vec<Ref>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub priority: Vec<Ref>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status: Vec<Ref>,
+    /// Accepted values by custom field name. A multiple value field matches if any of its values
+    /// is accepted.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom_fields: BTreeMap<String, Vec<String>>,
+    /// Names of fields as reported by [Issue::diff](../issues/struct.Issue.html#method.diff),
+    /// e.g. `Status` or the name of a custom field, of which at least one has to be changed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed: Vec<String>,
+}
+impl Conditions {
+    /// Checks if an issue and its changes match all conditions.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue` - the current state of the issue
+    /// * `changes` - the changes pulled for the issue
+    pub fn matches(&self, issue: &Issue, changes: &[FieldChange]) -> bool {
+        any_ref(&self.project, &issue.project) &&
+            any_ref(&self.tracker, &issue.tracker) &&
+            any_ref(&self.priority, &issue.priority) &&
+            any_ref(&self.status, &issue.status) &&
+            self.custom_fields.iter().all(|(name, accepted)| {
+                issue.custom_field(name).is_some_and(|cf| {
+                    values(&cf.value).iter().any(|v| accepted.iter().any(|a| a == v))
+                })
+            }) &&
+            (self.changed.is_empty() ||
+                 changes.iter().any(|c| self.changed.contains(&c.field)))
+    }
+}
+
+/// Refers to a project, tracker, priority or status by id or name. Issues only carry the name of
+/// their project, so projects can't be referred to by identifier.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Ref {
+    Id(u32),
+    Name(String),
+}
+impl Ref {
+    /// Checks if the reference matches an object.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - the referenced object of an issue
+    pub fn matches(&self, object: &NamedObject) -> bool {
+        match *self {
+            Ref::Id(id) => object.id() == id,
+            Ref::Name(ref name) => object.name() == name,
+        }
+    }
+}
+
+/// Routing decision of a rule.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Notifies the given channel, e.g. a chat room or mailing list; the meaning is up to the
+    /// caller.
+    Notify(String),
+    /// Escalates the issue, e.g. pages the person on call.
+    Escalate,
+    /// Drops the issue; no notification is sent.
+    Ignore,
+}
+
+/// Holds an action together with the name of the rule deciding it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decision {
+    pub rule: Option<String>,
+    pub action: Action,
+}
+
+/// Checks if no reference is given or any of them matches the object.
+fn any_ref(refs: &[Ref], object: &NamedObject) -> bool {
+    refs.is_empty() || refs.iter().any(|r| r.matches(object))
+}
+
+/// Returns the values of a custom field as strings.
+fn values(value: &Value) -> Vec<String> {
+    match *value {
+        Value::Null => Vec::new(),
+        Value::String(ref s) => vec![s.clone()],
+        Value::Array(ref values) => values.iter().flat_map(self::values).collect(),
+        ref other => vec![other.to_string()],
+    }
+}
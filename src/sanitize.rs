@@ -0,0 +1,581 @@
+//! This module makes text pulled from redmine application safe to embed in web pages. Issue
+//! descriptions, notes and wiki pages are written by users, so embedding them unchecked lets
+//! anyone who can write a ticket run scripts in the pages of the embedding application.
+//!
+//! Text in redmine's markup (Textile or Markdown) is embedded as is after
+//! [escape](fn.escape.html)-ing it. HTML, e.g. markup rendered by the embedding application, is
+//! cleaned by a [Sanitizer](struct.Sanitizer.html), which keeps a small set of formatting
+//! elements, drops everything else and makes relative links absolute, so they point to redmine
+//! application instead of the embedding one.
+//!
+//! # Example
+//!
+//! ```
+//! use redmine_api::RedmineApi;
+//!
+//! let redmine = RedmineApi::new(
+//!     "http://www.redmine.org/".to_string(),
+//!     "1234".to_string()
+//! );
+//!
+//! let html = r#"<p onclick="steal()">See <a href="/issues/2">#2</a></p><script>steal()</script>"#;
+//! assert_eq!(
+//!     redmine.sanitizer().clean_html(html),
+//!     r#"<p>See <a href="http://www.redmine.org/issues/2">#2</a></p>"#
+//! );
+//! ```
+
+use url::Url;
+
+/// Elements which are kept, together with their attributes which are kept.
+const ALLOWED: &[(&str, &[&str])] = &[
+    ("a", &["href", "title"]),
+    ("abbr", &["title"]),
+    ("b", &[]),
+    ("blockquote", &[]),
+    ("br", &[]),
+    ("caption", &[]),
+    ("cite", &[]),
+    ("code", &["class"]),
+    ("del", &[]),
+    ("div", &["class"]),
+    ("em", &[]),
+    ("h1", &[]),
+    ("h2", &[]),
+    ("h3", &[]),
+    ("h4", &[]),
+    ("h5", &[]),
+    ("h6", &[]),
+    ("hr", &[]),
+    ("i", &[]),
+    ("img", &["src", "alt", "title", "width", "height"]),
+    ("ins", &[]),
+    ("li", &[]),
+    ("ol", &["start"]),
+    ("p", &[]),
+    ("pre", &["class"]),
+    ("s", &[]),
+    ("span", &["class"]),
+    ("strong", &[]),
+    ("sub", &[]),
+    ("sup", &[]),
+    ("table", &[]),
+    ("tbody", &[]),
+    ("td", &["colspan", "rowspan"]),
+    ("tfoot", &[]),
+    ("th", &["colspan", "rowspan"]),
+    ("thead", &[]),
+    ("tr", &[]),
+    ("u", &[]),
+    ("ul", &[]),
+];
+
+/// Elements which are dropped together with their content.
+const DROPPED: &[&str] = &[
+    "applet",
+    "embed",
+    "frame",
+    "frameset",
+    "head",
+    "iframe",
+    "math",
+    "noscript",
+    "object",
+    "script",
+    "select",
+    "style",
+    "svg",
+    "template",
+    "textarea",
+    "title",
+];
+
+/// Elements which have no content and no closing tag.
+const VOID: &[&str] = &["br", "hr", "img"];
+
+/// Escapes text for embedding in HTML, e.g. descriptions in Textile or Markdown which are shown
+/// as they are.
+///
+/// # Arguments
+///
+/// * `text` - a string slice holding the text
+///
+/// # Example
+///
+/// ```
+/// use redmine_api::sanitize::escape;
+///
+/// assert_eq!(escape("<b>\"bold\"</b>"), "&lt;b&gt;&quot;bold&quot;&lt;/b&gt;");
+/// ```
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Cleans HTML for embedding in web pages. Is returned by
+/// [RedmineApi::sanitizer](../struct.RedmineApi.html#method.sanitizer).
+///
+/// Only formatting elements like paragraphs, lists, tables, links and images are kept, with
+/// few attributes; event handlers and styles are always dropped. Scripts, styles, frames and
+/// embedded objects are dropped together with their content. Links and images may only refer to
+/// `http`, `https` and (links only) `mailto` urls; relative urls are resolved against redmine
+/// application. Unclosed elements are closed, so the result can't break the surrounding page.
+#[derive(Debug, Clone)]
+pub struct Sanitizer {
+    base: Option<Url>,
+}
+impl Sanitizer {
+    /// Creates a new instance resolving relative urls against `base_url`. Relative urls are
+    /// kept as they are if `base_url` isn't a valid url.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - a string slice holding the url of the redmine application
+    pub fn new(base_url: &str) -> Self {
+        // without trailing slash, the last segment of sub path installations is replaced
+        let base = format!("{}/", base_url.trim_end_matches('/'));
+
+        Sanitizer { base: Url::parse(&base).ok() }
+    }
+
+    /// Returns the cleaned HTML.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - a string slice holding the HTML to clean
+    pub fn clean_html(&self, html: &str) -> String {
+        let mut out = String::with_capacity(html.len());
+        let mut open: Vec<&'static str> = Vec::new();
+        let mut rest = html;
+
+        while !rest.is_empty() {
+            if rest.starts_with("<!--") {
+                rest = rest.find("-->").map_or("", |i| &rest[i + 3..]);
+                continue;
+            }
+
+            let tag = if rest.starts_with('<') {
+                parse_tag(rest)
+            } else {
+                None
+            };
+            let (tag, len) = match tag {
+                Some(tag) => tag,
+                None => {
+                    // a `<` which doesn't start a tag is text, too
+                    let skip = if rest.starts_with('<') { 1 } else { 0 };
+                    let end = rest[skip..].find('<').map_or(rest.len(), |i| i + skip);
+                    push_text(&mut out, &rest[..end]);
+                    rest = &rest[end..];
+                    continue;
+                }
+            };
+            rest = &rest[len..];
+
+            if DROPPED.contains(&tag.name.as_str()) {
+                if !tag.closing && !tag.self_closing {
+                    rest = skip_element(rest, &tag.name);
+                }
+                continue;
+            }
+
+            let &(name, attributes) = match ALLOWED.iter().find(|&&(n, _)| n == tag.name) {
+                Some(allowed) => allowed,
+                None => continue,
+            };
+
+            if tag.closing {
+                // close elements opened within, drop closing tags of elements never opened
+                if let Some(i) = open.iter().rposition(|&n| n == name) {
+                    for n in open.drain(i..).rev() {
+                        out.push_str(&format!("</{}>", n));
+                    }
+                }
+                continue;
+            }
+
+            out.push('<');
+            out.push_str(name);
+            for (attr, value) in &tag.attributes {
+                if !attributes.contains(&attr.as_str()) {
+                    continue;
+                }
+                let value = match attr.as_str() {
+                    "href" => self.url(value, true),
+                    "src" => self.url(value, false),
+                    _ => Some(value.clone()),
+                };
+                if let Some(value) = value {
+                    out.push_str(&format!(" {}=\"{}\"", attr, escape(&value)));
+                }
+            }
+            out.push('>');
+
+            if !VOID.contains(&name) {
+                open.push(name);
+            }
+        }
+
+        for n in open.into_iter().rev() {
+            out.push_str(&format!("</{}>", n));
+        }
+        out
+    }
+
+    /// Returns the url to use for a link or image, or `None` if it's not allowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - a string slice holding the decoded url
+    /// * `link` - a boolean: true for links, which may use `mailto` urls, too
+    fn url(&self, value: &str, link: bool) -> Option<String> {
+        // browsers ignore whitespace and control characters, e.g. in `java\tscript:`
+        let url: String = value
+            .chars()
+            .filter(|c| !c.is_whitespace() && !c.is_control())
+            .collect();
+        if url.starts_with('#') {
+            return Some(url);
+        }
+
+        let scheme_end = url.find(&[':', '/', '?', '#'][..]);
+        if let Some(i) = scheme_end {
+            if url[i..].starts_with(':') {
+                let scheme = url[..i].to_lowercase();
+                let allowed = scheme == "http" || scheme == "https" ||
+                    (link && scheme == "mailto");
+                return if allowed { Some(url) } else { None };
+            }
+        }
+
+        match self.base {
+            Some(ref base) => base.join(&url).ok().map(|u| u.to_string()),
+            None => Some(url),
+        }
+    }
+}
+
+/// A parsed start or end tag.
+struct Tag {
+    name: String,
+    closing: bool,
+    self_closing: bool,
+    /// Names (lower case) and decoded values.
+    attributes: Vec<(String, String)>,
+}
+
+/// Parses the tag at the start of `html`, returning it with its length in bytes. Returns `None`
+/// if `html` doesn't start with a complete tag, so the `<` is text.
+///
+/// # Arguments
+///
+/// * `html` - a string slice starting with `<`
+fn parse_tag(html: &str) -> Option<(Tag, usize)> {
+    let bytes = html.as_bytes();
+    let mut i = 1;
+    let closing = bytes.get(i) == Some(&b'/');
+    if closing {
+        i += 1;
+    }
+
+    // doctypes and processing instructions are dropped like unknown elements
+    let first = *bytes.get(i)?;
+    if !(first.is_ascii_alphabetic() || (!closing && (first == b'!' || first == b'?'))) {
+        return None;
+    }
+
+    let start = i;
+    while i < bytes.len() && !is_tag_delimiter(bytes[i]) {
+        i += 1;
+    }
+    let name = html[start..i].to_lowercase();
+
+    let mut attributes = Vec::new();
+    let mut self_closing = false;
+    loop {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        match bytes.get(i) {
+            None => return None,
+            Some(&b'>') => break,
+            Some(&b'/') => {
+                self_closing = true;
+                i += 1;
+                continue;
+            }
+            _ => self_closing = false,
+        }
+
+        let start = i;
+        while i < bytes.len() && !is_tag_delimiter(bytes[i]) && bytes[i] != b'=' {
+            i += 1;
+        }
+        // a stray `=` would never be consumed otherwise
+        let attr = if i == start {
+            i += 1;
+            String::new()
+        } else {
+            html[start..i].to_lowercase()
+        };
+
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        let mut value = String::new();
+        if bytes.get(i) == Some(&b'=') {
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            match bytes.get(i) {
+                Some(&quote) if quote == b'"' || quote == b'\'' => {
+                    let end = html[i + 1..].find(quote as char)? + i + 1;
+                    value = decode(&html[i + 1..end]);
+                    i = end + 1;
+                }
+                _ => {
+                    let start = i;
+                    while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                        i += 1;
+                    }
+                    value = decode(&html[start..i]);
+                }
+            }
+        }
+
+        if !attr.is_empty() {
+            attributes.push((attr, value));
+        }
+    }
+
+    let tag = Tag {
+        name: name,
+        closing: closing,
+        self_closing: self_closing,
+        attributes: attributes,
+    };
+    Some((tag, i + 1))
+}
+
+/// Checks if a byte ends a tag or attribute name.
+fn is_tag_delimiter(b: u8) -> bool {
+    b.is_ascii_whitespace() || b == b'>' || b == b'/'
+}
+
+/// Returns the rest of `html` after the end tag of an element which is dropped with its content,
+/// or an empty string if it's never closed.
+///
+/// # Arguments
+///
+/// * `html` - a string slice holding the html following the start tag
+/// * `name` - a string slice holding the lower case name of the element
+fn skip_element<'a>(html: &'a str, name: &str) -> &'a str {
+    let lower = html.to_ascii_lowercase();
+    let end_tag = format!("</{}", name);
+    let mut from = 0;
+    while let Some(i) = lower[from..].find(&end_tag) {
+        let after = from + i + end_tag.len();
+        // `</scripts>` doesn't end a script
+        if lower.as_bytes().get(after).is_none_or(|&b| is_tag_delimiter(b)) {
+            return html[after..].find('>').map_or("", |j| &html[after + j + 1..]);
+        }
+        from = after;
+    }
+    ""
+}
+
+/// Appends text, escaping characters which would start markup. Valid character references are
+/// kept.
+///
+/// # Arguments
+///
+/// * `out` - the cleaned html
+/// * `text` - a string slice holding the text
+fn push_text(out: &mut String, text: &str) {
+    for (i, c) in text.char_indices() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' if !is_reference(&text[i..]) => out.push_str("&amp;"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Checks if `text` starts with a character reference, e.g. `&amp;` or `&#39;`.
+fn is_reference(text: &str) -> bool {
+    let end = match text.find(';') {
+        Some(end) if end > 1 => end,
+        _ => return false,
+    };
+    let name = &text[1..end];
+
+    if name.starts_with("#x") || name.starts_with("#X") {
+        name.len() > 2 && name[2..].chars().all(|c| c.is_ascii_hexdigit())
+    } else if let Some(digits) = name.strip_prefix('#') {
+        !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+    } else {
+        name.chars().all(|c| c.is_ascii_alphanumeric())
+    }
+}
+
+/// Decodes the character references of an attribute value which matter for checking urls.
+/// Other named references are kept as they are.
+///
+/// # Arguments
+///
+/// * `value` - a string slice holding the raw attribute value
+fn decode(value: &str) -> String {
+    let mut decoded = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(i) = rest.find('&') {
+        decoded.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        // numeric references may lack the semicolon, e.g. `&#106avascript:`
+        let hex = rest[1..].starts_with("#x") || rest[1..].starts_with("#X");
+        let start = if hex { 3 } else if rest[1..].starts_with('#') { 2 } else { 1 };
+        let end = rest[start..]
+            .find(|c: char| if hex {
+                !c.is_ascii_hexdigit()
+            } else if start == 2 {
+                !c.is_ascii_digit()
+            } else {
+                !c.is_ascii_alphanumeric()
+            })
+            .map_or(rest.len(), |j| j + start);
+        let name = &rest[start..end];
+        let c = if hex {
+            u32::from_str_radix(name, 16).ok().and_then(::std::char::from_u32)
+        } else if start == 2 {
+            name.parse().ok().and_then(::std::char::from_u32)
+        } else {
+            match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "colon" => Some(':'),
+                "tab" => Some('\t'),
+                "newline" => Some('\n'),
+                _ => None,
+            }
+        };
+
+        match c {
+            Some(c) => {
+                decoded.push(c);
+                rest = &rest[end..];
+                if rest.starts_with(';') {
+                    rest = &rest[1..];
+                }
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sanitizer;
+
+    fn clean(html: &str) -> String {
+        Sanitizer::new("http://www.redmine.org/").clean_html(html)
+    }
+
+    #[test]
+    fn obfuscated_javascript_urls() {
+        let hrefs = [
+            "javascript:alert(1)",
+            "JaVaScRiPt:alert(1)",
+            "&#106;avascript:alert(1)",
+            "&#106avascript:alert(1)",
+            "&#x6A;avascript:alert(1)",
+            "&#X6a;avascript:alert(1)",
+            "javascript&colon;alert(1)",
+            "java&Tab;script:alert(1)",
+            "java&tab;script:alert(1)",
+            "java&#9;script:alert(1)",
+            "java\tscript:alert(1)",
+            "java&newline;script:alert(1)",
+            "java\u{0}script:alert(1)",
+            "java&#0;script:alert(1)",
+            " \u{1}javascript:alert(1)",
+            "vbscript:msgbox(1)",
+        ];
+        for href in hrefs.iter() {
+            let html = format!("<a href=\"{}\">x</a>", href);
+            assert_eq!(clean(&html), "<a>x</a>", "{}", href);
+        }
+    }
+
+    #[test]
+    fn allowed_urls() {
+        assert_eq!(
+            clean("<a href=\"/issues/2\">#2</a>"),
+            "<a href=\"http://www.redmine.org/issues/2\">#2</a>"
+        );
+        assert_eq!(
+            clean("<a href=\"mailto:admin@example.com\">mail</a>"),
+            "<a href=\"mailto:admin@example.com\">mail</a>"
+        );
+        assert_eq!(clean("<a href=\"#note-1\">note</a>"), "<a href=\"#note-1\">note</a>");
+    }
+
+    #[test]
+    fn nested_script_tags() {
+        assert_eq!(clean("<scr<script>ipt>alert(1)</script>"), "ipt&gt;alert(1)");
+        assert_eq!(clean("<script><script>alert(1)</script>after"), "after");
+    }
+
+    #[test]
+    fn end_tags_with_whitespace() {
+        assert_eq!(clean("<script>alert(1)</script >after"), "after");
+        assert_eq!(clean("<script>alert(1)</SCRIPT\n>after"), "after");
+        assert_eq!(clean("<script>alert(1)</scripts>alert(2)"), "");
+    }
+
+    #[test]
+    fn comments() {
+        assert_eq!(clean("a<!-- <script>alert(1)</script> -->b"), "ab");
+        assert_eq!(clean("a<!-- --><script>alert(1)</script>b"), "ab");
+        assert_eq!(clean("a<!-- <script>alert(1)</script>"), "a");
+    }
+
+    #[test]
+    fn data_urls() {
+        assert_eq!(clean("<img src=\"data:image/png;base64,iVBORw0KGgo=\">"), "<img>");
+        assert_eq!(
+            clean("<a href=\"data:text/html;base64,PHNjcmlwdD4=\">x</a>"),
+            "<a>x</a>"
+        );
+    }
+
+    #[test]
+    fn style_attributes_and_elements() {
+        assert_eq!(
+            clean("<p style=\"background:url(javascript:alert(1))\">x</p>"),
+            "<p>x</p>"
+        );
+        assert_eq!(clean("<style>p { color: red }</style><p>x</p>"), "<p>x</p>");
+    }
+}
@@ -0,0 +1,51 @@
+//! This module holds everything needed to represent the redmine queries api as described by
+//! following link: http://www.redmine.org/projects/redmine/wiki/Rest_Queries. Saved queries are
+//! applied to issue lists with
+//! [IssueFilter::saved_query](../issues/struct.IssueFilter.html#method.saved_query).
+
+use super::ClientHandle;
+
+/// This struct exposes all methods provided by the redmine queries api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns SavedQueryListExecutor struct which offers an `execute` function which returns
+    /// the public queries and the queries of the user the api key belongs to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.saved_queries().list().execute();
+    /// ```
+    pub fn list(&self) -> SavedQueryListExecutor {
+        SavedQueryListExecutor::new(self.client.clone(), "/queries.json".to_string())
+    }
+}
+
+list_endpoint! {
+    /// Helper struct to provide a unified interface for all query api methods.
+    SavedQueryListExecutor => SavedQueryList<SavedQuery>, queries
+}
+
+/// Represents an issue query saved in redmine application.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct SavedQuery {
+    pub id: u32,
+    pub name: String,
+    pub is_public: bool,
+    /// The project the query has been saved for, `None` for queries of all projects.
+    pub project_id: Option<u32>,
+}
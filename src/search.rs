@@ -0,0 +1,338 @@
+//! This module holds everything needed to represent the redmine search api as described by
+//! following link: http://www.redmine.org/projects/redmine/wiki/Rest_Search. Requires redmine
+//! 3.3 or newer.
+
+extern crate serde_json;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::collections::HashMap;
+use super::errors::*;
+use super::ClientHandle;
+
+/// This struct exposes all methods provided by the redmine search api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns SearchQuery struct (builder pattern) which offers an `execute` function which
+    /// returns the issues, news, wiki pages, etc. matching `q`. All types are searched unless
+    /// some of them are selected.
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - a string slice holding the words to search for
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::search::SearchResult;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(results) = redmine.search().query("ldap login").issues().wiki_pages().execute() {
+    ///     for result in results {
+    ///         match result {
+    ///             SearchResult::Issue(hit) => println!("Issue #{}: {}", hit.id, hit.title),
+    ///             other => println!("{}", other.hit().title),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn query(&self, q: &str) -> SearchQuery {
+        SearchQuery {
+            client: self.client.clone(),
+            q: q.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Scope of a search, i.e. which projects are searched.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchScope {
+    /// All projects visible to the user.
+    All,
+    /// The projects the user is a member of.
+    MyProjects,
+    /// The project of the search and its subprojects, see
+    /// [SearchQuery::project_id](struct.SearchQuery.html#method.project_id).
+    Subprojects,
+}
+impl SearchScope {
+    /// Returns the name redmine expects as `scope` parameter.
+    fn name(&self) -> &'static str {
+        match *self {
+            SearchScope::All => "all",
+            SearchScope::MyProjects => "my_projects",
+            SearchScope::Subprojects => "subprojects",
+        }
+    }
+}
+
+/// Holds parameters of a search and implements a builder pattern. Is used as return type for
+/// [search.query](struct.Api.html#method.query).
+#[derive(Default)]
+pub struct SearchQuery {
+    client: ClientHandle,
+    q: String,
+    project_id: Option<u32>,
+    scope: Option<SearchScope>,
+    all_words: Option<bool>,
+    titles_only: Option<bool>,
+    types: Vec<&'static str>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+}
+impl SearchQuery {
+    /// Searches the project specified by `id` instead of all projects.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the project id
+    pub fn project_id(&mut self, id: u32) -> &mut SearchQuery {
+        self.project_id = Some(id);
+        self
+    }
+
+    /// Sets which projects are searched.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - the scope of the search
+    pub fn scope(&mut self, scope: SearchScope) -> &mut SearchQuery {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Sets whether results have to match all words or any of them. Redmine matches all words
+    /// by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - a boolean: true means all words, false means any word
+    pub fn all_words(&mut self, b: bool) -> &mut SearchQuery {
+        self.all_words = Some(b);
+        self
+    }
+
+    /// Sets whether only titles are searched. Redmine searches descriptions as well by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - a boolean: true means titles only
+    pub fn titles_only(&mut self, b: bool) -> &mut SearchQuery {
+        self.titles_only = Some(b);
+        self
+    }
+
+    /// Selects issues to be searched.
+    pub fn issues(&mut self) -> &mut SearchQuery {
+        self.types.push("issues");
+        self
+    }
+
+    /// Selects news to be searched.
+    pub fn news(&mut self) -> &mut SearchQuery {
+        self.types.push("news");
+        self
+    }
+
+    /// Selects wiki pages to be searched.
+    pub fn wiki_pages(&mut self) -> &mut SearchQuery {
+        self.types.push("wiki_pages");
+        self
+    }
+
+    /// Sets the number of results to skip.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the offset
+    pub fn offset(&mut self, n: u32) -> &mut SearchQuery {
+        self.offset = Some(n);
+        self
+    }
+
+    /// Sets the maximum number of results to return (redmine caps this at 100).
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the limit
+    pub fn limit(&mut self, n: u32) -> &mut SearchQuery {
+        self.limit = Some(n);
+        self
+    }
+
+    /// Performs request to redmine application and returns the results of the search.
+    pub fn execute(&self) -> Result<SearchResultList> {
+        let path = match self.project_id {
+            Some(id) => format!("/projects/{}/search.json", id),
+            None => "/search.json".to_string(),
+        };
+        let result = self.client.get(&path, &self.params())?;
+
+        serde_json::from_str(&result).chain_err(|| "Can't parse json")
+    }
+
+    /// Transcodes the search parameters to query parameters.
+    fn params(&self) -> HashMap<&str, String> {
+        let mut params: HashMap<&str, String> = HashMap::new();
+        params.insert("q", self.q.clone());
+
+        if let Some(scope) = self.scope {
+            params.insert("scope", scope.name().to_string());
+        }
+
+        // redmine treats every non-empty value as true
+        if let Some(b) = self.all_words {
+            params.insert("all_words", if b { "1" } else { "" }.to_string());
+        }
+
+        if let Some(b) = self.titles_only {
+            params.insert("titles_only", if b { "1" } else { "" }.to_string());
+        }
+
+        for t in &self.types {
+            params.insert(t, "1".to_string());
+        }
+
+        if let Some(n) = self.offset {
+            params.insert("offset", n.to_string());
+        }
+
+        if let Some(n) = self.limit {
+            params.insert("limit", n.to_string());
+        }
+
+        params
+    }
+}
+
+/// Holds a vector of [SearchResult](enum.SearchResult.html)s ordered by relevance. Implements
+/// IntoIterator trait for easy iteration.
+#[derive(Deserialize, Debug)]
+pub struct SearchResultList {
+    results: Vec<SearchResult>,
+    total_count: Option<u32>,
+}
+impl SearchResultList {
+    /// Returns the results of the list.
+    pub fn results(&self) -> &[SearchResult] {
+        &self.results
+    }
+
+    /// Returns the total number of results, which may be more than the list holds.
+    pub fn total_count(&self) -> Option<u32> {
+        self.total_count
+    }
+}
+impl IntoIterator for SearchResultList {
+    type Item = SearchResult;
+    type IntoIter = ::std::vec::IntoIter<SearchResult>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.results.into_iter()
+    }
+}
+
+/// A single result of a search, distinguished by the kind of entity found.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchResult {
+    /// An open or closed issue.
+    Issue(SearchHit),
+    News(SearchHit),
+    WikiPage(SearchHit),
+    /// Any other kind of entity together with the type redmine reports, e.g. `changeset`,
+    /// `document`, `message` or `project`.
+    Other(String, SearchHit),
+}
+impl SearchResult {
+    /// Returns the found entity regardless of its kind.
+    pub fn hit(&self) -> &SearchHit {
+        match *self {
+            SearchResult::Issue(ref h) |
+            SearchResult::News(ref h) |
+            SearchResult::WikiPage(ref h) |
+            SearchResult::Other(_, ref h) => h,
+        }
+    }
+}
+
+/// Represents an entity found by a search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub id: u32,
+    pub title: String,
+    /// Url of the page showing the entity in the redmine application.
+    pub url: String,
+    pub description: String,
+    pub datetime: String,
+}
+
+/// Representation of a search result in json, which holds the kind of entity as `type`.
+#[derive(Serialize, Deserialize)]
+struct RawSearchResult {
+    id: u32,
+    title: String,
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+    #[serde(default)]
+    description: String,
+    datetime: String,
+}
+impl Serialize for SearchResult {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let kind = match *self {
+            SearchResult::Issue(_) => "issue",
+            SearchResult::News(_) => "news",
+            SearchResult::WikiPage(_) => "wiki-page",
+            SearchResult::Other(ref kind, _) => kind,
+        };
+        let hit = self.hit().clone();
+
+        RawSearchResult {
+            id: hit.id,
+            title: hit.title,
+            kind: kind.to_string(),
+            url: hit.url,
+            description: hit.description,
+            datetime: hit.datetime,
+        }.serialize(serializer)
+    }
+}
+impl<'de> Deserialize<'de> for SearchResult {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = RawSearchResult::deserialize(deserializer)?;
+        let hit = SearchHit {
+            id: raw.id,
+            title: raw.title,
+            url: raw.url,
+            description: raw.description,
+            datetime: raw.datetime,
+        };
+
+        Ok(match raw.kind.as_str() {
+            "issue" | "issue-closed" => SearchResult::Issue(hit),
+            "news" => SearchResult::News(hit),
+            "wiki-page" => SearchResult::WikiPage(hit),
+            _ => SearchResult::Other(raw.kind, hit),
+        })
+    }
+}
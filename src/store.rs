@@ -0,0 +1,178 @@
+//! This module keeps fetched issues, users and projects in memory, indexed by id. Entities
+//! reference each other by [NamedObject](../struct.NamedObject.html), e.g. the author of an
+//! issue; a [Store](struct.Store.html) expands such references to full entities and requests
+//! each entity only once, which saves duplicate show calls in interactive applications. Requires
+//! the `store` feature.
+//!
+//! Entities stay in the store until they are evicted, so they may become outdated. Evict an
+//! entity after changing it or [clear](struct.Store.html#method.clear) the store to pull fresh
+//! data.
+//!
+//! # Example
+//!
+//! ```
+//! use redmine_api::RedmineApi;
+//! use redmine_api::users::User;
+//! use std::rc::Rc;
+//!
+//! let redmine = RedmineApi::new(
+//!     "http://www.redmine.org/".to_string(),
+//!     "1234".to_string()
+//! );
+//!
+//! let store = redmine.store();
+//! if let Ok(issue) = redmine.issues().show(1).execute() {
+//!     if let Ok(author) = store.expand::<User>(&issue.author) {
+//!         println!("Author: {} {}", author.firstname, author.lastname);
+//!     }
+//! }
+//! ```
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use super::errors::*;
+use super::issues::{self, Issue};
+use super::projects::{self, Project};
+use super::users::{self, User};
+use super::{ClientHandle, NamedObject};
+
+/// An entity which can be kept in a [Store](struct.Store.html). Is implemented for issues, users
+/// and projects.
+pub trait Stored: Any {
+    /// Performs request to redmine application and returns the entity. Should not be called
+    /// externally.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - the client the request is sent with
+    /// * `id` - an integer holding the id of the entity
+    fn fetch(client: &ClientHandle, id: u32) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// Returns the id of the entity.
+    fn id(&self) -> u32;
+}
+impl Stored for Issue {
+    fn fetch(client: &ClientHandle, id: u32) -> Result<Self> {
+        issues::Api::new(client.clone()).show(id).execute()
+    }
+
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+impl Stored for User {
+    fn fetch(client: &ClientHandle, id: u32) -> Result<Self> {
+        users::Api::new(client.clone()).show(id).execute()
+    }
+
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+impl Stored for Project {
+    fn fetch(client: &ClientHandle, id: u32) -> Result<Self> {
+        projects::Api::new(client.clone()).show(id).execute()
+    }
+
+    fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+/// In-memory store of entities, indexed by kind and id. Is returned by
+/// [RedmineApi::store](../struct.RedmineApi.html#method.store).
+pub struct Store {
+    client: ClientHandle,
+    entities: RefCell<HashMap<(TypeId, u32), Rc<dyn Any>>>,
+}
+impl Store {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Self {
+        Store {
+            client: client,
+            entities: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the entity with the given id. It's pulled from redmine application unless it's
+    /// already in the store.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the id of the entity
+    pub fn get<T: Stored>(&self, id: u32) -> Result<Rc<T>> {
+        if let Some(entity) = self.cached(id) {
+            return Ok(entity);
+        }
+
+        let entity = T::fetch(&self.client, id)?;
+        Ok(self.insert(entity))
+    }
+
+    /// Returns the entity a reference points to, e.g. the author of an issue. It's pulled from
+    /// redmine application unless it's already in the store. The kind of entity has to be given
+    /// by the caller, since references don't hold it.
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - the reference to the entity
+    pub fn expand<T: Stored>(&self, reference: &NamedObject) -> Result<Rc<T>> {
+        self.get(reference.id())
+    }
+
+    /// Returns the entity with the given id if it's in the store. Never sends a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the id of the entity
+    pub fn cached<T: Stored>(&self, id: u32) -> Option<Rc<T>> {
+        self.entities
+            .borrow()
+            .get(&(TypeId::of::<T>(), id))
+            .and_then(|e| Rc::clone(e).downcast().ok())
+    }
+
+    /// Puts an entity pulled otherwise into the store, e.g. the issues of a list, and returns it.
+    /// Replaces the entity of the same kind and id if there is any.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - the entity to keep
+    pub fn insert<T: Stored>(&self, entity: T) -> Rc<T> {
+        let entity = Rc::new(entity);
+        self.entities.borrow_mut().insert(
+            (TypeId::of::<T>(), entity.id()),
+            Rc::clone(&entity) as Rc<dyn Any>,
+        );
+
+        entity
+    }
+
+    /// Removes the entity with the given id from the store, so it's pulled again on next use.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the id of the entity
+    pub fn evict<T: Stored>(&self, id: u32) {
+        self.entities.borrow_mut().remove(&(TypeId::of::<T>(), id));
+    }
+
+    /// Removes all entities from the store.
+    pub fn clear(&self) {
+        self.entities.borrow_mut().clear();
+    }
+
+    /// Returns the number of entities in the store.
+    pub fn len(&self) -> usize {
+        self.entities.borrow().len()
+    }
+
+    /// Checks if the store holds no entities.
+    pub fn is_empty(&self) -> bool {
+        self.entities.borrow().is_empty()
+    }
+}
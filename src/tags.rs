@@ -0,0 +1,75 @@
+//! This module provides label-like tags for issues. Redmine has no tags, so they are emulated with
+//! a list custom field allowing multiple values; a [TagField](struct.TagField.html) holds the id
+//! of this custom field and is passed to
+//! [Issue::tags](../issues/struct.Issue.html#method.tags),
+//! [IssueBuilder::add_tag](../issues/struct.IssueBuilder.html#method.add_tag) and
+//! [IssueFilter::tag](../issues/struct.IssueFilter.html#method.tag).
+//!
+//! # Example
+//!
+//! ```
+//! use redmine_api::RedmineApi;
+//! use redmine_api::tags::TagField;
+//!
+//! let redmine = RedmineApi::new(
+//!     "http://www.redmine.org/".to_string(),
+//!     "1234".to_string()
+//! );
+//! let tags = TagField::new(7);
+//!
+//! let result = redmine.issues().list().tag(&tags, "infra").execute();
+//! ```
+
+use super::CustomField;
+
+/// Refers to the list custom field holding the tags of issues.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TagField {
+    id: u32,
+}
+impl TagField {
+    /// Creates a new instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the id of the custom field
+    pub fn new(id: u32) -> Self {
+        TagField { id: id }
+    }
+
+    /// Returns the id of the custom field.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the tags found in the custom fields of an issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `custom_fields` - the custom fields of an issue
+    pub fn values(&self, custom_fields: &[CustomField]) -> Vec<String> {
+        let cf = match custom_fields.iter().find(|cf| cf.id == self.id) {
+            Some(cf) => cf,
+            None => return Vec::new(),
+        };
+
+        // single value fields are returned as string
+        match cf.value.as_array() {
+            Some(values) => {
+                values
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .filter(|v| !v.is_empty())
+                    .map(|v| v.to_string())
+                    .collect()
+            }
+            None => {
+                cf.as_str()
+                    .into_iter()
+                    .filter(|v| !v.is_empty())
+                    .map(|v| v.to_string())
+                    .collect()
+            }
+        }
+    }
+}
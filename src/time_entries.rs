@@ -3,18 +3,24 @@
 
 extern crate serde_json;
 
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, TimeZone};
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::fmt;
+use std::time::Duration;
+use super::entity::{Creatable, Deletable, Listable, Page, Resource, Showable};
 use super::errors::*;
-use super::{Object, NamedObject, RedmineClient};
+use super::{create_from_json, duration_to_hours, format_table, ClientHandle, NamedObject, Object,
+            Tristate};
 
 /// Exposes all methods provided by the redmine time entries api as implemented so far.
 pub struct Api {
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
 }
 impl Api {
     /// Creates a new instance. Should not be called externally.
-    pub fn new(client: Rc<RedmineClient>) -> Api {
+    pub fn new(client: ClientHandle) -> Api {
         Api { client: client }
     }
 
@@ -33,7 +39,7 @@ impl Api {
     /// let result = redmine.time_entries().list().user_id(1).execute();
     /// ```
     pub fn list(&self) -> TimeEntryFilter {
-        TimeEntryFilter::new(Rc::clone(&self.client))
+        TimeEntryFilter::new(self.client.clone())
     }
 
     /// Returns a single time entry by id.
@@ -56,7 +62,7 @@ impl Api {
     /// ```
     pub fn show(&self, id: u32) -> TimeEntryShow {
         TimeEntryShow {
-            client: Rc::clone(&self.client),
+            client: self.client.clone(),
             show_id: id,
             ..Default::default()
         }
@@ -86,7 +92,7 @@ impl Api {
     ///     .execute();
     /// ```
     pub fn create(&self, issue_id: u32, hours: f32, activity_id: u32) -> TimeEntryBuilder {
-        TimeEntryBuilder::for_create(Rc::clone(&self.client), issue_id, hours, activity_id)
+        TimeEntryBuilder::for_create(self.client.clone(), issue_id, hours, activity_id)
     }
 
     /// Returns a TimeEntryBuilder and ultimately updates an existing time entry in redmine
@@ -112,7 +118,7 @@ impl Api {
     ///
     /// ```
     pub fn update(&self, id: u32) -> TimeEntryBuilder {
-        TimeEntryBuilder::for_update(Rc::clone(&self.client), id)
+        TimeEntryBuilder::for_update(self.client.clone(), id)
     }
 
     /// Returns TimeEntryDelete struct which offers an `execute` function which deletes the time
@@ -136,27 +142,63 @@ impl Api {
     /// ```
     pub fn delete(&self, id: u32) -> TimeEntryDelete {
         TimeEntryDelete {
-            client: Rc::clone(&self.client),
+            client: self.client.clone(),
             delete_id: id,
         }
     }
 }
+impl Resource for Api {
+    type Entity = TimeEntry;
+
+    fn name(&self) -> &'static str {
+        "time_entries"
+    }
+}
+impl Listable for Api {
+    fn list_page(&self, offset: u32, limit: u32) -> Result<Page<TimeEntry>> {
+        let list = self.list().offset(offset).limit(limit).execute()?;
+
+        Ok(Page {
+            total_count: list.total_count(),
+            items: list.into_iter().collect(),
+        })
+    }
+}
+impl Showable for Api {
+    fn fetch(&self, id: u32) -> Result<TimeEntry> {
+        self.show(id).execute()
+    }
+}
+impl Creatable for Api {
+    fn create_from_json(&self, fields: &serde_json::Value) -> Result<u32> {
+        create_from_json(&self.client, "/time_entries.json", "time_entry", fields)
+    }
+}
+impl Deletable for Api {
+    fn remove(&self, id: u32) -> Result<()> {
+        self.delete(id).execute()
+    }
+}
 
 /// Holds parameters the time entries in redmine application should be filtered by and implements
 /// builder pattern. Is used as return type by time_entries.list function.
 #[derive(Default)]
 pub struct TimeEntryFilter {
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     user_id: Option<u32>,
     project_id: Option<u32>,
+    from: Option<String>,
+    to: Option<String>,
+    offset: Option<u32>,
+    limit: Option<u32>,
 }
 impl TimeEntryFilter {
     /// Creates new instance.
     ///
     /// # Arguments
     ///
-    /// * `client` - a Rc boxed RedmineClient
-    fn new(client: Rc<RedmineClient>) -> Self {
+    /// * `client` - a handle to the RedmineClient
+    fn new(client: ClientHandle) -> Self {
         TimeEntryFilter {
             client: client,
             ..Default::default()
@@ -183,9 +225,119 @@ impl TimeEntryFilter {
         self
     }
 
+    /// Sets filter to get only time entries spent on or after a date.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the date in `YYYY-MM-DD` format
+    pub fn from(&mut self, s: &str) -> &mut Self {
+        self.from = Some(s.to_string());
+        self
+    }
+
+    /// Sets filter to get only time entries spent on or before a date.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the date in `YYYY-MM-DD` format
+    pub fn to(&mut self, s: &str) -> &mut Self {
+        self.to = Some(s.to_string());
+        self
+    }
+
+    /// Sets filter to get only time entries spent on or after the day of a point in time. The day
+    /// is taken in the time zone set by
+    /// [RedmineApi::set_timezone](../struct.RedmineApi.html#method.set_timezone) (UTC by
+    /// default). Requires the `chrono` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - a date time
+    #[cfg(feature = "chrono")]
+    pub fn from_datetime<Tz: TimeZone>(&mut self, dt: &DateTime<Tz>) -> &mut Self {
+        self.from = Some(self.client.local_date(dt));
+        self
+    }
+
+    /// Sets filter to get only time entries spent on or before the day of a point in time. See
+    /// [from_datetime](#method.from_datetime). Requires the `chrono` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - a date time
+    #[cfg(feature = "chrono")]
+    pub fn to_datetime<Tz: TimeZone>(&mut self, dt: &DateTime<Tz>) -> &mut Self {
+        self.to = Some(self.client.local_date(dt));
+        self
+    }
+
+    /// Sets the number of time entries which should be skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the offset
+    pub fn offset(&mut self, n: u32) -> &mut Self {
+        self.offset = Some(n);
+        self
+    }
+
+    /// Sets the maximum number of time entries which should be returned (redmine caps this at
+    /// 100).
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the limit
+    pub fn limit(&mut self, n: u32) -> &mut Self {
+        self.limit = Some(n);
+        self
+    }
+
     /// Performs request to redmine application and returns a list of time entries matching the
     /// filter parameters.
     pub fn execute(&self) -> Result<TimeEntryList> {
+        let result = self.client.get("/time_entries.json", &self.params())?;
+
+        serde_json::from_str(&result).chain_err(|| "Can't parse json")
+    }
+
+    /// Performs as many requests to redmine application as needed to walk through all pages of
+    /// time entries matching the filter parameters and returns all of them. Offset and limit set
+    /// on the filter are ignored.
+    pub fn execute_all(&self) -> Result<TimeEntryList> {
+        let mut params = self.params();
+        params.insert("limit", "100".to_string());
+
+        let mut time_entries: Vec<TimeEntry> = Vec::new();
+        loop {
+            params.insert("offset", time_entries.len().to_string());
+            let result = self.client.get("/time_entries.json", &params)?;
+            let list: TimeEntryList =
+                serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+
+            let count = list.time_entries.len();
+            let total_count = list.total_count.unwrap_or(0) as usize;
+            time_entries.extend(list.time_entries);
+
+            if count == 0 || time_entries.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(TimeEntryList {
+            total_count: Some(time_entries.len() as u32),
+            time_entries: time_entries,
+        })
+    }
+
+    /// Performs as many requests to redmine application as needed to walk through all pages of
+    /// time entries matching the filter parameters and returns the sum of their hours. Offset and
+    /// limit set on the filter are ignored.
+    pub fn total_hours(&self) -> Result<f32> {
+        Ok(self.execute_all()?.into_iter().map(|t| t.hours).sum())
+    }
+
+    /// Transcodes the filter parameters to query parameters.
+    fn params(&self) -> HashMap<&str, String> {
         let mut params: HashMap<&str, String> = HashMap::new();
 
         if let Some(id) = self.user_id {
@@ -196,9 +348,23 @@ impl TimeEntryFilter {
             params.insert("project_id", id.to_string());
         }
 
-        let result = self.client.get("/time_entries.json", &params)?;
+        if let Some(ref s) = self.from {
+            params.insert("from", s.clone());
+        }
 
-        serde_json::from_str(&result).chain_err(|| "Can't parse json")
+        if let Some(ref s) = self.to {
+            params.insert("to", s.clone());
+        }
+
+        if let Some(n) = self.offset {
+            params.insert("offset", n.to_string());
+        }
+
+        if let Some(n) = self.limit {
+            params.insert("limit", n.to_string());
+        }
+
+        params
     }
 }
 
@@ -206,6 +372,36 @@ impl TimeEntryFilter {
 #[derive(Deserialize, Debug)]
 pub struct TimeEntryList {
     time_entries: Vec<TimeEntry>,
+    total_count: Option<u32>,
+}
+impl TimeEntryList {
+    /// Returns the total number of time entries matching the filter, which may be more than the
+    /// list holds.
+    pub fn total_count(&self) -> Option<u32> {
+        self.total_count
+    }
+
+    /// Formats the time entries as plain text table, e.g. for command line tools.
+    pub fn table(&self) -> String {
+        let rows: Vec<Vec<String>> = self.time_entries
+            .iter()
+            .map(|t| {
+                vec![
+                    t.id.to_string(),
+                    t.spent_on.clone(),
+                    format!("{:.2}", t.hours),
+                    format!("#{}", t.issue.id),
+                    t.activity.name.clone(),
+                    t.comments.clone(),
+                ]
+            })
+            .collect();
+
+        format_table(
+            &["ID", "Date", "Hours", "Issue", "Activity", "Comments"],
+            &rows,
+        )
+    }
 }
 impl IntoIterator for TimeEntryList {
     type Item = TimeEntry;
@@ -217,7 +413,7 @@ impl IntoIterator for TimeEntryList {
 }
 
 /// Represents a time entry as fetched from redmine application.
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct TimeEntry {
     pub activity: NamedObject,
     pub comments: String,
@@ -225,7 +421,10 @@ pub struct TimeEntry {
     pub id: u32,
     pub issue: Object,
     pub project: NamedObject,
-    pub user: Object,
+    /// The user who logged the time. Is `None` if redmine doesn't report the user, e.g. for
+    /// entries of deleted users.
+    #[serde(default)]
+    pub user: Option<NamedObject>,
     pub spent_on: String,
     pub created_on: String,
     pub updated_on: String,
@@ -235,12 +434,31 @@ impl From<TimeEntryShow> for TimeEntry {
         item.time_entry
     }
 }
+// e.g. `2017-09-16: 1.50 h on #123 (Development) Fixed login`
+impl fmt::Display for TimeEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {:.2} h on #{} ({})",
+            self.spent_on,
+            self.hours,
+            self.issue.id,
+            self.activity.name
+        )?;
+
+        if !self.comments.is_empty() {
+            write!(f, " {}", self.comments)?;
+        }
+
+        Ok(())
+    }
+}
 
 /// Wrapper struct for deserialization of a single issue pulled from redmine application.
 #[derive(Deserialize, Debug, Default)]
 pub struct TimeEntryShow {
     #[serde(skip_deserializing)]
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     #[serde(skip_deserializing)]
     show_id: u32,
 
@@ -265,12 +483,12 @@ impl TimeEntryShow {
 
 /// Helper struct to provide a unified interface for all time entry api methods.
 pub struct TimeEntryDelete {
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     delete_id: u32,
 }
 impl TimeEntryDelete {
     /// Performs request to redmine application and deletes a time entry.
-    pub fn execute(&self) -> Result<bool> {
+    pub fn execute(&self) -> Result<()> {
         self.client.delete(
             &(format!(
                 "/time_entries/{}.json",
@@ -306,7 +524,7 @@ impl Default for TimeEntryBuilderKind {
 pub struct TimeEntryBuilder<'a> {
     // internal
     #[serde(skip_serializing)]
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     #[serde(skip_serializing)]
     kind: TimeEntryBuilderKind,
     #[serde(skip_serializing)]
@@ -319,10 +537,12 @@ pub struct TimeEntryBuilder<'a> {
     hours: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     activity_id: Option<u32>,
-    #[serde(skip_serializing_if = "str::is_empty")]
-    spent_on: &'a str,
-    #[serde(skip_serializing_if = "str::is_empty")]
-    comments: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_id: Option<u32>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    spent_on: Tristate<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    comments: Tristate<&'a str>,
 }
 impl<'a> TimeEntryBuilder<'a> {
     /// Creates new instance for creation of a time entry. Function takes all mandatory parameters
@@ -330,12 +550,12 @@ impl<'a> TimeEntryBuilder<'a> {
     ///
     /// # Arguments
     ///
-    /// * `client` - an Rc boxed [RedmineClient](struct.RedmineClient.html)
+    /// * `client` - a [ClientHandle](../struct.ClientHandle.html)
     /// * `issue_id` - an integer holding the issue id
     /// * `hours` - an floating point number holding the spent hours
     /// * `activity_id` - an integer holding the activity id
     pub fn for_create(
-        client: Rc<RedmineClient>,
+        client: ClientHandle,
         issue_id: u32,
         hours: f32,
         activity_id: u32,
@@ -355,7 +575,7 @@ impl<'a> TimeEntryBuilder<'a> {
     /// # Arguments
     ///
     /// * `id` - an integer holding the id of the time entry which should be changed
-    pub fn for_update(client: Rc<RedmineClient>, id: u32) -> Self {
+    pub fn for_update(client: ClientHandle, id: u32) -> Self {
         TimeEntryBuilder {
             client: client,
             kind: TimeEntryBuilderKind::Update,
@@ -364,13 +584,59 @@ impl<'a> TimeEntryBuilder<'a> {
         }
     }
 
+    /// Sets spent hours for time entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `h` - a floating point number holding the spent hours
+    pub fn hours(mut self, h: f32) -> Self {
+        self.hours = Some(h);
+        self
+    }
+
+    /// Sets spent hours for time entry from a duration, which avoids mixing up minutes and hours.
+    /// The value is rounded to two decimal places. A chrono duration can be passed by converting
+    /// it with `to_std()` first.
+    ///
+    /// # Arguments
+    ///
+    /// * `d` - a duration holding the spent time
+    pub fn hours_from_duration(self, d: Duration) -> Self {
+        self.hours(duration_to_hours(d))
+    }
+
     /// Sets spent on date for time entry.
     ///
     /// # Arguments
     ///
     /// * `s` - string slice holding the spent on date
     pub fn spent_on(mut self, s: &'a str) -> Self {
-        self.spent_on = s;
+        self.spent_on = Tristate::Set(Cow::Borrowed(s));
+        self
+    }
+
+    /// Sets spent on date for time entry from a point in time. The date is taken in the time
+    /// zone set by [RedmineApi::set_timezone](../struct.RedmineApi.html#method.set_timezone)
+    /// (UTC by default), so entries logged near midnight get the right day regardless of the
+    /// time zone of `dt`. Requires the `chrono` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - a date time holding the time the work was done
+    #[cfg(feature = "chrono")]
+    pub fn spent_at<Tz: TimeZone>(mut self, dt: &DateTime<Tz>) -> Self {
+        self.spent_on = Tristate::Set(Cow::Owned(self.client.local_date(dt)));
+        self
+    }
+
+    /// Sets the user the time entry is logged for. Requires the permission to log time for other
+    /// users.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the user id
+    pub fn user_id(mut self, id: u32) -> Self {
+        self.user_id = Some(id);
         self
     }
 
@@ -380,22 +646,31 @@ impl<'a> TimeEntryBuilder<'a> {
     ///
     /// * `s` - string slice holding the comment
     pub fn comments(mut self, s: &'a str) -> Self {
-        self.comments = s;
+        self.comments = Tristate::Set(s);
+        self
+    }
+
+    /// Clears the comment of the time entry.
+    pub fn clear_comments(mut self) -> Self {
+        self.comments = Tristate::Clear;
         self
     }
 
-    /// Performs request to redmine application to create or update a time entry.
-    pub fn execute(&self) -> Result<String> {
+    /// Performs request to redmine application to create or update a time entry and returns it.
+    pub fn execute(&self) -> Result<TimeEntry> {
         let te = TimeEntryBuilderWrapper { time_entry: self };
         match self.kind {
-            TimeEntryBuilderKind::Create => self.client.create("/time_entries.json", &te),
+            TimeEntryBuilderKind::Create => {
+                self.client.create_entity("/time_entries.json", &te, "time_entry")
+            }
             TimeEntryBuilderKind::Update => {
-                self.client.update(
+                self.client.update_entity(
                     &(format!(
                         "/time_entries/{}.json",
                         self.update_id
                     )),
                     &te,
+                    "time_entry",
                 )
             }
         }
@@ -0,0 +1,103 @@
+//! This module holds everything needed to represent the redmine trackers api as described by
+//! following link: http://www.redmine.org/projects/redmine/wiki/Rest_Trackers.
+
+extern crate serde_json;
+
+use std::collections::HashMap;
+use super::errors::*;
+use super::{ClientHandle, NamedObject};
+
+/// This struct exposes all methods provided by the redmine trackers api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns TrackerListExecutor struct which provides an `execute` function for retrieving
+    /// all trackers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(trackers) = redmine.trackers().list().execute() {
+    ///     if let Some(bug) = trackers.by_name("Bug") {
+    ///         let status_id = bug.default_status.as_ref().map_or(1, |s| s.id());
+    ///         let result = redmine.issues().create(1, bug.id, status_id, 2, "Login fails")
+    ///             .execute();
+    ///     }
+    /// }
+    /// ```
+    pub fn list(&self) -> TrackerListExecutor {
+        TrackerListExecutor { client: self.client.clone() }
+    }
+}
+
+/// Helper struct to provide a unified interface for all tracker api methods.
+pub struct TrackerListExecutor {
+    client: ClientHandle,
+}
+impl TrackerListExecutor {
+    /// Performs request to redmine application and returns a list of trackers.
+    pub fn execute(&self) -> Result<TrackerList> {
+        let result = self.client.get("/trackers.json", &HashMap::new())?;
+
+        serde_json::from_str(&result).chain_err(|| "Can't parse json")
+    }
+}
+
+/// Holds a vector of [Tracker](struct.Tracker.html)s in the order configured in redmine
+/// application. Implements IntoIterator trait for easy iteration.
+#[derive(Deserialize, Debug, Default)]
+pub struct TrackerList {
+    trackers: Vec<Tracker>,
+}
+impl TrackerList {
+    /// Returns the tracker with the given id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the tracker id
+    pub fn by_id(&self, id: u32) -> Option<&Tracker> {
+        self.trackers.iter().find(|t| t.id == id)
+    }
+
+    /// Returns the tracker with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the tracker
+    pub fn by_name(&self, name: &str) -> Option<&Tracker> {
+        self.trackers.iter().find(|t| t.name == name)
+    }
+}
+impl IntoIterator for TrackerList {
+    type Item = Tracker;
+    type IntoIter = ::std::vec::IntoIter<Tracker>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.trackers.into_iter()
+    }
+}
+
+/// Represents a tracker of a redmine application.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Tracker {
+    pub id: u32,
+    pub name: String,
+    /// The status new issues get, only reported by redmine 3.0 or newer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_status: Option<NamedObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
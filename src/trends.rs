@@ -0,0 +1,381 @@
+//! This module records the history redmine application doesn't keep, i.e. how many issues have
+//! been in which status over time, and computes burndown and burnup series from it. Snapshots are
+//! stored in an embedded SQLite database by a [TrendStore](struct.TrendStore.html); recording
+//! them regularly, e.g. daily by cron, is up to the caller. Requires the `trends` feature.
+//!
+//! A snapshot counts the issues of a project, including its subprojects, by target version and
+//! status. There is one snapshot per project and day; recording again on the same day replaces
+//! it, so the last snapshot of a day wins.
+//!
+//! # Example
+//!
+//! ```
+//! use redmine_api::RedmineApi;
+//! use redmine_api::trends::TrendStore;
+//!
+//! let redmine = RedmineApi::new(
+//!     "http://www.redmine.org/".to_string(),
+//!     "1234".to_string()
+//! );
+//!
+//! let store = TrendStore::open_in_memory().unwrap();
+//! let result = store.record(&redmine, 1);
+//!
+//! // burndown of version 3
+//! for point in store.series(1, Some(3)).unwrap() {
+//!     println!("{}: {} open of {}", point.date, point.open, point.total);
+//! }
+//! ```
+
+use rusqlite::{Connection, Row};
+use std::collections::BTreeMap;
+use std::path::Path;
+use super::dates;
+use super::errors::*;
+use super::RedmineApi;
+
+/// Statements creating the schema, if it doesn't exist yet.
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS snapshots (
+    taken_on TEXT NOT NULL,
+    project_id INTEGER NOT NULL,
+    version_id INTEGER NOT NULL,
+    status_id INTEGER NOT NULL,
+    status_name TEXT NOT NULL,
+    is_closed INTEGER NOT NULL,
+    issues INTEGER NOT NULL,
+    estimated_hours REAL NOT NULL,
+    PRIMARY KEY (taken_on, project_id, version_id, status_id)
+)";
+
+/// Stores snapshots of issue counts in a SQLite database.
+pub struct TrendStore {
+    conn: Connection,
+}
+impl TrendStore {
+    /// Opens the database at `path`, creating it if it doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the database file
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let conn = Connection::open(path).chain_err(|| "Can't open trend store")?;
+        TrendStore::init(conn)
+    }
+
+    /// Opens a database which is kept in memory and dropped together with the store, e.g. for
+    /// tests.
+    pub fn open_in_memory() -> Result<Self> {
+        let conn = Connection::open_in_memory().chain_err(|| "Can't open trend store")?;
+        TrendStore::init(conn)
+    }
+
+    /// Creates the schema if needed.
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA).chain_err(
+            || "Can't create trend store",
+        )?;
+
+        Ok(TrendStore { conn: conn })
+    }
+
+    /// Performs requests to redmine application and records today's snapshot of the project
+    /// specified by `project_id` parameter. Returns the recorded counts.
+    ///
+    /// # Arguments
+    ///
+    /// * `redmine` - the redmine application
+    /// * `project_id` - an integer holding the project id
+    pub fn record(&self, redmine: &RedmineApi, project_id: u32) -> Result<Vec<StatusCount>> {
+        let closed = redmine.issue_statuses().list().execute()?.closed_ids();
+        let issues = redmine
+            .issues()
+            .list()
+            .project_id(project_id)
+            .any_status()
+            .execute_all()?;
+
+        let today = dates::today();
+        let mut counts: BTreeMap<(u32, u32), StatusCount> = BTreeMap::new();
+        for issue in issues {
+            let version_id = issue.fixed_version.as_ref().map_or(0, |v| v.id());
+            let count = counts
+                .entry((version_id, issue.status.id()))
+                .or_insert_with(|| {
+                    StatusCount {
+                        date: today.clone(),
+                        version_id: version_id,
+                        status_id: issue.status.id(),
+                        status: issue.status.name().to_string(),
+                        is_closed: closed.contains(&issue.status.id()),
+                        issues: 0,
+                        estimated_hours: 0.0,
+                    }
+                });
+            count.issues += 1;
+            count.estimated_hours += issue.estimated_hours.unwrap_or(0.0);
+        }
+
+        let counts: Vec<StatusCount> = counts.into_values().collect();
+        self.insert(project_id, &today, &counts)?;
+        Ok(counts)
+    }
+
+    /// Replaces the snapshot of a project and day.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    /// * `date` - a string slice holding the date in `YYYY-MM-DD` format
+    /// * `counts` - the counts of the snapshot
+    fn insert(&self, project_id: u32, date: &str, counts: &[StatusCount]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction().chain_err(
+            || "Can't write trend store",
+        )?;
+
+        tx.execute(
+            "DELETE FROM snapshots WHERE taken_on = ?1 AND project_id = ?2",
+            params![date, project_id],
+        ).chain_err(|| "Can't write trend store")?;
+        for c in counts {
+            tx.execute(
+                "INSERT INTO snapshots VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    date,
+                    project_id,
+                    c.version_id,
+                    c.status_id,
+                    c.status,
+                    c.is_closed,
+                    c.issues,
+                    f64::from(c.estimated_hours),
+                ],
+            ).chain_err(|| "Can't write trend store")?;
+        }
+
+        tx.commit().chain_err(|| "Can't write trend store")
+    }
+
+    /// Returns the recorded counts of a project by day, version and status, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    /// * `version_id` - an integer holding the version id, 0 for issues without target version
+    ///   or `None` for all issues
+    pub fn status_counts(
+        &self,
+        project_id: u32,
+        version_id: Option<u32>,
+    ) -> Result<Vec<StatusCount>> {
+        let mut stmt = self.conn
+            .prepare(
+                "SELECT taken_on, version_id, status_id, status_name, is_closed, issues,
+                        estimated_hours
+                 FROM snapshots
+                 WHERE project_id = ?1 AND (?2 IS NULL OR version_id = ?2)
+                 ORDER BY taken_on, version_id, status_id",
+            )
+            .chain_err(|| "Can't read trend store")?;
+        let rows = stmt.query_map(params![project_id, version_id], |row| {
+            Ok(StatusCount {
+                date: row.get(0)?,
+                version_id: row.get(1)?,
+                status_id: row.get(2)?,
+                status: row.get(3)?,
+                is_closed: row.get(4)?,
+                issues: row.get(5)?,
+                estimated_hours: row.get::<_, f64>(6)? as f32,
+            })
+        }).chain_err(|| "Can't read trend store")?;
+
+        rows.collect::<::std::result::Result<_, _>>().chain_err(
+            || "Can't read trend store",
+        )
+    }
+
+    /// Returns one point per recorded day, oldest first. A burndown chart plots the open issues,
+    /// a burnup chart the closed issues against the total.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    /// * `version_id` - an integer holding the version id, 0 for issues without target version
+    ///   or `None` for all issues
+    pub fn series(&self, project_id: u32, version_id: Option<u32>) -> Result<Vec<TrendPoint>> {
+        let mut stmt = self.conn
+            .prepare(
+                "SELECT taken_on,
+                        SUM(CASE WHEN is_closed THEN 0 ELSE issues END),
+                        SUM(CASE WHEN is_closed THEN issues ELSE 0 END),
+                        SUM(issues),
+                        SUM(CASE WHEN is_closed THEN 0.0 ELSE estimated_hours END)
+                 FROM snapshots
+                 WHERE project_id = ?1 AND (?2 IS NULL OR version_id = ?2)
+                 GROUP BY taken_on
+                 ORDER BY taken_on",
+            )
+            .chain_err(|| "Can't read trend store")?;
+        let rows = stmt.query_map(params![project_id, version_id], trend_point)
+            .chain_err(|| "Can't read trend store")?;
+
+        rows.collect::<::std::result::Result<_, _>>().chain_err(
+            || "Can't read trend store",
+        )
+    }
+}
+
+/// Number of issues of a version in a status on a day.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StatusCount {
+    /// The day of the snapshot in `YYYY-MM-DD` format.
+    pub date: String,
+    /// Id of the target version, 0 for issues without target version.
+    pub version_id: u32,
+    pub status_id: u32,
+    /// Name of the status when the snapshot has been recorded.
+    pub status: String,
+    pub is_closed: bool,
+    pub issues: u32,
+    /// Sum of the estimated hours of the issues.
+    pub estimated_hours: f32,
+}
+
+/// Issue counts of a day, as returned by
+/// [TrendStore::series](struct.TrendStore.html#method.series).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TrendPoint {
+    /// The day of the snapshot in `YYYY-MM-DD` format.
+    pub date: String,
+    pub open: u32,
+    pub closed: u32,
+    /// All issues, i.e. the scope of a burnup chart.
+    pub total: u32,
+    /// Sum of the estimated hours of the open issues.
+    pub open_estimated_hours: f32,
+}
+
+/// Reads a row of the series query.
+fn trend_point(row: &Row) -> ::rusqlite::Result<TrendPoint> {
+    Ok(TrendPoint {
+        date: row.get(0)?,
+        open: row.get(1)?,
+        closed: row.get(2)?,
+        total: row.get(3)?,
+        open_estimated_hours: row.get::<_, f64>(4)? as f32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StatusCount, TrendPoint, TrendStore};
+
+    fn count(
+        version_id: u32,
+        status_id: u32,
+        is_closed: bool,
+        issues: u32,
+        hours: f32,
+    ) -> StatusCount {
+        StatusCount {
+            date: String::new(),
+            version_id: version_id,
+            status_id: status_id,
+            status: format!("Status {}", status_id),
+            is_closed: is_closed,
+            issues: issues,
+            estimated_hours: hours,
+        }
+    }
+
+    fn point(date: &str, open: u32, closed: u32, open_estimated_hours: f32) -> TrendPoint {
+        TrendPoint {
+            date: date.to_string(),
+            open: open,
+            closed: closed,
+            total: open + closed,
+            open_estimated_hours: open_estimated_hours,
+        }
+    }
+
+    /// Returns a store with two days of project 1 and one day of project 2.
+    fn store() -> TrendStore {
+        let store = TrendStore::open_in_memory().unwrap();
+        store
+            .insert(
+                1,
+                "2017-09-14",
+                &[
+                    count(0, 1, false, 2, 3.0),
+                    count(3, 1, false, 5, 10.0),
+                    count(3, 2, false, 1, 2.5),
+                ],
+            )
+            .unwrap();
+        store
+            .insert(
+                1,
+                "2017-09-15",
+                &[
+                    count(3, 1, false, 3, 6.0),
+                    count(3, 2, false, 1, 2.5),
+                    count(3, 5, true, 2, 4.0),
+                ],
+            )
+            .unwrap();
+        store.insert(2, "2017-09-15", &[count(3, 1, false, 7, 7.0)]).unwrap();
+        store
+    }
+
+    #[test]
+    fn series_sums_statuses_per_day() {
+        assert_eq!(
+            store().series(1, None).unwrap(),
+            vec![point("2017-09-14", 8, 0, 15.5), point("2017-09-15", 4, 2, 8.5)]
+        );
+    }
+
+    #[test]
+    fn series_of_version() {
+        let store = store();
+        assert_eq!(
+            store.series(1, Some(3)).unwrap(),
+            vec![point("2017-09-14", 6, 0, 12.5), point("2017-09-15", 4, 2, 8.5)]
+        );
+        assert_eq!(store.series(1, Some(0)).unwrap(), vec![point("2017-09-14", 2, 0, 3.0)]);
+        assert_eq!(store.series(1, Some(4)).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn recording_a_day_again_replaces_it() {
+        let store = store();
+        store.insert(1, "2017-09-15", &[count(3, 5, true, 6, 0.0)]).unwrap();
+
+        assert_eq!(
+            store.series(1, None).unwrap(),
+            vec![point("2017-09-14", 8, 0, 15.5), point("2017-09-15", 0, 6, 0.0)]
+        );
+        assert_eq!(store.series(2, None).unwrap(), vec![point("2017-09-15", 7, 0, 7.0)]);
+    }
+
+    #[test]
+    fn status_counts_are_ordered() {
+        let counts = store().status_counts(1, Some(3)).unwrap();
+        let keys: Vec<(&str, u32)> = counts
+            .iter()
+            .map(|c| (c.date.as_str(), c.status_id))
+            .collect();
+
+        assert_eq!(
+            keys,
+            vec![
+                ("2017-09-14", 1),
+                ("2017-09-14", 2),
+                ("2017-09-15", 1),
+                ("2017-09-15", 2),
+                ("2017-09-15", 5),
+            ]
+        );
+        assert_eq!(counts[4].status, "Status 5");
+        assert!(counts[4].is_closed);
+    }
+}
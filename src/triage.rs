@@ -0,0 +1,180 @@
+//! This module packages the queries support-queue tooling runs for triage into one call: the new,
+//! unassigned issues of a project together with how many issues their authors reported before
+//! and which existing issues might be duplicates.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use super::errors::*;
+use super::issues::{self, Issue};
+use super::query::{fields, DaysAgo, Query};
+use super::ClientHandle;
+
+/// Default number of days issues are looked back for duplicate candidates.
+const DEFAULT_LOOKBACK_DAYS: u32 = 180;
+
+/// Default minimum similarity of subjects for duplicate candidates.
+const DEFAULT_MIN_SIMILARITY: f32 = 0.5;
+
+/// Struct to provide builder pattern for triage inboxes. Is used as return type for
+/// [issues.triage_inbox](../issues/struct.Api.html#method.triage_inbox).
+pub struct TriageInbox {
+    client: ClientHandle,
+    project_id: u32,
+    lookback_days: u32,
+    min_similarity: f32,
+}
+impl TriageInbox {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle, project_id: u32) -> Self {
+        TriageInbox {
+            client: client,
+            project_id: project_id,
+            lookback_days: DEFAULT_LOOKBACK_DAYS,
+            min_similarity: DEFAULT_MIN_SIMILARITY,
+        }
+    }
+
+    /// Sets the number of days issues created before are searched for duplicates. Defaults to
+    /// 180.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the number of days
+    pub fn lookback_days(mut self, n: u32) -> Self {
+        self.lookback_days = n;
+        self
+    }
+
+    /// Sets the minimum similarity of subjects (share of common words, between 0 and 1) for an
+    /// issue to be a duplicate candidate. Defaults to 0.5.
+    ///
+    /// # Arguments
+    ///
+    /// * `similarity` - a float holding the minimum similarity
+    pub fn min_similarity(mut self, similarity: f32) -> Self {
+        self.min_similarity = similarity;
+        self
+    }
+
+    /// Performs requests to redmine application and returns the open, unassigned issues of the
+    /// project, newest first, each with the author history and duplicate candidates.
+    pub fn execute(&self) -> Result<Vec<TriageItem>> {
+        let api = issues::Api::new(self.client.clone());
+
+        let mut inbox = Query::new();
+        inbox.push(fields::status().is_open())?;
+        inbox.push(fields::assigned_to().is_none())?;
+        let mut issues: Vec<Issue> = api.list()
+            .project_id(self.project_id)
+            .query(&inbox)
+            .execute_all()?
+            .into_iter()
+            .collect();
+        issues.sort_by(|a, b| b.created_on.cmp(&a.created_on));
+
+        let mut recent = Query::new();
+        recent.push(fields::status().is_any())?;
+        recent.push(fields::created_on().ge(self.lookback_days.days_ago()))?;
+        let candidates: Vec<(Issue, HashSet<String>)> = api.list()
+            .project_id(self.project_id)
+            .query(&recent)
+            .execute_all()?
+            .into_iter()
+            .map(|i| {
+                let words = words(&i.subject);
+                (i, words)
+            })
+            .collect();
+
+        let mut reports: HashMap<u32, u32> = HashMap::new();
+        let mut items = Vec::new();
+        for issue in issues {
+            let author_id = issue.author.id;
+            if let Entry::Vacant(entry) = reports.entry(author_id) {
+                let total = api.list()
+                    .author_id(author_id)
+                    .any_status()
+                    .limit(1)
+                    .execute()?
+                    .total_count()
+                    .unwrap_or(0);
+                entry.insert(total);
+            }
+
+            let subject = words(&issue.subject);
+            let mut duplicates: Vec<DuplicateCandidate> = candidates
+                .iter()
+                .filter(|(c, _)| c.id != issue.id)
+                .map(|(c, w)| {
+                    DuplicateCandidate {
+                        id: c.id,
+                        subject: c.subject.clone(),
+                        similarity: similarity(&subject, w),
+                    }
+                })
+                .filter(|d| d.similarity >= self.min_similarity)
+                .collect();
+            duplicates.sort_by(|a, b| {
+                b.similarity.partial_cmp(&a.similarity).unwrap_or(
+                    ::std::cmp::Ordering::Equal,
+                )
+            });
+
+            items.push(TriageItem {
+                // the issue itself is one of the reports of its author
+                prior_reports: reports[&author_id].saturating_sub(1),
+                duplicate_candidates: duplicates,
+                issue: issue,
+            });
+        }
+
+        Ok(items)
+    }
+}
+
+/// Returns the lowercase words of a subject, ignoring short words like `a` or `to`.
+///
+/// # Arguments
+///
+/// * `subject` - a string slice holding the subject of an issue
+fn words(subject: &str) -> HashSet<String> {
+    subject
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() > 2)
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+/// Returns the share of common words of two subjects (jaccard index).
+///
+/// # Arguments
+///
+/// * `a` - the words of the first subject
+/// * `b` - the words of the second subject
+fn similarity(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+
+    a.intersection(b).count() as f32 / union as f32
+}
+
+/// Represents an issue of a triage inbox.
+#[derive(Debug)]
+pub struct TriageItem {
+    pub issue: Issue,
+    /// Number of other issues the author of the issue has reported, in any project.
+    pub prior_reports: u32,
+    /// Issues with similar subjects, most similar first.
+    pub duplicate_candidates: Vec<DuplicateCandidate>,
+}
+
+/// Represents an issue which might be a duplicate of an issue in a triage inbox.
+#[derive(Debug, Clone)]
+pub struct DuplicateCandidate {
+    pub id: u32,
+    pub subject: String,
+    /// Share of common words of the subjects, between 0 and 1.
+    pub similarity: f32,
+}
@@ -0,0 +1,537 @@
+//! This module holds everything needed to upload files to the redmine application as described by
+//! following link: http://www.redmine.org/projects/redmine/wiki/Rest_api#Attaching-files. An
+//! upload returns a token which can be attached to an issue afterwards.
+
+extern crate serde_json;
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use super::errors::*;
+use super::http;
+use super::{failure, ClientHandle};
+
+/// Default number of retries of resumable uploads.
+const DEFAULT_RETRIES: u32 = 3;
+
+/// This struct exposes all methods to upload files to the redmine application.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns UploadExecutor struct which offers an `execute` function which uploads the content
+    /// of a byte slice, e.g. a report rendered in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` - a string slice holding the filename the attachment should get
+    /// * `bytes` - a byte slice holding the file content
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let upload = redmine.uploads().bytes("report.csv", b"a;b;c").execute();
+    /// ```
+    pub fn bytes(&self, filename: &str, bytes: &[u8]) -> UploadExecutor {
+        UploadExecutor::new(
+            self.client.clone(),
+            filename,
+            Box::new(Cursor::new(bytes.to_vec())),
+        )
+    }
+
+    /// Returns UploadExecutor struct which offers an `execute` function which uploads everything
+    /// read from `reader`. The content is streamed, so it doesn't have to fit into memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` - a string slice holding the filename the attachment should get
+    /// * `reader` - a reader providing the file content
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use std::io::Cursor;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let upload = redmine.uploads()
+    ///     .reader("report.csv", Cursor::new(b"a;b;c".to_vec()))
+    ///     .execute();
+    /// ```
+    pub fn reader<R: Read + Send + 'static>(&self, filename: &str, reader: R) -> UploadExecutor {
+        UploadExecutor::new(self.client.clone(), filename, Box::new(reader))
+    }
+
+    /// Returns UploadExecutor struct which offers an `execute` function which uploads a file from
+    /// the filesystem. The filename of the attachment is taken from `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - a path to the file which should be uploaded
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let upload = redmine.uploads().file("screenshot.png").and_then(|u| u.execute());
+    /// ```
+    pub fn file<P: AsRef<Path>>(&self, path: P) -> Result<UploadExecutor> {
+        let path = path.as_ref();
+        let filename = match path.file_name().and_then(|f| f.to_str()) {
+            Some(f) => f.to_string(),
+            None => bail!("Can't get filename of {}", path.display()),
+        };
+        let file = File::open(path)?;
+
+        Ok(self.reader(&filename, file))
+    }
+
+    /// Returns ResumableUpload struct (builder pattern) which ultimately uploads a large file from
+    /// the filesystem, retrying failed attempts and reporting progress. With a token file, a
+    /// file uploaded by a previous run isn't sent again. The filename of the attachment is taken
+    /// from `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - a path to the file which should be uploaded
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let upload = redmine.uploads().resumable("backup.tar.gz").and_then(|u| {
+    ///     u.token_file("backup.tar.gz.token")
+    ///         .progress(|sent, total| println!("{}/{} bytes", sent, total))
+    ///         .execute()
+    /// });
+    /// ```
+    pub fn resumable<P: AsRef<Path>>(&self, path: P) -> Result<ResumableUpload> {
+        let path = path.as_ref();
+        let filename = match path.file_name().and_then(|f| f.to_str()) {
+            Some(f) => f.to_string(),
+            None => bail!("Can't get filename of {}", path.display()),
+        };
+        let size = fs::metadata(path)
+            .chain_err(|| format!("Can't read {}", path.display()))?
+            .len();
+
+        Ok(ResumableUpload {
+            client: self.client.clone(),
+            path: path.to_path_buf(),
+            filename: filename,
+            size: size,
+            content_type: None,
+            description: None,
+            retries: DEFAULT_RETRIES,
+            token_file: None,
+            progress: None,
+        })
+    }
+}
+
+/// Helper struct to provide a unified interface for all upload api methods.
+pub struct UploadExecutor {
+    client: ClientHandle,
+    filename: String,
+    content_type: Option<String>,
+    description: Option<String>,
+    body: Box<dyn Read + Send>,
+}
+impl UploadExecutor {
+    /// Creates a new instance.
+    fn new(client: ClientHandle, filename: &str, body: Box<dyn Read + Send>) -> Self {
+        UploadExecutor {
+            client: client,
+            filename: filename.to_string(),
+            content_type: None,
+            description: None,
+            body: body,
+        }
+    }
+
+    /// Sets the content type of the attachment, e.g. `application/pdf`.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the content type
+    pub fn content_type(mut self, s: &str) -> Self {
+        self.content_type = Some(s.to_string());
+        self
+    }
+
+    /// Sets the description of the attachment.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the description
+    pub fn description(mut self, s: &str) -> Self {
+        self.description = Some(s.to_string());
+        self
+    }
+
+    /// Performs request to redmine application and uploads the content. Returns an
+    /// [Upload](struct.Upload.html) which can be attached to an issue. Consumes the executor
+    /// since the content can only be sent once.
+    pub fn execute(self) -> Result<Upload> {
+        #[derive(Deserialize)]
+        struct Token {
+            token: String,
+        }
+        #[derive(Deserialize)]
+        struct Wrapper {
+            upload: Token,
+        }
+
+        let mut params: HashMap<&str, String> = HashMap::new();
+        params.insert("filename", self.filename.clone());
+
+        let result = self.client.upload("/uploads.json", &params, self.body)?;
+        let wrapper: Wrapper = serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+
+        Ok(Upload {
+            token: wrapper.upload.token,
+            filename: self.filename,
+            content_type: self.content_type,
+            description: self.description,
+        })
+    }
+}
+
+/// Struct to provide builder pattern for resumable uploads. Is used as return type for
+/// [uploads.resumable](struct.Api.html#method.resumable).
+pub struct ResumableUpload {
+    client: ClientHandle,
+    path: PathBuf,
+    filename: String,
+    size: u64,
+    content_type: Option<String>,
+    description: Option<String>,
+    retries: u32,
+    token_file: Option<PathBuf>,
+    progress: Option<Arc<dyn Fn(u64, u64) + Send + Sync>>,
+}
+impl ResumableUpload {
+    /// Sets the content type of the attachment, e.g. `application/pdf`.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the content type
+    pub fn content_type(mut self, s: &str) -> Self {
+        self.content_type = Some(s.to_string());
+        self
+    }
+
+    /// Sets the description of the attachment.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the description
+    pub fn description(mut self, s: &str) -> Self {
+        self.description = Some(s.to_string());
+        self
+    }
+
+    /// Sets how often a failed upload is retried. Only transport errors and responses which may
+    /// succeed on another attempt (408, 429 and 5xx) are retried; other failures, e.g. a rejected
+    /// api key or a file exceeding the maximum attachment size, are returned right away.
+    /// Defaults to 3.
+    ///
+    /// Redmine doesn't tell whether a failed attempt has been stored anyway, e.g. if the
+    /// connection dropped after the file has been received. Such an attempt isn't verified, so a
+    /// retry may leave an orphaned upload behind, which redmine removes eventually.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the number of retries
+    pub fn retries(mut self, n: u32) -> Self {
+        self.retries = n;
+        self
+    }
+
+    /// Sets a file the token of a completed upload is stored in. If the file holds a token of a
+    /// previous run whose upload still exists with the same size, it's reused instead of sending
+    /// the file again, e.g. when the process died before attaching the upload.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the token file
+    pub fn token_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.token_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets a callback which is called with the number of bytes sent so far and the size of the
+    /// file while uploading. It starts at zero again on retries.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - a closure reporting the progress
+    pub fn progress<F>(mut self, f: F) -> Self
+    where
+        F: Fn(u64, u64) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(f));
+        self
+    }
+
+    /// Performs requests to redmine application and uploads the file unless a previous upload
+    /// can be reused. Returns an [Upload](struct.Upload.html) which can be attached to an issue.
+    pub fn execute(&self) -> Result<Upload> {
+        if let Some(token) = self.previous_token()? {
+            return Ok(self.upload(token));
+        }
+
+        let mut attempt = 0;
+        loop {
+            match self.send()? {
+                Attempt::Uploaded(token) => {
+                    if let Some(ref path) = self.token_file {
+                        File::create(path)
+                            .and_then(|mut f| f.write_all(token.as_bytes()))
+                            .chain_err(|| format!("Can't write {}", path.display()))?;
+                    }
+                    return Ok(self.upload(token));
+                }
+                Attempt::Failed(e) => {
+                    if attempt >= self.retries {
+                        return Err(e);
+                    }
+                }
+            }
+
+            attempt += 1;
+            thread::sleep(Duration::from_secs(u64::from(attempt)));
+        }
+    }
+
+    /// Sends the file once and returns the token. Failures which may go away on another attempt
+    /// are returned as `Attempt::Failed`, all others as error.
+    fn send(&self) -> Result<Attempt> {
+        #[derive(Deserialize)]
+        struct Token {
+            token: String,
+        }
+        #[derive(Deserialize)]
+        struct Wrapper {
+            upload: Token,
+        }
+
+        let file = File::open(&self.path).chain_err(
+            || format!("Can't open {}", self.path.display()),
+        )?;
+        let body: Box<dyn Read + Send> = match self.progress {
+            Some(ref f) => Box::new(ProgressReader {
+                inner: file,
+                sent: 0,
+                total: self.size,
+                callback: Arc::clone(f),
+            }),
+            None => Box::new(file),
+        };
+
+        let mut url = self.client.get_base_url("/uploads.json")?;
+        url.query_pairs_mut().append_pair("filename", &self.filename);
+
+        let mut response =
+            match self.client.send(http::Method::Post, url, Some(http::Body::Binary(body))) {
+                Ok(response) => response,
+                Err(e) => return Ok(Attempt::Failed(e)),
+            };
+        let result = response.text()?;
+        if !response.is_success() {
+            let e = failure(response.status, &result);
+            return match response.status {
+                408 | 429 | 500..=599 => Ok(Attempt::Failed(e)),
+                _ => Err(e),
+            };
+        }
+        let wrapper: Wrapper = serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+
+        Ok(Attempt::Uploaded(wrapper.upload.token))
+    }
+
+    /// Returns the token stored by a previous run if its upload still exists with the size of
+    /// the file. Tokens look like `<attachment id>.<digest>`.
+    fn previous_token(&self) -> Result<Option<String>> {
+        #[derive(Deserialize)]
+        struct Size {
+            filesize: u64,
+        }
+        #[derive(Deserialize)]
+        struct Wrapper {
+            attachment: Size,
+        }
+
+        let path = match self.token_file {
+            Some(ref path) => path,
+            None => return Ok(None),
+        };
+        let mut content = String::new();
+        let read = File::open(path).and_then(|mut f| f.read_to_string(&mut content));
+        match read {
+            Ok(_) => {}
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).chain_err(|| format!("Can't read {}", path.display())),
+        }
+        let token = content.trim().to_string();
+        let id = match token.split('.').next().and_then(|id| id.parse::<u32>().ok()) {
+            Some(id) => id,
+            None => return Ok(None),
+        };
+
+        let url = self.client.get_base_url(&format!("/attachments/{}.json", id))?;
+        let mut response = self.client.send(http::Method::Get, url, None)?;
+        if !response.is_success() {
+            return Ok(None);
+        }
+        let wrapper: Wrapper = serde_json::from_str(&response.text()?).chain_err(
+            || "Can't parse json",
+        )?;
+
+        if wrapper.attachment.filesize == self.size {
+            Ok(Some(token))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns the upload for a token.
+    fn upload(&self, token: String) -> Upload {
+        Upload {
+            token: token,
+            filename: self.filename.clone(),
+            content_type: self.content_type.clone(),
+            description: self.description.clone(),
+        }
+    }
+}
+
+/// Outcome of a single attempt of a resumable upload.
+enum Attempt {
+    Uploaded(String),
+    /// The attempt failed in a way another attempt may succeed, e.g. a timeout.
+    Failed(Error),
+}
+
+/// Reader reporting the number of bytes read to a callback.
+struct ProgressReader<R> {
+    inner: R,
+    sent: u64,
+    total: u64,
+    callback: Arc<dyn Fn(u64, u64) + Send + Sync>,
+}
+impl<R: Read> Read for ProgressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.sent += n as u64;
+        (self.callback)(self.sent, self.total);
+
+        Ok(n)
+    }
+}
+
+/// Represents an uploaded file which is not yet attached to any issue. Can be serialized to be
+/// used as json parameter for request to redmine application.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct Upload {
+    pub token: String,
+    pub filename: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+    use super::super::errors::*;
+    use super::super::{http, RedmineApi};
+
+    /// Responds to every request with the given status and counts the requests.
+    struct Status {
+        status: u16,
+        sent: Arc<Mutex<u32>>,
+    }
+    impl http::Transport for Status {
+        fn send(&self, _: http::Request) -> Result<http::Response> {
+            *self.sent.lock().unwrap() += 1;
+
+            Ok(http::Response {
+                status: self.status,
+                location: None,
+                body: Box::new(Cursor::new(Vec::new())),
+            })
+        }
+    }
+
+    /// Uploads a small file with one retry and returns the number of attempts.
+    fn attempts(status: u16) -> u32 {
+        let path = ::std::env::temp_dir().join(format!("redmine-upload-test-{}", status));
+        fs::write(&path, b"Hello").unwrap();
+
+        let sent = Arc::new(Mutex::new(0));
+        let api = RedmineApi::with_transport(
+            "http://localhost".to_string(),
+            "1234".to_string(),
+            Box::new(Status {
+                status: status,
+                sent: sent.clone(),
+            }),
+        );
+        let result = api.uploads().resumable(&path).unwrap().retries(1).execute();
+        let _ = fs::remove_file(&path);
+
+        assert!(result.is_err());
+        let n = *sent.lock().unwrap();
+        n
+    }
+
+    #[test]
+    fn unavailable_server_is_retried() {
+        assert_eq!(attempts(503), 2);
+    }
+
+    #[test]
+    fn rejected_uploads_are_not_retried() {
+        assert_eq!(attempts(401), 1);
+        assert_eq!(attempts(403), 1);
+        assert_eq!(attempts(404), 1);
+        assert_eq!(attempts(413), 1);
+    }
+}
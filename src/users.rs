@@ -4,17 +4,22 @@
 extern crate serde_json;
 
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::fmt;
+use super::entity::{Creatable, Deletable, Listable, Page, Resource, Showable};
 use super::errors::*;
-use super::RedmineClient;
+pub use super::memberships::{Membership, Role};
+#[cfg(all(feature = "issues", feature = "time_entries"))]
+use super::{issues, time_entries};
+use super::{create_from_json, format_table, missing_includes, Checked, ClientHandle, RedmineApi,
+            Tristate};
 
 /// This struct exposes all methods provided by the redmine users api.
 pub struct Api {
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
 }
 impl Api {
     /// Creates a new instance. Should not be called externally.
-    pub fn new(client: Rc<RedmineClient>) -> Api {
+    pub fn new(client: ClientHandle) -> Api {
         Api { client: client }
     }
 
@@ -33,7 +38,7 @@ impl Api {
     /// let result = redmine.users().list().execute();
     /// ```
     pub fn list(&self) -> UserFilter {
-        UserFilter::new(Rc::clone(&self.client))
+        UserFilter::new(self.client.clone())
     }
 
     /// Returns a single user by id.
@@ -56,7 +61,7 @@ impl Api {
     /// ```
     pub fn show(&self, id: u32) -> UserShow {
         UserShow {
-            client: Rc::clone(&self.client),
+            client: self.client.clone(),
             show_id: id,
             ..Default::default()
         }
@@ -93,7 +98,7 @@ impl Api {
         lastname: &'a str,
         mail: &'a str,
     ) -> UserBuilder<'a> {
-        UserBuilder::for_create(Rc::clone(&self.client), login, firstname, lastname, mail)
+        UserBuilder::for_create(self.client.clone(), login, firstname, lastname, mail)
     }
 
     /// Returns an UserBuilder and ultimately updates an existing prpoject in the redmine
@@ -118,7 +123,7 @@ impl Api {
     ///     .execute();
     /// ```
     pub fn update(&self, id: u32) -> UserBuilder {
-        UserBuilder::for_update(Rc::clone(&self.client), id)
+        UserBuilder::for_update(self.client.clone(), id)
     }
 
     /// Returns UserDelete struct which offers an `execute` function which deletes the user
@@ -142,10 +147,80 @@ impl Api {
     /// ```
     pub fn delete(&self, id: u32) -> UserDelete {
         UserDelete {
-            client: Rc::clone(&self.client),
+            client: self.client.clone(),
             delete_id: id,
         }
     }
+
+    /// Returns UserWorkload struct which offers an `execute` function which combines open issues,
+    /// estimated remaining hours and logged time of each user into a report, e.g. for capacity
+    /// planning. Open issues are counted across all projects. Requires the `issues` and
+    /// `time_entries` features.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_ids` - a vector of integers holding the user ids
+    /// * `from` - a string slice holding the first day of logged time in `YYYY-MM-DD` format
+    /// * `to` - a string slice holding the last day of logged time in `YYYY-MM-DD` format
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.users().workload(vec![1, 5], "2017-09-01", "2017-09-30").execute();
+    /// ```
+    #[cfg(all(feature = "issues", feature = "time_entries"))]
+    pub fn workload(&self, user_ids: Vec<u32>, from: &str, to: &str) -> UserWorkload {
+        UserWorkload {
+            client: self.client.clone(),
+            user_ids: user_ids,
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+}
+impl Resource for Api {
+    type Entity = User;
+
+    fn name(&self) -> &'static str {
+        "users"
+    }
+}
+impl Listable for Api {
+    fn list_page(&self, offset: u32, limit: u32) -> Result<Page<User>> {
+        let mut params: HashMap<&str, String> = HashMap::new();
+        params.insert("offset", offset.to_string());
+        params.insert("limit", limit.to_string());
+
+        let result = self.client.get("/users.json", &params)?;
+        let list: UserList = serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+
+        Ok(Page {
+            total_count: list.total_count,
+            items: list.users,
+        })
+    }
+}
+impl Showable for Api {
+    fn fetch(&self, id: u32) -> Result<User> {
+        self.show(id).execute()
+    }
+}
+impl Creatable for Api {
+    fn create_from_json(&self, fields: &serde_json::Value) -> Result<u32> {
+        create_from_json(&self.client, "/users.json", "user", fields)
+    }
+}
+impl Deletable for Api {
+    fn remove(&self, id: u32) -> Result<()> {
+        self.delete(id).execute()
+    }
 }
 
 /// Holds parameters the users in redmine application should be filtered by and implements a
@@ -153,15 +228,15 @@ impl Api {
 /// TODO
 #[derive(Default)]
 pub struct UserFilter {
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
 }
 impl UserFilter {
     /// Creates a new instance.
     ///
     /// # Arguments
     ///
-    /// * `client` - a Rc boxed RedmineClient
-    fn new(client: Rc<RedmineClient>) -> Self {
+    /// * `client` - a handle to the RedmineClient
+    fn new(client: ClientHandle) -> Self {
         Self { client: client }
     }
 
@@ -177,6 +252,25 @@ impl UserFilter {
 #[derive(Deserialize, Debug)]
 pub struct UserList {
     users: Vec<User>,
+    total_count: Option<u32>,
+}
+impl UserList {
+    /// Formats the users as plain text table, e.g. for command line tools.
+    pub fn table(&self) -> String {
+        let rows: Vec<Vec<String>> = self.users
+            .iter()
+            .map(|u| {
+                vec![
+                    u.id.to_string(),
+                    u.login.clone(),
+                    format!("{} {}", u.firstname, u.lastname),
+                    u.mail.clone(),
+                ]
+            })
+            .collect();
+
+        format_table(&["ID", "Login", "Name", "Mail"], &rows)
+    }
 }
 impl IntoIterator for UserList {
     type Item = User;
@@ -187,41 +281,152 @@ impl IntoIterator for UserList {
     }
 }
 
+/// Struct to provide workload reports. Is used as return type for
+/// [users.workload](struct.Api.html#method.workload).
+#[cfg(all(feature = "issues", feature = "time_entries"))]
+pub struct UserWorkload {
+    client: ClientHandle,
+    user_ids: Vec<u32>,
+    from: String,
+    to: String,
+}
+#[cfg(all(feature = "issues", feature = "time_entries"))]
+impl UserWorkload {
+    /// Performs requests to redmine application and returns the workload of every user, walking
+    /// through all pages of issues and time entries.
+    pub fn execute(&self) -> Result<Vec<Workload>> {
+        let issues = issues::Api::new(self.client.clone());
+        let time_entries = time_entries::Api::new(self.client.clone());
+
+        let mut result = Vec::new();
+        for &id in &self.user_ids {
+            // redmine only lists open issues unless told otherwise
+            let open = issues.list().assigned_to_id(id).execute_all()?;
+
+            let mut workload = Workload {
+                user_id: id,
+                logged_hours: time_entries
+                    .list()
+                    .user_id(id)
+                    .from(&self.from)
+                    .to(&self.to)
+                    .total_hours()?,
+                ..Default::default()
+            };
+            for issue in open {
+                workload.open_issues += 1;
+                if let Some(eh) = issue.estimated_hours {
+                    let open_ratio = 100 - issue.done_ratio.min(100);
+                    workload.remaining_hours += eh * open_ratio as f32 / 100.0;
+                }
+            }
+            result.push(workload);
+        }
+
+        Ok(result)
+    }
+}
+
+/// Holds the workload of a single user as returned by
+/// [UserWorkload::execute](struct.UserWorkload.html#method.execute).
+#[derive(Debug, Default, Clone)]
+pub struct Workload {
+    pub user_id: u32,
+    /// Number of open issues assigned to the user.
+    pub open_issues: u32,
+    /// Estimated hours of the open issues not covered by their done ratio.
+    pub remaining_hours: f32,
+    /// Hours logged by the user in the requested date range.
+    pub logged_hours: f32,
+}
+
 /// Wrapper struct for deserialization of a single User pulled from redmine application.
 #[derive(Deserialize, Debug, Default)]
 pub struct UserShow {
     #[serde(skip_deserializing)]
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     #[serde(skip_deserializing)]
     show_id: u32,
+    #[serde(skip_deserializing)]
+    include: Vec<String>,
 
     // fields used for deserialization
     user: User,
 }
 impl UserShow {
+    /// Requests additional associated data to be included in the user, i.e. `memberships` or
+    /// `groups`.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the name of the associated data
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(user) = redmine.users().show(1).include("memberships").execute() {
+    ///     for m in user.memberships_with_role("Manager") {
+    ///         println!("Manager of {}", m.project.name());
+    ///     }
+    /// }
+    /// ```
+    pub fn include(mut self, s: &str) -> Self {
+        self.include.push(s.to_string());
+        self
+    }
+
     /// Performs request to redmine application and returns a single user.
     pub fn execute(&self) -> Result<User> {
-        let result = self.client.get(
-            &(format!("/users/{}.json", self.show_id)),
-            &HashMap::new(),
-        )?;
-
         Ok(
-            serde_json::from_str::<UserShow>(&result)
+            serde_json::from_str::<UserShow>(&self.fetch()?)
                 .chain_err(|| "Can't parse json")?
                 .into(),
         )
     }
+
+    /// Performs request to redmine application like [execute](#method.execute), but reports
+    /// requested includes missing in the response as warnings, e.g. `memberships` if the user
+    /// isn't allowed to view them.
+    pub fn execute_checked(&self) -> Result<Checked<User>> {
+        let value: serde_json::Value =
+            serde_json::from_str(&self.fetch()?).chain_err(|| "Can't parse json")?;
+        let warnings = missing_includes(&value["user"], &self.include);
+        let show: UserShow = serde_json::from_value(value).chain_err(|| "Can't parse json")?;
+
+        Ok(Checked {
+            value: show.into(),
+            warnings: warnings,
+        })
+    }
+
+    /// Performs request to redmine application and returns the response body.
+    fn fetch(&self) -> Result<String> {
+        let mut params: HashMap<&str, String> = HashMap::new();
+        if !self.include.is_empty() {
+            params.insert("include", self.include.join(","));
+        }
+
+        self.client.get(&(format!("/users/{}.json", self.show_id)), &params)
+    }
 }
 
 /// Helper struct to provide a unified interface for all user api methods.
 pub struct UserDelete {
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     delete_id: u32,
 }
 impl UserDelete {
-    /// Performs request to redmine application and deletes a user.
-    pub fn execute(&self) -> Result<bool> {
+    /// Performs request to redmine application and deletes a user. Fails unless deletions
+    /// have been allowed, see
+    /// [RedmineApi::allow_destructive](../struct.RedmineApi.html#method.allow_destructive).
+    pub fn execute(&self) -> Result<()> {
         self.client.delete(
             &(format!("/users/{}.json", self.delete_id)),
         )
@@ -229,7 +434,7 @@ impl UserDelete {
 }
 
 /// Represents a user as pulled from redmine application.
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct User {
     pub id: u32,
     pub login: String,
@@ -238,12 +443,63 @@ pub struct User {
     pub mail: String,
     pub created_on: String,
     pub last_login_on: Option<String>,
+    /// The projects the user is a member of together with the roles. Is only pulled from redmine
+    /// application if `memberships` are included.
+    #[serde(default)]
+    pub memberships: Vec<Membership>,
 }
 impl From<UserShow> for User {
     fn from(item: UserShow) -> Self {
         item.user
     }
 }
+// e.g. `John Doe (jdoe)`
+impl fmt::Display for User {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} ({})", self.firstname, self.lastname, self.login)
+    }
+}
+impl User {
+    /// Returns the url of the page showing this user in the redmine application, e.g.
+    /// `http://www.redmine.org/users/1`, for linking users to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - the api pointing to the redmine application
+    pub fn web_url(&self, api: &RedmineApi) -> String {
+        api.client.web_url(&format!("/users/{}", self.id))
+    }
+
+    /// Returns the memberships in which the user has the role `name`, e.g. `Manager`, directly
+    /// or via a group. Requires the user to be pulled with `memberships` included.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - a string slice holding the name of the role
+    pub fn memberships_with_role(&self, name: &str) -> Vec<&Membership> {
+        self.memberships
+            .iter()
+            .filter(|m| m.roles.iter().any(|r| r.name == name))
+            .collect()
+    }
+}
+
+/// Refers to a user by id, e.g. as member of a pool issues are assigned to. Can be created from
+/// an id or a [User](struct.User.html).
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UserRef {
+    pub id: u32,
+}
+impl From<u32> for UserRef {
+    fn from(id: u32) -> Self {
+        UserRef { id: id }
+    }
+}
+impl<'a> From<&'a User> for UserRef {
+    fn from(user: &'a User) -> Self {
+        UserRef { id: user.id }
+    }
+}
 
 /// Helper struct for serialization.
 #[derive(Serialize)]
@@ -270,23 +526,23 @@ impl Default for UserBuilderKind {
 pub struct UserBuilder<'a> {
     // internal
     #[serde(skip_serializing)]
-    client: Rc<RedmineClient>,
+    client: ClientHandle,
     #[serde(skip_serializing)]
     kind: UserBuilderKind,
     #[serde(skip_serializing)]
     update_id: u32,
 
     // fields used for serialization
-    #[serde(skip_serializing_if = "str::is_empty")]
-    login: &'a str,
-    #[serde(skip_serializing_if = "str::is_empty")]
-    firstname: &'a str,
-    #[serde(skip_serializing_if = "str::is_empty")]
-    lastname: &'a str,
-    #[serde(skip_serializing_if = "str::is_empty")]
-    mail: &'a str,
-    #[serde(skip_serializing_if = "str::is_empty")]
-    password: &'a str,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    login: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    firstname: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    lastname: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    mail: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    password: Tristate<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     auth_source_id: Option<u32>,
     must_change_passwd: bool,
@@ -303,7 +559,7 @@ impl<'a> UserBuilder<'a> {
     /// * `lastname` - a string slice holding the lastname of the user
     /// * `mail` - a string slice holding the email address of the user
     pub fn for_create(
-        client: Rc<RedmineClient>,
+        client: ClientHandle,
         login: &'a str,
         firstname: &'a str,
         lastname: &'a str,
@@ -313,10 +569,10 @@ impl<'a> UserBuilder<'a> {
             client: client,
             kind: UserBuilderKind::Create,
 
-            login: login,
-            firstname: firstname,
-            lastname: lastname,
-            mail: mail,
+            login: Tristate::Set(login),
+            firstname: Tristate::Set(firstname),
+            lastname: Tristate::Set(lastname),
+            mail: Tristate::Set(mail),
             ..Default::default()
         }
     }
@@ -327,7 +583,7 @@ impl<'a> UserBuilder<'a> {
     /// # Arguments
     ///
     /// * `id` - an integer holding the user id
-    pub fn for_update(client: Rc<RedmineClient>, id: u32) -> Self {
+    pub fn for_update(client: ClientHandle, id: u32) -> Self {
         UserBuilder {
             client: client,
             kind: UserBuilderKind::Update,
@@ -342,7 +598,7 @@ impl<'a> UserBuilder<'a> {
     ///
     /// * `s` - a string slice holding the login
     pub fn login(mut self, s: &'a str) -> Self {
-        self.login = s;
+        self.login = Tristate::Set(s);
         self
     }
 
@@ -352,7 +608,7 @@ impl<'a> UserBuilder<'a> {
     ///
     /// * `s` - a string slice holding the firstname
     pub fn firstname(mut self, s: &'a str) -> Self {
-        self.firstname = s;
+        self.firstname = Tristate::Set(s);
         self
     }
 
@@ -362,7 +618,7 @@ impl<'a> UserBuilder<'a> {
     ///
     /// * `s` - a string slice holding the lastname
     pub fn lastname(mut self, s: &'a str) -> Self {
-        self.lastname = s;
+        self.lastname = Tristate::Set(s);
         self
     }
 
@@ -372,7 +628,7 @@ impl<'a> UserBuilder<'a> {
     ///
     /// * `s` - a string slice holding the email address
     pub fn mail(mut self, s: &'a str) -> Self {
-        self.mail = s;
+        self.mail = Tristate::Set(s);
         self
     }
 
@@ -382,7 +638,7 @@ impl<'a> UserBuilder<'a> {
     ///
     /// * `s` - a string slice holding the password
     pub fn password(mut self, s: &'a str) -> Self {
-        self.password = s;
+        self.password = Tristate::Set(s);
         self
     }
 
@@ -416,15 +672,16 @@ impl<'a> UserBuilder<'a> {
         self
     }
 
-    /// Performs request to redmine application to create or update a user.
-    pub fn execute(&self) -> Result<String> {
+    /// Performs request to redmine application to create or update a user and returns it.
+    pub fn execute(&self) -> Result<User> {
         let user = UserBuilderWrapper { user: self };
         match self.kind {
-            UserBuilderKind::Create => self.client.create("/users.json", &user),
+            UserBuilderKind::Create => self.client.create_entity("/users.json", &user, "user"),
             UserBuilderKind::Update => {
-                self.client.update(
+                self.client.update_entity(
                     &(format!("/users/{}.json", self.update_id)),
                     &user,
+                    "user",
                 )
             }
         }
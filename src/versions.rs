@@ -0,0 +1,577 @@
+//! This module holds everything needed to represent the redmine versions api as described by
+//! following link: http://www.redmine.org/projects/redmine/wiki/Rest_Versions.
+
+#[cfg(feature = "issues")]
+use std::collections::{BTreeMap, HashSet};
+#[cfg(feature = "issues")]
+use super::errors::*;
+#[cfg(feature = "issues")]
+use super::http;
+#[cfg(feature = "issues")]
+use super::issues::{self, Issue};
+#[cfg(feature = "issues")]
+use super::relations::RelationType;
+#[cfg(all(feature = "issues", feature = "wiki"))]
+use super::wiki::WikiPageBuilder;
+#[cfg(feature = "issues")]
+use super::failure;
+use super::{ClientHandle, CustomField, NamedObject, RedmineApi, Tristate};
+
+/// This struct exposes all methods provided by the redmine versions api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns a VersionListExecutor which ultimately leads to the versions of a project,
+    /// including the versions shared with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.versions().list(1).execute();
+    /// ```
+    pub fn list(&self, project_id: u32) -> VersionListExecutor {
+        VersionListExecutor::new(
+            self.client.clone(),
+            format!("/projects/{}/versions.json", project_id),
+        )
+    }
+
+    /// Returns VersionShow struct which offers an `execute` function which returns the version
+    /// specified by `id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the version id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.versions().show(1).execute();
+    /// ```
+    pub fn show(&self, id: u32) -> VersionShow {
+        VersionShow::new(self.client.clone(), id)
+    }
+
+    /// Returns a VersionBuilder (builder pattern) and ultimately creates a new version in the
+    /// project specified by `project_id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    /// * `name` - a string slice holding the name of the version
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::versions::{VersionSharing, VersionStatus};
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.versions().create(1, "2.0")
+    ///     .status(VersionStatus::Open)
+    ///     .sharing(VersionSharing::Descendants)
+    ///     .due_date("2018-03-31")
+    ///     .description("Second major release")
+    ///     .execute();
+    /// ```
+    pub fn create<'a>(&self, project_id: u32, name: &'a str) -> VersionBuilder<'a> {
+        VersionBuilder::for_create(
+            self.client.clone(),
+            format!("/projects/{}/versions.json", project_id),
+        ).name(name)
+    }
+
+    /// Returns a VersionBuilder (builder pattern) and ultimately updates an existing version.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the version id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::versions::VersionStatus;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.versions().update(1)
+    ///     .status(VersionStatus::Closed)
+    ///     .execute();
+    /// ```
+    pub fn update<'a>(&self, id: u32) -> VersionBuilder<'a> {
+        VersionBuilder::for_update(self.client.clone(), format!("/versions/{}.json", id))
+    }
+
+    /// Returns VersionDelete struct which offers an `execute` function which deletes the version
+    /// specified by `id` parameter. Redmine refuses to delete versions issues are assigned to.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the version id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.versions().delete(1).execute();
+    /// ```
+    pub fn delete(&self, id: u32) -> VersionDelete {
+        VersionDelete::new(self.client.clone(), id)
+    }
+
+    /// Returns ReleaseCheck struct which offers an `execute` function which checks whether a
+    /// version is ready to be released: it reports open issues, issues without estimate, issues
+    /// blocked by open issues and whether the wiki page holding the release notes exists.
+    /// Requires the `issues` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the version id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// if let Ok(readiness) = redmine.versions().release_check(1).execute() {
+    ///     println!("Ready: {}", readiness.is_ready());
+    /// }
+    /// ```
+    #[cfg(feature = "issues")]
+    pub fn release_check(&self, id: u32) -> ReleaseCheck {
+        ReleaseCheck {
+            client: self.client.clone(),
+            version_id: id,
+        }
+    }
+
+    /// Returns a Changelog (builder pattern) which ultimately renders the closed issues of a
+    /// version grouped by tracker (or category) as release notes, ready to be pasted into the
+    /// wiki page of the version or to be published directly. Requires the `issues` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - an integer holding the version id
+    /// * `format` - the [ChangelogFormat](enum.ChangelogFormat.html) of the release notes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    /// use redmine_api::versions::ChangelogFormat;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.versions().changelog(1, ChangelogFormat::Textile)
+    ///     .group_by_category()
+    ///     .publish_wiki()
+    ///     .execute();
+    /// ```
+    #[cfg(feature = "issues")]
+    pub fn changelog(&self, id: u32, format: ChangelogFormat) -> Changelog {
+        Changelog {
+            client: self.client.clone(),
+            version_id: id,
+            format: format,
+            by_category: false,
+            #[cfg(feature = "wiki")]
+            publish_wiki: false,
+            publish_news: false,
+        }
+    }
+}
+
+show_endpoint! {
+    /// Helper struct to provide a unified interface for all version api methods.
+    VersionShow => Version, version, "/versions/{}.json"
+}
+
+list_endpoint! {
+    /// Helper struct to provide a unified interface for all version api methods.
+    VersionListExecutor => VersionList<Version>, versions
+}
+
+builder_endpoint! {
+    /// Struct to provide builder pattern for creation and update of versions. Can be serialized
+    /// to be used as json parameter for request to redmine application.
+    VersionBuilder [create, update] => Version, version {
+        /// Sets name of the version.
+        name: &'a str,
+        /// Sets status of the version.
+        status: VersionStatus,
+        /// Sets due date of the version in `YYYY-MM-DD` format.
+        due_date: &'a str,
+        /// Sets with which projects the version is shared.
+        sharing: VersionSharing,
+        /// Sets description of the version.
+        description: &'a str,
+        /// Sets title of the wiki page describing the version.
+        wiki_page_title: &'a str,
+    }
+}
+
+delete_endpoint! {
+    /// Helper struct to provide a unified interface for all version api methods.
+    VersionDelete, "/versions/{}.json"
+}
+
+/// Struct to provide release readiness checks. Is used as return type for
+/// [versions.release_check](struct.Api.html#method.release_check).
+#[cfg(feature = "issues")]
+pub struct ReleaseCheck {
+    client: ClientHandle,
+    version_id: u32,
+}
+#[cfg(feature = "issues")]
+impl ReleaseCheck {
+    /// Performs requests to redmine application and returns the readiness of the version.
+    pub fn execute(&self) -> Result<ReleaseReadiness> {
+        let version = VersionShow::new(self.client.clone(), self.version_id).execute()?;
+
+        // redmine only lists open issues unless told otherwise
+        let open: Vec<Issue> = issues::Api::new(self.client.clone())
+            .list()
+            .fixed_version_id(self.version_id)
+            .include("relations")
+            .execute_all()?
+            .into_iter()
+            .collect();
+
+        Ok(ReleaseReadiness {
+            version_id: self.version_id,
+            open_issues: open.iter().map(|i| i.id).collect(),
+            without_estimate: open.iter()
+                .filter(|i| i.estimated_hours.is_none())
+                .map(|i| i.id)
+                .collect(),
+            blocked: self.blocked(&open)?,
+            release_notes: self.release_notes(&version)?,
+        })
+    }
+
+    /// Returns the ids of the issues which are blocked by open issues.
+    fn blocked(&self, open: &[Issue]) -> Result<Vec<u32>> {
+        let blockers: HashSet<u32> = open.iter()
+            .flat_map(|i| i.relations.iter())
+            .filter(|r| r.relation_type == RelationType::Blocks)
+            .map(|r| r.issue_id)
+            .collect();
+        if blockers.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // blocking issues may belong to other versions, so their status is requested separately
+        let open_blockers: HashSet<u32> = issues::Api::new(self.client.clone())
+            .list()
+            .issue_ids(blockers.into_iter().collect())
+            .execute_all()?
+            .into_iter()
+            .map(|i| i.id)
+            .collect();
+
+        Ok(
+            open.iter()
+                .filter(|i| {
+                    i.relations.iter().any(|r| {
+                        r.relation_type == RelationType::Blocks && r.issue_to_id == i.id &&
+                            open_blockers.contains(&r.issue_id)
+                    })
+                })
+                .map(|i| i.id)
+                .collect(),
+        )
+    }
+
+    /// Checks if the wiki page of the version exists.
+    fn release_notes(&self, version: &Version) -> Result<bool> {
+        let title = match version.wiki_page_title {
+            Some(ref t) if !t.is_empty() => t,
+            _ => return Ok(false),
+        };
+
+        let url = self.client.get_base_url(&format!(
+            "/projects/{}/wiki/{}.json",
+            version.project.id,
+            title
+        ))?;
+        let response = self.client.send(http::Method::Get, url, None)?;
+
+        match response.status {
+            404 => Ok(false),
+            _ if response.is_success() => Ok(true),
+            status => bail!("Error: {}", status),
+        }
+    }
+}
+
+/// Enumeration of the text formats release notes can be rendered in. Should match the text
+/// formatting configured in the redmine application.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangelogFormat {
+    Markdown,
+    Textile,
+}
+#[cfg(feature = "issues")]
+impl ChangelogFormat {
+    /// Returns the markup of a heading of the given level.
+    fn heading(&self, level: usize, text: &str) -> String {
+        match *self {
+            ChangelogFormat::Markdown => format!("{} {}\n\n", "#".repeat(level), text),
+            ChangelogFormat::Textile => format!("h{}. {}\n\n", level, text),
+        }
+    }
+}
+
+/// Struct to provide builder pattern for release notes. Is used as return type for
+/// [versions.changelog](struct.Api.html#method.changelog).
+#[cfg(feature = "issues")]
+pub struct Changelog {
+    client: ClientHandle,
+    version_id: u32,
+    format: ChangelogFormat,
+    by_category: bool,
+    #[cfg(feature = "wiki")]
+    publish_wiki: bool,
+    publish_news: bool,
+}
+#[cfg(feature = "issues")]
+impl Changelog {
+    /// Groups the issues by category instead of tracker. Issues without category are listed
+    /// under `Uncategorized`.
+    pub fn group_by_category(mut self) -> Self {
+        self.by_category = true;
+        self
+    }
+
+    /// Writes the release notes to the wiki page of the version. If the version has no wiki page
+    /// set, a page named after the version is used. Requires the `wiki` feature.
+    #[cfg(feature = "wiki")]
+    pub fn publish_wiki(mut self) -> Self {
+        self.publish_wiki = true;
+        self
+    }
+
+    /// Publishes the release notes as news of the project the version belongs to.
+    pub fn publish_news(mut self) -> Self {
+        self.publish_news = true;
+        self
+    }
+
+    /// Performs requests to redmine application, renders the release notes and publishes them if
+    /// requested. Returns the release notes.
+    pub fn execute(&self) -> Result<String> {
+        let version = VersionShow::new(self.client.clone(), self.version_id).execute()?;
+        let closed = issues::Api::new(self.client.clone())
+            .list()
+            .fixed_version_id(self.version_id)
+            .closed()
+            .execute_all()?;
+
+        // sorted by group name, issues sorted by id
+        let mut groups: BTreeMap<String, Vec<Issue>> = BTreeMap::new();
+        for issue in closed {
+            let group = if self.by_category {
+                issue.category.as_ref().map_or(
+                    "Uncategorized".to_string(),
+                    |c| c.name.clone(),
+                )
+            } else {
+                issue.tracker.name.clone()
+            };
+            groups.entry(group).or_default().push(issue);
+        }
+
+        let mut text = self.format.heading(1, &version.name);
+        if let Some(ref d) = version.description {
+            if !d.is_empty() {
+                text.push_str(&format!("{}\n\n", d));
+            }
+        }
+        for (group, mut issues) in groups {
+            issues.sort_by_key(|i| i.id);
+            text.push_str(&self.format.heading(2, &group));
+            for issue in issues {
+                text.push_str(&format!("* #{} {}\n", issue.id, issue.subject));
+            }
+            text.push('\n');
+        }
+
+        #[cfg(feature = "wiki")]
+        if self.publish_wiki {
+            let title = match version.wiki_page_title {
+                Some(ref t) if !t.is_empty() => t.clone(),
+                _ => version.name.replace(' ', "_"),
+            };
+            WikiPageBuilder::new(self.client.clone(), version.project.id, &title)
+                .text(&text)
+                .comments(&format!("Release notes of {}", version.name))
+                .execute()?;
+        }
+
+        if self.publish_news {
+            #[derive(Serialize)]
+            struct News<'a> {
+                title: &'a str,
+                description: &'a str,
+            }
+            #[derive(Serialize)]
+            struct NewsWrapper<'a> {
+                news: News<'a>,
+            }
+
+            let title = format!("{} released", version.name);
+            let news = NewsWrapper {
+                news: News {
+                    title: &title,
+                    description: &text,
+                },
+            };
+            let mut response = self.client.post(
+                &format!("/projects/{}/news.json", version.project.id),
+                &news,
+            )?;
+            if !response.is_success() {
+                let body = response.text()?;
+                return Err(failure(response.status, &body));
+            }
+        }
+
+        Ok(text)
+    }
+}
+
+/// Holds the result of a release readiness check of a version.
+#[derive(Debug, Default)]
+pub struct ReleaseReadiness {
+    pub version_id: u32,
+    /// Ids of the open issues of the version.
+    pub open_issues: Vec<u32>,
+    /// Ids of the open issues which have no estimated hours.
+    pub without_estimate: Vec<u32>,
+    /// Ids of the open issues which are blocked by other open issues.
+    pub blocked: Vec<u32>,
+    /// Whether the wiki page of the version holding the release notes exists.
+    pub release_notes: bool,
+}
+impl ReleaseReadiness {
+    /// Returns true if the version has no open issues and its release notes exist.
+    pub fn is_ready(&self) -> bool {
+        self.open_issues.is_empty() && self.release_notes
+    }
+}
+
+/// Represents a version as pulled from redmine application.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Version {
+    pub id: u32,
+    pub project: NamedObject,
+    pub name: String,
+    pub description: Option<String>,
+    pub status: VersionStatus,
+    pub due_date: Option<String>,
+    pub sharing: VersionSharing,
+    pub wiki_page_title: Option<String>,
+    pub estimated_hours: Option<f32>,
+    pub spent_hours: Option<f32>,
+    pub created_on: String,
+    pub updated_on: String,
+    #[serde(default)]
+    pub custom_fields: Vec<CustomField>,
+}
+impl Version {
+    /// Returns the url of the page showing this version in the redmine application, e.g.
+    /// `http://www.redmine.org/versions/1`, for linking users to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - the api pointing to the redmine application
+    pub fn web_url(&self, api: &RedmineApi) -> String {
+        api.client.web_url(&format!("/versions/{}", self.id))
+    }
+}
+
+/// Enumeration of the states of a version.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionStatus {
+    /// Issues can be assigned to the version.
+    Open,
+    /// No further issues can be assigned to the version.
+    Locked,
+    /// The version has been released.
+    Closed,
+}
+// Version implements Default trait, so VersionStatus has to implement Default, too.
+impl Default for VersionStatus {
+    fn default() -> Self {
+        VersionStatus::Open
+    }
+}
+
+/// Enumeration of the projects a version is shared with, i.e. whose issues can be assigned to it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionSharing {
+    /// Not shared.
+    None,
+    /// Shared with the subprojects.
+    Descendants,
+    /// Shared with the project hierarchy, i.e. parent projects and subprojects.
+    Hierarchy,
+    /// Shared with all projects of the project tree.
+    Tree,
+    /// Shared with all projects.
+    System,
+}
+// Version implements Default trait, so VersionSharing has to implement Default, too.
+impl Default for VersionSharing {
+    fn default() -> Self {
+        VersionSharing::None
+    }
+}
@@ -0,0 +1,372 @@
+//! This module holds everything needed to represent the redmine wiki pages api as described by
+//! following link: http://www.redmine.org/projects/redmine/wiki/Rest_WikiPages.
+
+extern crate serde_json;
+
+use std::collections::HashMap;
+use super::errors::*;
+use super::uploads::Upload;
+use super::{ClientHandle, NamedObject, Tristate};
+
+/// This struct exposes all methods provided by the redmine wiki pages api.
+pub struct Api {
+    client: ClientHandle,
+}
+impl Api {
+    /// Creates a new instance. Should not be called externally.
+    pub fn new(client: ClientHandle) -> Api {
+        Api { client: client }
+    }
+
+    /// Returns WikiIndexExecutor struct which offers an `execute` function which returns the
+    /// titles of all wiki pages of the project specified by `project_id` parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.wiki().index(1).execute();
+    /// ```
+    pub fn index(&self, project_id: u32) -> WikiIndexExecutor {
+        WikiIndexExecutor {
+            client: self.client.clone(),
+            project_id: project_id,
+        }
+    }
+
+    /// Returns WikiPageShow struct which offers an `execute` function which returns the wiki page
+    /// specified by `title` parameter, by default in its current version.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    /// * `title` - a string slice holding the title of the wiki page
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.wiki().show(1, "Installation").version(2).execute();
+    /// ```
+    pub fn show(&self, project_id: u32, title: &str) -> WikiPageShow {
+        WikiPageShow {
+            client: self.client.clone(),
+            project_id: project_id,
+            title: title.to_string(),
+            version: None,
+        }
+    }
+
+    /// Returns a WikiPageBuilder (builder pattern) and ultimately creates a wiki page of a
+    /// project. Redmine updates the page if it exists already.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    /// * `title` - a string slice holding the title of the wiki page
+    /// * `text` - a string slice holding the text of the wiki page
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.wiki().create(1, "Deployment", "h1. Deployment")
+    ///     .parent_title("Installation")
+    ///     .execute();
+    /// ```
+    pub fn create<'a>(
+        &self,
+        project_id: u32,
+        title: &'a str,
+        text: &'a str,
+    ) -> WikiPageBuilder<'a> {
+        WikiPageBuilder::new(self.client.clone(), project_id, title).text(text)
+    }
+
+    /// Returns a WikiPageBuilder (builder pattern) and ultimately creates or updates a wiki page
+    /// of a project. Redmine creates the page if it doesn't exist yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    /// * `title` - a string slice holding the title of the wiki page
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.wiki().update(1, "Architecture")
+    ///     .text("h1. Architecture\n\n!diagram.png!")
+    ///     .comments("Updated diagram")
+    ///     .execute();
+    /// ```
+    pub fn update<'a>(&self, project_id: u32, title: &'a str) -> WikiPageBuilder<'a> {
+        WikiPageBuilder::new(self.client.clone(), project_id, title)
+    }
+
+    /// Returns WikiPageDelete struct which offers an `execute` function which deletes the wiki
+    /// page specified by `title` parameter together with its history and child pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    /// * `title` - a string slice holding the title of the wiki page
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use redmine_api::RedmineApi;
+    ///
+    /// let redmine = RedmineApi::new(
+    ///     "http://www.redmine.org/".to_string(),
+    ///     "1234".to_string()
+    /// );
+    ///
+    /// let result = redmine.wiki().delete(1, "Deployment").execute();
+    /// ```
+    pub fn delete(&self, project_id: u32, title: &str) -> WikiPageDelete {
+        WikiPageDelete {
+            client: self.client.clone(),
+            project_id: project_id,
+            title: title.to_string(),
+        }
+    }
+}
+
+/// Helper struct to provide a unified interface for all wiki page api methods.
+pub struct WikiIndexExecutor {
+    client: ClientHandle,
+    project_id: u32,
+}
+impl WikiIndexExecutor {
+    /// Performs request to redmine application and returns the wiki pages of the project without
+    /// their texts.
+    pub fn execute(&self) -> Result<Vec<WikiPageSummary>> {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            wiki_pages: Vec<WikiPageSummary>,
+        }
+
+        let result = self.client.get(
+            &(format!("/projects/{}/wiki/index.json", self.project_id)),
+            &HashMap::new(),
+        )?;
+        let wrapper: Wrapper = serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+
+        Ok(wrapper.wiki_pages)
+    }
+}
+
+/// Helper struct to provide a unified interface for all wiki page api methods.
+pub struct WikiPageShow {
+    client: ClientHandle,
+    project_id: u32,
+    title: String,
+    version: Option<u32>,
+}
+impl WikiPageShow {
+    /// Requests an older version of the wiki page instead of the current one.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - an integer holding the version
+    pub fn version(&mut self, n: u32) -> &mut Self {
+        self.version = Some(n);
+        self
+    }
+
+    /// Performs request to redmine application and returns the wiki page.
+    pub fn execute(&self) -> Result<WikiPage> {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            wiki_page: WikiPage,
+        }
+
+        let path = match self.version {
+            Some(n) => format!("/projects/{}/wiki/{}/{}.json", self.project_id, self.title, n),
+            None => format!("/projects/{}/wiki/{}.json", self.project_id, self.title),
+        };
+        let result = self.client.get(&path, &HashMap::new())?;
+        let wrapper: Wrapper = serde_json::from_str(&result).chain_err(|| "Can't parse json")?;
+
+        Ok(wrapper.wiki_page)
+    }
+}
+
+/// Helper struct to provide a unified interface for all wiki page api methods.
+pub struct WikiPageDelete {
+    client: ClientHandle,
+    project_id: u32,
+    title: String,
+}
+impl WikiPageDelete {
+    /// Performs request to redmine application and deletes the wiki page.
+    pub fn execute(&self) -> Result<()> {
+        self.client.delete(
+            &(format!("/projects/{}/wiki/{}.json", self.project_id, self.title)),
+        )
+    }
+}
+
+/// Helper struct for serialization.
+#[derive(Serialize)]
+struct WikiPageBuilderWrapper<'a> {
+    wiki_page: &'a WikiPageBuilder<'a>,
+}
+
+/// Struct to provide builder pattern for creation and update of wiki pages. Can be serialized to
+/// be used as json parameter for request to redmine application.
+#[derive(Debug, Default, Serialize)]
+pub struct WikiPageBuilder<'a> {
+    // internal
+    #[serde(skip_serializing)]
+    client: ClientHandle,
+    #[serde(skip_serializing)]
+    project_id: u32,
+    #[serde(skip_serializing)]
+    title: &'a str,
+
+    // fields used for serialization
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    text: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    comments: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Tristate::is_unset")]
+    parent_title: Tristate<&'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    uploads: Vec<Upload>,
+}
+impl<'a> WikiPageBuilder<'a> {
+    /// Creates new instance for creation or update of a wiki page.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_id` - an integer holding the project id
+    /// * `title` - a string slice holding the title of the wiki page
+    pub fn new(client: ClientHandle, project_id: u32, title: &'a str) -> Self {
+        WikiPageBuilder {
+            client: client,
+            project_id: project_id,
+            title: title,
+            ..Default::default()
+        }
+    }
+
+    /// Sets text of the wiki page.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the text
+    pub fn text(mut self, s: &'a str) -> Self {
+        self.text = Tristate::Set(s);
+        self
+    }
+
+    /// Sets comment describing the change of the wiki page.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the comment
+    pub fn comments(mut self, s: &'a str) -> Self {
+        self.comments = Tristate::Set(s);
+        self
+    }
+
+    /// Sets the title of the parent page, i.e. moves the wiki page below it.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - a string slice holding the title of the parent page
+    pub fn parent_title(mut self, s: &'a str) -> Self {
+        self.parent_title = Tristate::Set(s);
+        self
+    }
+
+    /// Moves the wiki page to the top level.
+    pub fn clear_parent(mut self) -> Self {
+        self.parent_title = Tristate::Clear;
+        self
+    }
+
+    /// Attaches an uploaded file to the wiki page.
+    ///
+    /// # Arguments
+    ///
+    /// * `upload` - an [Upload](../uploads/struct.Upload.html) as returned by the uploads api
+    pub fn add_upload(mut self, upload: Upload) -> Self {
+        self.uploads.push(upload);
+        self
+    }
+
+    /// Performs request to redmine application to create or update a wiki page and returns it.
+    pub fn execute(&self) -> Result<WikiPage> {
+        let wiki_page = WikiPageBuilderWrapper { wiki_page: self };
+        self.client.update_entity(
+            &(format!(
+                "/projects/{}/wiki/{}.json",
+                self.project_id,
+                self.title
+            )),
+            &wiki_page,
+            "wiki_page",
+        )
+    }
+}
+
+/// Represents a wiki page as pulled from redmine application.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct WikiPage {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<WikiPageParent>,
+    pub text: String,
+    pub version: u32,
+    pub author: NamedObject,
+    pub comments: Option<String>,
+    pub created_on: String,
+    pub updated_on: String,
+}
+
+/// Represents a wiki page as listed by the wiki index, i.e. without text.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct WikiPageSummary {
+    pub title: String,
+    pub parent: Option<WikiPageParent>,
+    pub version: u32,
+    pub created_on: String,
+    pub updated_on: String,
+}
+
+/// Refers to the parent of a wiki page by title.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct WikiPageParent {
+    pub title: String,
+}